@@ -0,0 +1,394 @@
+// src/api/capability.rs
+
+use axum::{routing::MethodRouter, Router};
+use chrono::Utc;
+
+use crate::AppState;
+use super::format::SUPPORTED_FORMATS;
+use crate::domain::{
+    resources::capability_statement::*,
+    CodeableConcept, Coding, Extension, Code, FhirBoolean, FhirDateTime, FhirString, Uri,
+    Canonical,
+};
+
+pub const FHIR_VERSION: &str = "4.0.1";
+
+const OAUTH_URIS_EXTENSION: &str =
+    "http://fhir-registry.smarthealthit.org/StructureDefinition/oauth-uris";
+
+/// A search parameter honored by a search handler
+#[derive(Debug, Clone, Copy)]
+pub struct SearchParamDef {
+    pub name: &'static str,
+    pub type_: &'static str,
+    pub documentation: &'static str,
+}
+
+/// An extended operation ($name) exposed by the server
+#[derive(Debug, Clone, Copy)]
+pub struct OperationDef {
+    pub name: &'static str,
+    pub definition: &'static str,
+}
+
+/// Search parameters that apply to every search endpoint
+pub const COMMON_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "_count",
+        type_: "number",
+        documentation: "Maximum number of results per page",
+    },
+    SearchParamDef {
+        name: "_offset",
+        type_: "number",
+        documentation: "Number of results to skip",
+    },
+    SearchParamDef {
+        name: "_tag",
+        type_: "token",
+        documentation: "Tag in Resource.meta.tag, as system|code or code",
+    },
+    SearchParamDef {
+        name: "_profile",
+        type_: "uri",
+        documentation: "Profile URL in Resource.meta.profile",
+    },
+    SearchParamDef {
+        name: "_security",
+        type_: "token",
+        documentation: "Security label in Resource.meta.security, as system|code or code",
+    },
+];
+
+/// Capabilities recorded for a single resource type
+#[derive(Debug, Clone, Default)]
+pub struct ResourceCapabilities {
+    pub resource_type: &'static str,
+    pub interactions: Vec<&'static str>,
+    pub search_params: Vec<SearchParamDef>,
+    pub search_includes: Vec<&'static str>,
+    pub operations: Vec<OperationDef>,
+}
+
+/// Everything the router has registered, used to build the CapabilityStatement
+#[derive(Debug, Clone)]
+pub struct ServerCapabilities {
+    pub resources: Vec<ResourceCapabilities>,
+    pub operations: Vec<OperationDef>,
+    pub formats: Vec<&'static str>,
+    pub started_at: FhirDateTime,
+}
+
+/// Router builder that records each FHIR interaction as its route is mounted,
+/// so the CapabilityStatement cannot drift from what the server actually serves
+pub struct FhirRouter {
+    router: Router<AppState>,
+    capabilities: ServerCapabilities,
+}
+
+/// Route builder for a single resource type
+pub struct ResourceRoutes {
+    router: Router<AppState>,
+    capabilities: ResourceCapabilities,
+}
+
+impl FhirRouter {
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+            capabilities: ServerCapabilities {
+                resources: Vec::new(),
+                operations: Vec::new(),
+                formats: SUPPORTED_FORMATS.to_vec(),
+                started_at: FhirDateTime(Utc::now()),
+            },
+        }
+    }
+
+    /// Register the routes for a resource type
+    pub fn resource(
+        mut self,
+        resource_type: &'static str,
+        build: impl FnOnce(ResourceRoutes) -> ResourceRoutes,
+    ) -> Self {
+        let routes = build(ResourceRoutes {
+            router: self.router,
+            capabilities: ResourceCapabilities {
+                resource_type,
+                ..Default::default()
+            },
+        });
+        self.router = routes.router;
+        self.capabilities.resources.push(routes.capabilities);
+        self
+    }
+
+    /// Mount an operation invoked on the whole server (`/fhir/$name`)
+    pub fn system_operation(mut self, operation: OperationDef, route: MethodRouter<AppState>) -> Self {
+        self.router = self.router.route(&format!("/fhir/${}", operation.name), route);
+        if !self.capabilities.operations.iter().any(|op| op.name == operation.name) {
+            self.capabilities.operations.push(operation);
+        }
+        self
+    }
+
+    pub fn into_parts(self) -> (Router<AppState>, ServerCapabilities) {
+        (self.router, self.capabilities)
+    }
+}
+
+impl Default for FhirRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceRoutes {
+    fn type_path(&self) -> String {
+        format!("/fhir/{}", self.capabilities.resource_type)
+    }
+
+    fn instance_path(&self) -> String {
+        format!("/fhir/{}/:id", self.capabilities.resource_type)
+    }
+
+    fn mount(mut self, path: String, interaction: &'static str, route: MethodRouter<AppState>) -> Self {
+        self.router = self.router.route(&path, route);
+        self.capabilities.interactions.push(interaction);
+        self
+    }
+
+    pub fn create(self, route: MethodRouter<AppState>) -> Self {
+        let path = self.type_path();
+        self.mount(path, "create", route)
+    }
+
+    pub fn search(mut self, route: MethodRouter<AppState>, params: &[SearchParamDef]) -> Self {
+        self.capabilities.search_params.extend_from_slice(params);
+        let path = self.type_path();
+        self.mount(path, "search-type", route)
+    }
+
+    /// Advertise an `_include` the search handler resolves
+    pub fn search_include(mut self, include: &'static str) -> Self {
+        self.capabilities.search_includes.push(include);
+        self
+    }
+
+    pub fn read(self, route: MethodRouter<AppState>) -> Self {
+        let path = self.instance_path();
+        self.mount(path, "read", route)
+    }
+
+    pub fn update(self, route: MethodRouter<AppState>) -> Self {
+        let path = self.instance_path();
+        self.mount(path, "update", route)
+    }
+
+    pub fn delete(self, route: MethodRouter<AppState>) -> Self {
+        let path = self.instance_path();
+        self.mount(path, "delete", route)
+    }
+
+    pub fn history(self, route: MethodRouter<AppState>) -> Self {
+        let path = format!("{}/_history", self.instance_path());
+        self.mount(path, "history-instance", route)
+    }
+
+    /// Mount an operation invoked on the resource type (`Type/$name`)
+    pub fn type_operation(self, operation: OperationDef, route: MethodRouter<AppState>) -> Self {
+        let path = format!("{}/${}", self.type_path(), operation.name);
+        self.mount_operation(path, operation, route)
+    }
+
+    /// Mount an operation invoked on a resource instance (`Type/:id/$name`)
+    pub fn instance_operation(self, operation: OperationDef, route: MethodRouter<AppState>) -> Self {
+        let path = format!("{}/${}", self.instance_path(), operation.name);
+        self.mount_operation(path, operation, route)
+    }
+
+    fn mount_operation(mut self, path: String, operation: OperationDef, route: MethodRouter<AppState>) -> Self {
+        self.router = self.router.route(&path, route);
+        // Operations available at both levels are advertised once
+        if !self.capabilities.operations.iter().any(|op| op.name == operation.name) {
+            self.capabilities.operations.push(operation);
+        }
+        self
+    }
+}
+
+impl ServerCapabilities {
+    /// Build the CapabilityStatement for the server reachable at `base_url`
+    pub fn to_capability_statement(&self, base_url: &str) -> CapabilityStatement {
+        let mut statement = CapabilityStatement::new(
+            Code("active".to_string()),
+            self.started_at.clone(),
+            Code("instance".to_string()),
+            Code(FHIR_VERSION.to_string()),
+        );
+        statement.name = Some(FhirString("FhirServerCapabilityStatement".to_string()));
+        statement.title = Some(FhirString("FHIR Server Capability Statement".to_string()));
+        statement.software = Some(CapabilityStatementSoftware {
+            name: FhirString(env!("CARGO_PKG_NAME").to_string()),
+            version: Some(FhirString(env!("CARGO_PKG_VERSION").to_string())),
+        });
+        statement.implementation = Some(CapabilityStatementImplementation {
+            description: FhirString("FHIR R4 server (Axum, SQLx, PostgreSQL)".to_string()),
+            url: Some(Uri(format!("{}/fhir", base_url))),
+        });
+        statement.format = self.formats.iter().map(|f| Code(f.to_string())).collect();
+
+        let resources = self.resources.iter().map(Self::rest_resource).collect();
+
+        statement.rest = Some(vec![CapabilityStatementRest {
+            mode: Code("server".to_string()),
+            documentation: None,
+            security: Some(Self::security(base_url)),
+            resource: Some(resources),
+            interaction: None,
+            search_param: Some(COMMON_SEARCH_PARAMS.iter().map(to_search_param).collect()),
+            operation: non_empty(self.operations.iter().map(to_operation).collect()),
+        }]);
+
+        statement
+    }
+
+    fn rest_resource(resource: &ResourceCapabilities) -> CapabilityStatementResource {
+        let interactions = resource
+            .interactions
+            .iter()
+            .map(|code| CapabilityStatementInteraction {
+                code: Code(code.to_string()),
+                documentation: None,
+            })
+            .collect();
+
+        CapabilityStatementResource {
+            type_: Code(resource.resource_type.to_string()),
+            profile: Some(Canonical(format!(
+                "http://hl7.org/fhir/StructureDefinition/{}",
+                resource.resource_type
+            ))),
+            interaction: Some(interactions),
+            versioning: Some(Code("versioned".to_string())),
+            read_history: Some(FhirBoolean(false)),
+            update_create: Some(FhirBoolean(false)),
+            search_include: non_empty(
+                resource.search_includes.iter().map(|i| FhirString(i.to_string())).collect(),
+            ),
+            search_param: non_empty(resource.search_params.iter().map(to_search_param).collect()),
+            operation: non_empty(resource.operations.iter().map(to_operation).collect()),
+        }
+    }
+
+    fn security(base_url: &str) -> CapabilityStatementSecurity {
+        let mut token = Extension::new("token");
+        token.value_uri = Some(Uri(format!("{}/auth/login", base_url)));
+        let mut register = Extension::new("register");
+        register.value_uri = Some(Uri(format!("{}/auth/register", base_url)));
+
+        let mut oauth_uris = Extension::new(OAUTH_URIS_EXTENSION);
+        oauth_uris.extension = Some(vec![token, register]);
+
+        CapabilityStatementSecurity {
+            extension: Some(vec![oauth_uris]),
+            cors: Some(FhirBoolean(true)),
+            service: Some(vec![CodeableConcept {
+                coding: Some(vec![Coding {
+                    system: Some(Uri(
+                        "http://terminology.hl7.org/CodeSystem/restful-security-service".to_string(),
+                    )),
+                    version: None,
+                    code: Some(Code("OAuth".to_string())),
+                    display: Some(FhirString("OAuth".to_string())),
+                    user_selected: None,
+                }]),
+                text: Some(FhirString("JWT bearer token".to_string())),
+            }]),
+            description: Some(FhirString(
+                "Obtain a JWT from the token endpoint and send it as `Authorization: Bearer <token>`"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+fn to_search_param(param: &SearchParamDef) -> CapabilityStatementSearchParam {
+    CapabilityStatementSearchParam {
+        name: FhirString(param.name.to_string()),
+        definition: None,
+        type_: Code(param.type_.to_string()),
+        documentation: Some(FhirString(param.documentation.to_string())),
+    }
+}
+
+fn to_operation(operation: &OperationDef) -> CapabilityStatementOperation {
+    CapabilityStatementOperation {
+        name: FhirString(operation.name.to_string()),
+        definition: Canonical(operation.definition.to_string()),
+        documentation: None,
+    }
+}
+
+fn non_empty<T>(items: Vec<T>) -> Option<Vec<T>> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    const PARAMS: &[SearchParamDef] = &[SearchParamDef {
+        name: "family",
+        type_: "string",
+        documentation: "Family name",
+    }];
+
+    #[test]
+    fn test_records_registered_interactions() {
+        let (_, capabilities) = FhirRouter::new()
+            .resource("Patient", |r| {
+                r.create(axum::routing::post(|| async { "" }))
+                    .search(get(|| async { "" }), PARAMS)
+                    .read(get(|| async { "" }))
+                    .instance_operation(
+                        OperationDef { name: "everything", definition: "http://example.org/everything" },
+                        get(|| async { "" }),
+                    )
+            })
+            .into_parts();
+
+        assert_eq!(capabilities.resources.len(), 1);
+        let patient = &capabilities.resources[0];
+        assert_eq!(patient.resource_type, "Patient");
+        assert_eq!(patient.interactions, vec!["create", "search-type", "read"]);
+        assert_eq!(patient.search_params[0].name, "family");
+        assert_eq!(patient.operations[0].name, "everything");
+    }
+
+    #[test]
+    fn test_capability_statement_reflects_routes() {
+        let (_, capabilities) = FhirRouter::new()
+            .resource("Observation", |r| r.read(get(|| async { "" })))
+            .into_parts();
+
+        let statement = capabilities.to_capability_statement("http://localhost:8080");
+        let rest = &statement.rest.as_ref().unwrap()[0];
+        let resources = rest.resource.as_ref().unwrap();
+
+        assert_eq!(statement.fhir_version.0, FHIR_VERSION);
+        assert_eq!(resources[0].type_.0, "Observation");
+        assert_eq!(resources[0].interaction.as_ref().unwrap()[0].code.0, "read");
+        assert!(resources[0].search_param.is_none());
+
+        let security = rest.security.as_ref().unwrap();
+        let oauth = &security.extension.as_ref().unwrap()[0];
+        let token = &oauth.extension.as_ref().unwrap()[0];
+        assert_eq!(token.value_uri.as_ref().unwrap().0, "http://localhost:8080/auth/login");
+    }
+}
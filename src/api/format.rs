@@ -0,0 +1,547 @@
+// src/api/format.rs
+
+use std::collections::HashMap;
+
+use axum::{
+    async_trait,
+    body::{to_bytes, Body, Bytes},
+    extract::{FromRequest, Query, Request},
+    http::{header, HeaderMap, HeaderValue, Method, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+use serde_json::{error::Category, Value};
+
+use crate::domain::{
+    errors::{FhirError, FhirResult},
+    xml, Bundle, OperationOutcome, UnsignedInt,
+};
+
+/// Formats advertised in the CapabilityStatement
+pub const SUPPORTED_FORMATS: &[&str] = &["json", "xml"];
+
+const FHIR_JSON: &str = "application/fhir+json; fhirVersion=4.0";
+const FHIR_XML: &str = "application/fhir+xml; fhirVersion=4.0";
+
+/// Wire format of a FHIR resource. `Json` is the plain `application/json`
+/// API, which keeps the `data` response envelope; `FhirJson` and `Xml` carry
+/// bare FHIR resources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceFormat {
+    Json,
+    FhirJson,
+    Xml,
+}
+
+impl ResourceFormat {
+    /// Request body format, from the Content-Type header; JSON when absent
+    pub fn for_body(headers: &HeaderMap) -> FhirResult<Self> {
+        let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+            return Ok(Self::Json);
+        };
+
+        let content_type = content_type.to_str().unwrap_or_default();
+        MediaType::parse(content_type)
+            .format()
+            .filter(|_| !content_type.contains('*'))
+            .ok_or_else(|| FhirError::UnsupportedMediaType(content_type.to_string()))
+    }
+}
+
+/// A MIME type from an Accept or Content-Type header, or a `_format` value
+struct MediaType {
+    essence: String,
+    fhir_version: Option<String>,
+    quality: f32,
+}
+
+impl MediaType {
+    fn parse(value: &str) -> Self {
+        let mut parts = value.split(';');
+        let mut media = Self {
+            essence: parts.next().unwrap_or_default().trim().to_ascii_lowercase(),
+            fhir_version: None,
+            quality: 1.0,
+        };
+
+        for param in parts {
+            let Some((name, value)) = param.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            match name.trim().to_ascii_lowercase().as_str() {
+                "fhirversion" => media.fhir_version = Some(value.to_string()),
+                "q" => media.quality = value.parse().unwrap_or(0.0),
+                _ => {}
+            }
+        }
+        media
+    }
+
+    /// The format this media type selects, if the server supports it
+    fn format(&self) -> Option<ResourceFormat> {
+        // Only FHIR R4 (4.0.x) can be produced or consumed
+        if let Some(version) = &self.fhir_version {
+            if version != "4.0" && !version.starts_with("4.0.") {
+                return None;
+            }
+        }
+
+        match self.essence.as_str() {
+            "*/*" | "application/*" | "application/json" => Some(ResourceFormat::Json),
+            "json" | "application/fhir+json" | "application/json+fhir" => {
+                Some(ResourceFormat::FhirJson)
+            }
+            "xml" | "text/xml" | "application/xml" | "application/fhir+xml"
+            | "application/xml+fhir" => Some(ResourceFormat::Xml),
+            _ => None,
+        }
+    }
+}
+
+/// Response representation negotiated from `_format`, `_pretty` and Accept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Negotiated {
+    pub format: ResourceFormat,
+    pub pretty: bool,
+}
+
+impl Negotiated {
+    /// `_format` overrides the Accept header; plain JSON by default. Fails
+    /// with `NotAcceptable` when no requested type can be produced
+    pub fn for_request(uri: &Uri, headers: &HeaderMap) -> FhirResult<Self> {
+        let params = Query::<HashMap<String, String>>::try_from_uri(uri)
+            .map(|Query(params)| params)
+            .unwrap_or_default();
+        let pretty = params.get("_pretty").is_some_and(|pretty| pretty == "true");
+
+        let format = if let Some(format) = params.get("_format") {
+            // An unencoded '+' (as in `fhir+xml`) decodes to a space
+            MediaType::parse(&format.replace(' ', "+"))
+                .format()
+                .ok_or_else(|| {
+                    FhirError::NotAcceptable(format!("_format '{}' is not supported", format))
+                })?
+        } else {
+            match headers.get(header::ACCEPT).and_then(|accept| accept.to_str().ok()) {
+                Some(accept) if !accept.trim().is_empty() => accept_format(accept)?,
+                _ => ResourceFormat::Json,
+            }
+        };
+
+        Ok(Self { format, pretty })
+    }
+
+    /// Whether a Binary read asks for the Binary resource rather than its
+    /// content: it names a `_format`, or prefers a FHIR media type in Accept.
+    /// Any other read, including `*/*` and `application/json`, is served the
+    /// content in its own media type
+    pub fn wants_binary_resource(uri: &Uri, headers: &HeaderMap) -> bool {
+        let params = Query::<HashMap<String, String>>::try_from_uri(uri)
+            .map(|Query(params)| params)
+            .unwrap_or_default();
+        if params.contains_key("_format") {
+            return true;
+        }
+
+        let Some(accept) = headers.get(header::ACCEPT).and_then(|accept| accept.to_str().ok()) else {
+            return false;
+        };
+        let mut ranges: Vec<MediaType> = accept
+            .split(',')
+            .map(MediaType::parse)
+            .filter(|media| media.quality > 0.0)
+            .collect();
+        ranges.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+        ranges
+            .first()
+            .is_some_and(|media| media.essence.contains("fhir") && media.format().is_some())
+    }
+}
+
+/// Pick the most preferred supported format from an Accept header
+fn accept_format(accept: &str) -> FhirResult<ResourceFormat> {
+    let mut ranges: Vec<MediaType> = accept
+        .split(',')
+        .map(MediaType::parse)
+        .filter(|media| media.quality > 0.0)
+        .collect();
+    // Stable sort keeps the client's order among equal q-values
+    ranges.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+
+    ranges
+        .iter()
+        .find_map(MediaType::format)
+        .ok_or_else(|| {
+            FhirError::NotAcceptable(format!("None of the accepted media types are supported: {}", accept))
+        })
+}
+
+/// Middleware rendering FHIR responses in the negotiated format. Handlers
+/// always produce enveloped JSON; for FHIR formats the resource is unwrapped
+/// from its envelope and list responses become Bundles
+pub async fn negotiate_format(request: Request, next: Next) -> Response {
+    // Binary content is served as it was stored, in its own media type
+    if is_binary_content_read(&request) {
+        return next.run(request).await;
+    }
+
+    let negotiated = match Negotiated::for_request(request.uri(), request.headers()) {
+        Ok(negotiated) => negotiated,
+        Err(e) => return e.into_response(),
+    };
+
+    let response = next.run(request).await;
+    render(response, negotiated).await
+}
+
+/// A `GET /fhir/Binary/:id` that does not ask for the Binary resource
+fn is_binary_content_read(request: &Request) -> bool {
+    let is_binary_read = request.method() == Method::GET
+        && request
+            .uri()
+            .path()
+            .strip_prefix("/fhir/Binary/")
+            .is_some_and(|id| !id.is_empty() && !id.contains('/') && !id.starts_with('$'));
+    is_binary_read && !Negotiated::wants_binary_resource(request.uri(), request.headers())
+}
+
+async fn render(response: Response, negotiated: Negotiated) -> Response {
+    let (mut parts, body) = response.into_parts();
+
+    let is_json = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|content_type| content_type.as_bytes().starts_with(b"application/json"));
+    let outcome = parts.extensions.remove::<OperationOutcome>();
+    if !is_json || (negotiated.format == ResourceFormat::Json && !negotiated.pretty) {
+        return Response::from_parts(parts, body);
+    }
+
+    let resource = match (negotiated.format, outcome) {
+        (ResourceFormat::Json, _) => read_json(body).await,
+        (_, Some(outcome)) => serde_json::to_value(outcome).map_err(FhirError::from),
+        (_, None) => read_json(body).await.and_then(unwrap_resource),
+    };
+
+    let rendered = resource.and_then(|resource| match (negotiated.format, negotiated.pretty) {
+        (ResourceFormat::Xml, true) => xml::to_xml_pretty(&resource),
+        (ResourceFormat::Xml, false) => xml::to_xml(&resource),
+        (_, true) => Ok(serde_json::to_string_pretty(&resource)?),
+        (_, false) => Ok(serde_json::to_string(&resource)?),
+    });
+
+    match rendered {
+        Ok(body) => {
+            let content_type = match negotiated.format {
+                ResourceFormat::Json => "application/json",
+                ResourceFormat::FhirJson => FHIR_JSON,
+                ResourceFormat::Xml => FHIR_XML,
+            };
+            parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(body))
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn read_json(body: Body) -> FhirResult<Value> {
+    let bytes = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| FhirError::Validation(e.to_string()))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Extract the FHIR resource from a `SuccessResponse`/`PaginatedResponse` body
+fn unwrap_resource(body: Value) -> FhirResult<Value> {
+    let Value::Object(mut envelope) = body else {
+        return Ok(body);
+    };
+
+    match envelope.remove("data") {
+        Some(Value::Array(resources)) => {
+            // Paginated search results carry a total; history lists do not
+            let is_search = envelope.contains_key("total");
+            let mut bundle = Bundle::new(if is_search { "searchset" } else { "history" });
+            bundle.total = envelope
+                .get("total")
+                .and_then(Value::as_u64)
+                .map(|total| UnsignedInt(total as u32));
+            for resource in resources {
+                bundle.add_entry(resource, is_search.then_some("match"));
+            }
+            if let Some(Value::Array(included)) = envelope.remove("included") {
+                for resource in included {
+                    bundle.add_entry(resource, Some("include"));
+                }
+            }
+            Ok(serde_json::to_value(bundle)?)
+        }
+        Some(resource) => Ok(resource),
+        None => Ok(Value::Object(envelope)),
+    }
+}
+
+/// Parse a request body as a FHIR resource in the format its Content-Type
+/// names
+pub fn parse_resource<T: DeserializeOwned>(headers: &HeaderMap, body: &[u8]) -> FhirResult<T> {
+    match ResourceFormat::for_body(headers)? {
+        ResourceFormat::Xml => std::str::from_utf8(body)
+            .map_err(|e| FhirError::Validation(format!("Invalid FHIR XML: {}", e)))
+            .and_then(xml::from_xml),
+        ResourceFormat::Json | ResourceFormat::FhirJson => {
+            serde_json::from_slice(body).map_err(|e| match e.classify() {
+                Category::Data => FhirError::UnprocessableEntity(e.to_string()),
+                _ => FhirError::Validation(format!("Invalid JSON: {}", e)),
+            })
+        }
+    }
+}
+
+/// Request body extractor for FHIR resources. Accepts `application/json`,
+/// `application/fhir+json` and FHIR XML, per the Content-Type header
+pub struct FhirBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for FhirBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // Reject unsupported media types before reading the body
+        ResourceFormat::for_body(request.headers()).map_err(IntoResponse::into_response)?;
+
+        let headers = request.headers().clone();
+        let body = Bytes::from_request(request, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        parse_resource(&headers, &body)
+            .map(FhirBody)
+            .map_err(IntoResponse::into_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::{get, post}, Json, Router};
+    use tower::ServiceExt;
+    use crate::api::responses::SuccessResponse;
+    use crate::domain::Patient;
+
+    fn headers(name: header::HeaderName, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    fn negotiate(uri: &str, accept: Option<&'static str>) -> FhirResult<ResourceFormat> {
+        let headers = accept.map(|accept| headers(header::ACCEPT, accept)).unwrap_or_default();
+        Negotiated::for_request(&uri.parse().unwrap(), &headers).map(|n| n.format)
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/fhir/Patient/1", get(|| async { Json(SuccessResponse::new(Patient::new())) }))
+            .route("/fhir/Patient/2", get(|| async {
+                Err::<(), _>(FhirError::NotFound {
+                    resource_type: "Patient".to_string(),
+                    id: "2".to_string(),
+                })
+            }))
+            .route("/fhir/Patient", post(|FhirBody(patient): FhirBody<Patient>| async move {
+                Json(SuccessResponse::new(patient))
+            }))
+            .layer(axum::middleware::from_fn(negotiate_format))
+    }
+
+    async fn body_text(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8_lossy(&body).into_owned()
+    }
+
+    #[test]
+    fn test_format_param_overrides_accept() {
+        assert_eq!(
+            negotiate("/fhir/Patient?_format=xml", Some("application/fhir+json")).unwrap(),
+            ResourceFormat::Xml
+        );
+        assert_eq!(
+            negotiate("/fhir/Patient?_format=application/fhir+xml", None).unwrap(),
+            ResourceFormat::Xml
+        );
+        assert_eq!(negotiate("/fhir/Patient?_format=json", None).unwrap(), ResourceFormat::FhirJson);
+    }
+
+    #[test]
+    fn test_accept_header_quality_and_fhir_version() {
+        assert_eq!(negotiate("/fhir/Patient", None).unwrap(), ResourceFormat::Json);
+        assert_eq!(
+            negotiate("/fhir/Patient", Some("*/*;q=0.1, application/fhir+xml;q=0.9")).unwrap(),
+            ResourceFormat::Xml
+        );
+        assert_eq!(
+            negotiate("/fhir/Patient", Some("application/fhir+json; fhirVersion=4.0")).unwrap(),
+            ResourceFormat::FhirJson
+        );
+        assert_eq!(
+            negotiate("/fhir/Patient", Some("application/fhir+xml;q=0, application/json")).unwrap(),
+            ResourceFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_binary_reads_serve_content_unless_a_fhir_type_is_preferred() {
+        let wants = |uri: &str, accept: Option<&'static str>| {
+            let headers = accept.map(|a| headers(header::ACCEPT, a)).unwrap_or_default();
+            Negotiated::wants_binary_resource(&uri.parse().unwrap(), &headers)
+        };
+        assert!(!wants("/fhir/Binary/1", None));
+        assert!(!wants("/fhir/Binary/1", Some("application/pdf")));
+        assert!(!wants("/fhir/Binary/1", Some("*/*")));
+        assert!(!wants("/fhir/Binary/1", Some("application/json")));
+        assert!(!wants("/fhir/Binary/1", Some("application/fhir+json;q=0.5, application/pdf")));
+        assert!(wants("/fhir/Binary/1", Some("application/fhir+json")));
+        assert!(wants("/fhir/Binary/1?_format=json", Some("application/pdf")));
+    }
+
+    #[test]
+    fn test_unsupported_response_types_are_not_acceptable() {
+        assert!(matches!(
+            negotiate("/fhir/Patient", Some("text/html")),
+            Err(FhirError::NotAcceptable(_))
+        ));
+        assert!(matches!(
+            negotiate("/fhir/Patient", Some("application/fhir+json; fhirVersion=5.0")),
+            Err(FhirError::NotAcceptable(_))
+        ));
+        assert!(matches!(
+            negotiate("/fhir/Patient?_format=turtle", None),
+            Err(FhirError::NotAcceptable(_))
+        ));
+    }
+
+    #[test]
+    fn test_body_format_from_content_type() {
+        let xml = headers(header::CONTENT_TYPE, "application/fhir+xml; charset=utf-8");
+        let fhir_json = headers(header::CONTENT_TYPE, "application/fhir+json; fhirVersion=4.0");
+        let text = headers(header::CONTENT_TYPE, "text/plain");
+
+        assert_eq!(ResourceFormat::for_body(&xml).unwrap(), ResourceFormat::Xml);
+        assert_eq!(ResourceFormat::for_body(&fhir_json).unwrap(), ResourceFormat::FhirJson);
+        assert_eq!(ResourceFormat::for_body(&HeaderMap::new()).unwrap(), ResourceFormat::Json);
+        assert!(matches!(
+            ResourceFormat::for_body(&text),
+            Err(FhirError::UnsupportedMediaType(_))
+        ));
+    }
+
+    #[test]
+    fn test_paginated_response_becomes_searchset() {
+        let body = serde_json::json!({
+            "data": [{"resourceType": "Patient", "id": "1"}],
+            "total": 1,
+            "offset": 0,
+            "count": 1
+        });
+
+        let bundle = unwrap_resource(body).unwrap();
+        assert_eq!(bundle["resourceType"], "Bundle");
+        assert_eq!(bundle["type"], "searchset");
+        assert_eq!(bundle["total"], 1);
+        assert_eq!(bundle["entry"][0]["resource"]["id"], "1");
+    }
+
+    #[test]
+    fn test_included_resources_follow_matches() {
+        let body = serde_json::json!({
+            "data": [{"resourceType": "DiagnosticReport", "id": "r1"}],
+            "total": 1,
+            "offset": 0,
+            "count": 1,
+            "included": [{"resourceType": "Observation", "id": "o1"}]
+        });
+
+        let bundle = unwrap_resource(body).unwrap();
+        assert_eq!(bundle["total"], 1);
+        assert_eq!(bundle["entry"][0]["search"]["mode"], "match");
+        assert_eq!(bundle["entry"][1]["resource"]["id"], "o1");
+        assert_eq!(bundle["entry"][1]["search"]["mode"], "include");
+    }
+
+    #[tokio::test]
+    async fn test_xml_response_and_error_outcome() {
+        let request = Request::get("/fhir/Patient/1?_format=xml").body(Body::empty()).unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], FHIR_XML);
+        assert!(body_text(response).await.contains(r#"<Patient xmlns="http://hl7.org/fhir">"#));
+
+        let request = Request::get("/fhir/Patient/2")
+            .header(header::ACCEPT, "application/fhir+xml")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = body_text(response).await;
+        assert!(body.contains("<OperationOutcome"));
+        assert!(body.contains(r#"<code value="not-found"/>"#));
+    }
+
+    #[tokio::test]
+    async fn test_fhir_json_response_is_bare_and_pretty() {
+        let request = Request::get("/fhir/Patient/1?_pretty=true")
+            .header(header::ACCEPT, "application/fhir+json")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], FHIR_JSON);
+        let body = body_text(response).await;
+        assert!(body.starts_with("{\n  \"resourceType\": \"Patient\""));
+
+        let request = Request::get("/fhir/Patient/1").body(Body::empty()).unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert!(body_text(response).await.starts_with(r#"{"data":"#));
+    }
+
+    #[tokio::test]
+    async fn test_not_acceptable_and_unsupported_media_type() {
+        let request = Request::get("/fhir/Patient/1")
+            .header(header::ACCEPT, "text/html")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+        let request = Request::post("/fhir/Patient")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn test_fhir_json_request_body_is_accepted() {
+        let request = Request::post("/fhir/Patient")
+            .header(header::CONTENT_TYPE, "application/fhir+json; fhirVersion=4.0")
+            .body(Body::from(r#"{"resourceType": "Patient", "active": true}"#))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_text(response).await.contains(r#""active":true"#));
+
+        let request = Request::post("/fhir/Patient")
+            .header(header::CONTENT_TYPE, "application/fhir+json")
+            .body(Body::from("{not json"))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
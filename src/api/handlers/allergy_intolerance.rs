@@ -0,0 +1,193 @@
+// src/api/handlers/allergy_intolerance.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, AllergyIntolerance},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new allergy
+pub async fn create_allergy_intolerance(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(allergy): FhirBody<AllergyIntolerance>,
+) -> Result<(StatusCode, Json<SuccessResponse<AllergyIntolerance>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.allergy_intolerance_service.create(&context, allergy).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get an allergy by ID
+pub async fn get_allergy_intolerance(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<AllergyIntolerance>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let allergy = state.allergy_intolerance_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(allergy)))
+}
+
+/// Update an allergy
+pub async fn update_allergy_intolerance(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(allergy): FhirBody<AllergyIntolerance>,
+) -> Result<Json<SuccessResponse<AllergyIntolerance>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.allergy_intolerance_service.update(&context, &id, allergy).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete an allergy
+pub async fn delete_allergy_intolerance(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.allergy_intolerance_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_allergy_intolerances`
+pub const ALLERGY_INTOLERANCE_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The patient's ID",
+    },
+    SearchParamDef {
+        name: "clinical-status",
+        type_: "token",
+        documentation: "active, inactive or resolved; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "verification-status",
+        type_: "token",
+        documentation: "unconfirmed, confirmed, refuted or entered-in-error; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "criticality",
+        type_: "token",
+        documentation: "low, high or unable-to-assess; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "category",
+        type_: "token",
+        documentation: "food, medication, environment or biologic",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "The allergy or intolerance code, system|code or code",
+    },
+    SearchParamDef {
+        name: "date",
+        type_: "date",
+        documentation: "Date recorded, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search allergies
+#[derive(Debug, Deserialize)]
+pub struct AllergyIntoleranceSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    #[serde(rename = "clinical-status")]
+    pub clinical_status: Option<String>,
+    #[serde(rename = "verification-status")]
+    pub verification_status: Option<String>,
+    pub criticality: Option<String>,
+    pub category: Option<String>,
+    pub code: Option<String>,
+    pub date: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_allergy_intolerances(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<AllergyIntoleranceSearchQuery>,
+) -> Result<Json<PaginatedResponse<AllergyIntolerance>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("clinical-status", query.clinical_status),
+            ("verification-status", query.verification_status),
+            ("criticality", query.criticality),
+            ("category", query.category),
+            ("code", query.code),
+            ("date", query.date),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.allergy_intolerance_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get allergy history
+pub async fn get_allergy_intolerance_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<AllergyIntolerance>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.allergy_intolerance_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate an allergy without persisting it (AllergyIntolerance/$validate)
+pub async fn validate_allergy_intolerance(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let allergy = read_validate_body::<AllergyIntolerance>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.allergy_intolerance_service
+            .validate_operation(&context, mode, id.as_deref(), allergy.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,291 @@
+// src/api/handlers/binary.rs
+// Binary content: raw upload and download with range requests, or the Binary
+// resource with base64 data when a FHIR media type is used
+
+use std::ops::Range;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::TryStreamExt;
+
+use crate::{
+    AppState,
+    domain::{Binary, Code, FhirError, FhirResult, FhirString, Reference},
+    repository::{BlobStream, StoredBinary},
+    api::{format::{parse_resource, Negotiated}, responses::SuccessResponse, OptionalAuthUser},
+};
+use super::common::extract_optional_security_context;
+
+/// Header naming the resource whose access rules apply to uploaded content
+const SECURITY_CONTEXT: &str = "x-security-context";
+
+/// Create a Binary. A FHIR Content-Type carries a Binary resource with base64
+/// `data`; any other Content-Type is the content itself, streamed to storage
+pub async fn create_binary(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<(StatusCode, Json<SuccessResponse<Binary>>), FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = if is_resource_body(&headers) {
+        let binary = read_resource(&state, &headers, body).await?;
+        state.binary_service.create_resource(&context, binary).await?
+    } else {
+        state.binary_service.create(&context, content_binary(&headers)?, content_stream(body)).await?
+    };
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created.binary))))
+}
+
+/// Read a Binary. Its content is returned in its own media type, honoring a
+/// single byte `Range`, unless a FHIR media type asks for the resource
+pub async fn get_binary(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Result<Response, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    if Negotiated::wants_binary_resource(&uri, &headers) {
+        let binary = state.binary_service.get_with_data(&context, &id).await?;
+        return Ok(Json(SuccessResponse::new(binary)).into_response());
+    }
+
+    let stored = state.binary_service.get(&context, &id).await?;
+    let size = stored.blob.size;
+    let range = headers.get(header::RANGE).and_then(|range| range.to_str().ok());
+    let (status, range) = match byte_range(range, size) {
+        ByteRange::Full => (StatusCode::OK, None),
+        ByteRange::Partial(range) => (StatusCode::PARTIAL_CONTENT, Some(range)),
+        ByteRange::Unsatisfiable => {
+            let content_range = format!("bytes */{}", size);
+            return Ok((
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, content_range)],
+            ).into_response());
+        }
+    };
+
+    let content = state.binary_service.content(&stored, range.clone()).await?;
+    let mut response = Response::new(Body::from_stream(content));
+    *response.status_mut() = status;
+    let response_headers = response.headers_mut();
+    insert_header(response_headers, header::CONTENT_TYPE, &stored.binary.content_type.0);
+    insert_header(response_headers, header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    if !is_safe_inline(&stored.binary.content_type.0) {
+        insert_header(response_headers, header::CONTENT_DISPOSITION, "attachment");
+    }
+    insert_header(response_headers, header::ACCEPT_RANGES, "bytes");
+    insert_header(response_headers, header::HeaderName::from_static("digest"), &format!("sha={}", stored.blob.hash));
+    match range {
+        Some(range) => {
+            insert_header(response_headers, header::CONTENT_LENGTH, &(range.end - range.start).to_string());
+            insert_header(
+                response_headers,
+                header::CONTENT_RANGE,
+                &format!("bytes {}-{}/{}", range.start, range.end - 1, size),
+            );
+        }
+        None => insert_header(response_headers, header::CONTENT_LENGTH, &size.to_string()),
+    }
+    Ok(response)
+}
+
+/// Replace a Binary and its content, as `create_binary` reads them
+pub async fn update_binary(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<SuccessResponse<Binary>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated: StoredBinary = if is_resource_body(&headers) {
+        let binary = read_resource(&state, &headers, body).await?;
+        state.binary_service.update_resource(&context, &id, binary).await?
+    } else {
+        state.binary_service.update(&context, &id, content_binary(&headers)?, content_stream(body)).await?
+    };
+    Ok(Json(SuccessResponse::new(updated.binary)))
+}
+
+/// Delete a Binary and its content
+pub async fn delete_binary(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.binary_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get binary history. Versions carry metadata only; just the current
+/// content is kept
+pub async fn get_binary_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Binary>>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.binary_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Whether an upload is a FHIR Binary resource rather than raw content
+fn is_resource_body(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.to_ascii_lowercase().contains("fhir"))
+}
+
+/// Read a Binary resource body. Its base64 data takes a third more room than
+/// the content, plus some for the rest of the resource
+async fn read_resource(state: &AppState, headers: &HeaderMap, body: Body) -> FhirResult<Binary> {
+    let max_size = usize::try_from(state.binary_service.max_size()).unwrap_or(usize::MAX);
+    let limit = max_size.saturating_mul(4) / 3 + 64 * 1024;
+    let bytes = to_bytes(body, limit)
+        .await
+        .map_err(|_| FhirError::PayloadTooLarge(format!("Binary resource exceeds {} bytes", limit)))?;
+    parse_resource(headers, &bytes)
+}
+
+/// The Binary describing raw uploaded content: its Content-Type, and the
+/// `X-Security-Context` reference when given
+fn content_binary(headers: &HeaderMap) -> FhirResult<Binary> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(str::trim)
+        .filter(|content_type| !content_type.is_empty())
+        .ok_or_else(|| FhirError::MissingRequiredField("Content-Type header".to_string()))?;
+
+    let mut binary = Binary::new(Code(content_type.to_string()));
+    binary.security_context = headers
+        .get(SECURITY_CONTEXT)
+        .and_then(|context| context.to_str().ok())
+        .map(|context| Reference {
+            reference: Some(FhirString(context.trim().to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+    Ok(binary)
+}
+
+fn content_stream(body: Body) -> BlobStream {
+    Box::pin(body.into_data_stream().map_err(std::io::Error::other))
+}
+
+/// Whether content may be shown inline by a browser: images other than SVG,
+/// which can carry script, PDF, audio and video. Anything else, HTML above
+/// all, is served as an attachment
+fn is_safe_inline(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    match essence.split_once('/') {
+        Some(("image", subtype)) => subtype != "svg+xml",
+        Some(("audio" | "video", _)) => true,
+        _ => essence == "application/pdf",
+    }
+}
+
+fn insert_header(headers: &mut HeaderMap, name: header::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+/// Part of the content a `Range` header selects
+#[derive(Debug, PartialEq)]
+enum ByteRange {
+    Full,
+    Partial(Range<u64>),
+    Unsatisfiable,
+}
+
+/// Parse a single `bytes=` range (`a-b`, `a-` or `-n`) against content of
+/// `size` bytes. Other units, multiple ranges and malformed values are
+/// ignored and the full content is served, as HTTP allows
+fn byte_range(range: Option<&str>, size: u64) -> ByteRange {
+    let Some(spec) = range.and_then(|range| range.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+    let Some((start, end)) = spec.trim().split_once('-').filter(|_| !spec.contains(',')) else {
+        return ByteRange::Full;
+    };
+
+    match (start.trim(), end.trim()) {
+        // The last n bytes
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => ByteRange::Unsatisfiable,
+            Ok(_) if size == 0 => ByteRange::Unsatisfiable,
+            Ok(length) => ByteRange::Partial(size.saturating_sub(length)..size),
+            Err(_) => ByteRange::Full,
+        },
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return ByteRange::Full;
+            };
+            let end = match end {
+                "" => size,
+                end => match end.parse::<u64>() {
+                    Ok(end) if end >= start => end.saturating_add(1).min(size),
+                    _ => return ByteRange::Full,
+                },
+            };
+            if start >= size {
+                ByteRange::Unsatisfiable
+            } else {
+                ByteRange::Partial(start..end)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_range_forms() {
+        assert_eq!(byte_range(None, 100), ByteRange::Full);
+        assert_eq!(byte_range(Some("bytes=0-9"), 100), ByteRange::Partial(0..10));
+        assert_eq!(byte_range(Some("bytes=90-"), 100), ByteRange::Partial(90..100));
+        assert_eq!(byte_range(Some("bytes=-10"), 100), ByteRange::Partial(90..100));
+        // Ranges running past the end are cut short
+        assert_eq!(byte_range(Some("bytes=50-500"), 100), ByteRange::Partial(50..100));
+        assert_eq!(byte_range(Some("bytes=-500"), 100), ByteRange::Partial(0..100));
+    }
+
+    #[test]
+    fn test_byte_range_unsatisfiable_and_ignored() {
+        assert_eq!(byte_range(Some("bytes=100-"), 100), ByteRange::Unsatisfiable);
+        assert_eq!(byte_range(Some("bytes=-0"), 100), ByteRange::Unsatisfiable);
+        assert_eq!(byte_range(Some("bytes=0-"), 0), ByteRange::Unsatisfiable);
+
+        // Served whole: other units, multiple ranges and malformed values
+        assert_eq!(byte_range(Some("items=0-9"), 100), ByteRange::Full);
+        assert_eq!(byte_range(Some("bytes=0-9,20-29"), 100), ByteRange::Full);
+        assert_eq!(byte_range(Some("bytes=9-0"), 100), ByteRange::Full);
+        assert_eq!(byte_range(Some("bytes=a-b"), 100), ByteRange::Full);
+    }
+
+    #[test]
+    fn test_only_safe_types_are_served_inline() {
+        assert!(is_safe_inline("image/png"));
+        assert!(is_safe_inline("Application/PDF; name=report.pdf"));
+        assert!(is_safe_inline("video/mp4"));
+
+        assert!(!is_safe_inline("image/svg+xml"));
+        assert!(!is_safe_inline("text/html; charset=utf-8"));
+        assert!(!is_safe_inline("application/javascript"));
+        assert!(!is_safe_inline("application/octet-stream"));
+    }
+}
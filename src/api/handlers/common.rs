@@ -1,8 +1,12 @@
 // src/api/handlers/common.rs
 
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use crate::domain::{Bundle, FhirError, FhirResult};
 use crate::service::{SearchParameters, SecurityContext};
-use crate::api::{OptionalAuthUser, AuthUser};
+use crate::api::{capability::OperationDef, format::parse_resource, OptionalAuthUser, AuthUser};
 
 /// Common query parameters for search endpoints
 #[derive(Debug, Deserialize)]
@@ -13,15 +17,70 @@ pub struct SearchQuery {
     pub offset: Option<u32>,
     #[serde(rename = "_sort")]
     pub sort: Option<String>,
+    #[serde(rename = "_tag")]
+    pub tag: Option<String>,
+    #[serde(rename = "_profile")]
+    pub profile: Option<String>,
+    #[serde(rename = "_security")]
+    pub security: Option<String>,
 }
 
 impl SearchQuery {
     pub fn into_search_params(self) -> SearchParameters {
+        let filters = [("_tag", self.tag), ("_profile", self.profile), ("_security", self.security)]
+            .into_iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value?)))
+            .collect();
+
         SearchParameters {
             count: self.count,
             offset: self.offset,
             sort: self.sort,
-            filters: Vec::new(),
+            filters,
+        }
+    }
+
+    /// Refuse `_tag`, `_profile` and `_security` alongside a search
+    /// `parameter` whose dedicated query cannot apply them
+    pub fn reject_meta_filters(&self, parameter: &str) -> FhirResult<()> {
+        if self.tag.is_some() || self.profile.is_some() || self.security.is_some() {
+            return Err(FhirError::Validation(format!(
+                "_tag, _profile and _security cannot be combined with {}", parameter
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Resource $validate, mounted for every resource type
+pub const RESOURCE_VALIDATE: OperationDef = OperationDef {
+    name: "validate",
+    definition: "http://hl7.org/fhir/OperationDefinition/Resource-validate",
+};
+
+/// Query parameters of $validate
+#[derive(Debug, Deserialize)]
+pub struct ValidateQuery {
+    pub mode: Option<String>,
+}
+
+/// Read the resource posted to $validate, if any. Content that cannot be
+/// parsed is recorded as an issue, since reporting it is the point of the call
+pub fn read_validate_body<T: DeserializeOwned>(
+    headers: &HeaderMap,
+    body: &[u8],
+    issues: &mut Vec<FhirError>,
+) -> FhirResult<Option<T>> {
+    if body.is_empty() {
+        return Ok(None);
+    }
+
+    match parse_resource(headers, body) {
+        Ok(resource) => Ok(Some(resource)),
+        Err(e @ FhirError::UnsupportedMediaType(_)) => Err(e),
+        Err(e) => {
+            issues.push(e);
+            Ok(None)
         }
     }
 }
@@ -38,3 +97,100 @@ pub fn extract_optional_security_context(optional_auth: &OptionalAuthUser) -> Se
         None => SecurityContext::system(),
     }
 }
+
+/// Derive the externally visible base URL from the request headers
+pub fn base_url(headers: &HeaderMap) -> String {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("localhost:8080");
+    let scheme = headers
+        .get("x-forwarded-proto")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("http");
+    format!("{}://{}", scheme, host)
+}
+
+/// Parse a date or dateTime query parameter. A bare date means the start of
+/// that day, or its last second when `end_of_day` is set
+pub fn parse_date_param(name: &str, value: &str, end_of_day: bool) -> FhirResult<DateTime<Utc>> {
+    // An unencoded '+' in a timezone offset decodes to a space
+    let value = value.trim().replace(' ', "+");
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(&value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| {
+            if end_of_day {
+                date.and_hms_opt(23, 59, 59)
+            } else {
+                date.and_hms_opt(0, 0, 0)
+            }
+        })
+        .map(|date_time| date_time.and_utc())
+        .ok_or_else(|| FhirError::Validation(format!("Invalid date for {}: {}", name, value)))
+}
+
+/// Add self/next/previous links to a searchset Bundle page. `url` is the
+/// request URL without its query string; other query parameters are kept
+pub fn add_paging_links(bundle: &mut Bundle, url: &str, query: Option<&str>, offset: u32, count: u32) {
+    let kept: Vec<&str> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .filter(|param| !param.starts_with("_count=") && !param.starts_with("_offset="))
+        .collect();
+    let page_url = |offset: u32| {
+        let mut params = kept.join("&");
+        if !params.is_empty() {
+            params.push('&');
+        }
+        format!("{}?{}_count={}&_offset={}", url, params, count, offset)
+    };
+
+    let total = bundle.total.as_ref().map_or(0, |total| total.0);
+    bundle.add_link("self", page_url(offset));
+    if count > 0 && offset + count < total {
+        bundle.add_link("next", page_url(offset + count));
+    }
+    if offset > 0 {
+        bundle.add_link("previous", page_url(offset.saturating_sub(count)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::UnsignedInt;
+
+    #[test]
+    fn test_parse_date_param() {
+        let start = parse_date_param("start", "2024-03-01", false).unwrap();
+        let end = parse_date_param("end", "2024-03-01", true).unwrap();
+        assert_eq!(start.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2024-03-01T23:59:59+00:00");
+
+        let since = parse_date_param("_since", "2024-03-01T10:00:00 02:00", false).unwrap();
+        assert_eq!(since.to_rfc3339(), "2024-03-01T08:00:00+00:00");
+
+        assert!(parse_date_param("start", "March", false).is_err());
+    }
+
+    #[test]
+    fn test_paging_links_keep_other_params() {
+        let mut bundle = Bundle::new("searchset");
+        bundle.total = Some(UnsignedInt(25));
+
+        add_paging_links(&mut bundle, "http://h/fhir/Patient/1/$everything", Some("_type=Observation&_offset=10"), 10, 10);
+
+        let links = bundle.link.unwrap();
+        let urls: Vec<(&str, &str)> = links.iter().map(|l| (l.relation.0.as_str(), l.url.0.as_str())).collect();
+        assert_eq!(urls, vec![
+            ("self", "http://h/fhir/Patient/1/$everything?_type=Observation&_count=10&_offset=10"),
+            ("next", "http://h/fhir/Patient/1/$everything?_type=Observation&_count=10&_offset=20"),
+            ("previous", "http://h/fhir/Patient/1/$everything?_type=Observation&_count=10&_offset=0"),
+        ]);
+    }
+}
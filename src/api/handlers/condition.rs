@@ -1,25 +1,29 @@
 // src/api/handlers/condition.rs
 
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::Deserialize;
 
 use crate::{
     AppState,
-    domain::Condition,
-    service::ResourceService,
-    api::{responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+    domain::{Condition, OperationOutcome},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
 };
-use super::common::{SearchQuery, extract_optional_security_context};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
 
 /// Create a new condition
 pub async fn create_condition(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
-    Json(condition): Json<Condition>,
+    FhirBody(condition): FhirBody<Condition>,
 ) -> Result<(StatusCode, Json<SuccessResponse<Condition>>), crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let created = state.condition_service.create(&context, condition).await?;
@@ -42,7 +46,7 @@ pub async fn update_condition(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(condition): Json<Condition>,
+    FhirBody(condition): FhirBody<Condition>,
 ) -> Result<Json<SuccessResponse<Condition>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let updated = state.condition_service.update(&context, &id, condition).await?;
@@ -60,6 +64,20 @@ pub async fn delete_condition(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Search parameters honored by `search_conditions`
+pub const CONDITION_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The subject patient's ID",
+    },
+    SearchParamDef {
+        name: "clinical-status",
+        type_: "token",
+        documentation: "Only `active` is supported, combined with `patient`",
+    },
+];
+
 /// Search conditions
 #[derive(Debug, Deserialize)]
 pub struct ConditionSearchQuery {
@@ -77,6 +95,9 @@ pub async fn search_conditions(
     Query(query): Query<ConditionSearchQuery>,
 ) -> Result<Json<PaginatedResponse<Condition>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
+    if query.patient.is_some() {
+        query.common.reject_meta_filters("patient")?;
+    }
 
     // If searching for active conditions by patient
     if let Some(patient_id) = &query.patient {
@@ -124,3 +145,27 @@ pub async fn get_condition_history(
     // TODO: Implement history tracking
     Ok(Json(SuccessResponse::new(vec![])))
 }
+
+/// Validate a condition without persisting it (Condition/$validate)
+pub async fn validate_condition(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let condition = read_validate_body::<Condition>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.condition_service
+            .validate_operation(&context, mode, id.as_deref(), condition.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,226 @@
+// src/api/handlers/diagnostic_report.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, DiagnosticReport, FhirError},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new diagnostic report
+pub async fn create_diagnostic_report(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(report): FhirBody<DiagnosticReport>,
+) -> Result<(StatusCode, Json<SuccessResponse<DiagnosticReport>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.diagnostic_report_service.create(&context, report).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a diagnostic report by ID
+pub async fn get_diagnostic_report(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<DiagnosticReport>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let report = state.diagnostic_report_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(report)))
+}
+
+/// Update a diagnostic report
+pub async fn update_diagnostic_report(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(report): FhirBody<DiagnosticReport>,
+) -> Result<Json<SuccessResponse<DiagnosticReport>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.diagnostic_report_service.update(&context, &id, report).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a diagnostic report
+pub async fn delete_diagnostic_report(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.diagnostic_report_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_diagnostic_reports`
+pub const DIAGNOSTIC_REPORT_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The patient's ID",
+    },
+    SearchParamDef {
+        name: "subject",
+        type_: "reference",
+        documentation: "Same as patient",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "registered, partial, preliminary, final, amended, corrected, appended, cancelled, entered-in-error or unknown; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "category",
+        type_: "token",
+        documentation: "Code of the first category, such as LAB",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "The report code, system|code or code",
+    },
+    SearchParamDef {
+        name: "date",
+        type_: "date",
+        documentation: "Clinically relevant time, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "issued",
+        type_: "date",
+        documentation: "When the report was issued, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "result",
+        type_: "reference",
+        documentation: "An Observation in the report",
+    },
+    SearchParamDef {
+        name: "encounter",
+        type_: "reference",
+        documentation: "The encounter's ID",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// The `_include` that `search_diagnostic_reports` resolves
+pub const DIAGNOSTIC_REPORT_RESULT_INCLUDE: &str = "DiagnosticReport:result";
+
+/// Search diagnostic reports
+#[derive(Debug, Deserialize)]
+pub struct DiagnosticReportSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    pub subject: Option<String>,
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub code: Option<String>,
+    pub date: Option<String>,
+    pub issued: Option<String>,
+    pub result: Option<String>,
+    pub encounter: Option<String>,
+    pub identifier: Option<String>,
+    #[serde(rename = "_include")]
+    pub include: Option<String>,
+}
+
+pub async fn search_diagnostic_reports(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<DiagnosticReportSearchQuery>,
+) -> Result<Json<PaginatedResponse<DiagnosticReport>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    // `DiagnosticReport:result:Observation` names the only target type there is
+    let include_results = match query.include.as_deref() {
+        None => false,
+        Some(DIAGNOSTIC_REPORT_RESULT_INCLUDE) | Some("DiagnosticReport:result:Observation") => true,
+        Some(other) => {
+            return Err(FhirError::Validation(format!("Unsupported _include for DiagnosticReport: {}", other)));
+        }
+    };
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("subject", query.subject),
+            ("status", query.status),
+            ("category", query.category),
+            ("code", query.code),
+            ("date", query.date),
+            ("issued", query.issued),
+            ("result", query.result),
+            ("encounter", query.encounter),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.diagnostic_report_service.search(&context, params).await?;
+
+    let mut included = Vec::new();
+    if include_results {
+        for observation in state.diagnostic_report_service.include_results(&context, &result.resources).await? {
+            included.push(serde_json::to_value(observation)?);
+        }
+    }
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    ).with_included(included)))
+}
+
+/// Get diagnostic report history
+pub async fn get_diagnostic_report_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<DiagnosticReport>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.diagnostic_report_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a diagnostic report without persisting it (DiagnosticReport/$validate)
+pub async fn validate_diagnostic_report(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let report = read_validate_body::<DiagnosticReport>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.diagnostic_report_service
+            .validate_operation(&context, mode, id.as_deref(), report.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
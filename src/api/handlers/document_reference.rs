@@ -0,0 +1,193 @@
+// src/api/handlers/document_reference.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, DocumentReference},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new document reference. Inline attachment data is moved to
+/// Binary resources
+pub async fn create_document_reference(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(document_reference): FhirBody<DocumentReference>,
+) -> Result<(StatusCode, Json<SuccessResponse<DocumentReference>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.document_reference_service.create(&context, document_reference).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a document reference by ID
+pub async fn get_document_reference(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<DocumentReference>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let document_reference = state.document_reference_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(document_reference)))
+}
+
+/// Update a document reference
+pub async fn update_document_reference(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(document_reference): FhirBody<DocumentReference>,
+) -> Result<Json<SuccessResponse<DocumentReference>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.document_reference_service.update(&context, &id, document_reference).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a document reference
+pub async fn delete_document_reference(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.document_reference_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_document_references`
+pub const DOCUMENT_REFERENCE_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The patient's ID",
+    },
+    SearchParamDef {
+        name: "subject",
+        type_: "reference",
+        documentation: "Who the document is about, as Type/id",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "current, superseded or entered-in-error; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "type",
+        type_: "token",
+        documentation: "The kind of document, system|code or code",
+    },
+    SearchParamDef {
+        name: "category",
+        type_: "token",
+        documentation: "Categorization of the document, system|code or code",
+    },
+    SearchParamDef {
+        name: "date",
+        type_: "date",
+        documentation: "When the reference was created, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "Master or other identifier, system|value or value",
+    },
+    SearchParamDef {
+        name: "contenttype",
+        type_: "token",
+        documentation: "MIME type of the content, such as application/pdf",
+    },
+];
+
+/// Search document references
+#[derive(Debug, Deserialize)]
+pub struct DocumentReferenceSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    pub subject: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub category: Option<String>,
+    pub date: Option<String>,
+    pub identifier: Option<String>,
+    pub contenttype: Option<String>,
+}
+
+pub async fn search_document_references(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<DocumentReferenceSearchQuery>,
+) -> Result<Json<PaginatedResponse<DocumentReference>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("subject", query.subject),
+            ("status", query.status),
+            ("type", query.type_),
+            ("category", query.category),
+            ("date", query.date),
+            ("identifier", query.identifier),
+            ("contenttype", query.contenttype),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.document_reference_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get document reference history
+pub async fn get_document_reference_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<DocumentReference>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.document_reference_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a document reference without persisting it (DocumentReference/$validate)
+pub async fn validate_document_reference(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let document_reference = read_validate_body::<DocumentReference>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.document_reference_service
+            .validate_operation(&context, mode, id.as_deref(), document_reference.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -1,25 +1,29 @@
 // src/api/handlers/encounter.rs
 
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::Deserialize;
 
 use crate::{
     AppState,
-    domain::Encounter,
-    service::ResourceService,
-    api::{responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+    domain::{Bundle, Encounter, OperationOutcome},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
 };
-use super::common::{SearchQuery, extract_optional_security_context};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::{OperationDef, SearchParamDef};
 
 /// Create a new encounter
 pub async fn create_encounter(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
-    Json(encounter): Json<Encounter>,
+    FhirBody(encounter): FhirBody<Encounter>,
 ) -> Result<(StatusCode, Json<SuccessResponse<Encounter>>), crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let created = state.encounter_service.create(&context, encounter).await?;
@@ -42,7 +46,7 @@ pub async fn update_encounter(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(encounter): Json<Encounter>,
+    FhirBody(encounter): FhirBody<Encounter>,
 ) -> Result<Json<SuccessResponse<Encounter>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let updated = state.encounter_service.update(&context, &id, encounter).await?;
@@ -60,6 +64,20 @@ pub async fn delete_encounter(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Search parameters honored by `search_encounters`
+pub const ENCOUNTER_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The subject patient's ID",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "Only `in-progress` is supported, combined with `patient`",
+    },
+];
+
 /// Search encounters
 #[derive(Debug, Deserialize)]
 pub struct EncounterSearchQuery {
@@ -77,6 +95,9 @@ pub async fn search_encounters(
     Query(query): Query<EncounterSearchQuery>,
 ) -> Result<Json<PaginatedResponse<Encounter>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
+    if query.patient.is_some() {
+        query.common.reject_meta_filters("patient")?;
+    }
 
     // If searching for active encounters by patient
     if let Some(patient_id) = &query.patient {
@@ -124,3 +145,44 @@ pub async fn get_encounter_history(
     // TODO: Implement history tracking
     Ok(Json(SuccessResponse::new(vec![])))
 }
+
+/// Validate a encounter without persisting it (Encounter/$validate)
+pub async fn validate_encounter(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let encounter = read_validate_body::<Encounter>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.encounter_service
+            .validate_operation(&context, mode, id.as_deref(), encounter.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
+
+/// Encounter/$everything, as advertised in the CapabilityStatement
+pub const ENCOUNTER_EVERYTHING: OperationDef = OperationDef {
+    name: "everything",
+    definition: "http://hl7.org/fhir/OperationDefinition/Encounter-everything",
+};
+
+/// Get everything tied to one visit (Encounter/$everything)
+pub async fn encounter_everything(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Bundle>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let bundle = state.everything_service.encounter_everything(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(bundle)))
+}
@@ -0,0 +1,184 @@
+// src/api/handlers/export.rs
+// Bulk Data $export: kick-off, status polling, cancellation and file download
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, FhirError, FhirResult},
+    domain::resources::operation_outcome::OperationOutcomeIssue,
+    service::{ExportLevel, ExportParameters, ExportStatus},
+    api::AuthUser,
+};
+use super::common::{extract_security_context, base_url, parse_date_param};
+use crate::api::capability::OperationDef;
+
+/// System-level $export, as advertised in the CapabilityStatement
+pub const SYSTEM_EXPORT: OperationDef = OperationDef {
+    name: "export",
+    definition: "http://hl7.org/fhir/uv/bulkdata/OperationDefinition/export",
+};
+
+/// Patient/$export, as advertised in the CapabilityStatement
+pub const PATIENT_EXPORT: OperationDef = OperationDef {
+    name: "export",
+    definition: "http://hl7.org/fhir/uv/bulkdata/OperationDefinition/patient-export",
+};
+
+/// Group/[id]/$export, as advertised in the CapabilityStatement
+pub const GROUP_EXPORT: OperationDef = OperationDef {
+    name: "export",
+    definition: "http://hl7.org/fhir/uv/bulkdata/OperationDefinition/group-export",
+};
+
+/// Seconds clients are asked to wait between status polls
+const RETRY_AFTER_SECONDS: &str = "10";
+
+/// Export every resource on the server (`$export`)
+pub async fn system_export(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    Query(query): Query<Vec<(String, String)>>,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    kick_off(&auth, &state, ExportLevel::System, &headers, &uri, &query)
+}
+
+/// Export every patient compartment (`Patient/$export`)
+pub async fn patient_export(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    Query(query): Query<Vec<(String, String)>>,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    kick_off(&auth, &state, ExportLevel::Patient, &headers, &uri, &query)
+}
+
+/// Export the compartments of a group's current patient members
+/// (`Group/[id]/$export`)
+pub async fn group_export(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    uri: Uri,
+    Query(query): Query<Vec<(String, String)>>,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    let context = extract_security_context(&auth);
+    let patients = state.group_service.patient_ids(&context, &id).await?;
+    kick_off(&auth, &state, ExportLevel::Group(patients), &headers, &uri, &query)
+}
+
+/// Start the background job and point the client at its status endpoint
+fn kick_off(
+    auth: &AuthUser,
+    state: &AppState,
+    level: ExportLevel,
+    headers: &HeaderMap,
+    uri: &Uri,
+    query: &[(String, String)],
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    let context = extract_security_context(auth);
+
+    let respond_async = headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.split(',').any(|p| p.trim() == "respond-async"));
+    if !respond_async {
+        return Err(FhirError::Validation(
+            "$export requires the Prefer: respond-async header".to_string(),
+        ));
+    }
+
+    let base = base_url(headers);
+    let params = export_parameters(query)?;
+    let job_id = state.bulk_export_service
+        .kick_off(&context, level, params, &format!("{}{}", base, uri), &base)?;
+
+    let mut response_headers = HeaderMap::new();
+    let status_url = format!("{}/fhir/bulk-status/{}", base, job_id);
+    if let Ok(value) = HeaderValue::from_str(&status_url) {
+        response_headers.insert(header::CONTENT_LOCATION, value);
+    }
+    Ok((StatusCode::ACCEPTED, response_headers))
+}
+
+/// Read `_type`, `_since`, `_typeFilter` (repeatable) and `_outputFormat`
+fn export_parameters(query: &[(String, String)]) -> FhirResult<ExportParameters> {
+    let mut params = ExportParameters::default();
+    for (name, value) in query {
+        match name.as_str() {
+            "_type" => params.types.get_or_insert_with(Vec::new).extend(
+                value.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+            ),
+            "_since" => params.since = Some(parse_date_param("_since", value, false)?),
+            "_typeFilter" => params.type_filters.push(value.clone()),
+            "_outputFormat" => params.output_format = Some(value.clone()),
+            _ => {}
+        }
+    }
+    Ok(params)
+}
+
+/// Poll an export: 202 with `X-Progress` while it runs, then the manifest
+pub async fn export_status(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Response, FhirError> {
+    let context = extract_security_context(&auth);
+
+    let response = match state.bulk_export_service.status(&context, &job_id)? {
+        ExportStatus::InProgress(progress) => {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RETRY_AFTER, HeaderValue::from_static(RETRY_AFTER_SECONDS));
+            if let Ok(value) = HeaderValue::from_str(&progress) {
+                headers.insert("x-progress", value);
+            }
+            (StatusCode::ACCEPTED, headers).into_response()
+        }
+        ExportStatus::Completed(manifest) => Json(manifest).into_response(),
+        ExportStatus::Failed(message) => {
+            let outcome = OperationOutcome::new(vec![OperationOutcomeIssue::new("error", "exception", message)]);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(outcome)).into_response()
+        }
+    };
+    Ok(response)
+}
+
+/// Cancel a running export, or release a completed one and its files
+pub async fn cancel_export(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<StatusCode, FhirError> {
+    let context = extract_security_context(&auth);
+    state.bulk_export_service.cancel(&context, &job_id).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Stream one NDJSON output file of a completed export
+pub async fn export_file(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path((job_id, file_name)): Path<(String, String)>,
+) -> Result<Response, FhirError> {
+    let context = extract_security_context(&auth);
+    let path = state.bulk_export_service.file_path(&context, &job_id, &file_name)?;
+
+    let file = tokio::fs::File::open(&path)
+        .await
+        .map_err(|e| FhirError::Storage(e.to_string()))?;
+    let body = Body::from_stream(ReaderStream::new(file));
+    Ok(([(header::CONTENT_TYPE, "application/fhir+ndjson")], body).into_response())
+}
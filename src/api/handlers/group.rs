@@ -0,0 +1,193 @@
+// src/api/handlers/group.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Group},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new group
+pub async fn create_group(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(group): FhirBody<Group>,
+) -> Result<(StatusCode, Json<SuccessResponse<Group>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.group_service.create(&context, group).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a group by ID
+pub async fn get_group(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Group>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let group = state.group_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(group)))
+}
+
+/// Update a group
+pub async fn update_group(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(group): FhirBody<Group>,
+) -> Result<Json<SuccessResponse<Group>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.group_service.update(&context, &id, group).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a group
+pub async fn delete_group(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.group_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_groups`
+pub const GROUP_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "name",
+        type_: "string",
+        documentation: "Group name (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "type",
+        type_: "token",
+        documentation: "person, animal, practitioner, device, medication or substance; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "actual",
+        type_: "token",
+        documentation: "true for groups that list their members, false for definitional groups",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "Kind of group, system|code or code",
+    },
+    SearchParamDef {
+        name: "characteristic",
+        type_: "token",
+        documentation: "Code of a characteristic, system|code or code",
+    },
+    SearchParamDef {
+        name: "managing-entity",
+        type_: "reference",
+        documentation: "The managing entity's ID",
+    },
+    SearchParamDef {
+        name: "member",
+        type_: "reference",
+        documentation: "A member entity, e.g. Patient/123; a bare ID matches members of any type",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search groups
+#[derive(Debug, Deserialize)]
+pub struct GroupSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub actual: Option<String>,
+    pub code: Option<String>,
+    pub characteristic: Option<String>,
+    #[serde(rename = "managing-entity")]
+    pub managing_entity: Option<String>,
+    pub member: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_groups(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<GroupSearchQuery>,
+) -> Result<Json<PaginatedResponse<Group>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("name", query.name),
+            ("type", query.type_),
+            ("actual", query.actual),
+            ("code", query.code),
+            ("characteristic", query.characteristic),
+            ("managing-entity", query.managing_entity),
+            ("member", query.member),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.group_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get group history
+pub async fn get_group_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Group>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.group_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a group without persisting it (Group/$validate)
+pub async fn validate_group(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let group = read_validate_body::<Group>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.group_service
+            .validate_operation(&context, mode, id.as_deref(), group.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,171 @@
+// src/api/handlers/immunization.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Immunization},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new immunization
+pub async fn create_immunization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(immunization): FhirBody<Immunization>,
+) -> Result<(StatusCode, Json<SuccessResponse<Immunization>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.immunization_service.create(&context, immunization).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get an immunization by ID
+pub async fn get_immunization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Immunization>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let immunization = state.immunization_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(immunization)))
+}
+
+/// Update an immunization
+pub async fn update_immunization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(immunization): FhirBody<Immunization>,
+) -> Result<Json<SuccessResponse<Immunization>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.immunization_service.update(&context, &id, immunization).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete an immunization
+pub async fn delete_immunization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.immunization_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_immunizations`
+pub const IMMUNIZATION_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The patient's ID",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "completed, entered-in-error or not-done; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "vaccine-code",
+        type_: "token",
+        documentation: "The vaccine code, system|code or code",
+    },
+    SearchParamDef {
+        name: "date",
+        type_: "date",
+        documentation: "Vaccination date, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search immunizations
+#[derive(Debug, Deserialize)]
+pub struct ImmunizationSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "vaccine-code")]
+    pub vaccine_code: Option<String>,
+    pub date: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_immunizations(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<ImmunizationSearchQuery>,
+) -> Result<Json<PaginatedResponse<Immunization>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("status", query.status),
+            ("vaccine-code", query.vaccine_code),
+            ("date", query.date),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.immunization_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get immunization history
+pub async fn get_immunization_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Immunization>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.immunization_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate an immunization without persisting it (Immunization/$validate)
+pub async fn validate_immunization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let immunization = read_validate_body::<Immunization>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.immunization_service
+            .validate_operation(&context, mode, id.as_deref(), immunization.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
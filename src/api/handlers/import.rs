@@ -0,0 +1,123 @@
+// src/api/handlers/import.rs
+// Bulk $import: kick-off, status polling and the per-line error report
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Parameters, FhirError},
+    domain::resources::operation_outcome::OperationOutcomeIssue,
+    service::bulk_import_service::import_summary,
+    api::{format::FhirBody, responses::SuccessResponse, AuthUser},
+};
+use super::common::{extract_security_context, base_url};
+use crate::api::capability::OperationDef;
+
+/// System-level $import, as advertised in the CapabilityStatement
+pub const SYSTEM_IMPORT: OperationDef = OperationDef {
+    name: "import",
+    definition: "http://hl7.org/fhir/uv/bulkdata/OperationDefinition/import",
+};
+
+/// Seconds clients are asked to wait between status polls
+const RETRY_AFTER_SECONDS: &str = "10";
+
+/// Import an NDJSON file from the server's import directory (`$import`).
+/// The body is a Parameters resource with one `input` whose parts are `url`
+/// (a path relative to the import directory) and an optional `type`
+pub async fn system_import(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    FhirBody(parameters): FhirBody<Parameters>,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    let context = extract_security_context(&auth);
+
+    let inputs: Vec<_> = parameters.parameter.iter().flatten()
+        .filter(|p| p.name.0 == "input")
+        .collect();
+    let [input] = inputs.as_slice() else {
+        return Err(FhirError::Validation("$import takes exactly one input parameter".to_string()));
+    };
+    let part = |name: &str| input.part.iter().flatten().find(|p| p.name.0 == name);
+    let url = part("url")
+        .and_then(|p| p.value_uri.as_ref().map(|u| u.0.clone()).or_else(|| p.value_string.as_ref().map(|s| s.0.clone())))
+        .ok_or_else(|| FhirError::MissingRequiredField("$import input requires a url part".to_string()))?;
+    let resource_type = part("type").and_then(|p| p.value_code.as_ref()).map(|c| c.0.clone());
+
+    let service = state.bulk_import_service.clone();
+    let source = service.resolve_input(&url).await?;
+    let job = service.start(&context, &source, resource_type.as_deref()).await?;
+    let job_id = job.id;
+    tokio::spawn(async move {
+        // Failures are recorded on the job and reported by the status endpoint
+        let _ = service.run(job).await;
+    });
+
+    let mut response_headers = HeaderMap::new();
+    let status_url = format!("{}/fhir/import-status/{}", base_url(&headers), job_id);
+    if let Ok(value) = HeaderValue::from_str(&status_url) {
+        response_headers.insert(header::CONTENT_LOCATION, value);
+    }
+    Ok((StatusCode::ACCEPTED, response_headers))
+}
+
+/// Poll an import: 202 with `X-Progress` while it runs, then a summary
+pub async fn import_status(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(job_id): Path<String>,
+) -> Result<Response, FhirError> {
+    let context = extract_security_context(&auth);
+    let job = state.bulk_import_service.status(&context, &job_id).await?;
+
+    let response = match job.status.as_str() {
+        "completed" => {
+            let error_url = format!("{}/fhir/import-status/{}/errors", base_url(&headers), job.id);
+            Json(SuccessResponse::new(import_summary(&job, &error_url))).into_response()
+        }
+        "failed" => {
+            let message = job.error.unwrap_or_else(|| "Import failed".to_string());
+            let outcome = OperationOutcome::new(vec![OperationOutcomeIssue::new("error", "exception", message)]);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(outcome)).into_response()
+        }
+        _ => {
+            let mut response_headers = HeaderMap::new();
+            response_headers.insert(header::RETRY_AFTER, HeaderValue::from_static(RETRY_AFTER_SECONDS));
+            let progress = format!(
+                "{} lines processed, {} imported, {} failed",
+                job.lines_processed, job.lines_imported, job.lines_failed
+            );
+            if let Ok(value) = HeaderValue::from_str(&progress) {
+                response_headers.insert("x-progress", value);
+            }
+            (StatusCode::ACCEPTED, response_headers).into_response()
+        }
+    };
+    Ok(response)
+}
+
+/// Stream the OperationOutcome NDJSON of the lines an import rejected
+pub async fn import_errors(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Response, FhirError> {
+    let context = extract_security_context(&auth);
+    let job = state.bulk_import_service.status(&context, &job_id).await?;
+
+    let body = match tokio::fs::File::open(state.bulk_import_service.error_file(job.id)).await {
+        Ok(file) => Body::from_stream(ReaderStream::new(file)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Body::empty(),
+        Err(e) => return Err(FhirError::Storage(e.to_string())),
+    };
+    Ok(([(header::CONTENT_TYPE, "application/fhir+ndjson")], body).into_response())
+}
@@ -0,0 +1,224 @@
+// src/api/handlers/location.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Location},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new location
+pub async fn create_location(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(location): FhirBody<Location>,
+) -> Result<(StatusCode, Json<SuccessResponse<Location>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.location_service.create(&context, location).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a location by ID
+pub async fn get_location(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Location>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let location = state.location_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(location)))
+}
+
+/// Update a location
+pub async fn update_location(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(location): FhirBody<Location>,
+) -> Result<Json<SuccessResponse<Location>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.location_service.update(&context, &id, location).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a location
+pub async fn delete_location(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.location_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_locations`
+pub const LOCATION_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "name",
+        type_: "string",
+        documentation: "Name or alias (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "address",
+        type_: "string",
+        documentation: "Any part of the address (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "address-city",
+        type_: "string",
+        documentation: "City (starts with, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "address-state",
+        type_: "string",
+        documentation: "State (starts with, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "address-postalcode",
+        type_: "string",
+        documentation: "Postal code (starts with, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "address-country",
+        type_: "string",
+        documentation: "Country (starts with, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "active, suspended or inactive; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "type",
+        type_: "token",
+        documentation: "Location type, system|code or code",
+    },
+    SearchParamDef {
+        name: "partof",
+        type_: "reference",
+        documentation: "The parent location's ID",
+    },
+    SearchParamDef {
+        name: "organization",
+        type_: "reference",
+        documentation: "The managing organization's ID",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+    SearchParamDef {
+        name: "near",
+        type_: "special",
+        documentation: "latitude|longitude|distance|unit; distance defaults to 10 and unit to km (m and [mi_i] also accepted). Results come nearest first",
+    },
+];
+
+/// Search locations
+#[derive(Debug, Deserialize)]
+pub struct LocationSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    #[serde(rename = "address-city")]
+    pub address_city: Option<String>,
+    #[serde(rename = "address-state")]
+    pub address_state: Option<String>,
+    #[serde(rename = "address-postalcode")]
+    pub address_postalcode: Option<String>,
+    #[serde(rename = "address-country")]
+    pub address_country: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub partof: Option<String>,
+    pub organization: Option<String>,
+    pub identifier: Option<String>,
+    pub near: Option<String>,
+}
+
+pub async fn search_locations(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<LocationSearchQuery>,
+) -> Result<Json<PaginatedResponse<Location>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("name", query.name),
+            ("address", query.address),
+            ("address-city", query.address_city),
+            ("address-state", query.address_state),
+            ("address-postalcode", query.address_postalcode),
+            ("address-country", query.address_country),
+            ("status", query.status),
+            ("type", query.type_),
+            ("partof", query.partof),
+            ("organization", query.organization),
+            ("identifier", query.identifier),
+            ("near", query.near),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.location_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get location history
+pub async fn get_location_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Location>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.location_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a location without persisting it (Location/$validate)
+pub async fn validate_location(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let location = read_validate_body::<Location>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.location_service
+            .validate_operation(&context, mode, id.as_deref(), location.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
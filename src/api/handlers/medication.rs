@@ -0,0 +1,156 @@
+// src/api/handlers/medication.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Medication},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new medication
+pub async fn create_medication(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(medication): FhirBody<Medication>,
+) -> Result<(StatusCode, Json<SuccessResponse<Medication>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.medication_service.create(&context, medication).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a medication by ID
+pub async fn get_medication(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Medication>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let medication = state.medication_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(medication)))
+}
+
+/// Update a medication
+pub async fn update_medication(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(medication): FhirBody<Medication>,
+) -> Result<Json<SuccessResponse<Medication>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.medication_service.update(&context, &id, medication).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a medication
+pub async fn delete_medication(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.medication_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_medications`
+pub const MEDICATION_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "Medication code, system|code or code",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "active | inactive | entered-in-error; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search medications
+#[derive(Debug, Deserialize)]
+pub struct MedicationSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub code: Option<String>,
+    pub status: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_medications(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<MedicationSearchQuery>,
+) -> Result<Json<PaginatedResponse<Medication>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("code", query.code),
+            ("status", query.status),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.medication_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get medication history
+pub async fn get_medication_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Medication>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.medication_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a medication without persisting it (Medication/$validate)
+pub async fn validate_medication(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let medication = read_validate_body::<Medication>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.medication_service
+            .validate_operation(&context, mode, id.as_deref(), medication.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,191 @@
+// src/api/handlers/medication_request.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, MedicationRequest},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new medication request
+pub async fn create_medication_request(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(medication_request): FhirBody<MedicationRequest>,
+) -> Result<(StatusCode, Json<SuccessResponse<MedicationRequest>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.medication_request_service.create(&context, medication_request).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a medication request by ID
+pub async fn get_medication_request(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<MedicationRequest>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let medication_request = state.medication_request_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(medication_request)))
+}
+
+/// Update a medication request
+pub async fn update_medication_request(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(medication_request): FhirBody<MedicationRequest>,
+) -> Result<Json<SuccessResponse<MedicationRequest>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.medication_request_service.update(&context, &id, medication_request).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a medication request
+pub async fn delete_medication_request(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.medication_request_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_medication_requests`
+pub const MEDICATION_REQUEST_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The subject patient's ID",
+    },
+    SearchParamDef {
+        name: "subject",
+        type_: "reference",
+        documentation: "Same as patient",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "Request status; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "intent",
+        type_: "token",
+        documentation: "Request intent; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "medicationCodeableConcept, system|code or code",
+    },
+    SearchParamDef {
+        name: "medication",
+        type_: "reference",
+        documentation: "The referenced Medication's ID",
+    },
+    SearchParamDef {
+        name: "authoredon",
+        type_: "date",
+        documentation: "Date written, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search medication requests
+#[derive(Debug, Deserialize)]
+pub struct MedicationRequestSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    pub subject: Option<String>,
+    pub status: Option<String>,
+    pub intent: Option<String>,
+    pub code: Option<String>,
+    pub medication: Option<String>,
+    pub authoredon: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_medication_requests(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<MedicationRequestSearchQuery>,
+) -> Result<Json<PaginatedResponse<MedicationRequest>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("subject", query.subject),
+            ("status", query.status),
+            ("intent", query.intent),
+            ("code", query.code),
+            ("medication", query.medication),
+            ("authoredon", query.authoredon),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.medication_request_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get medication request history
+pub async fn get_medication_request_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<MedicationRequest>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.medication_request_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a medication request without persisting it (MedicationRequest/$validate)
+pub async fn validate_medication_request(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let medication_request = read_validate_body::<MedicationRequest>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.medication_request_service
+            .validate_operation(&context, mode, id.as_deref(), medication_request.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,184 @@
+// src/api/handlers/medication_statement.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, MedicationStatement},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new medication statement
+pub async fn create_medication_statement(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(medication_statement): FhirBody<MedicationStatement>,
+) -> Result<(StatusCode, Json<SuccessResponse<MedicationStatement>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.medication_statement_service.create(&context, medication_statement).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a medication statement by ID
+pub async fn get_medication_statement(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<MedicationStatement>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let medication_statement = state.medication_statement_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(medication_statement)))
+}
+
+/// Update a medication statement
+pub async fn update_medication_statement(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(medication_statement): FhirBody<MedicationStatement>,
+) -> Result<Json<SuccessResponse<MedicationStatement>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.medication_statement_service.update(&context, &id, medication_statement).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a medication statement
+pub async fn delete_medication_statement(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.medication_statement_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_medication_statements`
+pub const MEDICATION_STATEMENT_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The subject patient's ID",
+    },
+    SearchParamDef {
+        name: "subject",
+        type_: "reference",
+        documentation: "Same as patient",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "Statement status; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "medicationCodeableConcept, system|code or code",
+    },
+    SearchParamDef {
+        name: "medication",
+        type_: "reference",
+        documentation: "The referenced Medication's ID",
+    },
+    SearchParamDef {
+        name: "effective",
+        type_: "date",
+        documentation: "Date taken (a Period's start), with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search medication statements
+#[derive(Debug, Deserialize)]
+pub struct MedicationStatementSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    pub subject: Option<String>,
+    pub status: Option<String>,
+    pub code: Option<String>,
+    pub medication: Option<String>,
+    pub effective: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_medication_statements(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<MedicationStatementSearchQuery>,
+) -> Result<Json<PaginatedResponse<MedicationStatement>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("subject", query.subject),
+            ("status", query.status),
+            ("code", query.code),
+            ("medication", query.medication),
+            ("effective", query.effective),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.medication_statement_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get medication statement history
+pub async fn get_medication_statement_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<MedicationStatement>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.medication_statement_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a medication statement without persisting it (MedicationStatement/$validate)
+pub async fn validate_medication_statement(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let medication_statement = read_validate_body::<MedicationStatement>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.medication_statement_service
+            .validate_operation(&context, mode, id.as_deref(), medication_statement.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,104 @@
+// src/api/handlers/meta.rs
+// $meta, $meta-add and $meta-delete, mounted for every resource type
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+
+use crate::{
+    AppState,
+    domain::{Meta, Parameters, FhirError},
+    domain::resources::Resource,
+    service::meta_service::meta_parameters,
+    api::{format::FhirBody, responses::SuccessResponse, OptionalAuthUser},
+};
+use super::common::extract_optional_security_context;
+use crate::api::capability::OperationDef;
+
+/// Resource $meta, mounted at system, type and instance level
+pub const RESOURCE_META: OperationDef = OperationDef {
+    name: "meta",
+    definition: "http://hl7.org/fhir/OperationDefinition/Resource-meta",
+};
+
+/// Resource $meta-add, mounted at instance level
+pub const RESOURCE_META_ADD: OperationDef = OperationDef {
+    name: "meta-add",
+    definition: "http://hl7.org/fhir/OperationDefinition/Resource-meta-add",
+};
+
+/// Resource $meta-delete, mounted at instance level
+pub const RESOURCE_META_DELETE: OperationDef = OperationDef {
+    name: "meta-delete",
+    definition: "http://hl7.org/fhir/OperationDefinition/Resource-meta-delete",
+};
+
+/// Profiles, tags and security labels in use on the server (`$meta`)
+pub async fn system_meta(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<SuccessResponse<Parameters>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let meta = state.meta_service.in_use(&context, None).await?;
+    Ok(Json(SuccessResponse::new(meta_parameters(meta))))
+}
+
+/// Profiles, tags and security labels in use on one resource type
+/// (`Type/$meta`)
+pub async fn type_meta<R: Resource>(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<SuccessResponse<Parameters>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let meta = state.meta_service.in_use(&context, Some(R::resource_type())).await?;
+    Ok(Json(SuccessResponse::new(meta_parameters(meta))))
+}
+
+/// Meta of one resource (`Type/:id/$meta`)
+pub async fn instance_meta<R: Resource>(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Parameters>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let meta = state.meta_service.read(&context, R::resource_type(), &id).await?;
+    Ok(Json(SuccessResponse::new(meta_parameters(meta))))
+}
+
+/// Add profiles, tags and security labels to a resource without creating a
+/// new version (`Type/:id/$meta-add`). The body is a Parameters resource
+/// with a `meta` valueMeta
+pub async fn meta_add<R: Resource>(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(parameters): FhirBody<Parameters>,
+) -> Result<Json<SuccessResponse<Parameters>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let meta = input_meta(&parameters, RESOURCE_META_ADD)?;
+    let result = state.meta_service.add(&context, R::resource_type(), &id, meta).await?;
+    Ok(Json(SuccessResponse::new(meta_parameters(result))))
+}
+
+/// Remove profiles, tags and security labels from a resource without
+/// creating a new version (`Type/:id/$meta-delete`)
+pub async fn meta_delete<R: Resource>(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(parameters): FhirBody<Parameters>,
+) -> Result<Json<SuccessResponse<Parameters>>, FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let meta = input_meta(&parameters, RESOURCE_META_DELETE)?;
+    let result = state.meta_service.delete(&context, R::resource_type(), &id, meta).await?;
+    Ok(Json(SuccessResponse::new(meta_parameters(result))))
+}
+
+fn input_meta(parameters: &Parameters, operation: OperationDef) -> Result<&Meta, FhirError> {
+    parameters.get("meta")
+        .and_then(|p| p.value_meta.as_ref())
+        .ok_or_else(|| FhirError::MissingRequiredField(
+            format!("${} requires a meta parameter", operation.name),
+        ))
+}
@@ -0,0 +1,43 @@
+// src/api/handlers/metadata.rs
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Query,
+    http::HeaderMap,
+    Extension, Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    domain::{errors::FhirError, CapabilityStatement},
+    api::{capability::ServerCapabilities, responses::SuccessResponse},
+};
+use super::common::base_url;
+
+#[derive(Debug, Deserialize)]
+pub struct MetadataQuery {
+    pub mode: Option<String>,
+}
+
+/// Get the server's CapabilityStatement
+pub async fn get_metadata(
+    Extension(capabilities): Extension<Arc<ServerCapabilities>>,
+    headers: HeaderMap,
+    Query(query): Query<MetadataQuery>,
+) -> Result<Json<SuccessResponse<CapabilityStatement>>, FhirError> {
+    match query.mode.as_deref() {
+        None | Some("full") | Some("normal") => {}
+        // No terminology operations are implemented, so there is no
+        // TerminologyCapabilities resource to return
+        Some(mode) => {
+            return Err(FhirError::Validation(format!(
+                "Unsupported metadata mode: {}",
+                mode
+            )));
+        }
+    }
+
+    let statement = capabilities.to_capability_statement(&base_url(&headers));
+    Ok(Json(SuccessResponse::new(statement)))
+}
@@ -5,6 +5,25 @@ pub mod patient;
 pub mod observation;
 pub mod condition;
 pub mod encounter;
+pub mod practitioner;
+pub mod practitioner_role;
+pub mod organization;
+pub mod medication;
+pub mod medication_request;
+pub mod medication_statement;
+pub mod allergy_intolerance;
+pub mod procedure;
+pub mod diagnostic_report;
+pub mod immunization;
+pub mod location;
+pub mod group;
+pub mod document_reference;
+pub mod binary;
+pub mod metadata;
+pub mod meta;
+pub mod export;
+pub mod import;
+pub mod reindex;
 pub mod common;
 
 pub use auth_handlers::*;
@@ -12,3 +31,22 @@ pub use patient::*;
 pub use observation::*;
 pub use condition::*;
 pub use encounter::*;
+pub use practitioner::*;
+pub use practitioner_role::*;
+pub use organization::*;
+pub use medication::*;
+pub use medication_request::*;
+pub use medication_statement::*;
+pub use allergy_intolerance::*;
+pub use procedure::*;
+pub use diagnostic_report::*;
+pub use immunization::*;
+pub use location::*;
+pub use group::*;
+pub use document_reference::*;
+pub use binary::*;
+pub use metadata::*;
+pub use meta::*;
+pub use export::*;
+pub use import::*;
+pub use reindex::*;
@@ -1,25 +1,29 @@
 // src/api/handlers/observation.rs
 
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::Deserialize;
 
 use crate::{
     AppState,
-    domain::Observation,
-    service::ResourceService,
-    api::{responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+    domain::{Observation, OperationOutcome, Parameters},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
 };
-use super::common::{SearchQuery, extract_optional_security_context};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body, parse_date_param,
+};
+use crate::api::capability::{OperationDef, SearchParamDef};
 
 /// Create a new observation
 pub async fn create_observation(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
-    Json(observation): Json<Observation>,
+    FhirBody(observation): FhirBody<Observation>,
 ) -> Result<(StatusCode, Json<SuccessResponse<Observation>>), crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let created = state.observation_service.create(&context, observation).await?;
@@ -42,7 +46,7 @@ pub async fn update_observation(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(observation): Json<Observation>,
+    FhirBody(observation): FhirBody<Observation>,
 ) -> Result<Json<SuccessResponse<Observation>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let updated = state.observation_service.update(&context, &id, observation).await?;
@@ -60,6 +64,20 @@ pub async fn delete_observation(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Search parameters honored by `search_observations`
+pub const OBSERVATION_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The subject patient's ID",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "Observation code",
+    },
+];
+
 /// Search observations
 #[derive(Debug, Deserialize)]
 pub struct ObservationSearchQuery {
@@ -77,6 +95,10 @@ pub async fn search_observations(
 ) -> Result<Json<PaginatedResponse<Observation>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
 
+    if query.patient.is_some() || query.code.is_some() {
+        query.common.reject_meta_filters("patient or code")?;
+    }
+
     // If searching by patient, use specific method
     if let Some(patient_id) = query.patient {
         let observations = state.observation_service.search_by_patient(&context, &patient_id).await?;
@@ -113,6 +135,81 @@ pub async fn search_observations(
     )))
 }
 
+/// Observation/$lastn, as advertised in the CapabilityStatement
+pub const OBSERVATION_LASTN: OperationDef = OperationDef {
+    name: "lastn",
+    definition: "http://hl7.org/fhir/OperationDefinition/Observation-lastn",
+};
+
+/// Query parameters of Observation/$lastn
+#[derive(Debug, Deserialize)]
+pub struct LastNQuery {
+    pub patient: Option<String>,
+    pub category: Option<String>,
+    pub code: Option<String>,
+    pub max: Option<u32>,
+}
+
+/// Most recent observations per code for a patient (Observation/$lastn)
+pub async fn observation_lastn(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<LastNQuery>,
+) -> Result<Json<PaginatedResponse<Observation>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let patient = query.patient.ok_or_else(|| {
+        crate::domain::errors::FhirError::Validation("$lastn requires the patient parameter".to_string())
+    })?;
+
+    let observations = state.observation_service
+        .last_n(&context, &patient, query.category.as_deref(), query.code.as_deref(), query.max)
+        .await?;
+    let count = observations.len() as u32;
+    Ok(Json(PaginatedResponse::new(observations, Some(count), 0, count)))
+}
+
+/// Observation/$stats, as advertised in the CapabilityStatement
+pub const OBSERVATION_STATS: OperationDef = OperationDef {
+    name: "stats",
+    definition: "http://hl7.org/fhir/OperationDefinition/Observation-stats",
+};
+
+/// Query parameters of Observation/$stats
+#[derive(Debug, Deserialize)]
+pub struct StatsQuery {
+    pub patient: Option<String>,
+    pub subject: Option<String>,
+    pub code: Option<String>,
+    pub system: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub statistic: Option<String>,
+}
+
+/// Numeric statistics over a patient's observations (Observation/$stats)
+pub async fn observation_stats(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<SuccessResponse<Parameters>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let patient = query.patient.or(query.subject).ok_or_else(|| {
+        crate::domain::errors::FhirError::Validation("$stats requires the patient parameter".to_string())
+    })?;
+    let code = query.code.ok_or_else(|| {
+        crate::domain::errors::FhirError::Validation("$stats requires the code parameter".to_string())
+    })?;
+    let period = (
+        query.start.as_deref().map(|v| parse_date_param("start", v, false)).transpose()?,
+        query.end.as_deref().map(|v| parse_date_param("end", v, true)).transpose()?,
+    );
+
+    let parameters = state.observation_service
+        .stats(&context, &patient, &code, query.system.as_deref(), period, query.statistic.as_deref())
+        .await?;
+    Ok(Json(SuccessResponse::new(parameters)))
+}
+
 /// Get observation history
 pub async fn get_observation_history(
     State(_state): State<AppState>,
@@ -121,3 +218,27 @@ pub async fn get_observation_history(
     // TODO: Implement history tracking
     Ok(Json(SuccessResponse::new(vec![])))
 }
+
+/// Validate a observation without persisting it (Observation/$validate)
+pub async fn validate_observation(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let observation = read_validate_body::<Observation>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.observation_service
+            .validate_operation(&context, mode, id.as_deref(), observation.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
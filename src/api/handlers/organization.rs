@@ -0,0 +1,169 @@
+// src/api/handlers/organization.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Organization},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new organization
+pub async fn create_organization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(organization): FhirBody<Organization>,
+) -> Result<(StatusCode, Json<SuccessResponse<Organization>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.organization_service.create(&context, organization).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a organization by ID
+pub async fn get_organization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Organization>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let organization = state.organization_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(organization)))
+}
+
+/// Update a organization
+pub async fn update_organization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(organization): FhirBody<Organization>,
+) -> Result<Json<SuccessResponse<Organization>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.organization_service.update(&context, &id, organization).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a organization
+pub async fn delete_organization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.organization_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_organizations`
+pub const ORGANIZATION_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "name",
+        type_: "string",
+        documentation: "Name or alias (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+    SearchParamDef {
+        name: "active",
+        type_: "token",
+        documentation: "true or false",
+    },
+    SearchParamDef {
+        name: "partof",
+        type_: "reference",
+        documentation: "The parent organization's ID; partof:below matches every descendant and partof:above every ancestor",
+    },
+];
+
+/// Search organizations
+#[derive(Debug, Deserialize)]
+pub struct OrganizationSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub name: Option<String>,
+    pub identifier: Option<String>,
+    pub active: Option<String>,
+    pub partof: Option<String>,
+    #[serde(rename = "partof:above")]
+    pub partof_above: Option<String>,
+    #[serde(rename = "partof:below")]
+    pub partof_below: Option<String>,
+}
+
+pub async fn search_organizations(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<OrganizationSearchQuery>,
+) -> Result<Json<PaginatedResponse<Organization>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("name", query.name),
+            ("identifier", query.identifier),
+            ("active", query.active),
+            ("partof", query.partof),
+            ("partof:above", query.partof_above),
+            ("partof:below", query.partof_below),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.organization_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get organization history
+pub async fn get_organization_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Organization>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.organization_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a organization without persisting it (Organization/$validate)
+pub async fn validate_organization(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let organization = read_validate_body::<Organization>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.organization_service
+            .validate_operation(&context, mode, id.as_deref(), organization.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
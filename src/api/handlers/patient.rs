@@ -1,40 +1,66 @@
 // src/api/handlers/patient.rs
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{Path, Query, RawQuery, State},
+    http::{header, HeaderMap, StatusCode},
     Json,
 };
 use serde::Deserialize;
 
 use crate::{
     AppState,
-    domain::Patient,
-    service::ResourceService,
-    api::{responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+    domain::{Bundle, OperationOutcome, Parameters, Patient},
+    service::{EverythingParameters, ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
 };
-use super::common::{SearchQuery, extract_optional_security_context};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, base_url, parse_date_param,
+    add_paging_links, read_validate_body,
+};
+use crate::api::capability::{OperationDef, SearchParamDef};
 
 /// Create a new patient
 pub async fn create_patient(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
-    Json(patient): Json<Patient>,
+    FhirBody(patient): FhirBody<Patient>,
 ) -> Result<(StatusCode, Json<SuccessResponse<Patient>>), crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let created = state.patient_service.create(&context, patient).await?;
     Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
 }
 
-/// Get a patient by ID
+/// Query parameters of a patient read
+#[derive(Debug, Deserialize)]
+pub struct PatientReadQuery {
+    /// `true` to return the surviving record of a merged patient
+    #[serde(rename = "follow-replaced-by")]
+    pub follow_replaced_by: Option<String>,
+}
+
+/// Get a patient by ID. When following `replaced-by` links, a merged
+/// patient resolves to the surviving record, named in `Content-Location`
 pub async fn get_patient(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<Json<SuccessResponse<Patient>>, crate::domain::errors::FhirError> {
+    Query(query): Query<PatientReadQuery>,
+) -> Result<(HeaderMap, Json<SuccessResponse<Patient>>), crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
-    let patient = state.patient_service.get(&context, &id).await?;
-    Ok(Json(SuccessResponse::new(patient)))
+    let mut patient = state.patient_service.get(&context, &id).await?;
+
+    let mut response_headers = HeaderMap::new();
+    if query.follow_replaced_by.as_deref() == Some("true") {
+        patient = state.patient_service.resolve_replaced_by(&context, patient).await?;
+        if let Some(resolved) = patient.id.as_ref().filter(|resolved| resolved.0 != id) {
+            if let Ok(location) = format!("/fhir/Patient/{}", resolved.0).parse() {
+                response_headers.insert(header::CONTENT_LOCATION, location);
+            }
+        }
+    }
+
+    Ok((response_headers, Json(SuccessResponse::new(patient))))
 }
 
 /// Update a patient
@@ -42,7 +68,7 @@ pub async fn update_patient(
     auth: OptionalAuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(patient): Json<Patient>,
+    FhirBody(patient): FhirBody<Patient>,
 ) -> Result<Json<SuccessResponse<Patient>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
     let updated = state.patient_service.update(&context, &id, patient).await?;
@@ -60,6 +86,20 @@ pub async fn delete_patient(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Search parameters honored by `search_patients`
+pub const PATIENT_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "family",
+        type_: "string",
+        documentation: "Family name (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "follow-replaced-by",
+        type_: "token",
+        documentation: "true to return the surviving record in place of merged patients",
+    },
+];
+
 /// Search patients
 #[derive(Debug, Deserialize)]
 pub struct PatientSearchQuery {
@@ -68,6 +108,9 @@ pub struct PatientSearchQuery {
     pub family: Option<String>,
     pub given: Option<String>,
     pub identifier: Option<String>,
+    /// `true` to replace merged patients with their surviving records
+    #[serde(rename = "follow-replaced-by")]
+    pub follow_replaced_by: Option<String>,
 }
 
 pub async fn search_patients(
@@ -76,22 +119,17 @@ pub async fn search_patients(
     Query(query): Query<PatientSearchQuery>,
 ) -> Result<Json<PaginatedResponse<Patient>>, crate::domain::errors::FhirError> {
     let context = extract_optional_security_context(&auth);
+    let follow_replaced_by = query.follow_replaced_by.as_deref() == Some("true");
 
-    // If searching by family name, use specific method
+    // `family` is matched alongside the meta filters and paging
+    let mut params = query.common.into_search_params();
     if let Some(family) = query.family {
-        let patients = state.patient_service.search_by_family(&context, &family).await?;
-        let count = patients.len() as u32;
-        return Ok(Json(PaginatedResponse::new(
-            patients,
-            Some(count),
-            0,
-            count,
-        )));
+        params.filters.push(("family".to_string(), family));
+    }
+    let mut result = state.patient_service.search(&context, params).await?;
+    if follow_replaced_by {
+        result.resources = state.patient_service.resolve_all_replaced_by(&context, result.resources).await?;
     }
-
-    // Otherwise use general search
-    let params = query.common.into_search_params();
-    let result = state.patient_service.search(&context, params).await?;
 
     Ok(Json(PaginatedResponse::new(
         result.resources,
@@ -111,3 +149,157 @@ pub async fn get_patient_history(
     let history = state.patient_service.get_history(&context, &id).await?;
     Ok(Json(SuccessResponse::new(history)))
 }
+
+/// Patient/$everything, as advertised in the CapabilityStatement
+pub const PATIENT_EVERYTHING: OperationDef = OperationDef {
+    name: "everything",
+    definition: "http://hl7.org/fhir/OperationDefinition/Patient-everything",
+};
+
+/// Default page size for Patient/$everything
+const EVERYTHING_PAGE_SIZE: u32 = 100;
+
+/// Query parameters of Patient/$everything
+#[derive(Debug, Deserialize)]
+pub struct EverythingQuery {
+    #[serde(rename = "_since")]
+    pub since: Option<String>,
+    #[serde(rename = "_type")]
+    pub type_: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    #[serde(rename = "_count")]
+    pub count: Option<u32>,
+    #[serde(rename = "_offset")]
+    pub offset: Option<u32>,
+}
+
+/// Get a patient's whole record (Patient/$everything)
+pub async fn patient_everything(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    RawQuery(raw_query): RawQuery,
+    Query(query): Query<EverythingQuery>,
+) -> Result<Json<SuccessResponse<Bundle>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let count = query.count.unwrap_or(EVERYTHING_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0);
+
+    let params = EverythingParameters {
+        since: query.since.as_deref().map(|v| parse_date_param("_since", v, false)).transpose()?,
+        types: query.type_.map(|types| types.split(',').map(|t| t.trim().to_string()).collect()),
+        start: query.start.as_deref().map(|v| parse_date_param("start", v, false)).transpose()?,
+        end: query.end.as_deref().map(|v| parse_date_param("end", v, true)).transpose()?,
+        count: Some(count),
+        offset: Some(offset),
+    };
+
+    let mut bundle = state.everything_service.patient_everything(&context, &id, params).await?;
+    let url = format!("{}/fhir/Patient/{}/$everything", base_url(&headers), id);
+    add_paging_links(&mut bundle, &url, raw_query.as_deref(), offset, count);
+    Ok(Json(SuccessResponse::new(bundle)))
+}
+
+/// Validate a patient without persisting it (Patient/$validate)
+pub async fn validate_patient(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let patient = read_validate_body::<Patient>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.patient_service
+            .validate_operation(&context, mode, id.as_deref(), patient.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
+
+/// Patient/$match, as advertised in the CapabilityStatement
+pub const PATIENT_MATCH: OperationDef = OperationDef {
+    name: "match",
+    definition: "http://hl7.org/fhir/OperationDefinition/Patient-match",
+};
+
+/// Find likely duplicates of a patient record (Patient/$match). The body is
+/// a Parameters resource with `resource`, `onlyCertainMatches` and `count`
+pub async fn patient_match(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(parameters): FhirBody<Parameters>,
+) -> Result<Json<SuccessResponse<Bundle>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let resource = parameters.get("resource")
+        .and_then(|p| p.resource.clone())
+        .ok_or_else(|| crate::domain::errors::FhirError::MissingRequiredField(
+            "$match requires a resource parameter".to_string(),
+        ))?;
+    let patient: Patient = serde_json::from_value(resource)
+        .map_err(|e| crate::domain::errors::FhirError::UnprocessableEntity(e.to_string()))?;
+    let only_certain_matches = parameters.get("onlyCertainMatches")
+        .and_then(|p| p.value_boolean.as_ref())
+        .is_some_and(|b| b.0);
+    let count = match parameters.get("count").and_then(|p| p.value_integer.as_ref()) {
+        Some(count) if count.0 < 1 => {
+            return Err(crate::domain::errors::FhirError::Validation(
+                "count must be at least 1".to_string(),
+            ));
+        }
+        Some(count) => Some(count.0 as u32),
+        None => None,
+    };
+
+    let bundle = state.patient_service
+        .match_patients(&context, &patient, only_certain_matches, count)
+        .await?;
+    Ok(Json(SuccessResponse::new(bundle)))
+}
+
+/// Patient/$merge, as advertised in the CapabilityStatement
+pub const PATIENT_MERGE: OperationDef = OperationDef {
+    name: "merge",
+    definition: "http://hl7.org/fhir/OperationDefinition/Patient-merge",
+};
+
+/// Merge a duplicate patient into the surviving record (Patient/$merge).
+/// The body is a Parameters resource with `source-patient` and
+/// `target-patient` references and an optional `preview` flag
+pub async fn patient_merge(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(parameters): FhirBody<Parameters>,
+) -> Result<Json<SuccessResponse<Parameters>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let patient_id = |name: &str| {
+        parameters.get(name)
+            .and_then(|p| p.value_reference.as_ref())
+            .and_then(|r| r.reference.as_ref())
+            .map(|r| r.0.strip_prefix("Patient/").unwrap_or(&r.0).to_string())
+            .ok_or_else(|| crate::domain::errors::FhirError::MissingRequiredField(
+                format!("$merge requires a {} reference", name),
+            ))
+    };
+    let source_id = patient_id("source-patient")?;
+    let target_id = patient_id("target-patient")?;
+    let preview = parameters.get("preview")
+        .and_then(|p| p.value_boolean.as_ref())
+        .is_some_and(|b| b.0);
+
+    let result = state.patient_service
+        .merge(&context, &source_id, &target_id, preview)
+        .await?;
+    Ok(Json(SuccessResponse::new(result)))
+}
@@ -0,0 +1,177 @@
+// src/api/handlers/practitioner.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Practitioner},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new practitioner
+pub async fn create_practitioner(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(practitioner): FhirBody<Practitioner>,
+) -> Result<(StatusCode, Json<SuccessResponse<Practitioner>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.practitioner_service.create(&context, practitioner).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a practitioner by ID
+pub async fn get_practitioner(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Practitioner>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let practitioner = state.practitioner_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(practitioner)))
+}
+
+/// Update a practitioner
+pub async fn update_practitioner(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(practitioner): FhirBody<Practitioner>,
+) -> Result<Json<SuccessResponse<Practitioner>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.practitioner_service.update(&context, &id, practitioner).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a practitioner
+pub async fn delete_practitioner(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.practitioner_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_practitioners`
+pub const PRACTITIONER_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "name",
+        type_: "string",
+        documentation: "Family or given name (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "family",
+        type_: "string",
+        documentation: "Family name (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "given",
+        type_: "string",
+        documentation: "Given name (contains, case-insensitive)",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value, e.g. http://hl7.org/fhir/sid/us-npi|1234567893",
+    },
+    SearchParamDef {
+        name: "gender",
+        type_: "token",
+        documentation: "male | female | other | unknown",
+    },
+    SearchParamDef {
+        name: "active",
+        type_: "token",
+        documentation: "true or false",
+    },
+];
+
+/// Search practitioners
+#[derive(Debug, Deserialize)]
+pub struct PractitionerSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub name: Option<String>,
+    pub family: Option<String>,
+    pub given: Option<String>,
+    pub identifier: Option<String>,
+    pub gender: Option<String>,
+    pub active: Option<String>,
+}
+
+pub async fn search_practitioners(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<PractitionerSearchQuery>,
+) -> Result<Json<PaginatedResponse<Practitioner>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("name", query.name),
+            ("family", query.family),
+            ("given", query.given),
+            ("identifier", query.identifier),
+            ("gender", query.gender),
+            ("active", query.active),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.practitioner_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get practitioner history
+pub async fn get_practitioner_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Practitioner>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.practitioner_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a practitioner without persisting it (Practitioner/$validate)
+pub async fn validate_practitioner(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let practitioner = read_validate_body::<Practitioner>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.practitioner_service
+            .validate_operation(&context, mode, id.as_deref(), practitioner.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,177 @@
+// src/api/handlers/practitioner_role.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, PractitionerRole},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new practitioner role
+pub async fn create_practitioner_role(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(role): FhirBody<PractitionerRole>,
+) -> Result<(StatusCode, Json<SuccessResponse<PractitionerRole>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.practitioner_role_service.create(&context, role).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a practitioner role by ID
+pub async fn get_practitioner_role(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<PractitionerRole>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let role = state.practitioner_role_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(role)))
+}
+
+/// Update a practitioner role
+pub async fn update_practitioner_role(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(role): FhirBody<PractitionerRole>,
+) -> Result<Json<SuccessResponse<PractitionerRole>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.practitioner_role_service.update(&context, &id, role).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a practitioner role
+pub async fn delete_practitioner_role(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.practitioner_role_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_practitioner_roles`
+pub const PRACTITIONER_ROLE_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "practitioner",
+        type_: "reference",
+        documentation: "The practitioner's ID",
+    },
+    SearchParamDef {
+        name: "organization",
+        type_: "reference",
+        documentation: "The organization's ID",
+    },
+    SearchParamDef {
+        name: "specialty",
+        type_: "token",
+        documentation: "system|code or code of any specialty",
+    },
+    SearchParamDef {
+        name: "role",
+        type_: "token",
+        documentation: "system|code or code of any role the practitioner performs",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+    SearchParamDef {
+        name: "active",
+        type_: "token",
+        documentation: "true or false",
+    },
+];
+
+/// Search practitioner roles
+#[derive(Debug, Deserialize)]
+pub struct PractitionerRoleSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub practitioner: Option<String>,
+    pub organization: Option<String>,
+    pub specialty: Option<String>,
+    pub role: Option<String>,
+    pub identifier: Option<String>,
+    pub active: Option<String>,
+}
+
+pub async fn search_practitioner_roles(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<PractitionerRoleSearchQuery>,
+) -> Result<Json<PaginatedResponse<PractitionerRole>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("practitioner", query.practitioner),
+            ("organization", query.organization),
+            ("specialty", query.specialty),
+            ("role", query.role),
+            ("identifier", query.identifier),
+            ("active", query.active),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.practitioner_role_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get practitioner role history
+pub async fn get_practitioner_role_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<PractitionerRole>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.practitioner_role_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a practitioner role without persisting it (PractitionerRole/$validate)
+pub async fn validate_practitioner_role(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let role = read_validate_body::<PractitionerRole>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.practitioner_role_service
+            .validate_operation(&context, mode, id.as_deref(), role.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
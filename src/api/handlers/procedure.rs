@@ -0,0 +1,184 @@
+// src/api/handlers/procedure.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Procedure},
+    service::{ResourceService, ValidationMode},
+    api::{format::FhirBody, responses::{SuccessResponse, PaginatedResponse}, OptionalAuthUser},
+};
+use super::common::{
+    SearchQuery, ValidateQuery, extract_optional_security_context, read_validate_body,
+};
+use crate::api::capability::SearchParamDef;
+
+/// Create a new procedure
+pub async fn create_procedure(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    FhirBody(procedure): FhirBody<Procedure>,
+) -> Result<(StatusCode, Json<SuccessResponse<Procedure>>), crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let created = state.procedure_service.create(&context, procedure).await?;
+    Ok((StatusCode::CREATED, Json(SuccessResponse::new(created))))
+}
+
+/// Get a procedure by ID
+pub async fn get_procedure(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Procedure>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let procedure = state.procedure_service.get(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(procedure)))
+}
+
+/// Update a procedure
+pub async fn update_procedure(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    FhirBody(procedure): FhirBody<Procedure>,
+) -> Result<Json<SuccessResponse<Procedure>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let updated = state.procedure_service.update(&context, &id, procedure).await?;
+    Ok(Json(SuccessResponse::new(updated)))
+}
+
+/// Delete a procedure
+pub async fn delete_procedure(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    state.procedure_service.delete(&context, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Search parameters honored by `search_procedures`
+pub const PROCEDURE_SEARCH_PARAMS: &[SearchParamDef] = &[
+    SearchParamDef {
+        name: "patient",
+        type_: "reference",
+        documentation: "The patient's ID",
+    },
+    SearchParamDef {
+        name: "subject",
+        type_: "reference",
+        documentation: "Same as patient",
+    },
+    SearchParamDef {
+        name: "status",
+        type_: "token",
+        documentation: "preparation, in-progress, not-done, on-hold, stopped, completed, entered-in-error or unknown; Comma-separated codes match any",
+    },
+    SearchParamDef {
+        name: "code",
+        type_: "token",
+        documentation: "The procedure code, system|code or code",
+    },
+    SearchParamDef {
+        name: "date",
+        type_: "date",
+        documentation: "Date performed, with an eq, ne, gt, ge, lt or le prefix",
+    },
+    SearchParamDef {
+        name: "encounter",
+        type_: "reference",
+        documentation: "The encounter's ID",
+    },
+    SearchParamDef {
+        name: "identifier",
+        type_: "token",
+        documentation: "system|value or value",
+    },
+];
+
+/// Search procedures
+#[derive(Debug, Deserialize)]
+pub struct ProcedureSearchQuery {
+    #[serde(flatten)]
+    pub common: SearchQuery,
+    pub patient: Option<String>,
+    pub subject: Option<String>,
+    pub status: Option<String>,
+    pub code: Option<String>,
+    pub date: Option<String>,
+    pub encounter: Option<String>,
+    pub identifier: Option<String>,
+}
+
+pub async fn search_procedures(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<ProcedureSearchQuery>,
+) -> Result<Json<PaginatedResponse<Procedure>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+
+    let mut params = query.common.into_search_params();
+    params.filters.extend(
+        [
+            ("patient", query.patient),
+            ("subject", query.subject),
+            ("status", query.status),
+            ("code", query.code),
+            ("date", query.date),
+            ("encounter", query.encounter),
+            ("identifier", query.identifier),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?))),
+    );
+    let result = state.procedure_service.search(&context, params).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        result.resources,
+        result.total,
+        result.offset,
+        result.count,
+    )))
+}
+
+/// Get procedure history
+pub async fn get_procedure_history(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Vec<Procedure>>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let history = state.procedure_service.get_history(&context, &id).await?;
+    Ok(Json(SuccessResponse::new(history)))
+}
+
+/// Validate a procedure without persisting it (Procedure/$validate)
+pub async fn validate_procedure(
+    auth: OptionalAuthUser,
+    State(state): State<AppState>,
+    id: Option<Path<String>>,
+    Query(query): Query<ValidateQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<SuccessResponse<OperationOutcome>>, crate::domain::errors::FhirError> {
+    let context = extract_optional_security_context(&auth);
+    let mode = ValidationMode::parse(query.mode.as_deref())?;
+    let id = id.map(|Path(id)| id);
+
+    let mut issues = Vec::new();
+    let procedure = read_validate_body::<Procedure>(&headers, &body, &mut issues)?;
+    if issues.is_empty() {
+        issues = state.procedure_service
+            .validate_operation(&context, mode, id.as_deref(), procedure.as_ref())
+            .await?;
+    }
+
+    Ok(Json(SuccessResponse::new(OperationOutcome::from_issues(&issues))))
+}
@@ -0,0 +1,123 @@
+// src/api/handlers/reindex.rs
+// $reindex: rebuild search columns for one resource, one type or everything
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    AppState,
+    domain::{OperationOutcome, Parameters, FhirError, FhirInteger},
+    domain::resources::Resource,
+    domain::resources::operation_outcome::OperationOutcomeIssue,
+    domain::resources::parameters::ParametersParameter,
+    service::reindex_service::reindex_summary,
+    api::{responses::SuccessResponse, AuthUser},
+};
+use super::common::{extract_security_context, base_url};
+use crate::api::capability::OperationDef;
+
+/// $reindex, mounted at system, type and instance level
+pub const RESOURCE_REINDEX: OperationDef = OperationDef {
+    name: "reindex",
+    definition: "http://hl7.org/fhir/OperationDefinition/Resource-reindex",
+};
+
+/// Seconds clients are asked to wait between status polls
+const RETRY_AFTER_SECONDS: &str = "10";
+
+/// Reindex every resource type in the background (`$reindex`)
+pub async fn system_reindex(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    kick_off(&auth, &state, &headers, None).await
+}
+
+/// Reindex one resource type in the background (`Type/$reindex`)
+pub async fn type_reindex<R: Resource>(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    kick_off(&auth, &state, &headers, Some(R::resource_type())).await
+}
+
+/// Reindex one resource immediately (`Type/:id/$reindex`)
+pub async fn instance_reindex<R: Resource>(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SuccessResponse<Parameters>>, FhirError> {
+    let context = extract_security_context(&auth);
+    let updated = state.reindex_service
+        .reindex_resource(&context, R::resource_type(), &id)
+        .await?;
+
+    let mut parameters = Parameters::new();
+    let mut count = ParametersParameter::new("reindexed");
+    count.value_integer = Some(FhirInteger(i32::from(updated)));
+    parameters.add(count);
+    Ok(Json(SuccessResponse::new(parameters)))
+}
+
+/// Start (or resume) the job and point the client at its status endpoint
+async fn kick_off(
+    auth: &AuthUser,
+    state: &AppState,
+    headers: &HeaderMap,
+    resource_type: Option<&str>,
+) -> Result<(StatusCode, HeaderMap), FhirError> {
+    let context = extract_security_context(auth);
+
+    let service = state.reindex_service.clone();
+    let job = service.start(&context, resource_type).await?;
+    let job_id = job.id;
+    tokio::spawn(async move {
+        // Failures are recorded on the job and reported by the status endpoint
+        let _ = service.run(job).await;
+    });
+
+    let mut response_headers = HeaderMap::new();
+    let status_url = format!("{}/fhir/reindex-status/{}", base_url(headers), job_id);
+    if let Ok(value) = HeaderValue::from_str(&status_url) {
+        response_headers.insert(header::CONTENT_LOCATION, value);
+    }
+    Ok((StatusCode::ACCEPTED, response_headers))
+}
+
+/// Poll a reindex job: 202 with `X-Progress` while it runs, then a summary
+pub async fn reindex_status(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Response, FhirError> {
+    let context = extract_security_context(&auth);
+    let job = state.reindex_service.status(&context, &job_id).await?;
+
+    let response = match job.status.as_str() {
+        "completed" => Json(SuccessResponse::new(reindex_summary(&job))).into_response(),
+        "failed" => {
+            let message = job.error.unwrap_or_else(|| "Reindex failed".to_string());
+            let outcome = OperationOutcome::new(vec![OperationOutcomeIssue::new("error", "exception", message)]);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(outcome)).into_response()
+        }
+        _ => {
+            let mut response_headers = HeaderMap::new();
+            response_headers.insert(header::RETRY_AFTER, HeaderValue::from_static(RETRY_AFTER_SECONDS));
+            let progress = match &job.current_type {
+                Some(current) => format!("{} resources reindexed, at {}", job.resources_reindexed, current),
+                None => "Starting".to_string(),
+            };
+            if let Ok(value) = HeaderValue::from_str(&progress) {
+                response_headers.insert("x-progress", value);
+            }
+            (StatusCode::ACCEPTED, response_headers).into_response()
+        }
+    };
+    Ok(response)
+}
@@ -1,6 +1,8 @@
 // src/api/mod.rs
 
 pub mod auth;
+pub mod capability;
+pub mod format;
 pub mod handlers;
 pub mod router;
 pub mod responses;
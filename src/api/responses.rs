@@ -6,7 +6,7 @@ use axum::{
     Json,
 };
 use serde::Serialize;
-use crate::domain::errors::FhirError;
+use crate::domain::{errors::FhirError, OperationOutcome};
 
 /// Standard error response
 #[derive(Debug, Serialize)]
@@ -40,6 +40,7 @@ impl IntoResponse for FhirError {
             FhirError::Validation(_) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
             FhirError::Forbidden { .. } => (StatusCode::FORBIDDEN, "FORBIDDEN"),
             FhirError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR"),
+            FhirError::Storage(_) => (StatusCode::INTERNAL_SERVER_ERROR, "STORAGE_ERROR"),
             FhirError::Serialization(_) => (StatusCode::INTERNAL_SERVER_ERROR, "SERIALIZATION_ERROR"),
             FhirError::InvalidResourceType(_) => (StatusCode::BAD_REQUEST, "INVALID_RESOURCE_TYPE"),
             FhirError::MissingRequiredField(_) => (StatusCode::BAD_REQUEST, "MISSING_REQUIRED_FIELD"),
@@ -47,11 +48,17 @@ impl IntoResponse for FhirError {
             FhirError::Conflict(_) => (StatusCode::CONFLICT, "CONFLICT"),
             FhirError::PreconditionFailed(_) => (StatusCode::PRECONDITION_FAILED, "PRECONDITION_FAILED"),
             FhirError::UnprocessableEntity(_) => (StatusCode::UNPROCESSABLE_ENTITY, "UNPROCESSABLE_ENTITY"),
+            FhirError::NotAcceptable(_) => (StatusCode::NOT_ACCEPTABLE, "NOT_ACCEPTABLE"),
+            FhirError::UnsupportedMediaType(_) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, "UNSUPPORTED_MEDIA_TYPE"),
+            FhirError::PayloadTooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE"),
         };
 
         let error_response = ErrorResponse::new(error_type, self.to_string());
 
-        (status, Json(error_response)).into_response()
+        // Kept for formats without a JSON envelope (see `format::negotiate_format`)
+        let mut response = (status, Json(error_response)).into_response();
+        response.extensions_mut().insert(OperationOutcome::from(&self));
+        response
     }
 }
 
@@ -74,6 +81,9 @@ pub struct PaginatedResponse<T: Serialize> {
     pub total: Option<u32>,
     pub offset: u32,
     pub count: u32,
+    /// Resources added by `_include`, as JSON since they differ in type
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub included: Vec<serde_json::Value>,
 }
 
 impl<T: Serialize> PaginatedResponse<T> {
@@ -83,6 +93,12 @@ impl<T: Serialize> PaginatedResponse<T> {
             total,
             offset,
             count,
+            included: Vec::new(),
         }
     }
+
+    pub fn with_included(mut self, included: Vec<serde_json::Value>) -> Self {
+        self.included = included;
+        self
+    }
 }
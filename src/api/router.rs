@@ -1,8 +1,11 @@
 // src/api/router.rs
 
+use std::sync::Arc;
+
 use axum::{
+    middleware,
     routing::{get, post, put, delete},
-    Router,
+    Extension, Router,
 };
 use tower_http::{
     cors::{Any, CorsLayer},
@@ -10,25 +13,117 @@ use tower_http::{
 };
 
 use crate::AppState;
+use crate::domain::{
+    AllergyIntolerance, Condition, Encounter, Medication, MedicationRequest, MedicationStatement, Observation, Organization,
+    Patient, Practitioner, PractitionerRole, Procedure, DiagnosticReport, Immunization, Location,
+    Group, DocumentReference,
+};
+use super::capability::FhirRouter;
+use super::format::negotiate_format;
+use super::handlers::common::RESOURCE_VALIDATE;
 use super::handlers::{
     // Auth handlers
     login, register, me,
 
+    // Metadata handlers
+    get_metadata,
+
+    // Meta operations, generic over the resource type
+    system_meta, type_meta, instance_meta, meta_add, meta_delete,
+    RESOURCE_META, RESOURCE_META_ADD, RESOURCE_META_DELETE,
+
+    // Bulk Data export
+    system_export, patient_export, group_export, export_status, cancel_export, export_file,
+    SYSTEM_EXPORT, PATIENT_EXPORT, GROUP_EXPORT,
+
+    // Bulk import
+    system_import, import_status, import_errors, SYSTEM_IMPORT,
+
+    // Search column maintenance, generic over the resource type
+    system_reindex, type_reindex, instance_reindex, reindex_status, RESOURCE_REINDEX,
+
     // Patient handlers
     create_patient, get_patient, update_patient, delete_patient,
-    search_patients, get_patient_history,
+    search_patients, get_patient_history, validate_patient, PATIENT_SEARCH_PARAMS,
+    patient_everything, PATIENT_EVERYTHING, patient_match, PATIENT_MATCH, patient_merge, PATIENT_MERGE,
 
     // Observation handlers
     create_observation, get_observation, update_observation, delete_observation,
-    search_observations, get_observation_history,
+    search_observations, get_observation_history, validate_observation, OBSERVATION_SEARCH_PARAMS,
+    observation_lastn, OBSERVATION_LASTN, observation_stats, OBSERVATION_STATS,
 
     // Condition handlers
     create_condition, get_condition, update_condition, delete_condition,
-    search_conditions, get_condition_history,
+    search_conditions, get_condition_history, validate_condition, CONDITION_SEARCH_PARAMS,
 
     // Encounter handlers
     create_encounter, get_encounter, update_encounter, delete_encounter,
-    search_encounters, get_encounter_history,
+    search_encounters, get_encounter_history, validate_encounter, ENCOUNTER_SEARCH_PARAMS,
+    encounter_everything, ENCOUNTER_EVERYTHING,
+
+    // Practitioner handlers
+    create_practitioner, get_practitioner, update_practitioner, delete_practitioner,
+    search_practitioners, get_practitioner_history, validate_practitioner, PRACTITIONER_SEARCH_PARAMS,
+
+    // PractitionerRole handlers
+    create_practitioner_role, get_practitioner_role, update_practitioner_role, delete_practitioner_role,
+    search_practitioner_roles, get_practitioner_role_history, validate_practitioner_role,
+    PRACTITIONER_ROLE_SEARCH_PARAMS,
+    // Organization handlers
+    create_organization, get_organization, update_organization, delete_organization,
+    search_organizations, get_organization_history, validate_organization, ORGANIZATION_SEARCH_PARAMS,
+
+    // Medication handlers
+    create_medication, get_medication, update_medication, delete_medication,
+    search_medications, get_medication_history, validate_medication, MEDICATION_SEARCH_PARAMS,
+
+    // MedicationRequest handlers
+    create_medication_request, get_medication_request, update_medication_request, delete_medication_request,
+    search_medication_requests, get_medication_request_history, validate_medication_request,
+    MEDICATION_REQUEST_SEARCH_PARAMS,
+
+    // MedicationStatement handlers
+    create_medication_statement, get_medication_statement, update_medication_statement,
+    delete_medication_statement, search_medication_statements, get_medication_statement_history,
+    validate_medication_statement, MEDICATION_STATEMENT_SEARCH_PARAMS,
+
+    // AllergyIntolerance handlers
+    create_allergy_intolerance, get_allergy_intolerance, update_allergy_intolerance,
+    delete_allergy_intolerance, search_allergy_intolerances, get_allergy_intolerance_history,
+    validate_allergy_intolerance, ALLERGY_INTOLERANCE_SEARCH_PARAMS,
+
+    // Procedure handlers
+    create_procedure, get_procedure, update_procedure,
+    delete_procedure, search_procedures, get_procedure_history,
+    validate_procedure, PROCEDURE_SEARCH_PARAMS,
+
+    // DiagnosticReport handlers
+    create_diagnostic_report, get_diagnostic_report, update_diagnostic_report,
+    delete_diagnostic_report, search_diagnostic_reports, get_diagnostic_report_history,
+    validate_diagnostic_report, DIAGNOSTIC_REPORT_SEARCH_PARAMS, DIAGNOSTIC_REPORT_RESULT_INCLUDE,
+
+    // Immunization handlers
+    create_immunization, get_immunization, update_immunization,
+    delete_immunization, search_immunizations, get_immunization_history,
+    validate_immunization, IMMUNIZATION_SEARCH_PARAMS,
+
+    // Location handlers
+    create_location, get_location, update_location,
+    delete_location, search_locations, get_location_history,
+    validate_location, LOCATION_SEARCH_PARAMS,
+
+    // Group handlers
+    create_group, get_group, update_group,
+    delete_group, search_groups, get_group_history,
+    validate_group, GROUP_SEARCH_PARAMS,
+
+    // DocumentReference handlers
+    create_document_reference, get_document_reference, update_document_reference,
+    delete_document_reference, search_document_references, get_document_reference_history,
+    validate_document_reference, DOCUMENT_REFERENCE_SEARCH_PARAMS,
+
+    // Binary handlers
+    create_binary, get_binary, update_binary, delete_binary, get_binary_history,
 };
 
 /// Create the main application router
@@ -39,6 +134,331 @@ pub fn create_router(state: AppState) -> Router {
         .allow_methods(Any)
         .allow_headers(Any);
 
+    // FHIR routes are registered through FhirRouter so that the
+    // CapabilityStatement served at /fhir/metadata always matches them
+    let (fhir_routes, capabilities) = FhirRouter::new()
+        // Patient routes
+        .resource("Patient", |r| r
+            .create(post(create_patient))
+            .search(get(search_patients), PATIENT_SEARCH_PARAMS)
+            .read(get(get_patient))
+            .update(put(update_patient))
+            .delete(delete(delete_patient))
+            .history(get(get_patient_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_patient))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_patient))
+            .instance_operation(PATIENT_EVERYTHING, get(patient_everything))
+            .type_operation(PATIENT_MATCH, post(patient_match))
+            .type_operation(PATIENT_MERGE, post(patient_merge))
+            .type_operation(PATIENT_EXPORT, get(patient_export))
+            .type_operation(RESOURCE_META, get(type_meta::<Patient>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Patient>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Patient>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Patient>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Patient>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Patient>)))
+
+        // Observation routes
+        .resource("Observation", |r| r
+            .create(post(create_observation))
+            .search(get(search_observations), OBSERVATION_SEARCH_PARAMS)
+            .read(get(get_observation))
+            .update(put(update_observation))
+            .delete(delete(delete_observation))
+            .history(get(get_observation_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_observation))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_observation))
+            .type_operation(OBSERVATION_LASTN, get(observation_lastn))
+            .type_operation(OBSERVATION_STATS, get(observation_stats))
+            .type_operation(RESOURCE_META, get(type_meta::<Observation>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Observation>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Observation>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Observation>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Observation>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Observation>)))
+
+        // Condition routes
+        .resource("Condition", |r| r
+            .create(post(create_condition))
+            .search(get(search_conditions), CONDITION_SEARCH_PARAMS)
+            .read(get(get_condition))
+            .update(put(update_condition))
+            .delete(delete(delete_condition))
+            .history(get(get_condition_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_condition))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_condition))
+            .type_operation(RESOURCE_META, get(type_meta::<Condition>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Condition>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Condition>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Condition>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Condition>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Condition>)))
+
+        // Encounter routes
+        .resource("Encounter", |r| r
+            .create(post(create_encounter))
+            .search(get(search_encounters), ENCOUNTER_SEARCH_PARAMS)
+            .read(get(get_encounter))
+            .update(put(update_encounter))
+            .delete(delete(delete_encounter))
+            .history(get(get_encounter_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_encounter))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_encounter))
+            .instance_operation(ENCOUNTER_EVERYTHING, get(encounter_everything))
+            .type_operation(RESOURCE_META, get(type_meta::<Encounter>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Encounter>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Encounter>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Encounter>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Encounter>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Encounter>)))
+
+        // Practitioner routes
+        .resource("Practitioner", |r| r
+            .create(post(create_practitioner))
+            .search(get(search_practitioners), PRACTITIONER_SEARCH_PARAMS)
+            .read(get(get_practitioner))
+            .update(put(update_practitioner))
+            .delete(delete(delete_practitioner))
+            .history(get(get_practitioner_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_practitioner))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_practitioner))
+            .type_operation(RESOURCE_META, get(type_meta::<Practitioner>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Practitioner>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Practitioner>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Practitioner>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Practitioner>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Practitioner>)))
+
+        // PractitionerRole routes
+        .resource("PractitionerRole", |r| r
+            .create(post(create_practitioner_role))
+            .search(get(search_practitioner_roles), PRACTITIONER_ROLE_SEARCH_PARAMS)
+            .read(get(get_practitioner_role))
+            .update(put(update_practitioner_role))
+            .delete(delete(delete_practitioner_role))
+            .history(get(get_practitioner_role_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_practitioner_role))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_practitioner_role))
+            .type_operation(RESOURCE_META, get(type_meta::<PractitionerRole>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<PractitionerRole>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<PractitionerRole>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<PractitionerRole>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<PractitionerRole>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<PractitionerRole>)))
+        // Organization routes
+        .resource("Organization", |r| r
+            .create(post(create_organization))
+            .search(get(search_organizations), ORGANIZATION_SEARCH_PARAMS)
+            .read(get(get_organization))
+            .update(put(update_organization))
+            .delete(delete(delete_organization))
+            .history(get(get_organization_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_organization))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_organization))
+            .type_operation(RESOURCE_META, get(type_meta::<Organization>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Organization>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Organization>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Organization>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Organization>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Organization>)))
+
+        // Medication routes
+        .resource("Medication", |r| r
+            .create(post(create_medication))
+            .search(get(search_medications), MEDICATION_SEARCH_PARAMS)
+            .read(get(get_medication))
+            .update(put(update_medication))
+            .delete(delete(delete_medication))
+            .history(get(get_medication_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_medication))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_medication))
+            .type_operation(RESOURCE_META, get(type_meta::<Medication>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Medication>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Medication>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Medication>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Medication>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Medication>)))
+
+        // MedicationRequest routes
+        .resource("MedicationRequest", |r| r
+            .create(post(create_medication_request))
+            .search(get(search_medication_requests), MEDICATION_REQUEST_SEARCH_PARAMS)
+            .read(get(get_medication_request))
+            .update(put(update_medication_request))
+            .delete(delete(delete_medication_request))
+            .history(get(get_medication_request_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_medication_request))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_medication_request))
+            .type_operation(RESOURCE_META, get(type_meta::<MedicationRequest>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<MedicationRequest>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<MedicationRequest>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<MedicationRequest>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<MedicationRequest>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<MedicationRequest>)))
+
+        // MedicationStatement routes
+        .resource("MedicationStatement", |r| r
+            .create(post(create_medication_statement))
+            .search(get(search_medication_statements), MEDICATION_STATEMENT_SEARCH_PARAMS)
+            .read(get(get_medication_statement))
+            .update(put(update_medication_statement))
+            .delete(delete(delete_medication_statement))
+            .history(get(get_medication_statement_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_medication_statement))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_medication_statement))
+            .type_operation(RESOURCE_META, get(type_meta::<MedicationStatement>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<MedicationStatement>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<MedicationStatement>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<MedicationStatement>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<MedicationStatement>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<MedicationStatement>)))
+
+        // AllergyIntolerance routes
+        .resource("AllergyIntolerance", |r| r
+            .create(post(create_allergy_intolerance))
+            .search(get(search_allergy_intolerances), ALLERGY_INTOLERANCE_SEARCH_PARAMS)
+            .read(get(get_allergy_intolerance))
+            .update(put(update_allergy_intolerance))
+            .delete(delete(delete_allergy_intolerance))
+            .history(get(get_allergy_intolerance_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_allergy_intolerance))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_allergy_intolerance))
+            .type_operation(RESOURCE_META, get(type_meta::<AllergyIntolerance>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<AllergyIntolerance>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<AllergyIntolerance>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<AllergyIntolerance>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<AllergyIntolerance>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<AllergyIntolerance>)))
+
+        // Procedure routes
+        .resource("Procedure", |r| r
+            .create(post(create_procedure))
+            .search(get(search_procedures), PROCEDURE_SEARCH_PARAMS)
+            .read(get(get_procedure))
+            .update(put(update_procedure))
+            .delete(delete(delete_procedure))
+            .history(get(get_procedure_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_procedure))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_procedure))
+            .type_operation(RESOURCE_META, get(type_meta::<Procedure>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Procedure>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Procedure>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Procedure>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Procedure>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Procedure>)))
+
+        // DiagnosticReport routes
+        .resource("DiagnosticReport", |r| r
+            .create(post(create_diagnostic_report))
+            .search(get(search_diagnostic_reports), DIAGNOSTIC_REPORT_SEARCH_PARAMS)
+            .search_include(DIAGNOSTIC_REPORT_RESULT_INCLUDE)
+            .read(get(get_diagnostic_report))
+            .update(put(update_diagnostic_report))
+            .delete(delete(delete_diagnostic_report))
+            .history(get(get_diagnostic_report_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_diagnostic_report))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_diagnostic_report))
+            .type_operation(RESOURCE_META, get(type_meta::<DiagnosticReport>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<DiagnosticReport>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<DiagnosticReport>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<DiagnosticReport>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<DiagnosticReport>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<DiagnosticReport>)))
+
+        // Immunization routes
+        .resource("Immunization", |r| r
+            .create(post(create_immunization))
+            .search(get(search_immunizations), IMMUNIZATION_SEARCH_PARAMS)
+            .read(get(get_immunization))
+            .update(put(update_immunization))
+            .delete(delete(delete_immunization))
+            .history(get(get_immunization_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_immunization))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_immunization))
+            .type_operation(RESOURCE_META, get(type_meta::<Immunization>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Immunization>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Immunization>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Immunization>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Immunization>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Immunization>)))
+
+        // Location routes
+        .resource("Location", |r| r
+            .create(post(create_location))
+            .search(get(search_locations), LOCATION_SEARCH_PARAMS)
+            .read(get(get_location))
+            .update(put(update_location))
+            .delete(delete(delete_location))
+            .history(get(get_location_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_location))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_location))
+            .type_operation(RESOURCE_META, get(type_meta::<Location>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Location>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Location>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Location>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Location>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Location>)))
+
+        // Group routes
+        .resource("Group", |r| r
+            .create(post(create_group))
+            .search(get(search_groups), GROUP_SEARCH_PARAMS)
+            .read(get(get_group))
+            .update(put(update_group))
+            .delete(delete(delete_group))
+            .history(get(get_group_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_group))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_group))
+            .instance_operation(GROUP_EXPORT, get(group_export))
+            .type_operation(RESOURCE_META, get(type_meta::<Group>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<Group>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<Group>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<Group>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<Group>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<Group>)))
+
+        // DocumentReference routes
+        .resource("DocumentReference", |r| r
+            .create(post(create_document_reference))
+            .search(get(search_document_references), DOCUMENT_REFERENCE_SEARCH_PARAMS)
+            .read(get(get_document_reference))
+            .update(put(update_document_reference))
+            .delete(delete(delete_document_reference))
+            .history(get(get_document_reference_history))
+            .type_operation(RESOURCE_VALIDATE, post(validate_document_reference))
+            .instance_operation(RESOURCE_VALIDATE, post(validate_document_reference))
+            .type_operation(RESOURCE_META, get(type_meta::<DocumentReference>))
+            .instance_operation(RESOURCE_META, get(instance_meta::<DocumentReference>))
+            .instance_operation(RESOURCE_META_ADD, post(meta_add::<DocumentReference>))
+            .instance_operation(RESOURCE_META_DELETE, post(meta_delete::<DocumentReference>))
+            .type_operation(RESOURCE_REINDEX, post(type_reindex::<DocumentReference>))
+            .instance_operation(RESOURCE_REINDEX, post(instance_reindex::<DocumentReference>)))
+
+        // Binary routes. Content is read and written in its own media type,
+        // so there is no search, $validate or $meta
+        .resource("Binary", |r| r
+            .create(post(create_binary))
+            .read(get(get_binary))
+            .update(put(update_binary))
+            .delete(delete(delete_binary))
+            .history(get(get_binary_history)))
+
+        // Server-wide operations
+        .system_operation(RESOURCE_META, get(system_meta))
+        .system_operation(SYSTEM_EXPORT, get(system_export))
+        .system_operation(SYSTEM_IMPORT, post(system_import))
+        .system_operation(RESOURCE_REINDEX, post(system_reindex))
+
+        .into_parts();
+
+    // FHIR endpoints respond in the format negotiated via Accept/_format
+    let fhir_routes = Router::new()
+        .route("/fhir/metadata", get(get_metadata))
+        .route("/fhir/import-status/:job_id", get(import_status))
+        .route("/fhir/reindex-status/:job_id", get(reindex_status))
+        .merge(fhir_routes)
+        .layer(middleware::from_fn(negotiate_format));
+
     Router::new()
         // Health check endpoint
         .route("/health", get(health_check))
@@ -48,39 +468,17 @@ pub fn create_router(state: AppState) -> Router {
         .route("/auth/register", post(register))
         .route("/auth/me", get(me))
 
-        // Patient routes
-        .route("/fhir/Patient", post(create_patient))
-        .route("/fhir/Patient", get(search_patients))
-        .route("/fhir/Patient/:id", get(get_patient))
-        .route("/fhir/Patient/:id", put(update_patient))
-        .route("/fhir/Patient/:id", delete(delete_patient))
-        .route("/fhir/Patient/:id/_history", get(get_patient_history))
-
-        // Observation routes
-        .route("/fhir/Observation", post(create_observation))
-        .route("/fhir/Observation", get(search_observations))
-        .route("/fhir/Observation/:id", get(get_observation))
-        .route("/fhir/Observation/:id", put(update_observation))
-        .route("/fhir/Observation/:id", delete(delete_observation))
-        .route("/fhir/Observation/:id/_history", get(get_observation_history))
+        // FHIR routes (capability statement and resources)
+        .merge(fhir_routes)
 
-        // Condition routes
-        .route("/fhir/Condition", post(create_condition))
-        .route("/fhir/Condition", get(search_conditions))
-        .route("/fhir/Condition/:id", get(get_condition))
-        .route("/fhir/Condition/:id", put(update_condition))
-        .route("/fhir/Condition/:id", delete(delete_condition))
-        .route("/fhir/Condition/:id/_history", get(get_condition_history))
-
-        // Encounter routes
-        .route("/fhir/Encounter", post(create_encounter))
-        .route("/fhir/Encounter", get(search_encounters))
-        .route("/fhir/Encounter/:id", get(get_encounter))
-        .route("/fhir/Encounter/:id", put(update_encounter))
-        .route("/fhir/Encounter/:id", delete(delete_encounter))
-        .route("/fhir/Encounter/:id/_history", get(get_encounter_history))
+        // Bulk Data status and NDJSON files are not FHIR resources, so they
+        // bypass format negotiation
+        .route("/fhir/bulk-status/:job_id", get(export_status).delete(cancel_export))
+        .route("/fhir/bulk-files/:job_id/:file_name", get(export_file))
+        .route("/fhir/import-status/:job_id/errors", get(import_errors))
 
         // Add middleware
+        .layer(Extension(Arc::new(capabilities)))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
 
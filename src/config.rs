@@ -100,6 +100,223 @@ impl GrpcConfig {
     }
 }
 
+/// Patient/$match weights and grade thresholds. Scores are the weighted sum
+/// of per-field similarities divided by the sum of all weights
+#[derive(Debug, Clone)]
+pub struct MatchConfig {
+    pub identifier_weight: f64,
+    pub name_weight: f64,
+    pub birth_date_weight: f64,
+    pub gender_weight: f64,
+    pub address_weight: f64,
+    pub telecom_weight: f64,
+    pub certain_threshold: f64,
+    pub probable_threshold: f64,
+    pub possible_threshold: f64,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            identifier_weight: 0.35,
+            name_weight: 0.25,
+            birth_date_weight: 0.2,
+            gender_weight: 0.05,
+            address_weight: 0.075,
+            telecom_weight: 0.075,
+            certain_threshold: 0.8,
+            probable_threshold: 0.6,
+            possible_threshold: 0.4,
+        }
+    }
+}
+
+impl MatchConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            identifier_weight: var("MATCH_WEIGHT_IDENTIFIER", defaults.identifier_weight),
+            name_weight: var("MATCH_WEIGHT_NAME", defaults.name_weight),
+            birth_date_weight: var("MATCH_WEIGHT_BIRTH_DATE", defaults.birth_date_weight),
+            gender_weight: var("MATCH_WEIGHT_GENDER", defaults.gender_weight),
+            address_weight: var("MATCH_WEIGHT_ADDRESS", defaults.address_weight),
+            telecom_weight: var("MATCH_WEIGHT_TELECOM", defaults.telecom_weight),
+            certain_threshold: var("MATCH_THRESHOLD_CERTAIN", defaults.certain_threshold),
+            probable_threshold: var("MATCH_THRESHOLD_PROBABLE", defaults.probable_threshold),
+            possible_threshold: var("MATCH_THRESHOLD_POSSIBLE", defaults.possible_threshold),
+        }
+    }
+
+    pub fn total_weight(&self) -> f64 {
+        self.identifier_weight
+            + self.name_weight
+            + self.birth_date_weight
+            + self.gender_weight
+            + self.address_weight
+            + self.telecom_weight
+    }
+}
+
+/// Bulk Data `$export` output location and paging
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    /// Directory that receives one sub-directory of NDJSON files per job
+    pub output_dir: PathBuf,
+    /// Resources read from the database per query
+    pub page_size: i64,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("./exports"),
+            page_size: 1000,
+        }
+    }
+}
+
+impl ExportConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            output_dir: std::env::var("EXPORT_OUTPUT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(defaults.output_dir),
+            page_size: std::env::var("EXPORT_PAGE_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|size| *size > 0)
+                .unwrap_or(defaults.page_size),
+        }
+    }
+}
+
+/// Bulk `$import` input location, error reports and batching
+#[derive(Debug, Clone)]
+pub struct ImportConfig {
+    /// Directory `$import` requests may read NDJSON files from
+    pub input_dir: PathBuf,
+    /// Directory that receives the per-line OperationOutcome file of each job
+    pub error_dir: PathBuf,
+    /// NDJSON lines committed per transaction
+    pub batch_size: usize,
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self {
+            input_dir: PathBuf::from("./imports"),
+            error_dir: PathBuf::from("./imports/errors"),
+            batch_size: 1000,
+        }
+    }
+}
+
+impl ImportConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            input_dir: std::env::var("IMPORT_INPUT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(defaults.input_dir),
+            error_dir: std::env::var("IMPORT_ERROR_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(defaults.error_dir),
+            batch_size: std::env::var("IMPORT_BATCH_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|size| *size > 0)
+                .unwrap_or(defaults.batch_size),
+        }
+    }
+}
+
+/// Binary content storage. Content lives on the blob store rather than in
+/// the database; only its metadata is stored with the resource
+#[derive(Debug, Clone)]
+pub struct BinaryConfig {
+    /// Directory of the local filesystem blob store
+    pub storage_dir: PathBuf,
+    /// Largest upload accepted, in bytes
+    pub max_size: u64,
+    /// Largest content returned base64-encoded inside a Binary resource;
+    /// bigger content can only be downloaded in its native format
+    pub max_inline_size: u64,
+}
+
+impl Default for BinaryConfig {
+    fn default() -> Self {
+        Self {
+            storage_dir: PathBuf::from("./binaries"),
+            max_size: 512 * 1024 * 1024,
+            max_inline_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl BinaryConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            storage_dir: std::env::var("BINARY_STORAGE_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(defaults.storage_dir),
+            max_size: std::env::var("BINARY_MAX_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|size| *size > 0)
+                .unwrap_or(defaults.max_size),
+            max_inline_size: std::env::var("BINARY_MAX_INLINE_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_inline_size),
+        }
+    }
+}
+
+/// `$reindex` batching and throttling
+#[derive(Debug, Clone)]
+pub struct ReindexConfig {
+    /// Resources rewritten per transaction
+    pub batch_size: i64,
+    /// Pause between batches, to leave the database room for live traffic
+    pub batch_delay: Duration,
+}
+
+impl Default for ReindexConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 500,
+            batch_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+impl ReindexConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            batch_size: std::env::var("REINDEX_BATCH_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|size| *size > 0)
+                .unwrap_or(defaults.batch_size),
+            batch_delay: std::env::var("REINDEX_BATCH_DELAY_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.batch_delay),
+        }
+    }
+}
+
 // ============================================
 // .env file example
 // ============================================
@@ -120,6 +337,35 @@ GRPC_TLS_ENABLED=false
 GRPC_TLS_CERT_PATH=./certs/server.crt
 GRPC_TLS_KEY_PATH=./certs/server.key
 
+# Patient/$match scoring
+MATCH_WEIGHT_IDENTIFIER=0.35
+MATCH_WEIGHT_NAME=0.25
+MATCH_WEIGHT_BIRTH_DATE=0.2
+MATCH_WEIGHT_GENDER=0.05
+MATCH_WEIGHT_ADDRESS=0.075
+MATCH_WEIGHT_TELECOM=0.075
+MATCH_THRESHOLD_CERTAIN=0.8
+MATCH_THRESHOLD_PROBABLE=0.6
+MATCH_THRESHOLD_POSSIBLE=0.4
+
+# Bulk Data $export
+EXPORT_OUTPUT_DIR=./exports
+EXPORT_PAGE_SIZE=1000
+
+# Bulk $import
+IMPORT_INPUT_DIR=./imports
+IMPORT_ERROR_DIR=./imports/errors
+IMPORT_BATCH_SIZE=1000
+
+# $reindex
+REINDEX_BATCH_SIZE=500
+REINDEX_BATCH_DELAY_MS=100
+
+# Binary content (local filesystem blob store)
+BINARY_STORAGE_DIR=./binaries
+BINARY_MAX_SIZE=536870912
+BINARY_MAX_INLINE_SIZE=10485760
+
 RUST_LOG=info,fhir_server=debug
 */
 
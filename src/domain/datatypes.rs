@@ -1,10 +1,11 @@
 // src/domain/datatypes.rs
 // FHIR Complex Datatypes
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use crate::domain::primitives::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Meta {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -167,6 +168,15 @@ pub struct Range {
     pub high: Option<Quantity>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Ratio {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numerator: Option<Quantity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denominator: Option<Quantity>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Annotation {
@@ -182,4 +192,329 @@ pub struct Annotation {
 pub enum AnnotationAuthor {
     Reference(Reference),
     String(FhirString),
-}
\ No newline at end of file
+}
+
+/// Content in another format, inline as base64 `data` or by `url`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<Code>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<Code>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<FhirString>, // base64Binary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Uri>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<UnsignedInt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<FhirString>, // base64Binary SHA-1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation: Option<FhirDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Extension {
+    pub url: Uri,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_uri: Option<Uri>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_string: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_code: Option<Code>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_boolean: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_integer: Option<FhirInteger>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_decimal: Option<FhirDecimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<Vec<Extension>>,
+}
+
+impl Meta {
+    /// Server-assigned version metadata for a stored resource, keeping the
+    /// client-supplied source, profiles, security labels and tags
+    pub fn versioned(client: Option<&Meta>, version_id: i32) -> Self {
+        Self {
+            version_id: Some(Id(version_id.to_string())),
+            last_updated: Some(Instant(chrono::Utc::now())),
+            source: client.and_then(|m| m.source.clone()),
+            profile: client.and_then(|m| m.profile.clone()),
+            security: client.and_then(|m| m.security.clone()),
+            tag: client.and_then(|m| m.tag.clone()),
+        }
+    }
+
+    /// Add the profiles, security labels and tags of `other` that are not
+    /// already present ($meta-add)
+    pub fn add(&mut self, other: &Meta) {
+        for profile in other.profile.iter().flatten() {
+            let profiles = self.profile.get_or_insert_with(Vec::new);
+            if !profiles.contains(profile) {
+                profiles.push(profile.clone());
+            }
+        }
+        add_codings(&mut self.security, &other.security);
+        add_codings(&mut self.tag, &other.tag);
+    }
+
+    /// Remove the profiles, security labels and tags listed in `other`
+    /// ($meta-delete)
+    pub fn remove(&mut self, other: &Meta) {
+        if let Some(profiles) = &mut self.profile {
+            profiles.retain(|p| !other.profile.iter().flatten().any(|o| o == p));
+        }
+        remove_codings(&mut self.security, &other.security);
+        remove_codings(&mut self.tag, &other.tag);
+
+        if self.profile.as_ref().is_some_and(|p| p.is_empty()) {
+            self.profile = None;
+        }
+    }
+}
+
+/// Codings in meta are identified by system and code
+fn same_coding(a: &Coding, b: &Coding) -> bool {
+    a.system == b.system && a.code == b.code
+}
+
+fn add_codings(target: &mut Option<Vec<Coding>>, source: &Option<Vec<Coding>>) {
+    for coding in source.iter().flatten() {
+        let codings = target.get_or_insert_with(Vec::new);
+        if !codings.iter().any(|c| same_coding(c, coding)) {
+            codings.push(coding.clone());
+        }
+    }
+}
+
+fn remove_codings(target: &mut Option<Vec<Coding>>, source: &Option<Vec<Coding>>) {
+    if let Some(codings) = target {
+        codings.retain(|c| !source.iter().flatten().any(|s| same_coding(c, s)));
+        if codings.is_empty() {
+            *target = None;
+        }
+    }
+}
+
+impl Extension {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: Uri(url.to_string()),
+            value_uri: None,
+            value_string: None,
+            value_code: None,
+            value_boolean: None,
+            value_integer: None,
+            value_decimal: None,
+            extension: None,
+        }
+    }
+}
+
+/// Human-readable summary of a resource. `div` holds the raw XHTML fragment,
+/// including the outer `<div xmlns="http://www.w3.org/1999/xhtml">` element
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Narrative {
+    pub status: Code, // generated | extensions | additional | empty
+    pub div: String,
+}
+
+/// When an event occurs: explicit times, a repeating pattern, or a code
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Timing {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<Vec<FhirDateTime>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<TimingRepeat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<CodeableConcept>, // BID | TID | QID | AM | PM | ...
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingRepeat {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<TimingBounds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<PositiveInt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count_max: Option<PositiveInt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<FhirDecimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_max: Option<FhirDecimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_unit: Option<Code>, // s | min | h | d | wk | mo | a
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<PositiveInt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_max: Option<PositiveInt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<FhirDecimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_max: Option<FhirDecimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_unit: Option<Code>, // s | min | h | d | wk | mo | a
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day_of_week: Option<Vec<Code>>, // mon | tue | wed | thu | fri | sat | sun
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_of_day: Option<Vec<FhirTime>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<Vec<Code>>, // MORN | AFT | EVE | NIGHT | HS | WAKE | C | ...
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<UnsignedInt>, // minutes from the `when` event
+}
+
+/// Timing.repeat.bounds[x]
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum TimingBounds {
+    Duration(Quantity),
+    Range(Range),
+    Period(Period),
+}
+
+impl<'de> Deserialize<'de> for TimingBounds {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if has_any_key(&value, &["low", "high"]) {
+            from_value(value).map(Self::Range)
+        } else if has_any_key(&value, &["start", "end"]) {
+            from_value(value).map(Self::Period)
+        } else {
+            from_value(value).map(Self::Duration)
+        }
+    }
+}
+
+/// How a medication is or should be taken
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Dosage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<FhirInteger>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_instruction: Option<Vec<CodeableConcept>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patient_instruction: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_needed: Option<DosageAsNeeded>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site: Option<CodeableConcept>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<CodeableConcept>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<CodeableConcept>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dose_and_rate: Option<Vec<DosageDoseAndRate>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_dose_per_period: Option<Ratio>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_dose_per_administration: Option<Quantity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_dose_per_lifetime: Option<Quantity>,
+}
+
+/// Dosage.asNeeded[x]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum DosageAsNeeded {
+    Boolean(FhirBoolean),
+    CodeableConcept(CodeableConcept),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DosageDoseAndRate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<CodeableConcept>, // calculated | ordered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dose: Option<DosageDose>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<DosageRate>,
+}
+
+/// Dosage.doseAndRate.dose[x]
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum DosageDose {
+    Range(Range),
+    Quantity(Quantity),
+}
+
+impl<'de> Deserialize<'de> for DosageDose {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if has_any_key(&value, &["low", "high"]) {
+            from_value(value).map(Self::Range)
+        } else {
+            from_value(value).map(Self::Quantity)
+        }
+    }
+}
+
+/// Dosage.doseAndRate.rate[x]
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum DosageRate {
+    Ratio(Ratio),
+    Range(Range),
+    Quantity(Quantity),
+}
+
+impl<'de> Deserialize<'de> for DosageRate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if has_any_key(&value, &["numerator", "denominator"]) {
+            from_value(value).map(Self::Ratio)
+        } else if has_any_key(&value, &["low", "high"]) {
+            from_value(value).map(Self::Range)
+        } else {
+            from_value(value).map(Self::Quantity)
+        }
+    }
+}
+
+/// A choice between a code and a reference to a resource, as in
+/// `medication[x]`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum CodeableConceptOrReference {
+    CodeableConcept(CodeableConcept),
+    Reference(Reference),
+}
+
+impl<'de> Deserialize<'de> for CodeableConceptOrReference {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if has_any_key(&value, &["reference", "identifier", "type", "display"]) {
+            from_value(value).map(Self::Reference)
+        } else {
+            from_value(value).map(Self::CodeableConcept)
+        }
+    }
+}
+
+/// Choice types whose datatypes are all objects of optional elements cannot
+/// use a plain untagged derive, which would accept any object as the first
+/// variant. They pick the variant from the element names present instead
+pub(crate) fn has_any_key(value: &Value, keys: &[&str]) -> bool {
+    value
+        .as_object()
+        .is_some_and(|object| keys.iter().any(|key| object.contains_key(*key)))
+}
+
+pub(crate) fn from_value<T: serde::de::DeserializeOwned, E: de::Error>(value: Value) -> Result<T, E> {
+    serde_json::from_value(value).map_err(E::custom)
+}
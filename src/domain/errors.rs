@@ -27,6 +27,9 @@ pub enum FhirError {
     
     #[error("Database error: {0}")]
     Database(String),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
     
     #[error("Conflict: {0}")]
     Conflict(String),
@@ -37,6 +40,15 @@ pub enum FhirError {
     #[error("Unprocessable entity: {0}")]
     UnprocessableEntity(String),
 
+    #[error("Not acceptable: {0}")]
+    NotAcceptable(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
     #[error("Forbidden: {message}")]
     Forbidden {
         message: String,
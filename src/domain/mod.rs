@@ -2,6 +2,7 @@ pub mod primitives;
 pub mod datatypes;
 pub mod resources;
 pub mod errors;
+pub mod xml;
 
 // Re-export commonly used types
 pub use primitives::*;
@@ -2,7 +2,7 @@
 // FHIR Primitive Types
 
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
@@ -44,6 +44,10 @@ pub struct FhirDate(pub NaiveDate);
 #[serde(transparent)]
 pub struct FhirDateTime(pub DateTime<Utc>);
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct FhirTime(pub NaiveTime);
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 pub struct FhirBoolean(pub bool);
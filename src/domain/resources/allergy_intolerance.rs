@@ -0,0 +1,171 @@
+// src/domain/resources/allergy_intolerance.rs
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllergyIntolerance {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clinical_status: Option<CodeableConcept>, // active | inactive | resolved
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_status: Option<CodeableConcept>, // unconfirmed | confirmed | refuted | entered-in-error
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<Code>, // allergy | intolerance
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Vec<Code>>, // food | medication | environment | biologic
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub criticality: Option<Code>, // low | high | unable-to-assess
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<CodeableConcept>,
+
+    pub patient: Reference,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encounter: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onset: Option<AllergyIntoleranceOnset>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorded_date: Option<FhirDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorder: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asserter: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_occurrence: Option<FhirDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Vec<Annotation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reaction: Option<Vec<AllergyIntoleranceReaction>>,
+}
+
+/// `onset[x]`; Age, Period and Range are told apart by their element names
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum AllergyIntoleranceOnset {
+    DateTime(FhirDateTime),
+    Age(Quantity),
+    Period(Period),
+    Range(Range),
+    String(FhirString),
+}
+
+impl<'de> Deserialize<'de> for AllergyIntoleranceOnset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if let Value::String(text) = &value {
+            // Free text such as "childhood" is onsetString
+            Ok(serde_json::from_value(value.clone())
+                .map(Self::DateTime)
+                .unwrap_or_else(|_| Self::String(FhirString(text.clone()))))
+        } else if has_any_key(&value, &["low", "high"]) {
+            from_value(value).map(Self::Range)
+        } else if has_any_key(&value, &["start", "end"]) {
+            from_value(value).map(Self::Period)
+        } else {
+            from_value(value).map(Self::Age)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllergyIntoleranceReaction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub substance: Option<CodeableConcept>,
+
+    pub manifestation: Vec<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onset: Option<FhirDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Code>, // mild | moderate | severe
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exposure_route: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Vec<Annotation>>,
+}
+
+impl Resource for AllergyIntolerance {
+    fn resource_type() -> &'static str {
+        "AllergyIntolerance"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl AllergyIntolerance {
+    pub fn new(patient: Reference) -> Self {
+        Self {
+            resource_type: "AllergyIntolerance".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            clinical_status: None,
+            verification_status: None,
+            type_: None,
+            category: None,
+            criticality: None,
+            code: None,
+            patient,
+            encounter: None,
+            onset: None,
+            recorded_date: None,
+            recorder: None,
+            asserter: None,
+            last_occurrence: None,
+            note: None,
+            reaction: None,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// src/domain/resources/binary.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+/// Raw content in its native format. The content itself is kept on the blob
+/// store; `data` is only filled in when a Binary is exchanged as a FHIR
+/// resource
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Binary {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    pub content_type: Code, // MimeType of the content
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_context: Option<Reference>, // Resource whose access rules apply
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<FhirString>, // base64Binary
+}
+
+impl Resource for Binary {
+    fn resource_type() -> &'static str {
+        "Binary"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Binary {
+    pub fn new(content_type: Code) -> Self {
+        Self {
+            resource_type: "Binary".to_string(),
+            id: None,
+            meta: None,
+            content_type,
+            security_context: None,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// src/domain/resources/bundle.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    pub type_: Code, // document | message | transaction | transaction-response | batch | batch-response | history | searchset | collection
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<Instant>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<UnsignedInt>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<Vec<BundleLink>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<Vec<BundleEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleLink {
+    pub relation: FhirString, // self | first | previous | next | last
+    pub url: Uri,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_url: Option<Uri>,
+    /// Entries may hold any resource type, so the resource is kept as JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<BundleEntrySearch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleEntrySearch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<Vec<Extension>>, // e.g. match-grade on Patient/$match results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<Code>, // match | include | outcome
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<FhirDecimal>,
+}
+
+impl Resource for Bundle {
+    fn resource_type() -> &'static str {
+        "Bundle"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Bundle {
+    pub fn new(type_: &str) -> Self {
+        Self {
+            resource_type: "Bundle".to_string(),
+            id: None,
+            meta: None,
+            type_: Code(type_.to_string()),
+            timestamp: None,
+            total: None,
+            link: None,
+            entry: None,
+        }
+    }
+
+    /// Add a link, e.g. a paging link for searchset bundles
+    pub fn add_link(&mut self, relation: &str, url: String) {
+        self.link.get_or_insert_with(Vec::new).push(BundleLink {
+            relation: FhirString(relation.to_string()),
+            url: Uri(url),
+        });
+    }
+
+    /// Add a resource entry, with `search.mode` set for searchset bundles
+    pub fn add_entry(&mut self, resource: serde_json::Value, search_mode: Option<&str>) {
+        let entry = BundleEntry {
+            full_url: None,
+            resource: Some(resource),
+            search: search_mode.map(|mode| BundleEntrySearch {
+                extension: None,
+                mode: Some(Code(mode.to_string())),
+                score: None,
+            }),
+        };
+        self.entry.get_or_insert_with(Vec::new).push(entry);
+    }
+}
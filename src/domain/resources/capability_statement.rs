@@ -0,0 +1,204 @@
+// src/domain/resources/capability_statement.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatement {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Uri>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<FhirString>,
+
+    pub status: Code, // draft | active | retired | unknown
+
+    pub date: FhirDateTime,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<FhirString>,
+
+    pub kind: Code, // instance | capability | requirements
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub software: Option<CapabilityStatementSoftware>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<CapabilityStatementImplementation>,
+
+    pub fhir_version: Code,
+
+    pub format: Vec<Code>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rest: Option<Vec<CapabilityStatementRest>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementSoftware {
+    pub name: FhirString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<FhirString>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementImplementation {
+    pub description: FhirString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Uri>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementRest {
+    pub mode: Code, // client | server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<CapabilityStatementSecurity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<Vec<CapabilityStatementResource>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interaction: Option<Vec<CapabilityStatementInteraction>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_param: Option<Vec<CapabilityStatementSearchParam>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<Vec<CapabilityStatementOperation>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementSecurity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<Vec<Extension>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cors: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<Vec<CodeableConcept>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<FhirString>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementResource {
+    pub type_: Code,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Canonical>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interaction: Option<Vec<CapabilityStatementInteraction>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versioning: Option<Code>, // no-version | versioned | versioned-update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_history: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_create: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_include: Option<Vec<FhirString>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_param: Option<Vec<CapabilityStatementSearchParam>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<Vec<CapabilityStatementOperation>>,
+}
+
+/// Used for both resource-level (TypeRestfulInteraction) and
+/// system-level (SystemRestfulInteraction) interactions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementInteraction {
+    pub code: Code,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<FhirString>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementSearchParam {
+    pub name: FhirString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<Canonical>,
+    pub type_: Code, // number | date | string | token | reference | composite | quantity | uri | special
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<FhirString>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityStatementOperation {
+    pub name: FhirString,
+    pub definition: Canonical,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<FhirString>,
+}
+
+impl Resource for CapabilityStatement {
+    fn resource_type() -> &'static str {
+        "CapabilityStatement"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl CapabilityStatement {
+    pub fn new(status: Code, date: FhirDateTime, kind: Code, fhir_version: Code) -> Self {
+        Self {
+            resource_type: "CapabilityStatement".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            url: None,
+            version: None,
+            name: None,
+            title: None,
+            status,
+            date,
+            publisher: None,
+            description: None,
+            kind,
+            software: None,
+            implementation: None,
+            fhir_version,
+            format: Vec::new(),
+            rest: None,
+        }
+    }
+}
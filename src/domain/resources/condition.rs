@@ -16,6 +16,9 @@ pub struct Condition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Meta>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<Vec<Identifier>>,
     
@@ -135,6 +138,7 @@ impl Condition {
             resource_type: "Condition".to_string(),
             id: None,
             meta: None,
+            text: None,
             identifier: None,
             clinical_status: None,
             verification_status: None,
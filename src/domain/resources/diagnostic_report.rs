@@ -0,0 +1,122 @@
+// src/domain/resources/diagnostic_report.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticReport {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub based_on: Option<Vec<Reference>>,
+
+    pub status: Code, // registered | partial | preliminary | final | amended | corrected | appended | cancelled | entered-in-error | unknown
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Vec<CodeableConcept>>,
+
+    pub code: CodeableConcept,
+
+    pub subject: Reference, // Patient or Group
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encounter: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective: Option<DiagnosticReportEffective>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued: Option<Instant>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub performer: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results_interpreter: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub specimen: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Vec<Reference>>, // Observation
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conclusion: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conclusion_code: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presented_form: Option<Vec<Attachment>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum DiagnosticReportEffective {
+    DateTime(FhirDateTime),
+    Period(Period),
+}
+
+impl Resource for DiagnosticReport {
+    fn resource_type() -> &'static str {
+        "DiagnosticReport"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl DiagnosticReport {
+    pub fn new(status: Code, code: CodeableConcept, subject: Reference) -> Self {
+        Self {
+            resource_type: "DiagnosticReport".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            based_on: None,
+            status,
+            category: None,
+            code,
+            subject,
+            encounter: None,
+            effective: None,
+            issued: None,
+            performer: None,
+            results_interpreter: None,
+            specimen: None,
+            result: None,
+            conclusion: None,
+            conclusion_code: None,
+            presented_form: None,
+        }
+    }
+}
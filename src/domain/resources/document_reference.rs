@@ -0,0 +1,158 @@
+// src/domain/resources/document_reference.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentReference {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_identifier: Option<Identifier>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    pub status: Code, // current | superseded | entered-in-error
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_status: Option<Code>, // preliminary | final | amended | entered-in-error
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<Reference>, // Patient | Practitioner | Group | Device
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<Instant>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authenticator: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custodian: Option<Reference>, // Organization
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relates_to: Option<Vec<DocumentReferenceRelatesTo>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_label: Option<Vec<CodeableConcept>>,
+
+    pub content: Vec<DocumentReferenceContent>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<DocumentReferenceContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentReferenceRelatesTo {
+    pub code: Code, // replaces | transforms | signs | appends
+
+    pub target: Reference, // DocumentReference
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentReferenceContent {
+    pub attachment: Attachment,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Coding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentReferenceContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encounter: Option<Vec<Reference>>, // Encounter | EpisodeOfCare
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facility_type: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub practice_setting: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_patient_info: Option<Reference>, // Patient
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related: Option<Vec<Reference>>,
+}
+
+impl Resource for DocumentReference {
+    fn resource_type() -> &'static str {
+        "DocumentReference"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl DocumentReference {
+    pub fn new(status: Code, content: Vec<DocumentReferenceContent>) -> Self {
+        Self {
+            resource_type: "DocumentReference".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            master_identifier: None,
+            identifier: None,
+            status,
+            doc_status: None,
+            type_: None,
+            category: None,
+            subject: None,
+            date: None,
+            author: None,
+            authenticator: None,
+            custodian: None,
+            relates_to: None,
+            description: None,
+            security_label: None,
+            content,
+            context: None,
+        }
+    }
+}
@@ -16,6 +16,9 @@ pub struct Encounter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Meta>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<Vec<Identifier>>,
     
@@ -182,6 +185,7 @@ impl Encounter {
             resource_type: "Encounter".to_string(),
             id: None,
             meta: None,
+            text: None,
             identifier: None,
             status,
             status_history: None,
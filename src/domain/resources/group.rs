@@ -0,0 +1,147 @@
+// src/domain/resources/group.rs
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Group {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<FhirBoolean>,
+
+    #[serde(rename = "type")]
+    pub type_: Code, // person | animal | practitioner | device | medication | substance
+
+    pub actual: FhirBoolean, // true: enumerated members; false: defined by characteristics
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<UnsignedInt>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managing_entity: Option<Reference>, // Organization | RelatedPerson | Practitioner | PractitionerRole
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub characteristic: Option<Vec<GroupCharacteristic>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<Vec<GroupMember>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupCharacteristic {
+    pub code: CodeableConcept,
+
+    pub value: GroupCharacteristicValue,
+
+    pub exclude: FhirBoolean,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+}
+
+/// `characteristic.value[x]`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum GroupCharacteristicValue {
+    CodeableConcept(CodeableConcept),
+    Boolean(FhirBoolean),
+    Quantity(Quantity),
+    Range(Range),
+    Reference(Reference),
+}
+
+impl<'de> Deserialize<'de> for GroupCharacteristicValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if value.is_boolean() {
+            from_value(value).map(Self::Boolean)
+        } else if has_any_key(&value, &["reference", "identifier", "type", "display"]) {
+            from_value(value).map(Self::Reference)
+        } else if has_any_key(&value, &["low", "high"]) {
+            from_value(value).map(Self::Range)
+        } else if has_any_key(&value, &["value", "comparator", "unit", "system", "code"]) {
+            from_value(value).map(Self::Quantity)
+        } else {
+            from_value(value).map(Self::CodeableConcept)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMember {
+    pub entity: Reference, // Patient | Practitioner | PractitionerRole | Device | Medication | Substance | Group
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inactive: Option<FhirBoolean>,
+}
+
+impl Resource for Group {
+    fn resource_type() -> &'static str {
+        "Group"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Group {
+    pub fn new(type_: Code, actual: FhirBoolean) -> Self {
+        Self {
+            resource_type: "Group".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            active: None,
+            type_,
+            actual,
+            code: None,
+            name: None,
+            quantity: None,
+            managing_entity: None,
+            characteristic: None,
+            member: None,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// src/domain/resources/immunization.rs
+
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Immunization {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    pub status: Code, // completed | entered-in-error | not-done
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<CodeableConcept>,
+
+    pub vaccine_code: CodeableConcept,
+
+    pub patient: Reference,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encounter: Option<Reference>,
+
+    pub occurrence: ImmunizationOccurrence,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorded: Option<FhirDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_source: Option<FhirBoolean>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lot_number: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<FhirDate>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dose_quantity: Option<Quantity>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub performer: Option<Vec<ImmunizationPerformer>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Vec<Annotation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_reference: Option<Vec<Reference>>, // Condition | Observation | DiagnosticReport
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_subpotent: Option<FhirBoolean>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_applied: Option<Vec<ImmunizationProtocolApplied>>,
+}
+
+/// `occurrence[x]`; free text such as "last spring" is occurrenceString
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ImmunizationOccurrence {
+    DateTime(FhirDateTime),
+    String(FhirString),
+}
+
+impl<'de> Deserialize<'de> for ImmunizationOccurrence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Ok(serde_json::from_value(serde_json::Value::String(text.clone()))
+            .map(Self::DateTime)
+            .unwrap_or(Self::String(FhirString(text))))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImmunizationPerformer {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<CodeableConcept>, // OP (ordering) | AP (administering)
+
+    pub actor: Reference,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImmunizationProtocolApplied {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authority: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_disease: Option<Vec<CodeableConcept>>,
+
+    pub dose_number: ImmunizationDoseNumber,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series_doses: Option<ImmunizationDoseNumber>,
+}
+
+/// `doseNumber[x]` and `seriesDoses[x]`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ImmunizationDoseNumber {
+    PositiveInt(PositiveInt),
+    String(FhirString),
+}
+
+impl Resource for Immunization {
+    fn resource_type() -> &'static str {
+        "Immunization"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Immunization {
+    pub fn new(
+        status: Code,
+        vaccine_code: CodeableConcept,
+        patient: Reference,
+        occurrence: ImmunizationOccurrence,
+    ) -> Self {
+        Self {
+            resource_type: "Immunization".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            status,
+            status_reason: None,
+            vaccine_code,
+            patient,
+            encounter: None,
+            occurrence,
+            recorded: None,
+            primary_source: None,
+            location: None,
+            manufacturer: None,
+            lot_number: None,
+            expiration_date: None,
+            site: None,
+            route: None,
+            dose_quantity: None,
+            performer: None,
+            note: None,
+            reason_code: None,
+            reason_reference: None,
+            is_subpotent: None,
+            protocol_applied: None,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+// src/domain/resources/location.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Code>, // active | suspended | inactive
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operational_status: Option<Coding>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<Vec<FhirString>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<Code>, // instance | kind
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telecom: Option<Vec<ContactPoint>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_type: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<LocationPosition>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managing_organization: Option<Reference>, // Organization
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Reference>, // Location
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours_of_operation: Option<Vec<LocationHoursOfOperation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability_exceptions: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<Vec<Reference>>,
+}
+
+/// WGS84 coordinates of a location, as used by the `near` search
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationPosition {
+    pub longitude: FhirDecimal,
+    pub latitude: FhirDecimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<FhirDecimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationHoursOfOperation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_of_week: Option<Vec<Code>>, // mon | tue | wed | thu | fri | sat | sun
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_day: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opening_time: Option<FhirTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closing_time: Option<FhirTime>,
+}
+
+impl Resource for Location {
+    fn resource_type() -> &'static str {
+        "Location"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Location {
+    pub fn new() -> Self {
+        Self {
+            resource_type: "Location".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            status: None,
+            operational_status: None,
+            name: None,
+            alias: None,
+            description: None,
+            mode: None,
+            type_: None,
+            telecom: None,
+            address: None,
+            physical_type: None,
+            position: None,
+            managing_organization: None,
+            part_of: None,
+            hours_of_operation: None,
+            availability_exceptions: None,
+            endpoint: None,
+        }
+    }
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,111 @@
+// src/domain/resources/medication.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Medication {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Code>, // active | inactive | entered-in-error
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<Reference>, // Organization
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Ratio>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingredient: Option<Vec<MedicationIngredient>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<MedicationBatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MedicationIngredient {
+    pub item: CodeableConceptOrReference, // Substance | Medication
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strength: Option<Ratio>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MedicationBatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lot_number: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<FhirDateTime>,
+}
+
+impl Resource for Medication {
+    fn resource_type() -> &'static str {
+        "Medication"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Medication {
+    pub fn new() -> Self {
+        Self {
+            resource_type: "Medication".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            code: None,
+            status: None,
+            manufacturer: None,
+            form: None,
+            amount: None,
+            ingredient: None,
+            batch: None,
+        }
+    }
+}
+
+impl Default for Medication {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,173 @@
+// src/domain/resources/medication_request.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MedicationRequest {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    pub status: Code, // active | on-hold | cancelled | completed | entered-in-error | stopped | draft | unknown
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<CodeableConcept>,
+
+    pub intent: Code, // proposal | plan | order | original-order | reflex-order | filler-order | instance-order | option
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Code>, // routine | urgent | asap | stat
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub do_not_perform: Option<FhirBoolean>,
+
+    pub medication: CodeableConceptOrReference, // Medication
+
+    pub subject: Reference, // Patient or Group
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encounter: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authored_on: Option<FhirDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requester: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub performer: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorder: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_reference: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub based_on: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_identifier: Option<Identifier>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Vec<Annotation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dosage_instruction: Option<Vec<Dosage>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dispense_request: Option<MedicationRequestDispenseRequest>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub substitution: Option<MedicationRequestSubstitution>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prior_prescription: Option<Reference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MedicationRequestDispenseRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity_period: Option<Period>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_repeats_allowed: Option<UnsignedInt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<Quantity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_supply_duration: Option<Quantity>, // Duration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub performer: Option<Reference>, // Organization
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MedicationRequestSubstitution {
+    pub allowed: SubstitutionAllowed,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<CodeableConcept>,
+}
+
+/// MedicationRequest.substitution.allowed[x]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum SubstitutionAllowed {
+    Boolean(FhirBoolean),
+    CodeableConcept(CodeableConcept),
+}
+
+impl Resource for MedicationRequest {
+    fn resource_type() -> &'static str {
+        "MedicationRequest"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl MedicationRequest {
+    pub fn new(status: Code, intent: Code, medication: CodeableConceptOrReference, subject: Reference) -> Self {
+        Self {
+            resource_type: "MedicationRequest".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            status,
+            status_reason: None,
+            intent,
+            category: None,
+            priority: None,
+            do_not_perform: None,
+            medication,
+            subject,
+            encounter: None,
+            authored_on: None,
+            requester: None,
+            performer: None,
+            recorder: None,
+            reason_code: None,
+            reason_reference: None,
+            based_on: None,
+            group_identifier: None,
+            note: None,
+            dosage_instruction: None,
+            dispense_request: None,
+            substitution: None,
+            prior_prescription: None,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// src/domain/resources/medication_statement.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MedicationStatement {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub based_on: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Vec<Reference>>,
+
+    pub status: Code, // active | completed | entered-in-error | intended | stopped | on-hold | unknown | not-taken
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<CodeableConcept>,
+
+    pub medication: CodeableConceptOrReference, // Medication
+
+    pub subject: Reference, // Patient or Group
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Reference>, // Encounter or EpisodeOfCare
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective: Option<MedicationStatementEffective>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_asserted: Option<FhirDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub information_source: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derived_from: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_reference: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Vec<Annotation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dosage: Option<Vec<Dosage>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum MedicationStatementEffective {
+    DateTime(FhirDateTime),
+    Period(Period),
+}
+
+impl Resource for MedicationStatement {
+    fn resource_type() -> &'static str {
+        "MedicationStatement"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl MedicationStatement {
+    pub fn new(status: Code, medication: CodeableConceptOrReference, subject: Reference) -> Self {
+        Self {
+            resource_type: "MedicationStatement".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            based_on: None,
+            part_of: None,
+            status,
+            status_reason: None,
+            category: None,
+            medication,
+            subject,
+            context: None,
+            effective: None,
+            date_asserted: None,
+            information_source: None,
+            derived_from: None,
+            reason_code: None,
+            reason_reference: None,
+            note: None,
+            dosage: None,
+        }
+    }
+}
@@ -4,11 +4,47 @@ pub mod patient;
 pub mod observation;
 pub mod condition;
 pub mod encounter;
+pub mod practitioner;
+pub mod practitioner_role;
+pub mod organization;
+pub mod medication;
+pub mod medication_request;
+pub mod medication_statement;
+pub mod allergy_intolerance;
+pub mod procedure;
+pub mod diagnostic_report;
+pub mod immunization;
+pub mod location;
+pub mod group;
+pub mod document_reference;
+pub mod binary;
+pub mod capability_statement;
+pub mod bundle;
+pub mod operation_outcome;
+pub mod parameters;
 
 pub use patient::Patient;
 pub use observation::Observation;
 pub use condition::Condition;
 pub use encounter::Encounter;
+pub use practitioner::Practitioner;
+pub use practitioner_role::PractitionerRole;
+pub use organization::Organization;
+pub use medication::Medication;
+pub use medication_request::MedicationRequest;
+pub use medication_statement::MedicationStatement;
+pub use allergy_intolerance::AllergyIntolerance;
+pub use procedure::Procedure;
+pub use diagnostic_report::DiagnosticReport;
+pub use immunization::Immunization;
+pub use location::Location;
+pub use group::Group;
+pub use document_reference::DocumentReference;
+pub use binary::Binary;
+pub use capability_statement::CapabilityStatement;
+pub use bundle::Bundle;
+pub use operation_outcome::OperationOutcome;
+pub use parameters::Parameters;
 
 use crate::domain::primitives::{Id};
 use crate::domain::datatypes::Meta;
@@ -16,6 +16,9 @@ pub struct Observation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Meta>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<Vec<Identifier>>,
     
@@ -167,6 +170,7 @@ impl Observation {
             resource_type: "Observation".to_string(),
             id: None,
             meta: None,
+            text: None,
             identifier: None,
             based_on: None,
             part_of: None,
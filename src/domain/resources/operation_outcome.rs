@@ -0,0 +1,121 @@
+// src/domain/resources/operation_outcome.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*, errors::FhirError};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationOutcome {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    pub issue: Vec<OperationOutcomeIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationOutcomeIssue {
+    pub severity: Code, // fatal | error | warning | information
+    pub code: Code, // invalid | structure | required | not-found | forbidden | conflict | exception +
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<CodeableConcept>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression: Option<Vec<FhirString>>,
+}
+
+impl Resource for OperationOutcome {
+    fn resource_type() -> &'static str {
+        "OperationOutcome"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl OperationOutcome {
+    pub fn new(issue: Vec<OperationOutcomeIssue>) -> Self {
+        Self {
+            resource_type: "OperationOutcome".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            issue,
+        }
+    }
+
+    /// Outcome of a validation run: one issue per problem, or a single
+    /// informational issue when there are none
+    pub fn from_issues(issues: &[FhirError]) -> Self {
+        if issues.is_empty() {
+            return Self::new(vec![OperationOutcomeIssue::new("information", "informational", "All OK")]);
+        }
+        Self::new(issues.iter().map(OperationOutcomeIssue::from).collect())
+    }
+}
+
+impl OperationOutcomeIssue {
+    pub fn new(severity: &str, code: &str, diagnostics: impl Into<String>) -> Self {
+        Self {
+            severity: Code(severity.to_string()),
+            code: Code(code.to_string()),
+            details: None,
+            diagnostics: Some(FhirString(diagnostics.into())),
+            expression: None,
+        }
+    }
+}
+
+impl From<&FhirError> for OperationOutcomeIssue {
+    fn from(error: &FhirError) -> Self {
+        let code = match error {
+            FhirError::Validation(_) => "invalid",
+            FhirError::NotFound { .. } => "not-found",
+            FhirError::InvalidResourceType(_) => "not-supported",
+            FhirError::MissingRequiredField(_) => "required",
+            FhirError::InvalidReference(_) => "invalid",
+            FhirError::Serialization(_) => "structure",
+            FhirError::Database(_) => "exception",
+            FhirError::Storage(_) => "exception",
+            FhirError::Conflict(_) => "conflict",
+            FhirError::PreconditionFailed(_) => "conflict",
+            FhirError::UnprocessableEntity(_) => "processing",
+            FhirError::NotAcceptable(_) => "not-supported",
+            FhirError::UnsupportedMediaType(_) => "not-supported",
+            FhirError::PayloadTooLarge(_) => "too-long",
+            FhirError::Forbidden { .. } => "forbidden",
+        };
+
+        Self::new("error", code, error.to_string())
+    }
+}
+
+impl From<&FhirError> for OperationOutcome {
+    fn from(error: &FhirError) -> Self {
+        Self::new(vec![OperationOutcomeIssue::from(error)])
+    }
+}
@@ -0,0 +1,113 @@
+// src/domain/resources/organization.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Organization {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<FhirBoolean>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<Vec<CodeableConcept>>, // prov | dept | team | govt | ins | edu | ...
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<FhirString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<Vec<FhirString>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telecom: Option<Vec<ContactPoint>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Vec<Address>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Reference>, // Organization
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Vec<OrganizationContact>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<Vec<Reference>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationContact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<CodeableConcept>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<HumanName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telecom: Option<Vec<ContactPoint>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+}
+
+impl Resource for Organization {
+    fn resource_type() -> &'static str {
+        "Organization"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Organization {
+    pub fn new() -> Self {
+        Self {
+            resource_type: "Organization".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            active: None,
+            type_: None,
+            name: None,
+            alias: None,
+            telecom: None,
+            address: None,
+            part_of: None,
+            contact: None,
+            endpoint: None,
+        }
+    }
+}
+
+impl Default for Organization {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,133 @@
+// src/domain/resources/parameters.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+/// Operation request/response parameters
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameters {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<Vec<ParametersParameter>>,
+}
+
+/// A named parameter. Like Extension, value[x] is spelled out per type so
+/// that operation inputs (e.g. `valueReference`) are unambiguous
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParametersParameter {
+    pub name: FhirString,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_string: Option<FhirString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_code: Option<Code>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_uri: Option<Uri>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_boolean: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_integer: Option<FhirInteger>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_decimal: Option<FhirDecimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_date_time: Option<FhirDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_coding: Option<Coding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_quantity: Option<Quantity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_period: Option<Period>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_reference: Option<Reference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_meta: Option<Meta>,
+
+    /// Parameters may carry any resource type, so the resource is kept as JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<Vec<ParametersParameter>>,
+}
+
+impl Resource for Parameters {
+    fn resource_type() -> &'static str {
+        "Parameters"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Parameters {
+    pub fn new() -> Self {
+        Self {
+            resource_type: "Parameters".to_string(),
+            id: None,
+            meta: None,
+            parameter: None,
+        }
+    }
+
+    /// Add a parameter
+    pub fn add(&mut self, parameter: ParametersParameter) {
+        self.parameter.get_or_insert_with(Vec::new).push(parameter);
+    }
+
+    /// The first parameter with the given name
+    pub fn get(&self, name: &str) -> Option<&ParametersParameter> {
+        self.parameter.iter().flatten().find(|p| p.name.0 == name)
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParametersParameter {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: FhirString(name.to_string()),
+            value_string: None,
+            value_code: None,
+            value_uri: None,
+            value_boolean: None,
+            value_integer: None,
+            value_decimal: None,
+            value_date_time: None,
+            value_coding: None,
+            value_quantity: None,
+            value_period: None,
+            value_reference: None,
+            value_meta: None,
+            resource: None,
+            part: None,
+        }
+    }
+}
@@ -16,6 +16,9 @@ pub struct Patient {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Meta>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<Vec<Identifier>>,
     
@@ -57,6 +60,9 @@ pub struct Patient {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub managing_organization: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<Vec<PatientLink>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -100,6 +106,14 @@ pub struct PatientCommunication {
     pub preferred: Option<FhirBoolean>,
 }
 
+/// Link to another patient resource concerning the same person
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PatientLink {
+    pub other: Reference,
+    pub type_: Code, // replaced-by | replaces | refer | seealso
+}
+
 impl Resource for Patient {
     fn resource_type() -> &'static str {
         "Patient"
@@ -128,6 +142,7 @@ impl Patient {
             resource_type: "Patient".to_string(),
             id: None,
             meta: None,
+            text: None,
             identifier: None,
             active: None,
             name: None,
@@ -142,6 +157,7 @@ impl Patient {
             communication: None,
             general_practitioner: None,
             managing_organization: None,
+            link: None,
         }
     }
 }
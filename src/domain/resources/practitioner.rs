@@ -0,0 +1,112 @@
+// src/domain/resources/practitioner.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Practitioner {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>, // NPI, license numbers
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<FhirBoolean>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Vec<HumanName>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telecom: Option<Vec<ContactPoint>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Vec<Address>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Code>, // male | female | other | unknown
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub birth_date: Option<FhirDate>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo: Option<Vec<Attachment>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualification: Option<Vec<PractitionerQualification>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication: Option<Vec<CodeableConcept>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PractitionerQualification {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+    pub code: CodeableConcept,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<Reference>, // Organization
+}
+
+impl Resource for Practitioner {
+    fn resource_type() -> &'static str {
+        "Practitioner"
+    }
+    
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+    
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+    
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+    
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Practitioner {
+    pub fn new() -> Self {
+        Self {
+            resource_type: "Practitioner".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            active: None,
+            name: None,
+            telecom: None,
+            address: None,
+            gender: None,
+            birth_date: None,
+            photo: None,
+            qualification: None,
+            communication: None,
+        }
+    }
+}
+
+impl Default for Practitioner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,137 @@
+// src/domain/resources/practitioner_role.rs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PractitionerRole {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<FhirBoolean>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub practitioner: Option<Reference>, // Practitioner
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Reference>, // Organization
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Vec<CodeableConcept>>, // doctor | nurse | pharmacist | researcher +
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub specialty: Option<Vec<CodeableConcept>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Vec<Reference>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub healthcare_service: Option<Vec<Reference>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telecom: Option<Vec<ContactPoint>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_time: Option<Vec<PractitionerRoleAvailableTime>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_available: Option<Vec<PractitionerRoleNotAvailable>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability_exceptions: Option<FhirString>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<Vec<Reference>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PractitionerRoleAvailableTime {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_of_week: Option<Vec<Code>>, // mon | tue | wed | thu | fri | sat | sun
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_day: Option<FhirBoolean>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_start_time: Option<FhirTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_end_time: Option<FhirTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PractitionerRoleNotAvailable {
+    pub description: FhirString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub during: Option<Period>,
+}
+
+impl Resource for PractitionerRole {
+    fn resource_type() -> &'static str {
+        "PractitionerRole"
+    }
+    
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+    
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+    
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+    
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl PractitionerRole {
+    pub fn new() -> Self {
+        Self {
+            resource_type: "PractitionerRole".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            active: None,
+            period: None,
+            practitioner: None,
+            organization: None,
+            code: None,
+            specialty: None,
+            location: None,
+            healthcare_service: None,
+            telecom: None,
+            available_time: None,
+            not_available: None,
+            availability_exceptions: None,
+            endpoint: None,
+        }
+    }
+}
+
+impl Default for PractitionerRole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
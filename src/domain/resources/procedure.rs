@@ -0,0 +1,182 @@
+// src/domain/resources/procedure.rs
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use crate::domain::{datatypes::*, primitives::*};
+use super::Resource;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Procedure {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Narrative>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Vec<Identifier>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub based_on: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Vec<Reference>>,
+
+    pub status: Code, // preparation | in-progress | not-done | on-hold | stopped | completed | entered-in-error | unknown
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<CodeableConcept>,
+
+    pub subject: Reference, // Patient or Group
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encounter: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub performed: Option<ProcedurePerformed>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorder: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asserter: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub performer: Option<Vec<ProcedurePerformer>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Reference>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_reference: Option<Vec<Reference>>, // Condition
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_site: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<Vec<Reference>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complication: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow_up: Option<Vec<CodeableConcept>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Vec<Annotation>>,
+}
+
+/// `performed[x]`; Period, Age and Range are told apart by their element names
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ProcedurePerformed {
+    DateTime(FhirDateTime),
+    Period(Period),
+    String(FhirString),
+    Age(Quantity),
+    Range(Range),
+}
+
+impl<'de> Deserialize<'de> for ProcedurePerformed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if let Value::String(text) = &value {
+            // Free text such as "about 2 years ago" is performedString
+            Ok(serde_json::from_value(value.clone())
+                .map(Self::DateTime)
+                .unwrap_or_else(|_| Self::String(FhirString(text.clone()))))
+        } else if has_any_key(&value, &["low", "high"]) {
+            from_value(value).map(Self::Range)
+        } else if has_any_key(&value, &["start", "end"]) {
+            from_value(value).map(Self::Period)
+        } else {
+            from_value(value).map(Self::Age)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcedurePerformer {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<CodeableConcept>,
+
+    pub actor: Reference, // Practitioner, PractitionerRole, Organization, Patient, ...
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_behalf_of: Option<Reference>, // Organization
+}
+
+impl Resource for Procedure {
+    fn resource_type() -> &'static str {
+        "Procedure"
+    }
+
+    fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    fn meta(&self) -> Option<&Meta> {
+        self.meta.as_ref()
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = Some(id);
+    }
+
+    fn set_meta(&mut self, meta: Meta) {
+        self.meta = Some(meta);
+    }
+}
+
+impl Procedure {
+    pub fn new(status: Code, subject: Reference) -> Self {
+        Self {
+            resource_type: "Procedure".to_string(),
+            id: None,
+            meta: None,
+            text: None,
+            identifier: None,
+            based_on: None,
+            part_of: None,
+            status,
+            status_reason: None,
+            category: None,
+            code: None,
+            subject,
+            encounter: None,
+            performed: None,
+            recorder: None,
+            asserter: None,
+            performer: None,
+            location: None,
+            reason_code: None,
+            reason_reference: None,
+            body_site: None,
+            outcome: None,
+            report: None,
+            complication: None,
+            follow_up: None,
+            note: None,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// src/domain/xml/choice.rs
+// Choice-type ([x]) elements. The domain model stores these as untagged
+// enums, so their JSON form carries no type suffix; FHIR XML requires one
+// (e.g. `valueQuantity`), which is derived here from the element path.
+
+use chrono::{DateTime, NaiveDate};
+use serde_json::Value;
+
+struct ChoiceElement {
+    /// Element path, matched against the end of the full element path
+    path: &'static str,
+    /// Allowed types, in the order the domain enum declares them
+    types: &'static [&'static str],
+}
+
+const OBSERVATION_VALUE: &[&str] = &[
+    "Quantity", "CodeableConcept", "String", "Boolean", "Integer", "Range", "Period", "DateTime",
+];
+
+const CONDITION_ONSET: &[&str] = &["DateTime", "Age", "Period", "Range", "String"];
+
+const CODEABLE_CONCEPT_OR_REFERENCE: &[&str] = &["CodeableConcept", "Reference"];
+
+const BOOLEAN_OR_CODEABLE_CONCEPT: &[&str] = &["Boolean", "CodeableConcept"];
+
+const CHOICE_ELEMENTS: &[ChoiceElement] = &[
+    ChoiceElement { path: "Patient.deceased", types: &["Boolean", "DateTime"] },
+    ChoiceElement { path: "Patient.multipleBirth", types: &["Boolean", "Integer"] },
+    ChoiceElement { path: "Observation.effective", types: &["DateTime", "Period", "Instant"] },
+    ChoiceElement { path: "Observation.value", types: OBSERVATION_VALUE },
+    ChoiceElement { path: "Observation.component.value", types: OBSERVATION_VALUE },
+    ChoiceElement { path: "Condition.onset", types: CONDITION_ONSET },
+    ChoiceElement { path: "Condition.abatement", types: CONDITION_ONSET },
+    ChoiceElement { path: "Medication.ingredient.item", types: CODEABLE_CONCEPT_OR_REFERENCE },
+    ChoiceElement { path: "MedicationRequest.medication", types: CODEABLE_CONCEPT_OR_REFERENCE },
+    ChoiceElement { path: "MedicationRequest.substitution.allowed", types: BOOLEAN_OR_CODEABLE_CONCEPT },
+    ChoiceElement { path: "MedicationStatement.medication", types: CODEABLE_CONCEPT_OR_REFERENCE },
+    ChoiceElement { path: "MedicationStatement.effective", types: &["DateTime", "Period"] },
+    ChoiceElement { path: "AllergyIntolerance.onset", types: CONDITION_ONSET },
+    ChoiceElement { path: "Procedure.performed", types: &["DateTime", "Period", "String", "Age", "Range"] },
+    ChoiceElement { path: "DiagnosticReport.effective", types: &["DateTime", "Period"] },
+    ChoiceElement { path: "Immunization.occurrence", types: &["DateTime", "String"] },
+    ChoiceElement { path: "Immunization.protocolApplied.doseNumber", types: &["PositiveInt", "String"] },
+    ChoiceElement { path: "Immunization.protocolApplied.seriesDoses", types: &["PositiveInt", "String"] },
+    ChoiceElement {
+        path: "Group.characteristic.value",
+        types: &["CodeableConcept", "Boolean", "Quantity", "Range", "Reference"],
+    },
+    // Dosage and Timing choices, wherever a Dosage is used
+    ChoiceElement { path: "asNeeded", types: BOOLEAN_OR_CODEABLE_CONCEPT },
+    ChoiceElement { path: "doseAndRate.dose", types: &["Range", "Quantity"] },
+    ChoiceElement { path: "doseAndRate.rate", types: &["Ratio", "Range", "Quantity"] },
+    ChoiceElement { path: "repeat.bounds", types: &["Duration", "Range", "Period"] },
+    // Annotation.author[x], wherever an Annotation is used
+    ChoiceElement { path: "note.author", types: &["Reference", "String"] },
+];
+
+/// Allowed types for the choice element at `path`, if it is one
+pub(super) fn choice_types(path: &str) -> Option<&'static [&'static str]> {
+    CHOICE_ELEMENTS
+        .iter()
+        .find(|choice| {
+            path == choice.path
+                || path
+                    .strip_suffix(choice.path)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+        .map(|choice| choice.types)
+}
+
+/// Split a suffixed XML element name (`valueQuantity`) under `parent` into
+/// its base name and type, if it names a choice element
+pub(super) fn split_choice(parent: &str, name: &str) -> Option<(String, &'static str)> {
+    name.char_indices()
+        .filter(|(i, c)| *i > 0 && c.is_ascii_uppercase())
+        .find_map(|(i, _)| {
+            let (base, suffix) = name.split_at(i);
+            let types = choice_types(&format!("{}.{}", parent, base))?;
+            types
+                .iter()
+                .find(|t| **t == suffix)
+                .map(|t| (base.to_string(), *t))
+        })
+}
+
+/// Pick the type suffix for a choice value from its JSON shape
+pub(super) fn infer_type(types: &'static [&'static str], value: &Value) -> &'static str {
+    let allowed = |candidates: &[&str]| {
+        types.iter().copied().find(|t| candidates.contains(t))
+    };
+
+    let inferred = match value {
+        Value::Bool(_) => allowed(&["Boolean"]),
+        Value::Number(n) if n.is_i64() || n.is_u64() => allowed(&["Integer", "PositiveInt", "Decimal"]),
+        Value::Number(_) => allowed(&["Decimal"]),
+        Value::String(s) => {
+            let temporal = if DateTime::parse_from_rfc3339(s).is_ok() {
+                allowed(&["DateTime", "Instant"])
+            } else if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+                allowed(&["Date", "DateTime"])
+            } else {
+                None
+            };
+            temporal.or_else(|| allowed(&["String", "Code", "Uri"]))
+        }
+        Value::Object(object) => types.iter().copied().find(|t| {
+            let fields = complex_fields(t);
+            !fields.is_empty() && object.keys().all(|k| fields.contains(&k.as_str()))
+        }),
+        _ => None,
+    };
+
+    inferred.unwrap_or(types[0])
+}
+
+/// Element names of the complex datatypes that appear in choice elements
+fn complex_fields(type_: &str) -> &'static [&'static str] {
+    match type_ {
+        "Quantity" | "Age" | "Duration" | "Distance" | "Count" | "SimpleQuantity" => {
+            &["value", "comparator", "unit", "system", "code"]
+        }
+        "CodeableConcept" => &["coding", "text"],
+        "Coding" => &["system", "version", "code", "display", "userSelected"],
+        "Range" => &["low", "high"],
+        "Ratio" => &["numerator", "denominator"],
+        "Period" => &["start", "end"],
+        "Reference" => &["reference", "type", "identifier", "display"],
+        _ => &[],
+    }
+}
@@ -0,0 +1,422 @@
+// src/domain/xml/mod.rs
+// FHIR XML representation (http://hl7.org/fhir/xml.html)
+//
+// Resources are converted through their JSON form, so every domain type with
+// serde support can be written and read as XML without per-type code:
+// primitives become `value` attributes, repeated elements become sibling
+// elements, choice elements get their type suffix (see `choice.rs`),
+// `Extension.url` is an attribute and narrative XHTML is embedded verbatim.
+
+mod choice;
+mod reader;
+mod writer;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::domain::errors::{FhirError, FhirResult};
+use writer::XmlWriter;
+
+pub const FHIR_NAMESPACE: &str = "http://hl7.org/fhir";
+
+/// Serialize a resource as a FHIR XML document
+pub fn to_xml<T: Serialize>(resource: &T) -> FhirResult<String> {
+    XmlWriter::new(false).write_document(&serde_json::to_value(resource)?)
+}
+
+/// Serialize a resource as an indented FHIR XML document
+pub fn to_xml_pretty<T: Serialize>(resource: &T) -> FhirResult<String> {
+    XmlWriter::new(true).write_document(&serde_json::to_value(resource)?)
+}
+
+/// Deserialize a resource from a FHIR XML document
+pub fn from_xml<T: DeserializeOwned>(xml: &str) -> FhirResult<T> {
+    reader::read_document(xml).map_err(|e| FhirError::Validation(format!("Invalid FHIR XML: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        resources::observation::{ObservationEffective, ObservationValue},
+        resources::patient::PatientDeceased,
+        *,
+    };
+    use chrono::{TimeZone, Utc};
+
+    fn sample_observation() -> Observation {
+        let mut observation = Observation::new(
+            Code("final".to_string()),
+            CodeableConcept {
+                coding: Some(vec![Coding {
+                    system: Some(Uri("http://loinc.org".to_string())),
+                    version: None,
+                    code: Some(Code("8867-4".to_string())),
+                    display: Some(FhirString("Heart rate".to_string())),
+                    user_selected: None,
+                }]),
+                text: None,
+            },
+        );
+        observation.id = Some(Id("obs-1".to_string()));
+        observation.effective = Some(ObservationEffective::DateTime(FhirDateTime(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+        )));
+        observation.value = Some(ObservationValue::Quantity(Quantity {
+            value: Some(FhirDecimal(72.5)),
+            comparator: None,
+            unit: Some(FhirString("beats/minute".to_string())),
+            system: Some(Uri("http://unitsofmeasure.org".to_string())),
+            code: Some(Code("/min".to_string())),
+        }));
+        observation
+    }
+
+    #[test]
+    fn test_primitives_are_value_attributes() {
+        let xml = to_xml(&sample_observation()).unwrap();
+
+        assert!(xml.contains(r#"<Observation xmlns="http://hl7.org/fhir">"#));
+        assert!(xml.contains(r#"<id value="obs-1"/>"#));
+        assert!(xml.contains(r#"<status value="final"/>"#));
+        assert!(xml.contains(r#"<code value="8867-4"/>"#));
+    }
+
+    #[test]
+    fn test_choice_elements_get_type_suffix() {
+        let xml = to_xml(&sample_observation()).unwrap();
+
+        assert!(xml.contains("<effectiveDateTime value=\"2024-01-15T10:30:00Z\"/>"));
+        assert!(xml.contains("<valueQuantity><value value=\"72.5\"/>"));
+    }
+
+    #[test]
+    fn test_observation_round_trip() {
+        let observation = sample_observation();
+        let xml = to_xml(&observation).unwrap();
+        let parsed: Observation = from_xml(&xml).unwrap();
+
+        assert_eq!(parsed, observation);
+    }
+
+    #[test]
+    fn test_patient_repeated_elements_and_narrative() {
+        let mut patient = Patient::new();
+        patient.text = Some(Narrative {
+            status: Code("generated".to_string()),
+            div: r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>John <b>Doe</b></p></div>"#.to_string(),
+        });
+        patient.name = Some(vec![HumanName {
+            use_: Some(Code("official".to_string())),
+            text: None,
+            family: Some(FhirString("Doe".to_string())),
+            given: Some(vec![FhirString("John".to_string()), FhirString("Q".to_string())]),
+            prefix: None,
+            suffix: None,
+            period: None,
+        }]);
+        patient.active = Some(FhirBoolean(true));
+        patient.deceased = Some(PatientDeceased::Boolean(FhirBoolean(false)));
+
+        let xml = to_xml(&patient).unwrap();
+        assert!(xml.contains(r#"<given value="John"/><given value="Q"/>"#));
+        assert!(xml.contains(r#"<use value="official"/>"#));
+        assert!(xml.contains(r#"<deceasedBoolean value="false"/>"#));
+        assert!(xml.contains("<p>John <b>Doe</b></p>"));
+
+        let parsed: Patient = from_xml(&xml).unwrap();
+        assert_eq!(parsed, patient);
+    }
+
+    #[test]
+    fn test_single_repeated_element_reads_as_list() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Patient xmlns="http://hl7.org/fhir">
+                <!-- a comment -->
+                <name>
+                    <family value="Doe"/>
+                    <given value="Jane"/>
+                </name>
+                <gender value="female"/>
+                <birthDate value="1990-05-01"/>
+                <multipleBirthInteger value="2"/>
+            </Patient>"#;
+
+        let patient: Patient = from_xml(xml).unwrap();
+        let name = &patient.name.unwrap()[0];
+        assert_eq!(name.given.as_ref().unwrap()[0].0, "Jane");
+        assert_eq!(patient.gender.unwrap().0, "female");
+        assert_eq!(patient.resource_type, "Patient");
+        assert!(patient.multiple_birth.is_some());
+    }
+
+    #[test]
+    fn test_extension_url_is_attribute() {
+        let mut extension = Extension::new("http://example.org/oauth-uris");
+        let mut token = Extension::new("token");
+        token.value_uri = Some(Uri("http://localhost/auth/login".to_string()));
+        extension.extension = Some(vec![token]);
+
+        let mut statement = CapabilityStatement::new(
+            Code("active".to_string()),
+            FhirDateTime(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+            Code("instance".to_string()),
+            Code("4.0.1".to_string()),
+        );
+        statement.format = vec![Code("json".to_string()), Code("xml".to_string())];
+        statement.rest = Some(vec![resources::capability_statement::CapabilityStatementRest {
+            mode: Code("server".to_string()),
+            documentation: None,
+            security: Some(resources::capability_statement::CapabilityStatementSecurity {
+                extension: Some(vec![extension]),
+                cors: Some(FhirBoolean(true)),
+                service: None,
+                description: None,
+            }),
+            resource: None,
+            interaction: None,
+            search_param: None,
+            operation: None,
+        }]);
+
+        let xml = to_xml(&statement).unwrap();
+        assert!(xml.contains(
+            r#"<extension url="http://example.org/oauth-uris"><extension url="token"><valueUri value="http://localhost/auth/login"/></extension></extension>"#
+        ));
+
+        let parsed: CapabilityStatement = from_xml(&xml).unwrap();
+        assert_eq!(parsed, statement);
+    }
+
+    #[test]
+    fn test_nested_resources_are_wrapped() {
+        let mut bundle = Bundle::new("searchset");
+        bundle.add_entry(serde_json::to_value(sample_observation()).unwrap(), Some("match"));
+
+        let xml = to_xml(&bundle).unwrap();
+        assert!(xml.contains("<entry><resource><Observation><id value=\"obs-1\"/>"));
+        assert!(xml.contains("<search><mode value=\"match\"/></search>"));
+    }
+
+    #[test]
+    fn test_pretty_output_reads_back() {
+        let observation = sample_observation();
+        let xml = to_xml_pretty(&observation).unwrap();
+
+        assert!(xml.contains("\n  <status value=\"final\"/>"));
+        assert!(xml.contains("\n    <coding>\n      <system value=\"http://loinc.org\"/>"));
+
+        let parsed: Observation = from_xml(&xml).unwrap();
+        assert_eq!(parsed, observation);
+    }
+
+    #[test]
+    fn test_medication_request_choices_round_trip() {
+        let reference = |target: &str| Reference {
+            reference: Some(FhirString(target.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let quantity = |value: f64, unit: &str| Quantity {
+            value: Some(FhirDecimal(value)),
+            comparator: None,
+            unit: Some(FhirString(unit.to_string())),
+            system: None,
+            code: None,
+        };
+        let mut request = MedicationRequest::new(
+            Code("active".to_string()),
+            Code("order".to_string()),
+            CodeableConceptOrReference::Reference(reference("Medication/med-1")),
+            reference("Patient/pat-1"),
+        );
+        request.dosage_instruction = Some(vec![Dosage {
+            sequence: None,
+            text: Some(FhirString("1 tablet every 8 hours".to_string())),
+            additional_instruction: None,
+            patient_instruction: None,
+            timing: Some(Timing {
+                event: None,
+                repeat: Some(TimingRepeat {
+                    bounds: Some(TimingBounds::Duration(quantity(10.0, "d"))),
+                    count: None,
+                    count_max: None,
+                    duration: None,
+                    duration_max: None,
+                    duration_unit: None,
+                    frequency: Some(PositiveInt(1)),
+                    frequency_max: None,
+                    period: Some(FhirDecimal(8.0)),
+                    period_max: None,
+                    period_unit: Some(Code("h".to_string())),
+                    day_of_week: None,
+                    time_of_day: None,
+                    when: None,
+                    offset: None,
+                }),
+                code: None,
+            }),
+            as_needed: Some(DosageAsNeeded::Boolean(FhirBoolean(false))),
+            site: None,
+            route: None,
+            method: None,
+            dose_and_rate: Some(vec![DosageDoseAndRate {
+                type_: None,
+                dose: Some(DosageDose::Quantity(quantity(1.0, "tablet"))),
+                rate: None,
+            }]),
+            max_dose_per_period: None,
+            max_dose_per_administration: None,
+            max_dose_per_lifetime: None,
+        }]);
+
+        let xml = to_xml(&request).unwrap();
+        assert!(xml.contains("<medicationReference><reference value=\"Medication/med-1\"/>"));
+        assert!(xml.contains("<asNeededBoolean value=\"false\"/>"));
+        assert!(xml.contains("<doseQuantity>"));
+        assert!(xml.contains("<boundsDuration>"));
+
+        let parsed: MedicationRequest = from_xml(&xml).unwrap();
+        assert_eq!(parsed, request);
+
+        // JSON picks the medication[x] variant from the element names
+        let json = serde_json::json!({ "coding": [{ "code": "197361" }] });
+        let medication: CodeableConceptOrReference = serde_json::from_value(json).unwrap();
+        assert!(matches!(medication, CodeableConceptOrReference::CodeableConcept(_)));
+    }
+
+    #[test]
+    fn test_allergy_onset_round_trip() {
+        use crate::domain::resources::allergy_intolerance::AllergyIntoleranceOnset;
+
+        let mut allergy = AllergyIntolerance::new(Reference {
+            reference: Some(FhirString("Patient/pat-1".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+        allergy.onset = Some(AllergyIntoleranceOnset::Period(Period {
+            start: Some(FhirDateTime(Utc.with_ymd_and_hms(2020, 5, 1, 0, 0, 0).unwrap())),
+            end: None,
+        }));
+
+        let xml = to_xml(&allergy).unwrap();
+        assert!(xml.contains("<onsetPeriod>"));
+        let parsed: AllergyIntolerance = from_xml(&xml).unwrap();
+        assert_eq!(parsed, allergy);
+
+        // A Period is not taken for an Age, and free text is onsetString
+        let period: AllergyIntoleranceOnset = serde_json::from_value(serde_json::json!({ "start": "2020-05-01T00:00:00Z" })).unwrap();
+        assert!(matches!(period, AllergyIntoleranceOnset::Period(_)));
+        let text: AllergyIntoleranceOnset = serde_json::from_value(serde_json::json!("childhood")).unwrap();
+        assert_eq!(text, AllergyIntoleranceOnset::String(FhirString("childhood".to_string())));
+    }
+
+    #[test]
+    fn test_immunization_choices_round_trip() {
+        use crate::domain::resources::immunization::{
+            ImmunizationDoseNumber, ImmunizationOccurrence, ImmunizationProtocolApplied,
+        };
+
+        let mut immunization = Immunization::new(
+            Code("completed".to_string()),
+            CodeableConcept {
+                coding: None,
+                text: Some(FhirString("Influenza vaccine".to_string())),
+            },
+            Reference {
+                reference: Some(FhirString("Patient/pat-1".to_string())),
+                type_: None,
+                identifier: None,
+                display: None,
+            },
+            ImmunizationOccurrence::String(FhirString("last autumn".to_string())),
+        );
+        immunization.protocol_applied = Some(vec![ImmunizationProtocolApplied {
+            series: None,
+            authority: None,
+            target_disease: None,
+            dose_number: ImmunizationDoseNumber::PositiveInt(PositiveInt(2)),
+            series_doses: Some(ImmunizationDoseNumber::String(FhirString("booster".to_string()))),
+        }]);
+
+        let xml = to_xml(&immunization).unwrap();
+        assert!(xml.contains("<occurrenceString value=\"last autumn\"/>"));
+        assert!(xml.contains("<doseNumberPositiveInt value=\"2\"/>"));
+        assert!(xml.contains("<seriesDosesString value=\"booster\"/>"));
+        let parsed: Immunization = from_xml(&xml).unwrap();
+        assert_eq!(parsed, immunization);
+    }
+
+    #[test]
+    fn test_group_characteristic_values_round_trip() {
+        use crate::domain::resources::group::{GroupCharacteristic, GroupCharacteristicValue};
+
+        let characteristic = |text: &str, value| GroupCharacteristic {
+            code: CodeableConcept {
+                coding: None,
+                text: Some(FhirString(text.to_string())),
+            },
+            value,
+            exclude: FhirBoolean(false),
+            period: None,
+        };
+        let mut group = Group::new(Code("person".to_string()), FhirBoolean(false));
+        group.characteristic = Some(vec![
+            characteristic("smoker", GroupCharacteristicValue::Boolean(FhirBoolean(true))),
+            characteristic("age", GroupCharacteristicValue::Range(Range {
+                low: Some(Quantity {
+                    value: Some(FhirDecimal(18.0)),
+                    comparator: None,
+                    unit: Some(FhirString("a".to_string())),
+                    system: None,
+                    code: None,
+                }),
+                high: None,
+            })),
+            characteristic("clinic", GroupCharacteristicValue::Reference(Reference {
+                reference: Some(FhirString("Organization/org-1".to_string())),
+                type_: None,
+                identifier: None,
+                display: None,
+            })),
+        ]);
+
+        let xml = to_xml(&group).unwrap();
+        assert!(xml.contains("<valueBoolean value=\"true\"/>"));
+        assert!(xml.contains("<valueRange>"));
+        assert!(xml.contains("<valueReference>"));
+        let parsed: Group = from_xml(&xml).unwrap();
+        assert_eq!(parsed, group);
+    }
+
+    #[test]
+    fn test_malformed_or_hostile_narrative_is_rejected() {
+        let mut patient = Patient::new();
+        for div in [
+            r#"<div xmlns="http://www.w3.org/1999/xhtml">x</div></text><script>alert(1)</script>"#,
+            r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>unclosed</div>"#,
+            "</div></text><name><family value=\"Injected\"/></name><text><div>",
+            "<p>not a div</p>",
+            "plain text",
+        ] {
+            patient.text = Some(Narrative {
+                status: Code("generated".to_string()),
+                div: div.to_string(),
+            });
+            assert!(matches!(to_xml(&patient), Err(FhirError::Validation(_))), "accepted {}", div);
+        }
+
+        patient.text = Some(Narrative {
+            status: Code("generated".to_string()),
+            div: r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>a &amp; b<br/></p></div>"#.to_string(),
+        });
+        assert!(to_xml(&patient).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_xml_is_a_validation_error() {
+        let result: FhirResult<Patient> = from_xml("<Patient><name></Patient>");
+        assert!(matches!(result, Err(FhirError::Validation(_))));
+    }
+}
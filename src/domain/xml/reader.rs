@@ -0,0 +1,421 @@
+// src/domain/xml/reader.rs
+
+use std::fmt;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::de::value::BorrowedStrDeserializer;
+use serde::{forward_to_deserialize_any, Deserializer};
+use serde_json::Value;
+
+use crate::domain::{datatypes::*, primitives::*};
+use super::choice;
+
+#[derive(Debug)]
+pub(super) struct XmlError(String);
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for XmlError {}
+
+impl de::Error for XmlError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        XmlError(msg.to_string())
+    }
+}
+
+/// Parsed FHIR XML element
+#[derive(Debug)]
+enum Node {
+    /// Primitive `value` attribute, attribute (e.g. `url`) or raw XHTML
+    Primitive(String),
+    /// Child elements in document order
+    Element(Vec<(String, Node)>),
+    /// Choice element already converted to its typed JSON form
+    Json(Value),
+}
+
+/// Deserialize a FHIR XML document into a domain type
+pub(super) fn read_document<T: DeserializeOwned>(xml: &str) -> Result<T, XmlError> {
+    let (name, node) = parse(xml)?;
+    let node = resolve_resource(name, node)?;
+    T::deserialize(NodeDeserializer(&node))
+}
+
+struct Frame {
+    name: String,
+    value: Option<String>,
+    children: Vec<(String, Node)>,
+}
+
+impl Frame {
+    fn new(start: &BytesStart) -> Result<Self, XmlError> {
+        let mut frame = Frame {
+            name: local_name(start),
+            value: None,
+            children: Vec::new(),
+        };
+
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(de::Error::custom)?;
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            if attribute.key.as_namespace_binding().is_some() {
+                continue;
+            }
+            let value = attribute.unescape_value().map_err(de::Error::custom)?.into_owned();
+            if key == "value" {
+                frame.value = Some(value);
+            } else {
+                frame.children.push((key, Node::Primitive(value)));
+            }
+        }
+
+        Ok(frame)
+    }
+
+    fn into_node(self) -> (String, Node) {
+        // Extensions on primitive values are not modelled and are dropped
+        let node = match self.value {
+            Some(value) => Node::Primitive(value),
+            None => Node::Element(self.children),
+        };
+        (self.name, node)
+    }
+}
+
+fn local_name(start: &BytesStart) -> String {
+    String::from_utf8_lossy(start.local_name().as_ref()).into_owned()
+}
+
+fn parse(xml: &str) -> Result<(String, Node), XmlError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut root = None;
+
+    loop {
+        let finished = match reader.read_event().map_err(de::Error::custom)? {
+            Event::Start(start) if local_name(&start) == "div" => {
+                // Narrative XHTML is kept verbatim
+                let open = String::from_utf8_lossy(&start).into_owned();
+                let end = start.to_end().into_owned();
+                let span = reader.read_to_end(end.name()).map_err(de::Error::custom)?;
+                let inner = &xml[span.start as usize..span.end as usize];
+                let close = String::from_utf8_lossy(end.name().as_ref()).into_owned();
+                Some(("div".to_string(), Node::Primitive(format!("<{}>{}</{}>", open, inner, close))))
+            }
+            Event::Empty(start) if local_name(&start) == "div" => {
+                let open = String::from_utf8_lossy(&start).into_owned();
+                Some(("div".to_string(), Node::Primitive(format!("<{}/>", open))))
+            }
+            Event::Start(start) => {
+                stack.push(Frame::new(&start)?);
+                None
+            }
+            Event::Empty(start) => Some(Frame::new(&start)?.into_node()),
+            Event::End(_) => {
+                let frame = stack
+                    .pop()
+                    .ok_or_else(|| XmlError("unexpected closing tag".to_string()))?;
+                Some(frame.into_node())
+            }
+            Event::Eof => break,
+            // Declarations, comments and whitespace carry no FHIR content
+            _ => None,
+        };
+
+        if let Some(element) = finished {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+        }
+    }
+
+    root.ok_or_else(|| XmlError("document has no root element".to_string()))
+}
+
+/// Resolve a resource element: record its type and resolve its content
+fn resolve_resource(name: String, node: Node) -> Result<Node, XmlError> {
+    match resolve(node, &name)? {
+        Node::Element(mut children) => {
+            children.insert(0, ("resourceType".to_string(), Node::Primitive(name)));
+            Ok(Node::Element(children))
+        }
+        _ => Err(XmlError(format!("{} resource has no content", name))),
+    }
+}
+
+/// Unwrap nested resources and convert choice elements to their typed form
+fn resolve(node: Node, path: &str) -> Result<Node, XmlError> {
+    let children = match node {
+        Node::Element(children) => children,
+        other => return Ok(other),
+    };
+
+    // A resource inside a wrapper element (contained, Bundle.entry.resource);
+    // only resource names start with an uppercase letter
+    if children.len() == 1 && children[0].0.starts_with(|c: char| c.is_ascii_uppercase()) {
+        let (name, inner) = children.into_iter().next().unwrap();
+        return resolve_resource(name, inner);
+    }
+
+    let mut resolved = Vec::with_capacity(children.len());
+    for (name, child) in children {
+        match choice::split_choice(path, &name) {
+            Some((base, type_)) => {
+                let child = resolve(child, &format!("{}.{}", path, base))?;
+                resolved.push((base, Node::Json(typed_value(type_, &child)?)));
+            }
+            None => {
+                let child = resolve(child, &format!("{}.{}", path, name))?;
+                resolved.push((name, child));
+            }
+        }
+    }
+    Ok(Node::Element(resolved))
+}
+
+/// Convert a choice element to JSON through its concrete datatype, so the
+/// untagged domain enum sees correctly typed values
+fn typed_value(type_: &str, node: &Node) -> Result<Value, XmlError> {
+    match type_ {
+        "Quantity" | "Age" | "Duration" | "Distance" | "Count" | "SimpleQuantity" => {
+            to_json::<Quantity>(node)
+        }
+        "CodeableConcept" => to_json::<CodeableConcept>(node),
+        "Coding" => to_json::<Coding>(node),
+        "Range" => to_json::<Range>(node),
+        "Ratio" => to_json::<Ratio>(node),
+        "Period" => to_json::<Period>(node),
+        "Reference" => to_json::<Reference>(node),
+        "Boolean" => to_json::<FhirBoolean>(node),
+        "Integer" => to_json::<FhirInteger>(node),
+        "PositiveInt" => to_json::<PositiveInt>(node),
+        "Decimal" => to_json::<FhirDecimal>(node),
+        "DateTime" => to_json::<FhirDateTime>(node),
+        "Instant" => to_json::<Instant>(node),
+        "Date" => to_json::<FhirDate>(node),
+        _ => to_json::<FhirString>(node),
+    }
+}
+
+fn to_json<T: DeserializeOwned + serde::Serialize>(node: &Node) -> Result<Value, XmlError> {
+    let value = T::deserialize(NodeDeserializer(node))?;
+    serde_json::to_value(value).map_err(de::Error::custom)
+}
+
+/// Deserializer for a single element. XML carries no type information, so
+/// primitives are parsed according to what the target type asks for
+struct NodeDeserializer<'de>(&'de Node);
+
+impl<'de> NodeDeserializer<'de> {
+    fn primitive(&self) -> Result<&'de str, XmlError> {
+        match self.0 {
+            Node::Primitive(value) => Ok(value),
+            _ => Err(XmlError("expected a primitive value".to_string())),
+        }
+    }
+
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, XmlError>
+    where
+        T::Err: fmt::Display,
+    {
+        let value = self.primitive()?;
+        value
+            .trim()
+            .parse()
+            .map_err(|e| XmlError(format!("invalid value '{}': {}", value, e)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $ty:ty, $visit:ident;)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+            if let Node::Json(value) = self.0 {
+                return value.$method(visitor).map_err(de::Error::custom);
+            }
+            visitor.$visit(self.parse::<$ty>()?)
+        }
+    )*};
+}
+
+impl<'de> Deserializer<'de> for NodeDeserializer<'de> {
+    type Error = XmlError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+        match self.0 {
+            Node::Primitive(value) => visitor.visit_borrowed_str(value),
+            Node::Element(children) => visitor.visit_map(ElementAccess::new(children)),
+            Node::Json(value) => value.deserialize_any(visitor).map_err(de::Error::custom),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => bool, visit_bool;
+        deserialize_i8 => i64, visit_i64;
+        deserialize_i16 => i64, visit_i64;
+        deserialize_i32 => i64, visit_i64;
+        deserialize_i64 => i64, visit_i64;
+        deserialize_u8 => u64, visit_u64;
+        deserialize_u16 => u64, visit_u64;
+        deserialize_u32 => u64, visit_u64;
+        deserialize_u64 => u64, visit_u64;
+        deserialize_f32 => f64, visit_f64;
+        deserialize_f64 => f64, visit_f64;
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+        match self.0 {
+            Node::Json(value) => value.deserialize_option(visitor).map_err(de::Error::custom),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, XmlError> {
+        match self.0 {
+            Node::Json(value) => value
+                .deserialize_newtype_struct(name, visitor)
+                .map_err(de::Error::custom),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+        match self.0 {
+            Node::Json(value) => value.deserialize_seq(visitor).map_err(de::Error::custom),
+            node => visitor.visit_seq(NodeSeq(std::iter::once(node))),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct
+        map struct enum identifier ignored_any i128 u128
+    }
+}
+
+/// Deserializer for all occurrences of a child element. Repeated elements
+/// form a sequence; a single occurrence also satisfies a sequence
+struct FieldDeserializer<'de>(Vec<&'de Node>);
+
+impl<'de> FieldDeserializer<'de> {
+    fn first(&self) -> Result<NodeDeserializer<'de>, XmlError> {
+        self.0
+            .first()
+            .map(|node| NodeDeserializer(node))
+            .ok_or_else(|| XmlError("missing element".to_string()))
+    }
+}
+
+macro_rules! forward_to_first {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+            self.first()?.$method(visitor)
+        }
+    )*};
+}
+
+impl<'de> Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = XmlError;
+
+    forward_to_first! {
+        deserialize_any deserialize_bool
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, XmlError> {
+        self.first()?.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, XmlError> {
+        visitor.visit_seq(NodeSeq(self.0.into_iter()))
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct
+        map struct enum identifier ignored_any i128 u128
+    }
+}
+
+struct NodeSeq<I>(I);
+
+impl<'de, I: Iterator<Item = &'de Node>> SeqAccess<'de> for NodeSeq<I> {
+    type Error = XmlError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, XmlError> {
+        self.0
+            .next()
+            .map(|node| seed.deserialize(NodeDeserializer(node)))
+            .transpose()
+    }
+}
+
+/// Child elements grouped by name, in order of first occurrence
+struct ElementAccess<'de> {
+    fields: std::vec::IntoIter<(&'de str, Vec<&'de Node>)>,
+    value: Option<Vec<&'de Node>>,
+}
+
+impl<'de> ElementAccess<'de> {
+    fn new(children: &'de [(String, Node)]) -> Self {
+        let mut fields: Vec<(&'de str, Vec<&'de Node>)> = Vec::new();
+        for (name, node) in children {
+            match fields.iter_mut().find(|(existing, _)| existing == name) {
+                Some((_, nodes)) => nodes.push(node),
+                None => fields.push((name, vec![node])),
+            }
+        }
+        Self {
+            fields: fields.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for ElementAccess<'de> {
+    type Error = XmlError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, XmlError> {
+        match self.fields.next() {
+            Some((name, nodes)) => {
+                self.value = Some(nodes);
+                seed.deserialize(BorrowedStrDeserializer::new(name)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, XmlError> {
+        let nodes = self
+            .value
+            .take()
+            .ok_or_else(|| XmlError("value requested before key".to_string()))?;
+        seed.deserialize(FieldDeserializer(nodes))
+    }
+}
@@ -0,0 +1,201 @@
+// src/domain/xml/writer.rs
+
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::ser::Error as _;
+use serde_json::{Map, Value};
+
+use crate::domain::errors::{FhirError, FhirResult};
+use super::{choice, FHIR_NAMESPACE};
+
+/// Writes resources (in their JSON form) as FHIR XML
+pub(super) struct XmlWriter {
+    out: String,
+    pretty: bool,
+    depth: usize,
+}
+
+impl XmlWriter {
+    pub(super) fn new(pretty: bool) -> Self {
+        Self {
+            out: String::new(),
+            pretty,
+            depth: 0,
+        }
+    }
+
+    /// Write a complete XML document for `resource`
+    pub(super) fn write_document(mut self, resource: &Value) -> FhirResult<String> {
+        let object = resource
+            .as_object()
+            .ok_or_else(|| error("a resource must be a JSON object"))?;
+
+        self.out.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        self.write_resource(object, true)?;
+        if self.pretty {
+            self.out.push('\n');
+        }
+        Ok(self.out)
+    }
+
+    fn write_resource(&mut self, object: &Map<String, Value>, root: bool) -> FhirResult<()> {
+        let resource_type = object
+            .get("resourceType")
+            .and_then(Value::as_str)
+            .ok_or_else(|| error("resource is missing resourceType"))?;
+
+        self.newline();
+        self.out.push('<');
+        self.out.push_str(resource_type);
+        if root {
+            self.out.push_str(&format!(r#" xmlns="{}""#, FHIR_NAMESPACE));
+        }
+        self.out.push('>');
+        self.write_children(object, resource_type, &[])?;
+        self.close(resource_type);
+        Ok(())
+    }
+
+    fn write_children(
+        &mut self,
+        object: &Map<String, Value>,
+        path: &str,
+        skip: &[&str],
+    ) -> FhirResult<()> {
+        self.depth += 1;
+        for (key, value) in object {
+            if key == "resourceType" || skip.contains(&key.as_str()) {
+                continue;
+            }
+
+            let child_path = format!("{}.{}", path, key);
+            let choice_types = choice::choice_types(&child_path);
+
+            let items = match value {
+                Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            };
+
+            for item in items {
+                let name = match choice_types {
+                    Some(types) => format!("{}{}", key, choice::infer_type(types, item)),
+                    None => key.clone(),
+                };
+                self.write_element(&name, item, &child_path)?;
+            }
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn write_element(&mut self, name: &str, value: &Value, path: &str) -> FhirResult<()> {
+        match value {
+            Value::Null => {}
+            Value::Bool(b) => self.write_primitive(name, &b.to_string()),
+            Value::Number(n) => self.write_primitive(name, &n.to_string()),
+            // Narrative XHTML is embedded as-is, already in the XHTML namespace,
+            // once it is known to be a single well-formed `div` element
+            Value::String(s) if name == "div" => {
+                check_xhtml_div(s)?;
+                self.newline();
+                self.out.push_str(s);
+            }
+            Value::String(s) => self.write_primitive(name, s),
+            Value::Array(items) => {
+                for item in items {
+                    self.write_element(name, item, path)?;
+                }
+            }
+            Value::Object(object) if object.contains_key("resourceType") => {
+                self.newline();
+                self.out.push_str(&format!("<{}>", name));
+                self.depth += 1;
+                self.write_resource(object, false)?;
+                self.depth -= 1;
+                self.close(name);
+            }
+            Value::Object(object) => {
+                self.newline();
+                self.out.push('<');
+                self.out.push_str(name);
+
+                // Extension.url is an attribute rather than an element
+                let is_extension = name == "extension" || name == "modifierExtension";
+                let skip: &[&str] = if is_extension { &["url"] } else { &[] };
+                if let Some(Value::String(url)) = object.get("url").filter(|_| is_extension) {
+                    self.out.push_str(&format!(r#" url="{}""#, escape(url.as_str())));
+                }
+
+                if object.keys().all(|k| skip.contains(&k.as_str())) {
+                    self.out.push_str("/>");
+                } else {
+                    self.out.push('>');
+                    self.write_children(object, path, skip)?;
+                    self.close(name);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_primitive(&mut self, name: &str, value: &str) {
+        self.newline();
+        self.out.push_str(&format!(r#"<{} value="{}"/>"#, name, escape(value)));
+    }
+
+    fn close(&mut self, name: &str) {
+        self.newline();
+        self.out.push_str(&format!("</{}>", name));
+    }
+
+    fn newline(&mut self) {
+        if self.pretty {
+            self.out.push('\n');
+            self.out.push_str(&"  ".repeat(self.depth));
+        }
+    }
+}
+
+/// A narrative `div` must be exactly one well-formed `div` element, so that
+/// embedding it cannot close or add to the elements around it
+fn check_xhtml_div(div: &str) -> FhirResult<()> {
+    let invalid = |reason: &str| FhirError::Validation(format!("Invalid narrative div: {}", reason));
+
+    let mut reader = Reader::from_str(div);
+    let mut depth = 0usize;
+    let mut closed = false;
+    loop {
+        let event = reader.read_event().map_err(|e| invalid(&e.to_string()))?;
+        match event {
+            Event::Start(_) | Event::Empty(_) if closed => {
+                return Err(invalid("content after the closing </div>"));
+            }
+            Event::Start(start) | Event::Empty(start) if depth == 0 && start.local_name().as_ref() != b"div" => {
+                return Err(invalid("the root element must be <div>"));
+            }
+            Event::Start(_) => depth += 1,
+            Event::Empty(_) if depth == 0 => closed = true,
+            Event::Empty(_) => {}
+            Event::End(_) => {
+                // quick-xml has already checked it matches the open element
+                depth = depth.checked_sub(1).ok_or_else(|| invalid("unexpected closing tag"))?;
+                closed = depth == 0;
+            }
+            Event::Text(text) if depth == 0 && !text.iter().all(u8::is_ascii_whitespace) => {
+                return Err(invalid("text outside the <div>"));
+            }
+            Event::CData(_) if depth == 0 => return Err(invalid("text outside the <div>")),
+            Event::Decl(_) | Event::PI(_) | Event::DocType(_) => {
+                return Err(invalid("declarations are not allowed"));
+            }
+            Event::Eof if closed => return Ok(()),
+            Event::Eof => return Err(invalid("the <div> is not closed")),
+            _ => {}
+        }
+    }
+}
+
+fn error(message: &str) -> FhirError {
+    FhirError::Serialization(serde_json::Error::custom(message))
+}
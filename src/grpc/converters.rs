@@ -160,6 +160,90 @@ fn from_proto_quantity(quantity: &proto::Quantity) -> domain::Quantity {
     }
 }
 
+fn to_proto_identifier(identifier: &Identifier) -> proto::Identifier {
+    proto::Identifier {
+        system: identifier.system.as_ref().map(|s| s.0.clone()),
+        value: identifier.value.as_ref().map(|v| v.0.clone()),
+        r#use: identifier.use_.as_ref().map(|c| c.0.clone()),
+    }
+}
+
+fn from_proto_identifier(identifier: &proto::Identifier) -> Identifier {
+    Identifier {
+        use_: identifier.r#use.as_ref().map(|s| Code(s.clone())),
+        type_: None,
+        system: identifier.system.as_ref().map(|s| Uri(s.clone())),
+        value: identifier.value.as_ref().map(|v| FhirString(v.clone())),
+        period: None,
+        assigner: None,
+    }
+}
+
+fn to_proto_contact_point(contact: &ContactPoint) -> proto::ContactPoint {
+    proto::ContactPoint {
+        system: contact.system.as_ref().map(|s| s.0.clone()),
+        value: contact.value.as_ref().map(|v| v.0.clone()),
+        r#use: contact.use_.as_ref().map(|c| c.0.clone()),
+        rank: contact.rank.as_ref().map(|r| r.0),
+    }
+}
+
+fn from_proto_contact_point(contact: &proto::ContactPoint) -> ContactPoint {
+    ContactPoint {
+        system: contact.system.as_ref().map(|s| Code(s.clone())),
+        value: contact.value.as_ref().map(|v| FhirString(v.clone())),
+        use_: contact.r#use.as_ref().map(|s| Code(s.clone())),
+        rank: contact.rank.map(FhirInteger),
+        period: None,
+    }
+}
+
+fn to_proto_address(address: &Address) -> proto::Address {
+    proto::Address {
+        r#use: address.use_.as_ref().map(|c| c.0.clone()),
+        r#type: address.type_.as_ref().map(|c| c.0.clone()),
+        text: address.text.as_ref().map(|t| t.0.clone()),
+        line: address.line.as_ref().map(|l| l.iter().map(|s| s.0.clone()).collect()).unwrap_or_default(),
+        city: address.city.as_ref().map(|c| c.0.clone()),
+        district: address.district.as_ref().map(|d| d.0.clone()),
+        state: address.state.as_ref().map(|s| s.0.clone()),
+        postal_code: address.postal_code.as_ref().map(|p| p.0.clone()),
+        country: address.country.as_ref().map(|c| c.0.clone()),
+    }
+}
+
+fn from_proto_address(address: &proto::Address) -> Address {
+    Address {
+        use_: address.r#use.as_ref().map(|s| Code(s.clone())),
+        type_: address.r#type.as_ref().map(|s| Code(s.clone())),
+        text: address.text.as_ref().map(|t| FhirString(t.clone())),
+        line: if address.line.is_empty() {
+            None
+        } else {
+            Some(address.line.iter().map(|s| FhirString(s.clone())).collect())
+        },
+        city: address.city.as_ref().map(|c| FhirString(c.clone())),
+        district: address.district.as_ref().map(|d| FhirString(d.clone())),
+        state: address.state.as_ref().map(|s| FhirString(s.clone())),
+        postal_code: address.postal_code.as_ref().map(|p| FhirString(p.clone())),
+        country: address.country.as_ref().map(|c| FhirString(c.clone())),
+        period: None,
+    }
+}
+
+/// Repeated proto field to an optional FHIR list, empty meaning absent
+fn from_proto_list<P, T>(items: &[P], convert: impl Fn(&P) -> T) -> Option<Vec<T>> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(items.iter().map(convert).collect())
+    }
+}
+
+fn to_proto_list<T, P>(items: &Option<Vec<T>>, convert: impl Fn(&T) -> P) -> Vec<P> {
+    items.as_ref().map(|items| items.iter().map(convert).collect()).unwrap_or_default()
+}
+
 // Patient conversions
 pub fn to_proto_patient(patient: &domain::Patient) -> proto::Patient {
     proto::Patient {
@@ -175,6 +259,12 @@ pub fn to_proto_patient(patient: &domain::Patient) -> proto::Patient {
         birth_date: patient.birth_date.as_ref().map(|d| d.0.to_string()),
         address: vec![], // Simplified - implement if needed
         marital_status: patient.marital_status.as_ref().map(to_proto_codeable_concept),
+        link: patient.link.as_ref().map(|links| {
+            links.iter().map(|link| proto::PatientLink {
+                other: Some(to_proto_reference(&link.other)),
+                r#type: link.type_.0.clone(),
+            }).collect()
+        }).unwrap_or_default(),
     }
 }
 
@@ -185,6 +275,7 @@ pub fn from_proto_patient(proto: &proto::Patient) -> domain::Patient {
         resource_type: "Patient".to_string(),
         id: proto.id.as_ref().map(|id| Id(id.clone())),
         meta: from_proto_meta(&proto.meta),
+        text: None,
         identifier: None,
         active: proto.active.map(FhirBoolean),
         name: if proto.name.is_empty() {
@@ -205,6 +296,19 @@ pub fn from_proto_patient(proto: &proto::Patient) -> domain::Patient {
         communication: None,
         general_practitioner: None,
         managing_organization: None,
+        link: if proto.link.is_empty() {
+            None
+        } else {
+            Some(proto.link.iter().map(|link| patient::PatientLink {
+                other: link.other.as_ref().map(from_proto_reference).unwrap_or(Reference {
+                    reference: None,
+                    type_: None,
+                    identifier: None,
+                    display: None,
+                }),
+                type_: Code(link.r#type.clone()),
+            }).collect())
+        },
     }
 }
 
@@ -267,6 +371,7 @@ pub fn from_proto_observation(proto: &proto::Observation) -> domain::Observation
         resource_type: "Observation".to_string(),
         id: proto.id.as_ref().map(|id| Id(id.clone())),
         meta: from_proto_meta(&proto.meta),
+        text: None,
         identifier: None,
         based_on: None,
         part_of: None,
@@ -329,6 +434,7 @@ pub fn from_proto_condition(proto: &proto::Condition) -> domain::Condition {
         resource_type: "Condition".to_string(),
         id: proto.id.as_ref().map(|id| Id(id.clone())),
         meta: from_proto_meta(&proto.meta),
+        text: None,
         identifier: None,
         clinical_status: proto.clinical_status.as_ref().map(from_proto_codeable_concept),
         verification_status: proto.verification_status.as_ref().map(from_proto_codeable_concept),
@@ -376,6 +482,7 @@ pub fn from_proto_encounter(proto: &proto::Encounter) -> domain::Encounter {
         resource_type: "Encounter".to_string(),
         id: proto.id.as_ref().map(|id| Id(id.clone())),
         meta: from_proto_meta(&proto.meta),
+        text: None,
         identifier: None,
         status: proto.status.as_ref().map(|s| Code(s.clone())).unwrap_or(Code("planned".to_string())),
         status_history: None,
@@ -407,3 +514,934 @@ pub fn from_proto_encounter(proto: &proto::Encounter) -> domain::Encounter {
         part_of: None,
     }
 }
+
+// Practitioner conversions
+pub fn to_proto_practitioner(practitioner: &domain::Practitioner) -> proto::Practitioner {
+    proto::Practitioner {
+        id: practitioner.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&practitioner.meta),
+        identifier: to_proto_list(&practitioner.identifier, to_proto_identifier),
+        active: practitioner.active.as_ref().map(|a| a.0),
+        name: to_proto_list(&practitioner.name, to_proto_human_name),
+        telecom: to_proto_list(&practitioner.telecom, to_proto_contact_point),
+        address: to_proto_list(&practitioner.address, to_proto_address),
+        gender: practitioner.gender.as_ref().map(|g| g.0.clone()),
+        birth_date: practitioner.birth_date.as_ref().map(|d| d.0.to_string()),
+        qualification: to_proto_list(&practitioner.qualification, |q| proto::PractitionerQualification {
+            identifier: to_proto_list(&q.identifier, to_proto_identifier),
+            code: Some(to_proto_codeable_concept(&q.code)),
+            period: q.period.as_ref().map(to_proto_period),
+            issuer: q.issuer.as_ref().map(to_proto_reference),
+        }),
+    }
+}
+
+pub fn from_proto_practitioner(proto: &proto::Practitioner) -> domain::Practitioner {
+    use chrono::NaiveDate;
+
+    domain::Practitioner {
+        resource_type: "Practitioner".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        active: proto.active.map(FhirBoolean),
+        name: from_proto_list(&proto.name, from_proto_human_name),
+        telecom: from_proto_list(&proto.telecom, from_proto_contact_point),
+        address: from_proto_list(&proto.address, from_proto_address),
+        gender: proto.gender.as_ref().map(|g| Code(g.clone())),
+        birth_date: proto.birth_date.as_ref().and_then(|d| {
+            NaiveDate::parse_from_str(d, "%Y-%m-%d").ok().map(FhirDate)
+        }),
+        photo: None,
+        qualification: from_proto_list(&proto.qualification, |q| practitioner::PractitionerQualification {
+            identifier: from_proto_list(&q.identifier, from_proto_identifier),
+            code: q.code.as_ref().map(from_proto_codeable_concept).unwrap_or(CodeableConcept {
+                coding: None,
+                text: None,
+            }),
+            period: q.period.as_ref().map(from_proto_period),
+            issuer: q.issuer.as_ref().map(from_proto_reference),
+        }),
+        communication: None,
+    }
+}
+
+// PractitionerRole conversions
+pub fn to_proto_practitioner_role(role: &domain::PractitionerRole) -> proto::PractitionerRole {
+    proto::PractitionerRole {
+        id: role.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&role.meta),
+        identifier: to_proto_list(&role.identifier, to_proto_identifier),
+        active: role.active.as_ref().map(|a| a.0),
+        period: role.period.as_ref().map(to_proto_period),
+        practitioner: role.practitioner.as_ref().map(to_proto_reference),
+        organization: role.organization.as_ref().map(to_proto_reference),
+        code: to_proto_list(&role.code, to_proto_codeable_concept),
+        specialty: to_proto_list(&role.specialty, to_proto_codeable_concept),
+        telecom: to_proto_list(&role.telecom, to_proto_contact_point),
+    }
+}
+
+pub fn from_proto_practitioner_role(proto: &proto::PractitionerRole) -> domain::PractitionerRole {
+    domain::PractitionerRole {
+        resource_type: "PractitionerRole".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        active: proto.active.map(FhirBoolean),
+        period: proto.period.as_ref().map(from_proto_period),
+        practitioner: proto.practitioner.as_ref().map(from_proto_reference),
+        organization: proto.organization.as_ref().map(from_proto_reference),
+        code: from_proto_list(&proto.code, from_proto_codeable_concept),
+        specialty: from_proto_list(&proto.specialty, from_proto_codeable_concept),
+        location: None,
+        healthcare_service: None,
+        telecom: from_proto_list(&proto.telecom, from_proto_contact_point),
+        available_time: None,
+        not_available: None,
+        availability_exceptions: None,
+        endpoint: None,
+    }
+}
+
+// Organization conversions
+pub fn to_proto_organization(organization: &domain::Organization) -> proto::Organization {
+    proto::Organization {
+        id: organization.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&organization.meta),
+        identifier: to_proto_list(&organization.identifier, to_proto_identifier),
+        active: organization.active.as_ref().map(|a| a.0),
+        r#type: to_proto_list(&organization.type_, to_proto_codeable_concept),
+        name: organization.name.as_ref().map(|n| n.0.clone()),
+        alias: to_proto_list(&organization.alias, |a| a.0.clone()),
+        telecom: to_proto_list(&organization.telecom, to_proto_contact_point),
+        address: to_proto_list(&organization.address, to_proto_address),
+        part_of: organization.part_of.as_ref().map(to_proto_reference),
+    }
+}
+
+pub fn from_proto_organization(proto: &proto::Organization) -> domain::Organization {
+    domain::Organization {
+        resource_type: "Organization".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        active: proto.active.map(FhirBoolean),
+        type_: from_proto_list(&proto.r#type, from_proto_codeable_concept),
+        name: proto.name.as_ref().map(|n| FhirString(n.clone())),
+        alias: from_proto_list(&proto.alias, |a| FhirString(a.clone())),
+        telecom: from_proto_list(&proto.telecom, from_proto_contact_point),
+        address: from_proto_list(&proto.address, from_proto_address),
+        part_of: proto.part_of.as_ref().map(from_proto_reference),
+        contact: None,
+        endpoint: None,
+    }
+}
+
+// Dosage conversions; the proto carries the common subset of the FHIR model
+fn to_proto_dosage(dosage: &Dosage) -> proto::Dosage {
+    let repeat = dosage.timing.as_ref().and_then(|t| t.repeat.as_ref());
+    let dose_quantity = dosage.dose_and_rate.iter().flatten()
+        .find_map(|d| match &d.dose {
+            Some(DosageDose::Quantity(q)) => Some(to_proto_quantity(q)),
+            _ => None,
+        });
+
+    proto::Dosage {
+        text: dosage.text.as_ref().map(|t| t.0.clone()),
+        timing: dosage.timing.as_ref().map(|timing| proto::Timing {
+            code: timing.code.as_ref().map(to_proto_codeable_concept),
+            frequency: repeat.and_then(|r| r.frequency.as_ref()).map(|f| f.0),
+            period: repeat.and_then(|r| r.period.as_ref()).map(|p| p.0),
+            period_unit: repeat.and_then(|r| r.period_unit.as_ref()).map(|u| u.0.clone()),
+        }),
+        as_needed: match &dosage.as_needed {
+            Some(DosageAsNeeded::Boolean(b)) => Some(b.0),
+            _ => None,
+        },
+        route: dosage.route.as_ref().map(to_proto_codeable_concept),
+        dose_quantity,
+    }
+}
+
+fn from_proto_dosage(proto: &proto::Dosage) -> Dosage {
+    let timing = proto.timing.as_ref().map(|timing| {
+        let has_repeat = timing.frequency.is_some() || timing.period.is_some() || timing.period_unit.is_some();
+        Timing {
+            event: None,
+            repeat: has_repeat.then(|| TimingRepeat {
+                bounds: None,
+                count: None,
+                count_max: None,
+                duration: None,
+                duration_max: None,
+                duration_unit: None,
+                frequency: timing.frequency.map(PositiveInt),
+                frequency_max: None,
+                period: timing.period.map(FhirDecimal),
+                period_max: None,
+                period_unit: timing.period_unit.as_ref().map(|u| Code(u.clone())),
+                day_of_week: None,
+                time_of_day: None,
+                when: None,
+                offset: None,
+            }),
+            code: timing.code.as_ref().map(from_proto_codeable_concept),
+        }
+    });
+
+    Dosage {
+        sequence: None,
+        text: proto.text.as_ref().map(|t| FhirString(t.clone())),
+        additional_instruction: None,
+        patient_instruction: None,
+        timing,
+        as_needed: proto.as_needed.map(|b| DosageAsNeeded::Boolean(FhirBoolean(b))),
+        site: None,
+        route: proto.route.as_ref().map(from_proto_codeable_concept),
+        method: None,
+        dose_and_rate: proto.dose_quantity.as_ref().map(|q| vec![DosageDoseAndRate {
+            type_: None,
+            dose: Some(DosageDose::Quantity(from_proto_quantity(q))),
+            rate: None,
+        }]),
+        max_dose_per_period: None,
+        max_dose_per_administration: None,
+        max_dose_per_lifetime: None,
+    }
+}
+
+fn from_proto_date_time(value: &Option<String>) -> Option<FhirDateTime> {
+    value.as_ref().and_then(|s| {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| FhirDateTime(dt.with_timezone(&chrono::Utc)))
+    })
+}
+
+fn to_proto_attachment(attachment: &Attachment) -> proto::Attachment {
+    proto::Attachment {
+        content_type: attachment.content_type.as_ref().map(|c| c.0.clone()),
+        data: attachment.data.as_ref().map(|d| d.0.clone()),
+        url: attachment.url.as_ref().map(|u| u.0.clone()),
+        size: attachment.size.as_ref().map(|s| s.0),
+        hash: attachment.hash.as_ref().map(|h| h.0.clone()),
+        title: attachment.title.as_ref().map(|t| t.0.clone()),
+    }
+}
+
+fn from_proto_attachment(attachment: &proto::Attachment) -> Attachment {
+    Attachment {
+        content_type: attachment.content_type.as_ref().map(|c| Code(c.clone())),
+        language: None,
+        data: attachment.data.as_ref().map(|d| FhirString(d.clone())),
+        url: attachment.url.as_ref().map(|u| Uri(u.clone())),
+        size: attachment.size.map(UnsignedInt),
+        hash: attachment.hash.as_ref().map(|h| FhirString(h.clone())),
+        title: attachment.title.as_ref().map(|t| FhirString(t.clone())),
+        creation: None,
+    }
+}
+
+fn empty_reference() -> Reference {
+    Reference { reference: None, type_: None, identifier: None, display: None }
+}
+
+// Medication conversions
+pub fn to_proto_medication(medication: &domain::Medication) -> proto::Medication {
+    proto::Medication {
+        id: medication.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&medication.meta),
+        identifier: to_proto_list(&medication.identifier, to_proto_identifier),
+        code: medication.code.as_ref().map(to_proto_codeable_concept),
+        status: medication.status.as_ref().map(|s| s.0.clone()),
+        manufacturer: medication.manufacturer.as_ref().map(to_proto_reference),
+        form: medication.form.as_ref().map(to_proto_codeable_concept),
+    }
+}
+
+pub fn from_proto_medication(proto: &proto::Medication) -> domain::Medication {
+    domain::Medication {
+        resource_type: "Medication".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        code: proto.code.as_ref().map(from_proto_codeable_concept),
+        status: proto.status.as_ref().map(|s| Code(s.clone())),
+        manufacturer: proto.manufacturer.as_ref().map(from_proto_reference),
+        form: proto.form.as_ref().map(from_proto_codeable_concept),
+        amount: None,
+        ingredient: None,
+        batch: None,
+    }
+}
+
+// MedicationRequest conversions
+pub fn to_proto_medication_request(request: &domain::MedicationRequest) -> proto::MedicationRequest {
+    use proto::medication_request::Medication as ProtoMedication;
+
+    proto::MedicationRequest {
+        id: request.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&request.meta),
+        identifier: to_proto_list(&request.identifier, to_proto_identifier),
+        status: Some(request.status.0.clone()),
+        intent: Some(request.intent.0.clone()),
+        medication: Some(match &request.medication {
+            CodeableConceptOrReference::CodeableConcept(cc) => {
+                ProtoMedication::MedicationCodeableConcept(to_proto_codeable_concept(cc))
+            }
+            CodeableConceptOrReference::Reference(r) => ProtoMedication::MedicationReference(to_proto_reference(r)),
+        }),
+        subject: Some(to_proto_reference(&request.subject)),
+        encounter: request.encounter.as_ref().map(to_proto_reference),
+        authored_on: request.authored_on.as_ref().map(|dt| dt.0.to_rfc3339()),
+        requester: request.requester.as_ref().map(to_proto_reference),
+        dosage_instruction: to_proto_list(&request.dosage_instruction, to_proto_dosage),
+    }
+}
+
+pub fn from_proto_medication_request(proto: &proto::MedicationRequest) -> domain::MedicationRequest {
+    use proto::medication_request::Medication as ProtoMedication;
+
+    // A missing medication is left empty for the validator to report
+    let medication = match &proto.medication {
+        Some(ProtoMedication::MedicationReference(r)) => CodeableConceptOrReference::Reference(from_proto_reference(r)),
+        Some(ProtoMedication::MedicationCodeableConcept(cc)) => {
+            CodeableConceptOrReference::CodeableConcept(from_proto_codeable_concept(cc))
+        }
+        None => CodeableConceptOrReference::CodeableConcept(CodeableConcept { coding: None, text: None }),
+    };
+
+    domain::MedicationRequest {
+        resource_type: "MedicationRequest".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        status: Code(proto.status.clone().unwrap_or_default()),
+        status_reason: None,
+        intent: Code(proto.intent.clone().unwrap_or_default()),
+        category: None,
+        priority: None,
+        do_not_perform: None,
+        medication,
+        subject: proto.subject.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        encounter: proto.encounter.as_ref().map(from_proto_reference),
+        authored_on: from_proto_date_time(&proto.authored_on),
+        requester: proto.requester.as_ref().map(from_proto_reference),
+        performer: None,
+        recorder: None,
+        reason_code: None,
+        reason_reference: None,
+        based_on: None,
+        group_identifier: None,
+        note: None,
+        dosage_instruction: from_proto_list(&proto.dosage_instruction, from_proto_dosage),
+        dispense_request: None,
+        substitution: None,
+        prior_prescription: None,
+    }
+}
+
+// MedicationStatement conversions
+pub fn to_proto_medication_statement(statement: &domain::MedicationStatement) -> proto::MedicationStatement {
+    use proto::medication_statement::Medication as ProtoMedication;
+
+    // Extract effective date time from the enum
+    let effective_date_time = match &statement.effective {
+        Some(medication_statement::MedicationStatementEffective::DateTime(dt)) => Some(dt.0.to_rfc3339()),
+        _ => None,
+    };
+
+    proto::MedicationStatement {
+        id: statement.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&statement.meta),
+        identifier: to_proto_list(&statement.identifier, to_proto_identifier),
+        status: Some(statement.status.0.clone()),
+        medication: Some(match &statement.medication {
+            CodeableConceptOrReference::CodeableConcept(cc) => {
+                ProtoMedication::MedicationCodeableConcept(to_proto_codeable_concept(cc))
+            }
+            CodeableConceptOrReference::Reference(r) => ProtoMedication::MedicationReference(to_proto_reference(r)),
+        }),
+        subject: Some(to_proto_reference(&statement.subject)),
+        context: statement.context.as_ref().map(to_proto_reference),
+        effective_date_time,
+        date_asserted: statement.date_asserted.as_ref().map(|dt| dt.0.to_rfc3339()),
+        dosage: to_proto_list(&statement.dosage, to_proto_dosage),
+    }
+}
+
+pub fn from_proto_medication_statement(proto: &proto::MedicationStatement) -> domain::MedicationStatement {
+    use proto::medication_statement::Medication as ProtoMedication;
+
+    // A missing medication is left empty for the validator to report
+    let medication = match &proto.medication {
+        Some(ProtoMedication::MedicationReference(r)) => CodeableConceptOrReference::Reference(from_proto_reference(r)),
+        Some(ProtoMedication::MedicationCodeableConcept(cc)) => {
+            CodeableConceptOrReference::CodeableConcept(from_proto_codeable_concept(cc))
+        }
+        None => CodeableConceptOrReference::CodeableConcept(CodeableConcept { coding: None, text: None }),
+    };
+
+    domain::MedicationStatement {
+        resource_type: "MedicationStatement".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        based_on: None,
+        part_of: None,
+        status: Code(proto.status.clone().unwrap_or_default()),
+        status_reason: None,
+        category: None,
+        medication,
+        subject: proto.subject.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        context: proto.context.as_ref().map(from_proto_reference),
+        effective: from_proto_date_time(&proto.effective_date_time)
+            .map(medication_statement::MedicationStatementEffective::DateTime),
+        date_asserted: from_proto_date_time(&proto.date_asserted),
+        information_source: None,
+        derived_from: None,
+        reason_code: None,
+        reason_reference: None,
+        note: None,
+        dosage: from_proto_list(&proto.dosage, from_proto_dosage),
+    }
+}
+
+// AllergyIntolerance conversions
+pub fn to_proto_allergy_intolerance(allergy: &domain::AllergyIntolerance) -> proto::AllergyIntolerance {
+    // Extract onset datetime from the enum
+    let onset_date_time = match &allergy.onset {
+        Some(allergy_intolerance::AllergyIntoleranceOnset::DateTime(dt)) => Some(dt.0.to_rfc3339()),
+        _ => None,
+    };
+
+    proto::AllergyIntolerance {
+        id: allergy.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&allergy.meta),
+        identifier: to_proto_list(&allergy.identifier, to_proto_identifier),
+        clinical_status: allergy.clinical_status.as_ref().map(to_proto_codeable_concept),
+        verification_status: allergy.verification_status.as_ref().map(to_proto_codeable_concept),
+        r#type: allergy.type_.as_ref().map(|t| t.0.clone()),
+        category: to_proto_list(&allergy.category, |c| c.0.clone()),
+        criticality: allergy.criticality.as_ref().map(|c| c.0.clone()),
+        code: allergy.code.as_ref().map(to_proto_codeable_concept),
+        patient: Some(to_proto_reference(&allergy.patient)),
+        onset_date_time,
+        recorded_date: allergy.recorded_date.as_ref().map(|dt| dt.0.to_rfc3339()),
+        reaction: to_proto_list(&allergy.reaction, |reaction| proto::AllergyIntoleranceReaction {
+            substance: reaction.substance.as_ref().map(to_proto_codeable_concept),
+            manifestation: reaction.manifestation.iter().map(to_proto_codeable_concept).collect(),
+            description: reaction.description.as_ref().map(|d| d.0.clone()),
+            severity: reaction.severity.as_ref().map(|s| s.0.clone()),
+        }),
+    }
+}
+
+pub fn from_proto_allergy_intolerance(proto: &proto::AllergyIntolerance) -> domain::AllergyIntolerance {
+    domain::AllergyIntolerance {
+        resource_type: "AllergyIntolerance".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        clinical_status: proto.clinical_status.as_ref().map(from_proto_codeable_concept),
+        verification_status: proto.verification_status.as_ref().map(from_proto_codeable_concept),
+        type_: proto.r#type.as_ref().map(|t| Code(t.clone())),
+        category: from_proto_list(&proto.category, |c| Code(c.clone())),
+        criticality: proto.criticality.as_ref().map(|c| Code(c.clone())),
+        code: proto.code.as_ref().map(from_proto_codeable_concept),
+        patient: proto.patient.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        encounter: None,
+        onset: from_proto_date_time(&proto.onset_date_time)
+            .map(allergy_intolerance::AllergyIntoleranceOnset::DateTime),
+        recorded_date: from_proto_date_time(&proto.recorded_date),
+        recorder: None,
+        asserter: None,
+        last_occurrence: None,
+        note: None,
+        reaction: from_proto_list(&proto.reaction, |reaction| allergy_intolerance::AllergyIntoleranceReaction {
+            substance: reaction.substance.as_ref().map(from_proto_codeable_concept),
+            manifestation: reaction.manifestation.iter().map(from_proto_codeable_concept).collect(),
+            description: reaction.description.as_ref().map(|d| FhirString(d.clone())),
+            onset: None,
+            severity: reaction.severity.as_ref().map(|s| Code(s.clone())),
+            exposure_route: None,
+            note: None,
+        }),
+    }
+}
+
+// Procedure conversions
+pub fn to_proto_procedure(procedure: &domain::Procedure) -> proto::Procedure {
+    use proto::procedure::Performed as ProtoPerformed;
+
+    // Only performedDateTime and performedPeriod are carried over gRPC
+    let performed = match &procedure.performed {
+        Some(procedure::ProcedurePerformed::DateTime(dt)) => Some(ProtoPerformed::PerformedDateTime(dt.0.to_rfc3339())),
+        Some(procedure::ProcedurePerformed::Period(period)) => Some(ProtoPerformed::PerformedPeriod(to_proto_period(period))),
+        _ => None,
+    };
+
+    proto::Procedure {
+        id: procedure.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&procedure.meta),
+        identifier: to_proto_list(&procedure.identifier, to_proto_identifier),
+        status: Some(procedure.status.0.clone()),
+        code: procedure.code.as_ref().map(to_proto_codeable_concept),
+        subject: Some(to_proto_reference(&procedure.subject)),
+        encounter: procedure.encounter.as_ref().map(to_proto_reference),
+        performed,
+        performer: to_proto_list(&procedure.performer, |performer| proto::ProcedurePerformer {
+            function: performer.function.as_ref().map(to_proto_codeable_concept),
+            actor: Some(to_proto_reference(&performer.actor)),
+            on_behalf_of: performer.on_behalf_of.as_ref().map(to_proto_reference),
+        }),
+        reason_reference: to_proto_list(&procedure.reason_reference, to_proto_reference),
+        body_site: to_proto_list(&procedure.body_site, to_proto_codeable_concept),
+    }
+}
+
+pub fn from_proto_procedure(proto: &proto::Procedure) -> domain::Procedure {
+    use proto::procedure::Performed as ProtoPerformed;
+
+    let performed = match &proto.performed {
+        Some(ProtoPerformed::PerformedDateTime(dt)) => {
+            from_proto_date_time(&Some(dt.clone())).map(procedure::ProcedurePerformed::DateTime)
+        }
+        Some(ProtoPerformed::PerformedPeriod(period)) => {
+            Some(procedure::ProcedurePerformed::Period(from_proto_period(period)))
+        }
+        None => None,
+    };
+
+    domain::Procedure {
+        resource_type: "Procedure".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        based_on: None,
+        part_of: None,
+        status: Code(proto.status.clone().unwrap_or_default()),
+        status_reason: None,
+        category: None,
+        code: proto.code.as_ref().map(from_proto_codeable_concept),
+        subject: proto.subject.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        encounter: proto.encounter.as_ref().map(from_proto_reference),
+        performed,
+        recorder: None,
+        asserter: None,
+        performer: from_proto_list(&proto.performer, |performer| procedure::ProcedurePerformer {
+            function: performer.function.as_ref().map(from_proto_codeable_concept),
+            actor: performer.actor.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+            on_behalf_of: performer.on_behalf_of.as_ref().map(from_proto_reference),
+        }),
+        location: None,
+        reason_code: None,
+        reason_reference: from_proto_list(&proto.reason_reference, from_proto_reference),
+        body_site: from_proto_list(&proto.body_site, from_proto_codeable_concept),
+        outcome: None,
+        report: None,
+        complication: None,
+        follow_up: None,
+        note: None,
+    }
+}
+
+// DiagnosticReport conversions
+pub fn to_proto_diagnostic_report(report: &domain::DiagnosticReport) -> proto::DiagnosticReport {
+    use proto::diagnostic_report::Effective as ProtoEffective;
+
+    proto::DiagnosticReport {
+        id: report.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&report.meta),
+        identifier: to_proto_list(&report.identifier, to_proto_identifier),
+        status: Some(report.status.0.clone()),
+        category: to_proto_list(&report.category, to_proto_codeable_concept),
+        code: Some(to_proto_codeable_concept(&report.code)),
+        subject: Some(to_proto_reference(&report.subject)),
+        encounter: report.encounter.as_ref().map(to_proto_reference),
+        effective: report.effective.as_ref().map(|effective| match effective {
+            diagnostic_report::DiagnosticReportEffective::DateTime(dt) => ProtoEffective::EffectiveDateTime(dt.0.to_rfc3339()),
+            diagnostic_report::DiagnosticReportEffective::Period(period) => ProtoEffective::EffectivePeriod(to_proto_period(period)),
+        }),
+        issued: report.issued.as_ref().map(|i| i.0.to_rfc3339()),
+        result: to_proto_list(&report.result, to_proto_reference),
+        conclusion: report.conclusion.as_ref().map(|c| c.0.clone()),
+        conclusion_code: to_proto_list(&report.conclusion_code, to_proto_codeable_concept),
+        presented_form: to_proto_list(&report.presented_form, to_proto_attachment),
+    }
+}
+
+pub fn from_proto_diagnostic_report(proto: &proto::DiagnosticReport) -> domain::DiagnosticReport {
+    use proto::diagnostic_report::Effective as ProtoEffective;
+
+    let effective = match &proto.effective {
+        Some(ProtoEffective::EffectiveDateTime(dt)) => {
+            from_proto_date_time(&Some(dt.clone())).map(diagnostic_report::DiagnosticReportEffective::DateTime)
+        }
+        Some(ProtoEffective::EffectivePeriod(period)) => {
+            Some(diagnostic_report::DiagnosticReportEffective::Period(from_proto_period(period)))
+        }
+        None => None,
+    };
+
+    domain::DiagnosticReport {
+        resource_type: "DiagnosticReport".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        based_on: None,
+        status: Code(proto.status.clone().unwrap_or_default()),
+        category: from_proto_list(&proto.category, from_proto_codeable_concept),
+        // A missing code is left empty for the validator to report
+        code: proto.code.as_ref()
+            .map(from_proto_codeable_concept)
+            .unwrap_or(CodeableConcept { coding: None, text: None }),
+        subject: proto.subject.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        encounter: proto.encounter.as_ref().map(from_proto_reference),
+        effective,
+        issued: proto.issued.as_ref().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|dt| Instant(dt.with_timezone(&chrono::Utc)))
+        }),
+        performer: None,
+        results_interpreter: None,
+        specimen: None,
+        result: from_proto_list(&proto.result, from_proto_reference),
+        conclusion: proto.conclusion.as_ref().map(|c| FhirString(c.clone())),
+        conclusion_code: from_proto_list(&proto.conclusion_code, from_proto_codeable_concept),
+        presented_form: from_proto_list(&proto.presented_form, from_proto_attachment),
+    }
+}
+
+// Immunization conversions
+pub fn to_proto_immunization(immunization: &domain::Immunization) -> proto::Immunization {
+    use proto::immunization::Occurrence as ProtoOccurrence;
+    use proto::immunization_protocol_applied::{DoseNumber as ProtoDoseNumber, SeriesDoses as ProtoSeriesDoses};
+
+    proto::Immunization {
+        id: immunization.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&immunization.meta),
+        identifier: to_proto_list(&immunization.identifier, to_proto_identifier),
+        status: Some(immunization.status.0.clone()),
+        vaccine_code: Some(to_proto_codeable_concept(&immunization.vaccine_code)),
+        patient: Some(to_proto_reference(&immunization.patient)),
+        encounter: immunization.encounter.as_ref().map(to_proto_reference),
+        occurrence: Some(match &immunization.occurrence {
+            immunization::ImmunizationOccurrence::DateTime(dt) => ProtoOccurrence::OccurrenceDateTime(dt.0.to_rfc3339()),
+            immunization::ImmunizationOccurrence::String(text) => ProtoOccurrence::OccurrenceString(text.0.clone()),
+        }),
+        recorded: immunization.recorded.as_ref().map(|dt| dt.0.to_rfc3339()),
+        primary_source: immunization.primary_source.as_ref().map(|b| b.0),
+        lot_number: immunization.lot_number.as_ref().map(|l| l.0.clone()),
+        expiration_date: immunization.expiration_date.as_ref().map(|d| d.0.to_string()),
+        site: immunization.site.as_ref().map(to_proto_codeable_concept),
+        route: immunization.route.as_ref().map(to_proto_codeable_concept),
+        dose_quantity: immunization.dose_quantity.as_ref().map(to_proto_quantity),
+        performer: to_proto_list(&immunization.performer, |performer| proto::ImmunizationPerformer {
+            function: performer.function.as_ref().map(to_proto_codeable_concept),
+            actor: Some(to_proto_reference(&performer.actor)),
+        }),
+        protocol_applied: to_proto_list(&immunization.protocol_applied, |protocol| proto::ImmunizationProtocolApplied {
+            series: protocol.series.as_ref().map(|s| s.0.clone()),
+            target_disease: to_proto_list(&protocol.target_disease, to_proto_codeable_concept),
+            dose_number: Some(match &protocol.dose_number {
+                immunization::ImmunizationDoseNumber::PositiveInt(n) => ProtoDoseNumber::DoseNumberPositiveInt(n.0),
+                immunization::ImmunizationDoseNumber::String(s) => ProtoDoseNumber::DoseNumberString(s.0.clone()),
+            }),
+            series_doses: protocol.series_doses.as_ref().map(|doses| match doses {
+                immunization::ImmunizationDoseNumber::PositiveInt(n) => ProtoSeriesDoses::SeriesDosesPositiveInt(n.0),
+                immunization::ImmunizationDoseNumber::String(s) => ProtoSeriesDoses::SeriesDosesString(s.0.clone()),
+            }),
+        }),
+    }
+}
+
+pub fn from_proto_immunization(proto: &proto::Immunization) -> domain::Immunization {
+    use proto::immunization::Occurrence as ProtoOccurrence;
+    use proto::immunization_protocol_applied::{DoseNumber as ProtoDoseNumber, SeriesDoses as ProtoSeriesDoses};
+
+    // occurrence[x] is required; a missing or unparseable one is kept as text
+    let occurrence = match &proto.occurrence {
+        Some(ProtoOccurrence::OccurrenceDateTime(dt)) => from_proto_date_time(&Some(dt.clone()))
+            .map(immunization::ImmunizationOccurrence::DateTime)
+            .unwrap_or_else(|| immunization::ImmunizationOccurrence::String(FhirString(dt.clone()))),
+        Some(ProtoOccurrence::OccurrenceString(text)) => {
+            immunization::ImmunizationOccurrence::String(FhirString(text.clone()))
+        }
+        None => immunization::ImmunizationOccurrence::String(FhirString(String::new())),
+    };
+
+    domain::Immunization {
+        resource_type: "Immunization".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        status: Code(proto.status.clone().unwrap_or_default()),
+        status_reason: None,
+        // A missing vaccineCode is left empty for the validator to report
+        vaccine_code: proto.vaccine_code.as_ref()
+            .map(from_proto_codeable_concept)
+            .unwrap_or(CodeableConcept { coding: None, text: None }),
+        patient: proto.patient.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        encounter: proto.encounter.as_ref().map(from_proto_reference),
+        occurrence,
+        recorded: from_proto_date_time(&proto.recorded),
+        primary_source: proto.primary_source.map(FhirBoolean),
+        location: None,
+        manufacturer: None,
+        lot_number: proto.lot_number.as_ref().map(|l| FhirString(l.clone())),
+        expiration_date: proto.expiration_date.as_ref().and_then(|d| {
+            chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok().map(FhirDate)
+        }),
+        site: proto.site.as_ref().map(from_proto_codeable_concept),
+        route: proto.route.as_ref().map(from_proto_codeable_concept),
+        dose_quantity: proto.dose_quantity.as_ref().map(from_proto_quantity),
+        performer: from_proto_list(&proto.performer, |performer| immunization::ImmunizationPerformer {
+            function: performer.function.as_ref().map(from_proto_codeable_concept),
+            actor: performer.actor.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        }),
+        note: None,
+        reason_code: None,
+        reason_reference: None,
+        is_subpotent: None,
+        protocol_applied: from_proto_list(&proto.protocol_applied, |protocol| immunization::ImmunizationProtocolApplied {
+            series: protocol.series.as_ref().map(|s| FhirString(s.clone())),
+            authority: None,
+            target_disease: from_proto_list(&protocol.target_disease, from_proto_codeable_concept),
+            dose_number: match &protocol.dose_number {
+                Some(ProtoDoseNumber::DoseNumberPositiveInt(n)) => immunization::ImmunizationDoseNumber::PositiveInt(PositiveInt(*n)),
+                Some(ProtoDoseNumber::DoseNumberString(s)) => immunization::ImmunizationDoseNumber::String(FhirString(s.clone())),
+                None => immunization::ImmunizationDoseNumber::String(FhirString(String::new())),
+            },
+            series_doses: protocol.series_doses.as_ref().map(|doses| match doses {
+                ProtoSeriesDoses::SeriesDosesPositiveInt(n) => immunization::ImmunizationDoseNumber::PositiveInt(PositiveInt(*n)),
+                ProtoSeriesDoses::SeriesDosesString(s) => immunization::ImmunizationDoseNumber::String(FhirString(s.clone())),
+            }),
+        }),
+    }
+}
+
+// Location conversions
+pub fn to_proto_location(location: &domain::Location) -> proto::Location {
+    proto::Location {
+        id: location.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&location.meta),
+        identifier: to_proto_list(&location.identifier, to_proto_identifier),
+        status: location.status.as_ref().map(|s| s.0.clone()),
+        name: location.name.as_ref().map(|n| n.0.clone()),
+        alias: to_proto_list(&location.alias, |a| a.0.clone()),
+        description: location.description.as_ref().map(|d| d.0.clone()),
+        mode: location.mode.as_ref().map(|m| m.0.clone()),
+        r#type: to_proto_list(&location.type_, to_proto_codeable_concept),
+        telecom: to_proto_list(&location.telecom, to_proto_contact_point),
+        address: location.address.as_ref().map(to_proto_address),
+        physical_type: location.physical_type.as_ref().map(to_proto_codeable_concept),
+        position: location.position.as_ref().map(|p| proto::LocationPosition {
+            longitude: p.longitude.0,
+            latitude: p.latitude.0,
+            altitude: p.altitude.as_ref().map(|a| a.0),
+        }),
+        managing_organization: location.managing_organization.as_ref().map(to_proto_reference),
+        part_of: location.part_of.as_ref().map(to_proto_reference),
+    }
+}
+
+pub fn from_proto_location(proto: &proto::Location) -> domain::Location {
+    domain::Location {
+        resource_type: "Location".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        status: proto.status.as_ref().map(|s| Code(s.clone())),
+        operational_status: None,
+        name: proto.name.as_ref().map(|n| FhirString(n.clone())),
+        alias: from_proto_list(&proto.alias, |a| FhirString(a.clone())),
+        description: proto.description.as_ref().map(|d| FhirString(d.clone())),
+        mode: proto.mode.as_ref().map(|m| Code(m.clone())),
+        type_: from_proto_list(&proto.r#type, from_proto_codeable_concept),
+        telecom: from_proto_list(&proto.telecom, from_proto_contact_point),
+        address: proto.address.as_ref().map(from_proto_address),
+        physical_type: proto.physical_type.as_ref().map(from_proto_codeable_concept),
+        position: proto.position.as_ref().map(|p| location::LocationPosition {
+            longitude: FhirDecimal(p.longitude),
+            latitude: FhirDecimal(p.latitude),
+            altitude: p.altitude.map(FhirDecimal),
+        }),
+        managing_organization: proto.managing_organization.as_ref().map(from_proto_reference),
+        part_of: proto.part_of.as_ref().map(from_proto_reference),
+        hours_of_operation: None,
+        availability_exceptions: None,
+        endpoint: None,
+    }
+}
+
+// Group conversions
+pub fn to_proto_group(group: &domain::Group) -> proto::Group {
+    use proto::group_characteristic::Value as ProtoValue;
+
+    proto::Group {
+        id: group.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&group.meta),
+        identifier: to_proto_list(&group.identifier, to_proto_identifier),
+        active: group.active.as_ref().map(|a| a.0),
+        r#type: Some(group.type_.0.clone()),
+        actual: group.actual.0,
+        code: group.code.as_ref().map(to_proto_codeable_concept),
+        name: group.name.as_ref().map(|n| n.0.clone()),
+        quantity: group.quantity.as_ref().map(|q| q.0),
+        managing_entity: group.managing_entity.as_ref().map(to_proto_reference),
+        characteristic: to_proto_list(&group.characteristic, |characteristic| proto::GroupCharacteristic {
+            code: Some(to_proto_codeable_concept(&characteristic.code)),
+            // valueRange is only available over REST
+            value: match &characteristic.value {
+                group::GroupCharacteristicValue::CodeableConcept(cc) => Some(ProtoValue::ValueCodeableConcept(to_proto_codeable_concept(cc))),
+                group::GroupCharacteristicValue::Boolean(b) => Some(ProtoValue::ValueBoolean(b.0)),
+                group::GroupCharacteristicValue::Quantity(q) => Some(ProtoValue::ValueQuantity(to_proto_quantity(q))),
+                group::GroupCharacteristicValue::Reference(r) => Some(ProtoValue::ValueReference(to_proto_reference(r))),
+                group::GroupCharacteristicValue::Range(_) => None,
+            },
+            exclude: characteristic.exclude.0,
+            period: characteristic.period.as_ref().map(to_proto_period),
+        }),
+        member: to_proto_list(&group.member, |member| proto::GroupMember {
+            entity: Some(to_proto_reference(&member.entity)),
+            period: member.period.as_ref().map(to_proto_period),
+            inactive: member.inactive.as_ref().map(|i| i.0),
+        }),
+    }
+}
+
+pub fn from_proto_group(proto: &proto::Group) -> domain::Group {
+    use proto::group_characteristic::Value as ProtoValue;
+
+    domain::Group {
+        resource_type: "Group".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        active: proto.active.map(FhirBoolean),
+        type_: Code(proto.r#type.clone().unwrap_or_default()),
+        actual: FhirBoolean(proto.actual),
+        code: proto.code.as_ref().map(from_proto_codeable_concept),
+        name: proto.name.as_ref().map(|n| FhirString(n.clone())),
+        quantity: proto.quantity.map(UnsignedInt),
+        managing_entity: proto.managing_entity.as_ref().map(from_proto_reference),
+        characteristic: from_proto_list(&proto.characteristic, |characteristic| group::GroupCharacteristic {
+            code: characteristic.code.as_ref()
+                .map(from_proto_codeable_concept)
+                .unwrap_or(CodeableConcept { coding: None, text: None }),
+            value: match &characteristic.value {
+                Some(ProtoValue::ValueCodeableConcept(cc)) => group::GroupCharacteristicValue::CodeableConcept(from_proto_codeable_concept(cc)),
+                Some(ProtoValue::ValueBoolean(b)) => group::GroupCharacteristicValue::Boolean(FhirBoolean(*b)),
+                Some(ProtoValue::ValueQuantity(q)) => group::GroupCharacteristicValue::Quantity(from_proto_quantity(q)),
+                Some(ProtoValue::ValueReference(r)) => group::GroupCharacteristicValue::Reference(from_proto_reference(r)),
+                None => group::GroupCharacteristicValue::CodeableConcept(CodeableConcept { coding: None, text: None }),
+            },
+            exclude: FhirBoolean(characteristic.exclude),
+            period: characteristic.period.as_ref().map(from_proto_period),
+        }),
+        member: from_proto_list(&proto.member, |member| group::GroupMember {
+            entity: member.entity.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+            period: member.period.as_ref().map(from_proto_period),
+            inactive: member.inactive.map(FhirBoolean),
+        }),
+    }
+}
+
+// DocumentReference conversions
+pub fn to_proto_document_reference(document: &domain::DocumentReference) -> proto::DocumentReference {
+    proto::DocumentReference {
+        id: document.id.as_ref().map(|id| id.0.clone()),
+        meta: to_proto_meta(&document.meta),
+        master_identifier: document.master_identifier.as_ref().map(to_proto_identifier),
+        identifier: to_proto_list(&document.identifier, to_proto_identifier),
+        status: Some(document.status.0.clone()),
+        doc_status: document.doc_status.as_ref().map(|s| s.0.clone()),
+        r#type: document.type_.as_ref().map(to_proto_codeable_concept),
+        category: to_proto_list(&document.category, to_proto_codeable_concept),
+        subject: document.subject.as_ref().map(to_proto_reference),
+        date: document.date.as_ref().map(|d| d.0.to_rfc3339()),
+        author: to_proto_list(&document.author, to_proto_reference),
+        authenticator: document.authenticator.as_ref().map(to_proto_reference),
+        custodian: document.custodian.as_ref().map(to_proto_reference),
+        relates_to: to_proto_list(&document.relates_to, |relates_to| proto::DocumentReferenceRelatesTo {
+            code: relates_to.code.0.clone(),
+            target: Some(to_proto_reference(&relates_to.target)),
+        }),
+        description: document.description.as_ref().map(|d| d.0.clone()),
+        security_label: to_proto_list(&document.security_label, to_proto_codeable_concept),
+        content: document.content.iter().map(|content| proto::DocumentReferenceContent {
+            attachment: Some(to_proto_attachment(&content.attachment)),
+            format: content.format.as_ref().map(to_proto_coding),
+        }).collect(),
+        context: document.context.as_ref().map(|context| proto::DocumentReferenceContext {
+            encounter: to_proto_list(&context.encounter, to_proto_reference),
+            event: to_proto_list(&context.event, to_proto_codeable_concept),
+            period: context.period.as_ref().map(to_proto_period),
+            facility_type: context.facility_type.as_ref().map(to_proto_codeable_concept),
+            practice_setting: context.practice_setting.as_ref().map(to_proto_codeable_concept),
+            source_patient_info: context.source_patient_info.as_ref().map(to_proto_reference),
+            related: to_proto_list(&context.related, to_proto_reference),
+        }),
+    }
+}
+
+pub fn from_proto_document_reference(proto: &proto::DocumentReference) -> domain::DocumentReference {
+    domain::DocumentReference {
+        resource_type: "DocumentReference".to_string(),
+        id: proto.id.as_ref().map(|id| Id(id.clone())),
+        meta: from_proto_meta(&proto.meta),
+        text: None,
+        master_identifier: proto.master_identifier.as_ref().map(from_proto_identifier),
+        identifier: from_proto_list(&proto.identifier, from_proto_identifier),
+        status: Code(proto.status.clone().unwrap_or_default()),
+        doc_status: proto.doc_status.as_ref().map(|s| Code(s.clone())),
+        type_: proto.r#type.as_ref().map(from_proto_codeable_concept),
+        category: from_proto_list(&proto.category, from_proto_codeable_concept),
+        subject: proto.subject.as_ref().map(from_proto_reference),
+        date: proto.date.as_ref().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|dt| Instant(dt.with_timezone(&chrono::Utc)))
+        }),
+        author: from_proto_list(&proto.author, from_proto_reference),
+        authenticator: proto.authenticator.as_ref().map(from_proto_reference),
+        custodian: proto.custodian.as_ref().map(from_proto_reference),
+        relates_to: from_proto_list(&proto.relates_to, |relates_to| document_reference::DocumentReferenceRelatesTo {
+            code: Code(relates_to.code.clone()),
+            target: relates_to.target.as_ref().map(from_proto_reference).unwrap_or_else(empty_reference),
+        }),
+        description: proto.description.as_ref().map(|d| FhirString(d.clone())),
+        security_label: from_proto_list(&proto.security_label, from_proto_codeable_concept),
+        // An attachment left out is kept empty for the validator to report
+        content: proto.content.iter().map(|content| document_reference::DocumentReferenceContent {
+            attachment: content.attachment.as_ref()
+                .map(from_proto_attachment)
+                .unwrap_or_else(|| from_proto_attachment(&proto::Attachment::default())),
+            format: content.format.as_ref().map(from_proto_coding),
+        }).collect(),
+        context: proto.context.as_ref().map(|context| document_reference::DocumentReferenceContext {
+            encounter: from_proto_list(&context.encounter, from_proto_reference),
+            event: from_proto_list(&context.event, from_proto_codeable_concept),
+            period: context.period.as_ref().map(from_proto_period),
+            facility_type: context.facility_type.as_ref().map(from_proto_codeable_concept),
+            practice_setting: context.practice_setting.as_ref().map(from_proto_codeable_concept),
+            source_patient_info: context.source_patient_info.as_ref().map(from_proto_reference),
+            related: from_proto_list(&context.related, from_proto_reference),
+        }),
+    }
+}
@@ -1,6 +1,8 @@
 // src/grpc/mod.rs
 
-// Generated protobuf code (compiled during build)
+// Generated protobuf code (compiled during build). Oneof enums name their
+// variants after the FHIR choice element, e.g. `value` -> `ValueBoolean`
+#[allow(clippy::enum_variant_names)]
 pub mod proto {
     tonic::include_proto!("fhir");
 
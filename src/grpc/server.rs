@@ -14,6 +14,19 @@ use super::proto::{
     observation_service_server::ObservationServiceServer,
     condition_service_server::ConditionServiceServer,
     encounter_service_server::EncounterServiceServer,
+    practitioner_service_server::PractitionerServiceServer,
+    practitioner_role_service_server::PractitionerRoleServiceServer,
+    organization_service_server::OrganizationServiceServer,
+    medication_service_server::MedicationServiceServer,
+    medication_request_service_server::MedicationRequestServiceServer,
+    medication_statement_service_server::MedicationStatementServiceServer,
+    allergy_intolerance_service_server::AllergyIntoleranceServiceServer,
+    procedure_service_server::ProcedureServiceServer,
+    diagnostic_report_service_server::DiagnosticReportServiceServer,
+    immunization_service_server::ImmunizationServiceServer,
+    location_service_server::LocationServiceServer,
+    group_service_server::GroupServiceServer,
+    document_reference_service_server::DocumentReferenceServiceServer,
     FILE_DESCRIPTOR_SET,
 };
 use super::services::{
@@ -21,6 +34,19 @@ use super::services::{
     GrpcObservationService,
     GrpcConditionService,
     GrpcEncounterService,
+    GrpcPractitionerService,
+    GrpcPractitionerRoleService,
+    GrpcOrganizationService,
+    GrpcMedicationService,
+    GrpcMedicationRequestService,
+    GrpcMedicationStatementService,
+    GrpcAllergyIntoleranceService,
+    GrpcProcedureService,
+    GrpcDiagnosticReportService,
+    GrpcImmunizationService,
+    GrpcLocationService,
+    GrpcGroupService,
+    GrpcDocumentReferenceService,
 };
 
 /// Start the gRPC server
@@ -35,6 +61,19 @@ pub async fn start_grpc_server(app_state: AppState, config: GrpcConfig) -> Resul
     let observation_service = GrpcObservationService::new(app_state.clone());
     let condition_service = GrpcConditionService::new(app_state.clone());
     let encounter_service = GrpcEncounterService::new(app_state.clone());
+    let practitioner_service = GrpcPractitionerService::new(app_state.clone());
+    let practitioner_role_service = GrpcPractitionerRoleService::new(app_state.clone());
+    let organization_service = GrpcOrganizationService::new(app_state.clone());
+    let medication_service = GrpcMedicationService::new(app_state.clone());
+    let medication_request_service = GrpcMedicationRequestService::new(app_state.clone());
+    let medication_statement_service = GrpcMedicationStatementService::new(app_state.clone());
+    let allergy_intolerance_service = GrpcAllergyIntoleranceService::new(app_state.clone());
+    let procedure_service = GrpcProcedureService::new(app_state.clone());
+    let diagnostic_report_service = GrpcDiagnosticReportService::new(app_state.clone());
+    let immunization_service = GrpcImmunizationService::new(app_state.clone());
+    let location_service = GrpcLocationService::new(app_state.clone());
+    let group_service = GrpcGroupService::new(app_state.clone());
+    let document_reference_service = GrpcDocumentReferenceService::new(app_state.clone());
 
     info!("✅ gRPC services initialized");
 
@@ -88,6 +127,19 @@ pub async fn start_grpc_server(app_state: AppState, config: GrpcConfig) -> Resul
         .add_service(ObservationServiceServer::new(observation_service))
         .add_service(ConditionServiceServer::new(condition_service))
         .add_service(EncounterServiceServer::new(encounter_service))
+        .add_service(PractitionerServiceServer::new(practitioner_service))
+        .add_service(PractitionerRoleServiceServer::new(practitioner_role_service))
+        .add_service(OrganizationServiceServer::new(organization_service))
+        .add_service(MedicationServiceServer::new(medication_service))
+        .add_service(MedicationRequestServiceServer::new(medication_request_service))
+        .add_service(MedicationStatementServiceServer::new(medication_statement_service))
+        .add_service(AllergyIntoleranceServiceServer::new(allergy_intolerance_service))
+        .add_service(ProcedureServiceServer::new(procedure_service))
+        .add_service(DiagnosticReportServiceServer::new(diagnostic_report_service))
+        .add_service(ImmunizationServiceServer::new(immunization_service))
+        .add_service(LocationServiceServer::new(location_service))
+        .add_service(GroupServiceServer::new(group_service))
+        .add_service(DocumentReferenceServiceServer::new(document_reference_service))
         .serve(addr)
         .await?;
 
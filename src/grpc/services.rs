@@ -5,7 +5,7 @@ use tonic::{Request, Response, Status};
 use std::sync::Arc;
 
 use crate::AppState;
-use crate::service::ResourceService;
+use crate::service::{ResourceService, SearchParameters};
 use super::proto;
 use super::converters;
 use super::auth::extract_security_context;
@@ -52,13 +52,20 @@ impl proto::patient_service_server::PatientService for GrpcPatientService {
         request: Request<proto::GetPatientRequest>,
     ) -> Result<Response<proto::GetPatientResponse>, Status> {
         let security_context = extract_security_context(&request)?;
-        let id = &request.into_inner().id;
+        let req = request.into_inner();
 
-        let patient = self.app_state.patient_service
-            .get(&security_context, id)
+        let mut patient = self.app_state.patient_service
+            .get(&security_context, &req.id)
             .await
             .map_err(|e| Status::not_found(format!("Patient not found: {}", e)))?;
 
+        if req.follow_replaced_by.unwrap_or(false) {
+            patient = self.app_state.patient_service
+                .resolve_replaced_by(&security_context, patient)
+                .await
+                .map_err(|e| Status::failed_precondition(format!("Failed to follow replaced-by link: {}", e)))?;
+        }
+
         let response = proto::GetPatientResponse {
             patient: Some(converters::to_proto_patient(&patient)),
         };
@@ -117,7 +124,7 @@ impl proto::patient_service_server::PatientService for GrpcPatientService {
 
         // For now, only implement family name search
         // TODO: Implement other search parameters
-        let patients = if let Some(family) = req.family {
+        let mut patients = if let Some(family) = req.family {
             self.app_state.patient_service
                 .search_by_family(&security_context, &family)
                 .await
@@ -126,6 +133,13 @@ impl proto::patient_service_server::PatientService for GrpcPatientService {
             vec![]
         };
 
+        if req.follow_replaced_by.unwrap_or(false) {
+            patients = self.app_state.patient_service
+                .resolve_all_replaced_by(&security_context, patients)
+                .await
+                .map_err(|e| Status::failed_precondition(format!("Failed to follow replaced-by links: {}", e)))?;
+        }
+
         let response = proto::SearchPatientsResponse {
             patients: patients.iter().map(converters::to_proto_patient).collect(),
         };
@@ -512,3 +526,1744 @@ impl proto::encounter_service_server::EncounterService for GrpcEncounterService
         Ok(Response::new(response))
     }
 }
+
+// Practitioner Service Implementation
+pub struct GrpcPractitionerService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcPractitionerService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::practitioner_service_server::PractitionerService for GrpcPractitionerService {
+    async fn create_practitioner(
+        &self,
+        request: Request<proto::CreatePractitionerRequest>,
+    ) -> Result<Response<proto::CreatePractitionerResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_practitioner = request.into_inner().practitioner
+            .ok_or_else(|| Status::invalid_argument("Practitioner is required"))?;
+
+        let practitioner = converters::from_proto_practitioner(&proto_practitioner);
+
+        let created_practitioner = self.app_state.practitioner_service
+            .create(&security_context, practitioner)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create practitioner: {}", e)))?;
+
+        let response = proto::CreatePractitionerResponse {
+            practitioner: Some(converters::to_proto_practitioner(&created_practitioner)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_practitioner(
+        &self,
+        request: Request<proto::GetPractitionerRequest>,
+    ) -> Result<Response<proto::GetPractitionerResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let practitioner = self.app_state.practitioner_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Practitioner not found: {}", e)))?;
+
+        let response = proto::GetPractitionerResponse {
+            practitioner: Some(converters::to_proto_practitioner(&practitioner)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_practitioner(
+        &self,
+        request: Request<proto::UpdatePractitionerRequest>,
+    ) -> Result<Response<proto::UpdatePractitionerResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_practitioner = req.practitioner
+            .ok_or_else(|| Status::invalid_argument("Practitioner is required"))?;
+
+        let practitioner = converters::from_proto_practitioner(&proto_practitioner);
+
+        let updated_practitioner = self.app_state.practitioner_service
+            .update(&security_context, &req.id, practitioner)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update practitioner: {}", e)))?;
+
+        let response = proto::UpdatePractitionerResponse {
+            practitioner: Some(converters::to_proto_practitioner(&updated_practitioner)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_practitioner(
+        &self,
+        request: Request<proto::DeletePractitionerRequest>,
+    ) -> Result<Response<proto::DeletePractitionerResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.practitioner_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete practitioner: {}", e)))?;
+
+        let response = proto::DeletePractitionerResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_practitioners(
+        &self,
+        request: Request<proto::SearchPractitionersRequest>,
+    ) -> Result<Response<proto::SearchPractitionersResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.name {
+            filters.push(("name".to_string(), value));
+        }
+        if let Some(value) = req.family {
+            filters.push(("family".to_string(), value));
+        }
+        if let Some(value) = req.given {
+            filters.push(("given".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+        if let Some(value) = req.gender {
+            filters.push(("gender".to_string(), value));
+        }
+        if let Some(value) = req.active {
+            filters.push(("active".to_string(), value.to_string()));
+        }
+
+        let result = self.app_state.practitioner_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchPractitionersResponse {
+            practitioners: result.resources.iter().map(converters::to_proto_practitioner).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// PractitionerRole Service Implementation
+pub struct GrpcPractitionerRoleService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcPractitionerRoleService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::practitioner_role_service_server::PractitionerRoleService for GrpcPractitionerRoleService {
+    async fn create_practitioner_role(
+        &self,
+        request: Request<proto::CreatePractitionerRoleRequest>,
+    ) -> Result<Response<proto::CreatePractitionerRoleResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_practitioner_role = request.into_inner().practitioner_role
+            .ok_or_else(|| Status::invalid_argument("PractitionerRole is required"))?;
+
+        let practitioner_role = converters::from_proto_practitioner_role(&proto_practitioner_role);
+
+        let created_practitioner_role = self.app_state.practitioner_role_service
+            .create(&security_context, practitioner_role)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create practitioner role: {}", e)))?;
+
+        let response = proto::CreatePractitionerRoleResponse {
+            practitioner_role: Some(converters::to_proto_practitioner_role(&created_practitioner_role)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_practitioner_role(
+        &self,
+        request: Request<proto::GetPractitionerRoleRequest>,
+    ) -> Result<Response<proto::GetPractitionerRoleResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let practitioner_role = self.app_state.practitioner_role_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("PractitionerRole not found: {}", e)))?;
+
+        let response = proto::GetPractitionerRoleResponse {
+            practitioner_role: Some(converters::to_proto_practitioner_role(&practitioner_role)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_practitioner_role(
+        &self,
+        request: Request<proto::UpdatePractitionerRoleRequest>,
+    ) -> Result<Response<proto::UpdatePractitionerRoleResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_practitioner_role = req.practitioner_role
+            .ok_or_else(|| Status::invalid_argument("PractitionerRole is required"))?;
+
+        let practitioner_role = converters::from_proto_practitioner_role(&proto_practitioner_role);
+
+        let updated_practitioner_role = self.app_state.practitioner_role_service
+            .update(&security_context, &req.id, practitioner_role)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update practitioner role: {}", e)))?;
+
+        let response = proto::UpdatePractitionerRoleResponse {
+            practitioner_role: Some(converters::to_proto_practitioner_role(&updated_practitioner_role)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_practitioner_role(
+        &self,
+        request: Request<proto::DeletePractitionerRoleRequest>,
+    ) -> Result<Response<proto::DeletePractitionerRoleResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.practitioner_role_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete practitioner role: {}", e)))?;
+
+        let response = proto::DeletePractitionerRoleResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_practitioner_roles(
+        &self,
+        request: Request<proto::SearchPractitionerRolesRequest>,
+    ) -> Result<Response<proto::SearchPractitionerRolesResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.practitioner {
+            filters.push(("practitioner".to_string(), value));
+        }
+        if let Some(value) = req.organization {
+            filters.push(("organization".to_string(), value));
+        }
+        if let Some(value) = req.specialty {
+            filters.push(("specialty".to_string(), value));
+        }
+        if let Some(value) = req.role {
+            filters.push(("role".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+        if let Some(value) = req.active {
+            filters.push(("active".to_string(), value.to_string()));
+        }
+
+        let result = self.app_state.practitioner_role_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchPractitionerRolesResponse {
+            practitioner_roles: result.resources.iter().map(converters::to_proto_practitioner_role).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// Organization Service Implementation
+pub struct GrpcOrganizationService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcOrganizationService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::organization_service_server::OrganizationService for GrpcOrganizationService {
+    async fn create_organization(
+        &self,
+        request: Request<proto::CreateOrganizationRequest>,
+    ) -> Result<Response<proto::CreateOrganizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_organization = request.into_inner().organization
+            .ok_or_else(|| Status::invalid_argument("Organization is required"))?;
+
+        let organization = converters::from_proto_organization(&proto_organization);
+
+        let created_organization = self.app_state.organization_service
+            .create(&security_context, organization)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create organization: {}", e)))?;
+
+        let response = proto::CreateOrganizationResponse {
+            organization: Some(converters::to_proto_organization(&created_organization)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_organization(
+        &self,
+        request: Request<proto::GetOrganizationRequest>,
+    ) -> Result<Response<proto::GetOrganizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let organization = self.app_state.organization_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Organization not found: {}", e)))?;
+
+        let response = proto::GetOrganizationResponse {
+            organization: Some(converters::to_proto_organization(&organization)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_organization(
+        &self,
+        request: Request<proto::UpdateOrganizationRequest>,
+    ) -> Result<Response<proto::UpdateOrganizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_organization = req.organization
+            .ok_or_else(|| Status::invalid_argument("Organization is required"))?;
+
+        let organization = converters::from_proto_organization(&proto_organization);
+
+        let updated_organization = self.app_state.organization_service
+            .update(&security_context, &req.id, organization)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update organization: {}", e)))?;
+
+        let response = proto::UpdateOrganizationResponse {
+            organization: Some(converters::to_proto_organization(&updated_organization)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_organization(
+        &self,
+        request: Request<proto::DeleteOrganizationRequest>,
+    ) -> Result<Response<proto::DeleteOrganizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.organization_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete organization: {}", e)))?;
+
+        let response = proto::DeleteOrganizationResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_organizations(
+        &self,
+        request: Request<proto::SearchOrganizationsRequest>,
+    ) -> Result<Response<proto::SearchOrganizationsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.name {
+            filters.push(("name".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+        if let Some(value) = req.active {
+            filters.push(("active".to_string(), value.to_string()));
+        }
+        if let Some(value) = req.partof {
+            filters.push(("partof".to_string(), value));
+        }
+        if let Some(value) = req.partof_above {
+            filters.push(("partof:above".to_string(), value));
+        }
+        if let Some(value) = req.partof_below {
+            filters.push(("partof:below".to_string(), value));
+        }
+
+        let result = self.app_state.organization_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchOrganizationsResponse {
+            organizations: result.resources.iter().map(converters::to_proto_organization).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// Medication Service Implementation
+pub struct GrpcMedicationService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcMedicationService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::medication_service_server::MedicationService for GrpcMedicationService {
+    async fn create_medication(
+        &self,
+        request: Request<proto::CreateMedicationRequest>,
+    ) -> Result<Response<proto::CreateMedicationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_medication = request.into_inner().medication
+            .ok_or_else(|| Status::invalid_argument("Medication is required"))?;
+
+        let medication = converters::from_proto_medication(&proto_medication);
+
+        let created_medication = self.app_state.medication_service
+            .create(&security_context, medication)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create medication: {}", e)))?;
+
+        let response = proto::CreateMedicationResponse {
+            medication: Some(converters::to_proto_medication(&created_medication)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_medication(
+        &self,
+        request: Request<proto::GetMedicationRequest>,
+    ) -> Result<Response<proto::GetMedicationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let medication = self.app_state.medication_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Medication not found: {}", e)))?;
+
+        let response = proto::GetMedicationResponse {
+            medication: Some(converters::to_proto_medication(&medication)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_medication(
+        &self,
+        request: Request<proto::UpdateMedicationRequest>,
+    ) -> Result<Response<proto::UpdateMedicationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_medication = req.medication
+            .ok_or_else(|| Status::invalid_argument("Medication is required"))?;
+
+        let medication = converters::from_proto_medication(&proto_medication);
+
+        let updated_medication = self.app_state.medication_service
+            .update(&security_context, &req.id, medication)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update medication: {}", e)))?;
+
+        let response = proto::UpdateMedicationResponse {
+            medication: Some(converters::to_proto_medication(&updated_medication)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_medication(
+        &self,
+        request: Request<proto::DeleteMedicationRequest>,
+    ) -> Result<Response<proto::DeleteMedicationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.medication_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete medication: {}", e)))?;
+
+        let response = proto::DeleteMedicationResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_medications(
+        &self,
+        request: Request<proto::SearchMedicationsRequest>,
+    ) -> Result<Response<proto::SearchMedicationsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+
+        let result = self.app_state.medication_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchMedicationsResponse {
+            medications: result.resources.iter().map(converters::to_proto_medication).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// MedicationRequest Service Implementation
+pub struct GrpcMedicationRequestService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcMedicationRequestService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::medication_request_service_server::MedicationRequestService for GrpcMedicationRequestService {
+    async fn create_medication_request(
+        &self,
+        request: Request<proto::CreateMedicationRequestRequest>,
+    ) -> Result<Response<proto::CreateMedicationRequestResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_medication_request = request.into_inner().medication_request
+            .ok_or_else(|| Status::invalid_argument("MedicationRequest is required"))?;
+
+        let medication_request = converters::from_proto_medication_request(&proto_medication_request);
+
+        let created_medication_request = self.app_state.medication_request_service
+            .create(&security_context, medication_request)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create medication request: {}", e)))?;
+
+        let response = proto::CreateMedicationRequestResponse {
+            medication_request: Some(converters::to_proto_medication_request(&created_medication_request)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_medication_request(
+        &self,
+        request: Request<proto::GetMedicationRequestRequest>,
+    ) -> Result<Response<proto::GetMedicationRequestResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let medication_request = self.app_state.medication_request_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("MedicationRequest not found: {}", e)))?;
+
+        let response = proto::GetMedicationRequestResponse {
+            medication_request: Some(converters::to_proto_medication_request(&medication_request)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_medication_request(
+        &self,
+        request: Request<proto::UpdateMedicationRequestRequest>,
+    ) -> Result<Response<proto::UpdateMedicationRequestResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_medication_request = req.medication_request
+            .ok_or_else(|| Status::invalid_argument("MedicationRequest is required"))?;
+
+        let medication_request = converters::from_proto_medication_request(&proto_medication_request);
+
+        let updated_medication_request = self.app_state.medication_request_service
+            .update(&security_context, &req.id, medication_request)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update medication request: {}", e)))?;
+
+        let response = proto::UpdateMedicationRequestResponse {
+            medication_request: Some(converters::to_proto_medication_request(&updated_medication_request)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_medication_request(
+        &self,
+        request: Request<proto::DeleteMedicationRequestRequest>,
+    ) -> Result<Response<proto::DeleteMedicationRequestResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.medication_request_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete medication request: {}", e)))?;
+
+        let response = proto::DeleteMedicationRequestResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_medication_requests(
+        &self,
+        request: Request<proto::SearchMedicationRequestsRequest>,
+    ) -> Result<Response<proto::SearchMedicationRequestsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.intent {
+            filters.push(("intent".to_string(), value));
+        }
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+        if let Some(value) = req.authoredon {
+            filters.push(("authoredon".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+
+        let result = self.app_state.medication_request_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchMedicationRequestsResponse {
+            medication_requests: result.resources.iter().map(converters::to_proto_medication_request).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// MedicationStatement Service Implementation
+pub struct GrpcMedicationStatementService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcMedicationStatementService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::medication_statement_service_server::MedicationStatementService for GrpcMedicationStatementService {
+    async fn create_medication_statement(
+        &self,
+        request: Request<proto::CreateMedicationStatementRequest>,
+    ) -> Result<Response<proto::CreateMedicationStatementResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_medication_statement = request.into_inner().medication_statement
+            .ok_or_else(|| Status::invalid_argument("MedicationStatement is required"))?;
+
+        let medication_statement = converters::from_proto_medication_statement(&proto_medication_statement);
+
+        let created_medication_statement = self.app_state.medication_statement_service
+            .create(&security_context, medication_statement)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create medication statement: {}", e)))?;
+
+        let response = proto::CreateMedicationStatementResponse {
+            medication_statement: Some(converters::to_proto_medication_statement(&created_medication_statement)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_medication_statement(
+        &self,
+        request: Request<proto::GetMedicationStatementRequest>,
+    ) -> Result<Response<proto::GetMedicationStatementResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let medication_statement = self.app_state.medication_statement_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("MedicationStatement not found: {}", e)))?;
+
+        let response = proto::GetMedicationStatementResponse {
+            medication_statement: Some(converters::to_proto_medication_statement(&medication_statement)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_medication_statement(
+        &self,
+        request: Request<proto::UpdateMedicationStatementRequest>,
+    ) -> Result<Response<proto::UpdateMedicationStatementResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_medication_statement = req.medication_statement
+            .ok_or_else(|| Status::invalid_argument("MedicationStatement is required"))?;
+
+        let medication_statement = converters::from_proto_medication_statement(&proto_medication_statement);
+
+        let updated_medication_statement = self.app_state.medication_statement_service
+            .update(&security_context, &req.id, medication_statement)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update medication statement: {}", e)))?;
+
+        let response = proto::UpdateMedicationStatementResponse {
+            medication_statement: Some(converters::to_proto_medication_statement(&updated_medication_statement)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_medication_statement(
+        &self,
+        request: Request<proto::DeleteMedicationStatementRequest>,
+    ) -> Result<Response<proto::DeleteMedicationStatementResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.medication_statement_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete medication statement: {}", e)))?;
+
+        let response = proto::DeleteMedicationStatementResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_medication_statements(
+        &self,
+        request: Request<proto::SearchMedicationStatementsRequest>,
+    ) -> Result<Response<proto::SearchMedicationStatementsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+        if let Some(value) = req.effective {
+            filters.push(("effective".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+
+        let result = self.app_state.medication_statement_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchMedicationStatementsResponse {
+            medication_statements: result.resources.iter().map(converters::to_proto_medication_statement).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// AllergyIntolerance Service Implementation
+pub struct GrpcAllergyIntoleranceService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcAllergyIntoleranceService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::allergy_intolerance_service_server::AllergyIntoleranceService for GrpcAllergyIntoleranceService {
+    async fn create_allergy_intolerance(
+        &self,
+        request: Request<proto::CreateAllergyIntoleranceRequest>,
+    ) -> Result<Response<proto::CreateAllergyIntoleranceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_allergy_intolerance = request.into_inner().allergy_intolerance
+            .ok_or_else(|| Status::invalid_argument("AllergyIntolerance is required"))?;
+
+        let allergy_intolerance = converters::from_proto_allergy_intolerance(&proto_allergy_intolerance);
+
+        let created_allergy_intolerance = self.app_state.allergy_intolerance_service
+            .create(&security_context, allergy_intolerance)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create allergy: {}", e)))?;
+
+        let response = proto::CreateAllergyIntoleranceResponse {
+            allergy_intolerance: Some(converters::to_proto_allergy_intolerance(&created_allergy_intolerance)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_allergy_intolerance(
+        &self,
+        request: Request<proto::GetAllergyIntoleranceRequest>,
+    ) -> Result<Response<proto::GetAllergyIntoleranceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let allergy_intolerance = self.app_state.allergy_intolerance_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("AllergyIntolerance not found: {}", e)))?;
+
+        let response = proto::GetAllergyIntoleranceResponse {
+            allergy_intolerance: Some(converters::to_proto_allergy_intolerance(&allergy_intolerance)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_allergy_intolerance(
+        &self,
+        request: Request<proto::UpdateAllergyIntoleranceRequest>,
+    ) -> Result<Response<proto::UpdateAllergyIntoleranceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_allergy_intolerance = req.allergy_intolerance
+            .ok_or_else(|| Status::invalid_argument("AllergyIntolerance is required"))?;
+
+        let allergy_intolerance = converters::from_proto_allergy_intolerance(&proto_allergy_intolerance);
+
+        let updated_allergy_intolerance = self.app_state.allergy_intolerance_service
+            .update(&security_context, &req.id, allergy_intolerance)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update allergy: {}", e)))?;
+
+        let response = proto::UpdateAllergyIntoleranceResponse {
+            allergy_intolerance: Some(converters::to_proto_allergy_intolerance(&updated_allergy_intolerance)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_allergy_intolerance(
+        &self,
+        request: Request<proto::DeleteAllergyIntoleranceRequest>,
+    ) -> Result<Response<proto::DeleteAllergyIntoleranceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.allergy_intolerance_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete allergy: {}", e)))?;
+
+        let response = proto::DeleteAllergyIntoleranceResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_allergy_intolerances(
+        &self,
+        request: Request<proto::SearchAllergyIntolerancesRequest>,
+    ) -> Result<Response<proto::SearchAllergyIntolerancesResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.clinical_status {
+            filters.push(("clinical-status".to_string(), value));
+        }
+        if let Some(value) = req.criticality {
+            filters.push(("criticality".to_string(), value));
+        }
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+
+        let result = self.app_state.allergy_intolerance_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchAllergyIntolerancesResponse {
+            allergy_intolerances: result.resources.iter().map(converters::to_proto_allergy_intolerance).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// Procedure Service Implementation
+pub struct GrpcProcedureService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcProcedureService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::procedure_service_server::ProcedureService for GrpcProcedureService {
+    async fn create_procedure(
+        &self,
+        request: Request<proto::CreateProcedureRequest>,
+    ) -> Result<Response<proto::CreateProcedureResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_procedure = request.into_inner().procedure
+            .ok_or_else(|| Status::invalid_argument("Procedure is required"))?;
+
+        let procedure = converters::from_proto_procedure(&proto_procedure);
+
+        let created_procedure = self.app_state.procedure_service
+            .create(&security_context, procedure)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create procedure: {}", e)))?;
+
+        let response = proto::CreateProcedureResponse {
+            procedure: Some(converters::to_proto_procedure(&created_procedure)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_procedure(
+        &self,
+        request: Request<proto::GetProcedureRequest>,
+    ) -> Result<Response<proto::GetProcedureResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let procedure = self.app_state.procedure_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Procedure not found: {}", e)))?;
+
+        let response = proto::GetProcedureResponse {
+            procedure: Some(converters::to_proto_procedure(&procedure)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_procedure(
+        &self,
+        request: Request<proto::UpdateProcedureRequest>,
+    ) -> Result<Response<proto::UpdateProcedureResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_procedure = req.procedure
+            .ok_or_else(|| Status::invalid_argument("Procedure is required"))?;
+
+        let procedure = converters::from_proto_procedure(&proto_procedure);
+
+        let updated_procedure = self.app_state.procedure_service
+            .update(&security_context, &req.id, procedure)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update procedure: {}", e)))?;
+
+        let response = proto::UpdateProcedureResponse {
+            procedure: Some(converters::to_proto_procedure(&updated_procedure)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_procedure(
+        &self,
+        request: Request<proto::DeleteProcedureRequest>,
+    ) -> Result<Response<proto::DeleteProcedureResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.procedure_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete procedure: {}", e)))?;
+
+        let response = proto::DeleteProcedureResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_procedures(
+        &self,
+        request: Request<proto::SearchProceduresRequest>,
+    ) -> Result<Response<proto::SearchProceduresResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+        if let Some(value) = req.date {
+            filters.push(("date".to_string(), value));
+        }
+        if let Some(value) = req.encounter {
+            filters.push(("encounter".to_string(), value));
+        }
+
+        let result = self.app_state.procedure_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchProceduresResponse {
+            procedures: result.resources.iter().map(converters::to_proto_procedure).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// DiagnosticReport Service Implementation
+pub struct GrpcDiagnosticReportService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcDiagnosticReportService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::diagnostic_report_service_server::DiagnosticReportService for GrpcDiagnosticReportService {
+    async fn create_diagnostic_report(
+        &self,
+        request: Request<proto::CreateDiagnosticReportRequest>,
+    ) -> Result<Response<proto::CreateDiagnosticReportResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_diagnostic_report = request.into_inner().diagnostic_report
+            .ok_or_else(|| Status::invalid_argument("DiagnosticReport is required"))?;
+
+        let diagnostic_report = converters::from_proto_diagnostic_report(&proto_diagnostic_report);
+
+        let created_diagnostic_report = self.app_state.diagnostic_report_service
+            .create(&security_context, diagnostic_report)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create diagnostic report: {}", e)))?;
+
+        let response = proto::CreateDiagnosticReportResponse {
+            diagnostic_report: Some(converters::to_proto_diagnostic_report(&created_diagnostic_report)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_diagnostic_report(
+        &self,
+        request: Request<proto::GetDiagnosticReportRequest>,
+    ) -> Result<Response<proto::GetDiagnosticReportResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let diagnostic_report = self.app_state.diagnostic_report_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("DiagnosticReport not found: {}", e)))?;
+
+        let response = proto::GetDiagnosticReportResponse {
+            diagnostic_report: Some(converters::to_proto_diagnostic_report(&diagnostic_report)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_diagnostic_report(
+        &self,
+        request: Request<proto::UpdateDiagnosticReportRequest>,
+    ) -> Result<Response<proto::UpdateDiagnosticReportResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_diagnostic_report = req.diagnostic_report
+            .ok_or_else(|| Status::invalid_argument("DiagnosticReport is required"))?;
+
+        let diagnostic_report = converters::from_proto_diagnostic_report(&proto_diagnostic_report);
+
+        let updated_diagnostic_report = self.app_state.diagnostic_report_service
+            .update(&security_context, &req.id, diagnostic_report)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update diagnostic report: {}", e)))?;
+
+        let response = proto::UpdateDiagnosticReportResponse {
+            diagnostic_report: Some(converters::to_proto_diagnostic_report(&updated_diagnostic_report)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_diagnostic_report(
+        &self,
+        request: Request<proto::DeleteDiagnosticReportRequest>,
+    ) -> Result<Response<proto::DeleteDiagnosticReportResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.diagnostic_report_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete diagnostic report: {}", e)))?;
+
+        let response = proto::DeleteDiagnosticReportResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_diagnostic_reports(
+        &self,
+        request: Request<proto::SearchDiagnosticReportsRequest>,
+    ) -> Result<Response<proto::SearchDiagnosticReportsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.category {
+            filters.push(("category".to_string(), value));
+        }
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+        if let Some(value) = req.date {
+            filters.push(("date".to_string(), value));
+        }
+
+        let result = self.app_state.diagnostic_report_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchDiagnosticReportsResponse {
+            diagnostic_reports: result.resources.iter().map(converters::to_proto_diagnostic_report).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// Immunization Service Implementation
+pub struct GrpcImmunizationService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcImmunizationService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::immunization_service_server::ImmunizationService for GrpcImmunizationService {
+    async fn create_immunization(
+        &self,
+        request: Request<proto::CreateImmunizationRequest>,
+    ) -> Result<Response<proto::CreateImmunizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_immunization = request.into_inner().immunization
+            .ok_or_else(|| Status::invalid_argument("Immunization is required"))?;
+
+        let immunization = converters::from_proto_immunization(&proto_immunization);
+
+        let created_immunization = self.app_state.immunization_service
+            .create(&security_context, immunization)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create immunization: {}", e)))?;
+
+        let response = proto::CreateImmunizationResponse {
+            immunization: Some(converters::to_proto_immunization(&created_immunization)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_immunization(
+        &self,
+        request: Request<proto::GetImmunizationRequest>,
+    ) -> Result<Response<proto::GetImmunizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let immunization = self.app_state.immunization_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Immunization not found: {}", e)))?;
+
+        let response = proto::GetImmunizationResponse {
+            immunization: Some(converters::to_proto_immunization(&immunization)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_immunization(
+        &self,
+        request: Request<proto::UpdateImmunizationRequest>,
+    ) -> Result<Response<proto::UpdateImmunizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_immunization = req.immunization
+            .ok_or_else(|| Status::invalid_argument("Immunization is required"))?;
+
+        let immunization = converters::from_proto_immunization(&proto_immunization);
+
+        let updated_immunization = self.app_state.immunization_service
+            .update(&security_context, &req.id, immunization)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update immunization: {}", e)))?;
+
+        let response = proto::UpdateImmunizationResponse {
+            immunization: Some(converters::to_proto_immunization(&updated_immunization)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_immunization(
+        &self,
+        request: Request<proto::DeleteImmunizationRequest>,
+    ) -> Result<Response<proto::DeleteImmunizationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.immunization_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete immunization: {}", e)))?;
+
+        let response = proto::DeleteImmunizationResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_immunizations(
+        &self,
+        request: Request<proto::SearchImmunizationsRequest>,
+    ) -> Result<Response<proto::SearchImmunizationsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.date {
+            filters.push(("date".to_string(), value));
+        }
+        if let Some(value) = req.vaccine_code {
+            filters.push(("vaccine-code".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+
+        let result = self.app_state.immunization_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchImmunizationsResponse {
+            immunizations: result.resources.iter().map(converters::to_proto_immunization).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// Location Service Implementation
+pub struct GrpcLocationService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcLocationService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::location_service_server::LocationService for GrpcLocationService {
+    async fn create_location(
+        &self,
+        request: Request<proto::CreateLocationRequest>,
+    ) -> Result<Response<proto::CreateLocationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_location = request.into_inner().location
+            .ok_or_else(|| Status::invalid_argument("Location is required"))?;
+
+        let location = converters::from_proto_location(&proto_location);
+
+        let created_location = self.app_state.location_service
+            .create(&security_context, location)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create location: {}", e)))?;
+
+        let response = proto::CreateLocationResponse {
+            location: Some(converters::to_proto_location(&created_location)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_location(
+        &self,
+        request: Request<proto::GetLocationRequest>,
+    ) -> Result<Response<proto::GetLocationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let location = self.app_state.location_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Location not found: {}", e)))?;
+
+        let response = proto::GetLocationResponse {
+            location: Some(converters::to_proto_location(&location)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_location(
+        &self,
+        request: Request<proto::UpdateLocationRequest>,
+    ) -> Result<Response<proto::UpdateLocationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_location = req.location
+            .ok_or_else(|| Status::invalid_argument("Location is required"))?;
+
+        let location = converters::from_proto_location(&proto_location);
+
+        let updated_location = self.app_state.location_service
+            .update(&security_context, &req.id, location)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update location: {}", e)))?;
+
+        let response = proto::UpdateLocationResponse {
+            location: Some(converters::to_proto_location(&updated_location)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_location(
+        &self,
+        request: Request<proto::DeleteLocationRequest>,
+    ) -> Result<Response<proto::DeleteLocationResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.location_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete location: {}", e)))?;
+
+        let response = proto::DeleteLocationResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_locations(
+        &self,
+        request: Request<proto::SearchLocationsRequest>,
+    ) -> Result<Response<proto::SearchLocationsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.name {
+            filters.push(("name".to_string(), value));
+        }
+        if let Some(value) = req.address {
+            filters.push(("address".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.partof {
+            filters.push(("partof".to_string(), value));
+        }
+        if let Some(value) = req.organization {
+            filters.push(("organization".to_string(), value));
+        }
+        if let Some(value) = req.near {
+            filters.push(("near".to_string(), value));
+        }
+
+        let result = self.app_state.location_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchLocationsResponse {
+            locations: result.resources.iter().map(converters::to_proto_location).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// Group Service Implementation
+pub struct GrpcGroupService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcGroupService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::group_service_server::GroupService for GrpcGroupService {
+    async fn create_group(
+        &self,
+        request: Request<proto::CreateGroupRequest>,
+    ) -> Result<Response<proto::CreateGroupResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_group = request.into_inner().group
+            .ok_or_else(|| Status::invalid_argument("Group is required"))?;
+
+        let group = converters::from_proto_group(&proto_group);
+
+        let created_group = self.app_state.group_service
+            .create(&security_context, group)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create group: {}", e)))?;
+
+        let response = proto::CreateGroupResponse {
+            group: Some(converters::to_proto_group(&created_group)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_group(
+        &self,
+        request: Request<proto::GetGroupRequest>,
+    ) -> Result<Response<proto::GetGroupResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let group = self.app_state.group_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("Group not found: {}", e)))?;
+
+        let response = proto::GetGroupResponse {
+            group: Some(converters::to_proto_group(&group)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_group(
+        &self,
+        request: Request<proto::UpdateGroupRequest>,
+    ) -> Result<Response<proto::UpdateGroupResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_group = req.group
+            .ok_or_else(|| Status::invalid_argument("Group is required"))?;
+
+        let group = converters::from_proto_group(&proto_group);
+
+        let updated_group = self.app_state.group_service
+            .update(&security_context, &req.id, group)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update group: {}", e)))?;
+
+        let response = proto::UpdateGroupResponse {
+            group: Some(converters::to_proto_group(&updated_group)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_group(
+        &self,
+        request: Request<proto::DeleteGroupRequest>,
+    ) -> Result<Response<proto::DeleteGroupResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.group_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete group: {}", e)))?;
+
+        let response = proto::DeleteGroupResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_groups(
+        &self,
+        request: Request<proto::SearchGroupsRequest>,
+    ) -> Result<Response<proto::SearchGroupsResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.name {
+            filters.push(("name".to_string(), value));
+        }
+        if let Some(value) = req.r#type {
+            filters.push(("type".to_string(), value));
+        }
+        if let Some(value) = req.actual {
+            filters.push(("actual".to_string(), value.to_string()));
+        }
+        if let Some(value) = req.code {
+            filters.push(("code".to_string(), value));
+        }
+        if let Some(value) = req.member {
+            filters.push(("member".to_string(), value));
+        }
+
+        let result = self.app_state.group_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchGroupsResponse {
+            groups: result.resources.iter().map(converters::to_proto_group).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
+
+// DocumentReference Service Implementation
+pub struct GrpcDocumentReferenceService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcDocumentReferenceService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::document_reference_service_server::DocumentReferenceService for GrpcDocumentReferenceService {
+    async fn create_document_reference(
+        &self,
+        request: Request<proto::CreateDocumentReferenceRequest>,
+    ) -> Result<Response<proto::CreateDocumentReferenceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let proto_document_reference = request.into_inner().document_reference
+            .ok_or_else(|| Status::invalid_argument("DocumentReference is required"))?;
+
+        let document_reference = converters::from_proto_document_reference(&proto_document_reference);
+
+        let created_document_reference = self.app_state.document_reference_service
+            .create(&security_context, document_reference)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create document reference: {}", e)))?;
+
+        let response = proto::CreateDocumentReferenceResponse {
+            document_reference: Some(converters::to_proto_document_reference(&created_document_reference)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_document_reference(
+        &self,
+        request: Request<proto::GetDocumentReferenceRequest>,
+    ) -> Result<Response<proto::GetDocumentReferenceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        let document_reference = self.app_state.document_reference_service
+            .get(&security_context, id)
+            .await
+            .map_err(|e| Status::not_found(format!("DocumentReference not found: {}", e)))?;
+
+        let response = proto::GetDocumentReferenceResponse {
+            document_reference: Some(converters::to_proto_document_reference(&document_reference)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_document_reference(
+        &self,
+        request: Request<proto::UpdateDocumentReferenceRequest>,
+    ) -> Result<Response<proto::UpdateDocumentReferenceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+        let proto_document_reference = req.document_reference
+            .ok_or_else(|| Status::invalid_argument("DocumentReference is required"))?;
+
+        let document_reference = converters::from_proto_document_reference(&proto_document_reference);
+
+        let updated_document_reference = self.app_state.document_reference_service
+            .update(&security_context, &req.id, document_reference)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to update document reference: {}", e)))?;
+
+        let response = proto::UpdateDocumentReferenceResponse {
+            document_reference: Some(converters::to_proto_document_reference(&updated_document_reference)),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_document_reference(
+        &self,
+        request: Request<proto::DeleteDocumentReferenceRequest>,
+    ) -> Result<Response<proto::DeleteDocumentReferenceResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let id = &request.into_inner().id;
+
+        self.app_state.document_reference_service
+            .delete(&security_context, id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete document reference: {}", e)))?;
+
+        let response = proto::DeleteDocumentReferenceResponse {
+            success: true,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn search_document_references(
+        &self,
+        request: Request<proto::SearchDocumentReferencesRequest>,
+    ) -> Result<Response<proto::SearchDocumentReferencesResponse>, Status> {
+        let security_context = extract_security_context(&request)?;
+        let req = request.into_inner();
+
+        let mut filters = Vec::new();
+        if let Some(value) = req.patient {
+            filters.push(("patient".to_string(), value));
+        }
+        if let Some(value) = req.subject {
+            filters.push(("subject".to_string(), value));
+        }
+        if let Some(value) = req.status {
+            filters.push(("status".to_string(), value));
+        }
+        if let Some(value) = req.r#type {
+            filters.push(("type".to_string(), value));
+        }
+        if let Some(value) = req.category {
+            filters.push(("category".to_string(), value));
+        }
+        if let Some(value) = req.date {
+            filters.push(("date".to_string(), value));
+        }
+        if let Some(value) = req.identifier {
+            filters.push(("identifier".to_string(), value));
+        }
+        if let Some(value) = req.contenttype {
+            filters.push(("contenttype".to_string(), value));
+        }
+
+        let result = self.app_state.document_reference_service
+            .search(&security_context, SearchParameters { filters, ..Default::default() })
+            .await
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+
+        let response = proto::SearchDocumentReferencesResponse {
+            document_references: result.resources.iter().map(converters::to_proto_document_reference).collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+}
@@ -13,18 +13,57 @@ use tracing::{info, error};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use domain::resources::observation::ObservationValue;
 
-use config::{DatabaseConfig, GrpcConfig};
+use config::{BinaryConfig, DatabaseConfig, ExportConfig, GrpcConfig, ImportConfig, MatchConfig, ReindexConfig};
 use repository::{
     PatientRepository, 
     ObservationRepository, 
     ConditionRepository, 
     EncounterRepository,
+    PractitionerRepository,
+    PractitionerRoleRepository,
+    OrganizationRepository,
+    MedicationRepository,
+    MedicationRequestRepository,
+    MedicationStatementRepository,
+    AllergyIntoleranceRepository,
+    ProcedureRepository,
+    DiagnosticReportRepository,
+    ImmunizationRepository,
+    LocationRepository,
+    GroupRepository,
+    DocumentReferenceRepository,
+    BinaryRepository,
+    BlobStore,
+    LocalBlobStore,
+    MetaRepository,
+    ExportRepository,
+    ImportRepository,
+    ReindexRepository,
 };
 use service::{
     PatientService, 
     ObservationService, 
     ConditionService, 
     EncounterService,
+    PractitionerService,
+    PractitionerRoleService,
+    OrganizationService,
+    MedicationService,
+    MedicationRequestService,
+    MedicationStatementService,
+    AllergyIntoleranceService,
+    ProcedureService,
+    DiagnosticReportService,
+    ImmunizationService,
+    LocationService,
+    GroupService,
+    DocumentReferenceService,
+    BinaryService,
+    EverythingService,
+    MetaService,
+    BulkExportService,
+    BulkImportService,
+    ReindexService,
 };
 
 /// Application state that will be shared across handlers
@@ -34,20 +73,78 @@ pub struct AppState {
     pub observation_service: Arc<ObservationService>,
     pub condition_service: Arc<ConditionService>,
     pub encounter_service: Arc<EncounterService>,
+    pub practitioner_service: Arc<PractitionerService>,
+    pub practitioner_role_service: Arc<PractitionerRoleService>,
+    pub organization_service: Arc<OrganizationService>,
+    pub medication_service: Arc<MedicationService>,
+    pub medication_request_service: Arc<MedicationRequestService>,
+    pub medication_statement_service: Arc<MedicationStatementService>,
+    pub allergy_intolerance_service: Arc<AllergyIntoleranceService>,
+    pub procedure_service: Arc<ProcedureService>,
+    pub diagnostic_report_service: Arc<DiagnosticReportService>,
+    pub immunization_service: Arc<ImmunizationService>,
+    pub location_service: Arc<LocationService>,
+    pub group_service: Arc<GroupService>,
+    pub document_reference_service: Arc<DocumentReferenceService>,
+    pub binary_service: Arc<BinaryService>,
+    pub everything_service: Arc<EverythingService>,
+    pub meta_service: Arc<MetaService>,
+    pub bulk_export_service: Arc<BulkExportService>,
+    pub bulk_import_service: Arc<BulkImportService>,
+    pub reindex_service: Arc<ReindexService>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         patient_service: PatientService,
         observation_service: ObservationService,
         condition_service: ConditionService,
         encounter_service: EncounterService,
+        practitioner_service: PractitionerService,
+        practitioner_role_service: PractitionerRoleService,
+        organization_service: OrganizationService,
+        medication_service: MedicationService,
+        medication_request_service: MedicationRequestService,
+        medication_statement_service: MedicationStatementService,
+        allergy_intolerance_service: AllergyIntoleranceService,
+        procedure_service: ProcedureService,
+        diagnostic_report_service: DiagnosticReportService,
+        immunization_service: ImmunizationService,
+        location_service: LocationService,
+        group_service: GroupService,
+        document_reference_service: DocumentReferenceService,
+        binary_service: BinaryService,
+        everything_service: EverythingService,
+        meta_service: MetaService,
+        bulk_export_service: BulkExportService,
+        bulk_import_service: BulkImportService,
+        reindex_service: ReindexService,
     ) -> Self {
         Self {
             patient_service: Arc::new(patient_service),
             observation_service: Arc::new(observation_service),
             condition_service: Arc::new(condition_service),
             encounter_service: Arc::new(encounter_service),
+            practitioner_service: Arc::new(practitioner_service),
+            practitioner_role_service: Arc::new(practitioner_role_service),
+            organization_service: Arc::new(organization_service),
+            medication_service: Arc::new(medication_service),
+            medication_request_service: Arc::new(medication_request_service),
+            medication_statement_service: Arc::new(medication_statement_service),
+            allergy_intolerance_service: Arc::new(allergy_intolerance_service),
+            procedure_service: Arc::new(procedure_service),
+            diagnostic_report_service: Arc::new(diagnostic_report_service),
+            immunization_service: Arc::new(immunization_service),
+            location_service: Arc::new(location_service),
+            group_service: Arc::new(group_service),
+            document_reference_service: Arc::new(document_reference_service),
+            binary_service: Arc::new(binary_service),
+            everything_service: Arc::new(everything_service),
+            meta_service: Arc::new(meta_service),
+            bulk_export_service: Arc::new(bulk_export_service),
+            bulk_import_service: Arc::new(bulk_import_service),
+            reindex_service: Arc::new(reindex_service),
         }
     }
 }
@@ -85,14 +182,107 @@ async fn main() -> Result<()> {
     let observation_repo = ObservationRepository::new(pool.clone());
     let condition_repo = ConditionRepository::new(pool.clone());
     let encounter_repo = EncounterRepository::new(pool.clone());
+    let practitioner_repo = PractitionerRepository::new(pool.clone());
+    let practitioner_role_repo = PractitionerRoleRepository::new(pool.clone());
+    let organization_repo = OrganizationRepository::new(pool.clone());
+    let medication_repo = MedicationRepository::new(pool.clone());
+    let medication_request_repo = MedicationRequestRepository::new(pool.clone());
+    let medication_statement_repo = MedicationStatementRepository::new(pool.clone());
+    let allergy_intolerance_repo = AllergyIntoleranceRepository::new(pool.clone());
+    let procedure_repo = ProcedureRepository::new(pool.clone());
+    let diagnostic_report_repo = DiagnosticReportRepository::new(pool.clone());
+    let immunization_repo = ImmunizationRepository::new(pool.clone());
+    let location_repo = LocationRepository::new(pool.clone());
+    let group_repo = GroupRepository::new(pool.clone());
+    let document_reference_repo = DocumentReferenceRepository::new(pool.clone());
+    let binary_repo = BinaryRepository::new(pool.clone());
     info!("✅ Repositories initialized");
     
     // Initialize services
     info!("⚙️  Initializing services...");
-    let patient_service = PatientService::new(patient_repo);
+    let patient_service = PatientService::new(patient_repo)
+        .with_match_config(MatchConfig::from_env());
     let observation_service = ObservationService::new(observation_repo);
     let condition_service = ConditionService::new(condition_repo);
     let encounter_service = EncounterService::new(encounter_repo);
+    let practitioner_service = PractitionerService::new(practitioner_repo);
+    let practitioner_role_service = PractitionerRoleService::new(
+        practitioner_role_repo,
+        PractitionerRepository::new(pool.clone()),
+        OrganizationRepository::new(pool.clone()),
+    );
+    let organization_service = OrganizationService::new(organization_repo);
+    let medication_service = MedicationService::new(medication_repo);
+    let medication_request_service = MedicationRequestService::new(
+        medication_request_repo,
+        PatientRepository::new(pool.clone()),
+        MedicationRepository::new(pool.clone()),
+    );
+    let medication_statement_service = MedicationStatementService::new(
+        medication_statement_repo,
+        PatientRepository::new(pool.clone()),
+        MedicationRepository::new(pool.clone()),
+    );
+    let allergy_intolerance_service = AllergyIntoleranceService::new(
+        allergy_intolerance_repo,
+        PatientRepository::new(pool.clone()),
+    );
+    let procedure_service = ProcedureService::new(
+        procedure_repo,
+        PatientRepository::new(pool.clone()),
+        ConditionRepository::new(pool.clone()),
+    );
+    let diagnostic_report_service = DiagnosticReportService::new(
+        diagnostic_report_repo,
+        PatientRepository::new(pool.clone()),
+        ObservationRepository::new(pool.clone()),
+    );
+    let immunization_service = ImmunizationService::new(
+        immunization_repo,
+        PatientRepository::new(pool.clone()),
+    );
+    let location_service = LocationService::new(
+        location_repo,
+        OrganizationRepository::new(pool.clone()),
+    );
+    let group_service = GroupService::new(
+        group_repo,
+        PatientRepository::new(pool.clone()),
+    );
+    let binary_config = BinaryConfig::from_env();
+    let blob_store: Arc<dyn BlobStore> = Arc::new(LocalBlobStore::new(binary_config.storage_dir.clone()));
+    let document_reference_service = DocumentReferenceService::new(
+        document_reference_repo,
+        PatientRepository::new(pool.clone()),
+        BinaryService::new(BinaryRepository::new(pool.clone()), blob_store.clone(), binary_config.clone()),
+    );
+    let binary_service = BinaryService::new(binary_repo, blob_store, binary_config);
+    let everything_service = EverythingService::new(
+        PatientRepository::new(pool.clone()),
+        ObservationRepository::new(pool.clone()),
+        ConditionRepository::new(pool.clone()),
+        EncounterRepository::new(pool.clone()),
+        MedicationRequestRepository::new(pool.clone()),
+        MedicationStatementRepository::new(pool.clone()),
+        AllergyIntoleranceRepository::new(pool.clone()),
+        ProcedureRepository::new(pool.clone()),
+        DiagnosticReportRepository::new(pool.clone()),
+        ImmunizationRepository::new(pool.clone()),
+        DocumentReferenceRepository::new(pool.clone()),
+    );
+    let meta_service = MetaService::new(MetaRepository::new(pool.clone()));
+    let bulk_export_service = BulkExportService::new(
+        ExportRepository::new(pool.clone()),
+        ExportConfig::from_env(),
+    );
+    let bulk_import_service = BulkImportService::new(
+        ImportRepository::new(pool.clone()),
+        ImportConfig::from_env(),
+    );
+    let reindex_service = ReindexService::new(
+        ReindexRepository::new(pool.clone()),
+        ReindexConfig::from_env(),
+    );
     info!("✅ Services initialized");
     
     // Create application state
@@ -101,7 +291,32 @@ async fn main() -> Result<()> {
         observation_service,
         condition_service,
         encounter_service,
+        practitioner_service,
+        practitioner_role_service,
+        organization_service,
+        medication_service,
+        medication_request_service,
+        medication_statement_service,
+        allergy_intolerance_service,
+        procedure_service,
+        diagnostic_report_service,
+        immunization_service,
+        location_service,
+        group_service,
+        document_reference_service,
+        binary_service,
+        everything_service,
+        meta_service,
+        bulk_export_service,
+        bulk_import_service,
+        reindex_service,
     );
+
+    // `fhir-server import <file> [--type <ResourceType>]` imports and exits
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("import") {
+        return run_import_command(&app_state, &args[1..]).await;
+    }
     
     info!("🎉 FHIR Server initialized successfully!");
     
@@ -164,6 +379,43 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Import an NDJSON file from the command line, as the system user. Unlike
+/// `$import`, any readable path is accepted
+async fn run_import_command(state: &AppState, args: &[String]) -> Result<()> {
+    use service::SecurityContext;
+
+    let mut path = None;
+    let mut resource_type = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" => resource_type = args.next().cloned(),
+            _ if path.is_none() => path = Some(arg.clone()),
+            other => anyhow::bail!("Unexpected argument: {}", other),
+        }
+    }
+    let Some(path) = path else {
+        anyhow::bail!("Usage: fhir-server import <file.ndjson> [--type <ResourceType>]");
+    };
+
+    let service = &state.bulk_import_service;
+    let source = std::fs::canonicalize(&path)?;
+    let job = service.start(&SecurityContext::system(), &source, resource_type.as_deref()).await?;
+    if job.lines_processed > 0 {
+        info!("🔁 Resuming import {} after line {}", job.id, job.lines_processed);
+    }
+    let job = service.run(job).await?;
+
+    info!(
+        "✅ Imported {} of {} lines from {} ({} failed)",
+        job.lines_imported, job.lines_processed, job.source, job.lines_failed
+    );
+    if job.lines_failed > 0 {
+        info!("📄 Rejected lines: {}", service.error_file(job.id).display());
+    }
+    Ok(())
+}
+
 /// Example operations to demonstrate the system
 async fn run_examples(state: AppState) -> Result<()> {
     use domain::{
@@ -314,6 +566,10 @@ async fn run_examples(state: AppState) -> Result<()> {
     let active_conditions = state.condition_service.get_active_conditions(&system_context, &patient_id).await?;
     info!("✅ Found {} active conditions for patient", active_conditions.len());
 
+    // Get active allergies
+    let active_allergies = state.allergy_intolerance_service.get_active_allergies(&system_context, &patient_id).await?;
+    info!("✅ Found {} active allergies for patient", active_allergies.len());
+
     // Get active encounters
     let active_encounters = state.encounter_service.get_active_encounters(&system_context, &patient_id).await?;
     info!("✅ Found {} active encounters for patient", active_encounters.len());
@@ -0,0 +1,5 @@
+-- Supports Observation/$lastn: per patient, rank each code's observations
+-- by effective time without sorting the whole compartment
+CREATE INDEX IF NOT EXISTS idx_observations_subject_code_effective
+    ON observations(subject_id, code_code, effective_datetime DESC)
+    WHERE deleted_at IS NULL;
@@ -0,0 +1,21 @@
+-- Bulk $import jobs. Each committed batch advances lines_processed in the
+-- same transaction as its inserts, so a rerun of the same file resumes
+-- after the last committed line
+CREATE TABLE IF NOT EXISTS import_jobs (
+    id UUID PRIMARY KEY,
+    source TEXT NOT NULL,
+    source_size BIGINT NOT NULL,
+    resource_type VARCHAR(64),
+    status VARCHAR(20) NOT NULL DEFAULT 'in-progress',
+    lines_processed BIGINT NOT NULL DEFAULT 0,
+    lines_imported BIGINT NOT NULL DEFAULT 0,
+    lines_failed BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one unfinished job per source file, which a rerun picks up
+CREATE UNIQUE INDEX IF NOT EXISTS idx_import_jobs_unfinished
+    ON import_jobs(source, source_size)
+    WHERE status <> 'completed';
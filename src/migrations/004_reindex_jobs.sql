@@ -0,0 +1,18 @@
+-- $reindex jobs. Each processed page records the type and last id reached,
+-- so restarting a job with the same scope continues from there
+CREATE TABLE IF NOT EXISTS reindex_jobs (
+    id UUID PRIMARY KEY,
+    resource_type VARCHAR(64),
+    status VARCHAR(20) NOT NULL DEFAULT 'in-progress',
+    current_type VARCHAR(64),
+    last_id UUID,
+    resources_reindexed BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one unfinished job per scope (one type, or '*' for all types)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reindex_jobs_unfinished
+    ON reindex_jobs((COALESCE(resource_type, '*')))
+    WHERE status <> 'completed';
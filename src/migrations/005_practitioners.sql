@@ -0,0 +1,80 @@
+-- Practitioner and PractitionerRole. Neither belongs to a patient
+-- compartment; roles link a practitioner to the organization they work for
+
+CREATE TABLE IF NOT EXISTS practitioners (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Practitioner',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+    
+    -- Indexed search parameters; identifiers are matched in the JSONB
+    active BOOLEAN,
+    family_name TEXT,
+    given_name TEXT,
+    gender VARCHAR(20),
+    
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+    
+    CONSTRAINT practitioners_resource_type_check CHECK (resource_type = 'Practitioner')
+);
+
+CREATE INDEX idx_practitioners_family_name ON practitioners USING gin(to_tsvector('english', family_name));
+CREATE INDEX idx_practitioners_given_name ON practitioners USING gin(to_tsvector('english', given_name));
+CREATE INDEX idx_practitioners_active ON practitioners(active) WHERE active = true;
+CREATE INDEX idx_practitioners_deleted_at ON practitioners(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_practitioners_resource_gin ON practitioners USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS practitioner_roles (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'PractitionerRole',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    
+    resource JSONB NOT NULL,
+    
+    -- Specialty and role codes are matched in the JSONB
+    active BOOLEAN,
+    practitioner_id UUID,
+    organization_id UUID,
+    
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+    
+    CONSTRAINT practitioner_roles_resource_type_check CHECK (resource_type = 'PractitionerRole')
+);
+
+CREATE INDEX idx_practitioner_roles_practitioner_id ON practitioner_roles(practitioner_id);
+CREATE INDEX idx_practitioner_roles_organization_id ON practitioner_roles(organization_id);
+CREATE INDEX idx_practitioner_roles_deleted_at ON practitioner_roles(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_practitioner_roles_resource_gin ON practitioner_roles USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS practitioners_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TABLE IF NOT EXISTS practitioner_roles_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_practitioners_updated_at BEFORE UPDATE ON practitioners
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_practitioner_roles_updated_at BEFORE UPDATE ON practitioner_roles
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
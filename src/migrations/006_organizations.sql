@@ -0,0 +1,42 @@
+-- Organization. part_of_id holds the parent organization so partof:above
+-- and partof:below can walk the hierarchy with recursive queries
+
+CREATE TABLE IF NOT EXISTS organizations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Organization',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters; identifiers are matched in the JSONB
+    active BOOLEAN,
+    name TEXT,
+    part_of_id UUID,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT organizations_resource_type_check CHECK (resource_type = 'Organization')
+);
+
+CREATE INDEX idx_organizations_name ON organizations USING gin(to_tsvector('english', name));
+CREATE INDEX idx_organizations_part_of_id ON organizations(part_of_id);
+CREATE INDEX idx_organizations_active ON organizations(active) WHERE active = true;
+CREATE INDEX idx_organizations_deleted_at ON organizations(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_organizations_resource_gin ON organizations USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS organizations_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_organizations_updated_at BEFORE UPDATE ON organizations
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,130 @@
+-- Medication, MedicationRequest and MedicationStatement. code_code and
+-- code_system hold the first coding of medicationCodeableConcept; requests
+-- and statements that reference a Medication leave them NULL
+
+CREATE TABLE IF NOT EXISTS medications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Medication',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    status VARCHAR(20),
+    code_code TEXT,
+    code_system TEXT,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT medications_resource_type_check CHECK (resource_type = 'Medication')
+);
+
+CREATE INDEX idx_medications_code_code ON medications(code_code);
+CREATE INDEX idx_medications_status ON medications(status);
+CREATE INDEX idx_medications_deleted_at ON medications(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_medications_resource_gin ON medications USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS medications_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_medications_updated_at BEFORE UPDATE ON medications
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS medication_requests (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'MedicationRequest',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    subject_id UUID REFERENCES patients(id),
+    status VARCHAR(20) NOT NULL,
+    intent VARCHAR(20) NOT NULL,
+    code_code TEXT,
+    code_system TEXT,
+    authored_on TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT medication_requests_resource_type_check CHECK (resource_type = 'MedicationRequest')
+);
+
+CREATE INDEX idx_medication_requests_subject_id ON medication_requests(subject_id);
+CREATE INDEX idx_medication_requests_status ON medication_requests(status);
+CREATE INDEX idx_medication_requests_intent ON medication_requests(intent);
+CREATE INDEX idx_medication_requests_code_code ON medication_requests(code_code);
+CREATE INDEX idx_medication_requests_authored_on ON medication_requests(authored_on);
+CREATE INDEX idx_medication_requests_deleted_at ON medication_requests(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_medication_requests_resource_gin ON medication_requests USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS medication_requests_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_medication_requests_updated_at BEFORE UPDATE ON medication_requests
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS medication_statements (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'MedicationStatement',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters; effective_datetime is the start of a Period
+    subject_id UUID REFERENCES patients(id),
+    status VARCHAR(20) NOT NULL,
+    code_code TEXT,
+    code_system TEXT,
+    effective_datetime TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT medication_statements_resource_type_check CHECK (resource_type = 'MedicationStatement')
+);
+
+CREATE INDEX idx_medication_statements_subject_id ON medication_statements(subject_id);
+CREATE INDEX idx_medication_statements_status ON medication_statements(status);
+CREATE INDEX idx_medication_statements_code_code ON medication_statements(code_code);
+CREATE INDEX idx_medication_statements_effective_datetime ON medication_statements(effective_datetime);
+CREATE INDEX idx_medication_statements_deleted_at ON medication_statements(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_medication_statements_resource_gin ON medication_statements USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS medication_statements_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_medication_statements_updated_at BEFORE UPDATE ON medication_statements
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,47 @@
+-- AllergyIntolerance. clinical_status and verification_status hold the
+-- code of the first coding; category is searched through the JSONB resource
+
+CREATE TABLE IF NOT EXISTS allergy_intolerances (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'AllergyIntolerance',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    patient_id UUID REFERENCES patients(id),
+    clinical_status VARCHAR(20),
+    verification_status VARCHAR(20),
+    criticality VARCHAR(20),
+    code_code TEXT,
+    code_system TEXT,
+    recorded_date TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT allergy_intolerances_resource_type_check CHECK (resource_type = 'AllergyIntolerance')
+);
+
+CREATE INDEX idx_allergy_intolerances_patient_id ON allergy_intolerances(patient_id);
+CREATE INDEX idx_allergy_intolerances_clinical_status ON allergy_intolerances(clinical_status);
+CREATE INDEX idx_allergy_intolerances_criticality ON allergy_intolerances(criticality);
+CREATE INDEX idx_allergy_intolerances_code_code ON allergy_intolerances(code_code);
+CREATE INDEX idx_allergy_intolerances_deleted_at ON allergy_intolerances(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_allergy_intolerances_resource_gin ON allergy_intolerances USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS allergy_intolerances_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_allergy_intolerances_updated_at BEFORE UPDATE ON allergy_intolerances
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,47 @@
+-- Procedure. code_code/code_system hold the first coding of code and
+-- performed_datetime is performedDateTime or the start of performedPeriod.
+-- The encounter reference is searched through the JSONB resource, as for
+-- Observation and Condition
+
+CREATE TABLE IF NOT EXISTS procedures (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Procedure',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    subject_id UUID REFERENCES patients(id),
+    status VARCHAR(20) NOT NULL,
+    code_code TEXT,
+    code_system TEXT,
+    performed_datetime TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT procedures_resource_type_check CHECK (resource_type = 'Procedure')
+);
+
+CREATE INDEX idx_procedures_subject_id ON procedures(subject_id);
+CREATE INDEX idx_procedures_status ON procedures(status);
+CREATE INDEX idx_procedures_code_code ON procedures(code_code);
+CREATE INDEX idx_procedures_performed_datetime ON procedures(performed_datetime);
+CREATE INDEX idx_procedures_deleted_at ON procedures(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_procedures_resource_gin ON procedures USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS procedures_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_procedures_updated_at BEFORE UPDATE ON procedures
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
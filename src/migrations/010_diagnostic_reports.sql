@@ -0,0 +1,49 @@
+-- DiagnosticReport. category_code, code_code and code_system hold the first
+-- coding; effective_datetime is effectiveDateTime or the start of
+-- effectivePeriod. `result` references are searched through the JSONB resource
+
+CREATE TABLE IF NOT EXISTS diagnostic_reports (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'DiagnosticReport',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    subject_id UUID REFERENCES patients(id),
+    status VARCHAR(20) NOT NULL,
+    category_code TEXT,
+    code_code TEXT,
+    code_system TEXT,
+    effective_datetime TIMESTAMP WITH TIME ZONE,
+    issued TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT diagnostic_reports_resource_type_check CHECK (resource_type = 'DiagnosticReport')
+);
+
+CREATE INDEX idx_diagnostic_reports_subject_id ON diagnostic_reports(subject_id);
+CREATE INDEX idx_diagnostic_reports_status ON diagnostic_reports(status);
+CREATE INDEX idx_diagnostic_reports_category_code ON diagnostic_reports(category_code);
+CREATE INDEX idx_diagnostic_reports_code_code ON diagnostic_reports(code_code);
+CREATE INDEX idx_diagnostic_reports_effective_datetime ON diagnostic_reports(effective_datetime);
+CREATE INDEX idx_diagnostic_reports_deleted_at ON diagnostic_reports(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_diagnostic_reports_resource_gin ON diagnostic_reports USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS diagnostic_reports_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_diagnostic_reports_updated_at BEFORE UPDATE ON diagnostic_reports
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,45 @@
+-- Immunization. vaccine_code holds the first coding; occurrence_datetime is
+-- NULL when the occurrence is only known as free text
+
+CREATE TABLE IF NOT EXISTS immunizations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Immunization',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    patient_id UUID REFERENCES patients(id),
+    status VARCHAR(20) NOT NULL,
+    vaccine_code_code TEXT,
+    vaccine_code_system TEXT,
+    occurrence_datetime TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT immunizations_resource_type_check CHECK (resource_type = 'Immunization')
+);
+
+CREATE INDEX idx_immunizations_patient_id ON immunizations(patient_id);
+CREATE INDEX idx_immunizations_status ON immunizations(status);
+CREATE INDEX idx_immunizations_vaccine_code_code ON immunizations(vaccine_code_code);
+CREATE INDEX idx_immunizations_occurrence_datetime ON immunizations(occurrence_datetime);
+CREATE INDEX idx_immunizations_deleted_at ON immunizations(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_immunizations_resource_gin ON immunizations USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS immunizations_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_immunizations_updated_at BEFORE UPDATE ON immunizations
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,67 @@
+-- Location. latitude/longitude hold position for the `near` search. When the
+-- earthdistance extension can be installed, a GiST index over ll_to_earth()
+-- serves near searches; otherwise they fall back to a bounding box on the
+-- plain (latitude, longitude) index followed by an exact distance check
+
+CREATE TABLE IF NOT EXISTS locations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Location',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    name TEXT,
+    status VARCHAR(20),
+    address_city TEXT,
+    address_state TEXT,
+    address_postal_code TEXT,
+    address_country TEXT,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    part_of_id UUID,
+    managing_organization_id UUID,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT locations_resource_type_check CHECK (resource_type = 'Location')
+);
+
+CREATE INDEX idx_locations_name ON locations USING gin(to_tsvector('english', name));
+CREATE INDEX idx_locations_status ON locations(status);
+CREATE INDEX idx_locations_address_city ON locations(address_city);
+CREATE INDEX idx_locations_address_postal_code ON locations(address_postal_code);
+CREATE INDEX idx_locations_position ON locations(latitude, longitude);
+CREATE INDEX idx_locations_part_of_id ON locations(part_of_id);
+CREATE INDEX idx_locations_managing_organization_id ON locations(managing_organization_id);
+CREATE INDEX idx_locations_deleted_at ON locations(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_locations_resource_gin ON locations USING gin(resource);
+
+DO $$
+BEGIN
+    CREATE EXTENSION IF NOT EXISTS cube;
+    CREATE EXTENSION IF NOT EXISTS earthdistance;
+    CREATE INDEX idx_locations_position_earth ON locations
+        USING gist(ll_to_earth(latitude, longitude))
+        WHERE latitude IS NOT NULL AND longitude IS NOT NULL;
+EXCEPTION WHEN OTHERS THEN
+    RAISE NOTICE 'earthdistance unavailable (%); near searches use idx_locations_position', SQLERRM;
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS locations_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_locations_updated_at BEFORE UPDATE ON locations
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
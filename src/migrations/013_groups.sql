@@ -0,0 +1,47 @@
+-- Group. code_code and code_system hold the first coding of `code`;
+-- `member` and `characteristic` are searched through the JSONB resource
+
+CREATE TABLE IF NOT EXISTS groups (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Group',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    name TEXT,
+    type VARCHAR(20) NOT NULL,
+    actual BOOLEAN NOT NULL,
+    active BOOLEAN,
+    code_code TEXT,
+    code_system TEXT,
+    managing_entity_id UUID,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT groups_resource_type_check CHECK (resource_type = 'Group')
+);
+
+CREATE INDEX idx_groups_name ON groups(name);
+CREATE INDEX idx_groups_type ON groups(type);
+CREATE INDEX idx_groups_code_code ON groups(code_code);
+CREATE INDEX idx_groups_managing_entity_id ON groups(managing_entity_id);
+CREATE INDEX idx_groups_deleted_at ON groups(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_groups_resource_gin ON groups USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS groups_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_groups_updated_at BEFORE UPDATE ON groups
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
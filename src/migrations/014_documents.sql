@@ -0,0 +1,89 @@
+-- DocumentReference and Binary. type_code and type_system hold the first
+-- coding of `type`; category, content type and identifiers are searched
+-- through the JSONB resource. patient_id is set when the subject is a Patient
+
+CREATE TABLE IF NOT EXISTS document_references (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'DocumentReference',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- Full FHIR resource as JSONB
+    resource JSONB NOT NULL,
+
+    -- Indexed search parameters
+    patient_id UUID REFERENCES patients(id),
+    subject_reference TEXT,
+    status VARCHAR(20) NOT NULL,
+    type_code TEXT,
+    type_system TEXT,
+    date TIMESTAMP WITH TIME ZONE,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT document_references_resource_type_check CHECK (resource_type = 'DocumentReference')
+);
+
+CREATE INDEX idx_document_references_patient_id ON document_references(patient_id);
+CREATE INDEX idx_document_references_subject_reference ON document_references(subject_reference);
+CREATE INDEX idx_document_references_status ON document_references(status);
+CREATE INDEX idx_document_references_type_code ON document_references(type_code);
+CREATE INDEX idx_document_references_date ON document_references(date);
+CREATE INDEX idx_document_references_deleted_at ON document_references(deleted_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_document_references_resource_gin ON document_references USING gin(resource);
+
+CREATE TABLE IF NOT EXISTS document_references_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_document_references_updated_at BEFORE UPDATE ON document_references
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Binary. The content lives on the blob store under storage_key; the JSONB
+-- resource never carries `data`. size and hash (base64 SHA-1) describe the
+-- stored content
+
+CREATE TABLE IF NOT EXISTS binaries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'Binary',
+    version_id INTEGER NOT NULL DEFAULT 1,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    -- FHIR resource without its data
+    resource JSONB NOT NULL,
+
+    content_type TEXT NOT NULL,
+    security_context TEXT,
+    size BIGINT NOT NULL,
+    hash TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+
+    -- Audit fields
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT binaries_resource_type_check CHECK (resource_type = 'Binary')
+);
+
+CREATE INDEX idx_binaries_deleted_at ON binaries(deleted_at) WHERE deleted_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS binaries_history (
+    id UUID NOT NULL,
+    version_id INTEGER NOT NULL,
+    resource JSONB NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    PRIMARY KEY (id, version_id)
+);
+
+CREATE TRIGGER update_binaries_updated_at BEFORE UPDATE ON binaries
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
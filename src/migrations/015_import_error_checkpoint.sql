@@ -0,0 +1,6 @@
+-- The $import error report is checkpointed with each batch: error_bytes is
+-- the length the error file reaches with the batch's rejected lines, which
+-- pending_errors holds until the next batch. A resumed job rewrites the file
+-- from them, so errors of a batch committed just before a crash are kept
+ALTER TABLE import_jobs ADD COLUMN IF NOT EXISTS error_bytes BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE import_jobs ADD COLUMN IF NOT EXISTS pending_errors TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,455 @@
+// src/repository/allergy_intolerance_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::{AllergyIntolerance, CodeableConcept, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, identifier_filter, insert_history, push_any_of, push_date_filter,
+    push_token_filter, reference_search_id, reference_uuid, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct AllergyIntoleranceRepository {
+    pool: PgPool,
+}
+
+/// Code of the first coding of a status CodeableConcept
+fn status_code(status: &Option<CodeableConcept>) -> Option<String> {
+    status.as_ref()
+        .and_then(|cc| cc.coding.as_ref())
+        .and_then(|codings| codings.first())
+        .and_then(|coding| coding.code.as_ref())
+        .map(|code| code.0.clone())
+}
+
+impl AllergyIntoleranceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, allergy: &AllergyIntolerance) -> AllergyIntoleranceSearchFields {
+        let coding = allergy.code.as_ref()
+            .and_then(|c| c.coding.as_ref())
+            .and_then(|codings| codings.first());
+        AllergyIntoleranceSearchFields {
+            patient_id: reference_uuid(Some(&allergy.patient)),
+            clinical_status: status_code(&allergy.clinical_status),
+            verification_status: status_code(&allergy.verification_status),
+            criticality: allergy.criticality.as_ref().map(|c| c.0.clone()),
+            code_code: coding.and_then(|c| c.code.as_ref()).map(|c| c.0.clone()),
+            code_system: coding.and_then(|c| c.system.as_ref()).map(|s| s.0.clone()),
+            recorded_date: allergy.recorded_date.as_ref().map(|d| d.0),
+        }
+    }
+
+    /// Insert imported allergies as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        allergies: &[AllergyIntolerance],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(allergies.len());
+        for allergy in allergies {
+            rows.push((stored_id(allergy)?, serde_json::to_value(allergy)?, self.extract_search_fields(allergy)));
+        }
+
+        // patient_id references patients, so rows for unknown patients are set aside
+        let patients: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.patient_id).collect();
+        let existing = existing_patients(tx, &patients).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.patient_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 9).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO allergy_intolerances (id, resource, patient_id, clinical_status, verification_status, criticality, code_code, code_system, recorded_date) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.patient_id)
+                    .push_bind(fields.clinical_status)
+                    .push_bind(fields.verification_status)
+                    .push_bind(fields.criticality)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.recorded_date);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "allergy_intolerances", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected allergies from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<AllergyIntolerance>(&self.pool, "allergy_intolerances", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // patient_id references patients; rows pointing at an unknown patient keep their columns
+        let patients: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, allergy)| self.extract_search_fields(allergy).patient_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &patients).await?;
+
+        let mut updated = 0;
+        for (id, allergy) in &rows {
+            let fields = self.extract_search_fields(allergy);
+            if fields.patient_id.is_some_and(|patient| !existing.contains(&patient)) {
+                tracing::warn!("Not reindexing AllergyIntolerance/{}: patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE allergy_intolerances
+                SET patient_id = $2,
+                    clinical_status = $3,
+                    verification_status = $4,
+                    criticality = $5,
+                    code_code = $6,
+                    code_system = $7,
+                    recorded_date = $8
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.patient_id)
+            .bind(fields.clinical_status)
+            .bind(fields.verification_status)
+            .bind(fields.criticality)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.recorded_date)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<AllergyIntolerance>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM allergy_intolerances
+            WHERE patient_id = $1 AND deleted_at IS NULL
+            ORDER BY recorded_date DESC
+            LIMIT 100
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut allergies = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let allergy: AllergyIntolerance = serde_json::from_value(resource_json)?;
+            allergies.push(allergy);
+        }
+
+        Ok(allergies)
+    }
+
+    /// Get allergy history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<AllergyIntolerance>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM allergy_intolerances_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut allergies = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let allergy: AllergyIntolerance = serde_json::from_value(resource_json)?;
+            allergies.push(allergy);
+        }
+
+        Ok(allergies)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<AllergyIntolerance> for AllergyIntoleranceRepository {
+    async fn create(&self, allergy: &AllergyIntolerance) -> FhirResult<AllergyIntolerance> {
+        let mut allergy = allergy.clone();
+
+        let id = Uuid::new_v4().to_string();
+        allergy.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(allergy.meta.as_ref(), 1);
+        allergy.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&allergy);
+        let resource_json = serde_json::to_value(&allergy)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO allergy_intolerances (
+                id, resource, patient_id, clinical_status, verification_status,
+                criticality, code_code, code_system, recorded_date
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.patient_id)
+        .bind(search_fields.clinical_status)
+        .bind(search_fields.verification_status)
+        .bind(search_fields.criticality)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.recorded_date)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO allergy_intolerances_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(allergy)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<AllergyIntolerance>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM allergy_intolerances
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let allergy: AllergyIntolerance = serde_json::from_value(resource_json)?;
+            Ok(Some(allergy))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, allergy: &AllergyIntolerance) -> FhirResult<AllergyIntolerance> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "AllergyIntolerance".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_allergy = allergy.clone();
+        updated_allergy.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_allergy.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_allergy.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_allergy);
+        let resource_json = serde_json::to_value(&updated_allergy)?;
+
+        sqlx::query(
+            r#"
+            UPDATE allergy_intolerances
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                patient_id = $4,
+                clinical_status = $5,
+                verification_status = $6,
+                criticality = $7,
+                code_code = $8,
+                code_system = $9,
+                recorded_date = $10
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.patient_id)
+        .bind(search_fields.clinical_status)
+        .bind(search_fields.verification_status)
+        .bind(search_fields.criticality)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.recorded_date)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO allergy_intolerances_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_allergy)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE allergy_intolerances
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "AllergyIntolerance".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `patient`, `clinical-status`, `verification-status` and
+    /// `criticality` (comma-separated values match any), `category`,
+    /// `code`, `date` (recorded date, with prefixes) and `identifier`,
+    /// plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<AllergyIntolerance>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM allergy_intolerances WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "patient" => {
+                    query.push(" AND patient_id = ").push_bind(reference_search_id(value)?);
+                }
+                "clinical-status" => push_any_of(&mut query, "clinical_status", value),
+                "verification-status" => push_any_of(&mut query, "verification_status", value),
+                "criticality" => push_any_of(&mut query, "criticality", value),
+                "category" => {
+                    query.push(" AND resource @> ")
+                        .push_bind(serde_json::json!({ "category": [value] }));
+                }
+                "code" => push_token_filter(&mut query, "code_code", "code_system", value),
+                "date" => push_date_filter(&mut query, "recorded_date", value)?,
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut allergies = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let allergy: AllergyIntolerance = serde_json::from_value(resource_json)?;
+            allergies.push(allergy);
+        }
+
+        Ok(allergies)
+    }
+}
+
+struct AllergyIntoleranceSearchFields {
+    patient_id: Option<Uuid>,
+    clinical_status: Option<String>,
+    verification_status: Option<String>,
+    criticality: Option<String>,
+    code_code: Option<String>,
+    code_system: Option<String>,
+    recorded_date: Option<chrono::DateTime<Utc>>,
+}
@@ -0,0 +1,239 @@
+// src/repository/binary_repository.rs
+
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::{Binary, Id, Meta, FhirError, FhirResult};
+use crate::domain::resources::Resource;
+use super::blob_store::StoredBlob;
+
+/// A stored Binary and the blob holding its content
+#[derive(Debug, Clone)]
+pub struct StoredBinary {
+    pub binary: Binary,
+    pub blob: StoredBlob,
+}
+
+/// Binary rows hold the resource without its `data` and the key of its
+/// content on the blob store. Only the current content is kept, so history
+/// versions carry metadata alone
+pub struct BinaryRepository {
+    pool: PgPool,
+}
+
+impl BinaryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, binary: &Binary, blob: &StoredBlob) -> FhirResult<Binary> {
+        let mut binary = binary.clone();
+        binary.data = None;
+
+        let id = Uuid::new_v4().to_string();
+        binary.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(binary.meta.as_ref(), 1);
+        binary.set_meta(meta);
+
+        let resource_json = serde_json::to_value(&binary)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO binaries (
+                id, resource, content_type, security_context, size, hash, storage_key
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(&binary.content_type.0)
+        .bind(security_context(&binary))
+        .bind(blob.size as i64)
+        .bind(&blob.hash)
+        .bind(&blob.key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO binaries_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(binary)
+    }
+
+    pub async fn read(&self, id: &str) -> FhirResult<Option<StoredBinary>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource, size, hash, storage_key
+            FROM binaries
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let resource_json: serde_json::Value = row.try_get("resource")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        let size: i64 = row.try_get("size")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        let blob = StoredBlob {
+            key: row.try_get("storage_key").map_err(|e| FhirError::Database(e.to_string()))?,
+            size: size as u64,
+            hash: row.try_get("hash").map_err(|e| FhirError::Database(e.to_string()))?,
+        };
+        Ok(Some(StoredBinary { binary: serde_json::from_value(resource_json)?, blob }))
+    }
+
+    /// Replace a Binary and its content, returning the new version and the
+    /// key of the content it replaced
+    pub async fn update(&self, id: &str, binary: &Binary, blob: &StoredBlob) -> FhirResult<(Binary, String)> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Binary".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.binary.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_binary = binary.clone();
+        updated_binary.data = None;
+        updated_binary.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_binary.meta.as_ref().or(current.binary.meta.as_ref()), new_version);
+        updated_binary.set_meta(meta);
+
+        let resource_json = serde_json::to_value(&updated_binary)?;
+
+        sqlx::query(
+            r#"
+            UPDATE binaries
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                content_type = $4,
+                security_context = $5,
+                size = $6,
+                hash = $7,
+                storage_key = $8
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(&updated_binary.content_type.0)
+        .bind(security_context(&updated_binary))
+        .bind(blob.size as i64)
+        .bind(&blob.hash)
+        .bind(&blob.key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO binaries_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok((updated_binary, current.blob.key))
+    }
+
+    /// Soft delete a Binary, returning the key of its content
+    pub async fn delete(&self, id: &str) -> FhirResult<String> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let key: Option<String> = sqlx::query_scalar(
+            r#"
+            UPDATE binaries
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING storage_key
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        key.ok_or_else(|| FhirError::NotFound {
+            resource_type: "Binary".to_string(),
+            id: id.to_string(),
+        })
+    }
+
+    /// Get binary history (all versions, without content)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Binary>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM binaries_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut binaries = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let binary: Binary = serde_json::from_value(resource_json)?;
+            binaries.push(binary);
+        }
+
+        Ok(binaries)
+    }
+}
+
+fn security_context(binary: &Binary) -> Option<&str> {
+    binary.security_context.as_ref()
+        .and_then(|context| context.reference.as_ref())
+        .map(|reference| reference.0.as_str())
+}
@@ -0,0 +1,207 @@
+// src/repository/blob_store.rs
+
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use sha1::{Digest, Sha1};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use crate::domain::errors::{FhirError, FhirResult};
+
+/// Content read from or written to a blob store, chunk by chunk
+pub type BlobStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+/// Where a blob was stored, with its size and base64 SHA-1 hash as FHIR
+/// `Attachment` records them
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredBlob {
+    pub key: String,
+    pub size: u64,
+    pub hash: String,
+}
+
+/// Storage for Binary content, kept outside the database
+#[async_trait::async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Store `content` under a new key. Content longer than `max_size` bytes
+    /// is refused and nothing is kept
+    async fn put(&self, content: BlobStream, max_size: u64) -> FhirResult<StoredBlob>;
+
+    /// Stream a stored blob, or the bytes of it within `range`
+    async fn get(&self, key: &str, range: Option<Range<u64>>) -> FhirResult<BlobStream>;
+
+    /// Remove a stored blob. Removing a missing blob is not an error
+    async fn delete(&self, key: &str) -> FhirResult<()>;
+}
+
+/// Blob store on the local filesystem, one file per blob
+pub struct LocalBlobStore {
+    dir: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Keys are generated by `put`, so anything but a UUID did not come from here
+    fn path(&self, key: &str) -> FhirResult<PathBuf> {
+        let key = Uuid::parse_str(key)
+            .map_err(|_| FhirError::Storage(format!("Invalid blob key: {}", key)))?;
+        Ok(self.dir.join(key.to_string()))
+    }
+
+    async fn write(&self, path: &std::path::Path, mut content: BlobStream, max_size: u64) -> FhirResult<(u64, String)> {
+        let mut file = tokio::fs::File::create(path).await.map_err(storage_error)?;
+        let mut digest = ContentDigest::new(max_size);
+        while let Some(chunk) = content.next().await {
+            let chunk = chunk.map_err(storage_error)?;
+            digest.update(&chunk)?;
+            file.write_all(&chunk).await.map_err(storage_error)?;
+        }
+        file.sync_all().await.map_err(storage_error)?;
+        Ok(digest.finish())
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, content: BlobStream, max_size: u64) -> FhirResult<StoredBlob> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(storage_error)?;
+
+        // Written under a temporary name so a failed upload never leaves a
+        // partial blob behind its key
+        let key = Uuid::new_v4().to_string();
+        let path = self.path(&key)?;
+        let partial = path.with_extension("part");
+        let (size, hash) = match self.write(&partial, content, max_size).await {
+            Ok(written) => written,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(e);
+            }
+        };
+        tokio::fs::rename(&partial, &path).await.map_err(storage_error)?;
+
+        Ok(StoredBlob { key, size, hash })
+    }
+
+    async fn get(&self, key: &str, range: Option<Range<u64>>) -> FhirResult<BlobStream> {
+        let mut file = tokio::fs::File::open(self.path(key)?).await.map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => FhirError::Storage(format!("Blob {} is missing", key)),
+            _ => storage_error(e),
+        })?;
+        match range {
+            Some(range) => {
+                file.seek(io::SeekFrom::Start(range.start)).await.map_err(storage_error)?;
+                let len = range.end.saturating_sub(range.start);
+                Ok(Box::pin(ReaderStream::new(file.take(len))))
+            }
+            None => Ok(Box::pin(ReaderStream::new(file))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> FhirResult<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+}
+
+fn storage_error(e: io::Error) -> FhirError {
+    FhirError::Storage(e.to_string())
+}
+
+/// Running size and SHA-1 of content being stored, enforcing the size limit
+struct ContentDigest {
+    size: u64,
+    max_size: u64,
+    sha1: Sha1,
+}
+
+impl ContentDigest {
+    fn new(max_size: u64) -> Self {
+        Self { size: 0, max_size, sha1: Sha1::new() }
+    }
+
+    fn update(&mut self, chunk: &[u8]) -> FhirResult<()> {
+        self.size += chunk.len() as u64;
+        if self.size > self.max_size {
+            return Err(FhirError::PayloadTooLarge(format!(
+                "Content exceeds the {} byte limit",
+                self.max_size
+            )));
+        }
+        self.sha1.update(chunk);
+        Ok(())
+    }
+
+    /// Size and base64 SHA-1 hash of the content
+    fn finish(self) -> (u64, String) {
+        let hash = base64::engine::general_purpose::STANDARD.encode(self.sha1.finalize());
+        (self.size, hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(chunks: &[&'static [u8]]) -> BlobStream {
+        let chunks: Vec<io::Result<Bytes>> = chunks.iter().map(|c| Ok(Bytes::from_static(c))).collect();
+        Box::pin(futures_util::stream::iter(chunks))
+    }
+
+    async fn read_all(mut stream: BlobStream) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+        }
+        bytes
+    }
+
+    fn store() -> LocalBlobStore {
+        LocalBlobStore::new(std::env::temp_dir().join(format!("blob-store-test-{}", Uuid::new_v4())))
+    }
+
+    #[tokio::test]
+    async fn test_put_records_size_and_sha1() {
+        let store = store();
+        let blob = store.put(content(&[b"hello ", b"world"]), 1024).await.unwrap();
+
+        assert_eq!(blob.size, 11);
+        // base64 of SHA-1("hello world")
+        assert_eq!(blob.hash, "Kq5sNclPz7QV2+lfQIuc6R7oRu0=");
+        assert_eq!(read_all(store.get(&blob.key, None).await.unwrap()).await, b"hello world");
+        assert_eq!(read_all(store.get(&blob.key, Some(3..8)).await.unwrap()).await, b"lo wo");
+
+        store.delete(&blob.key).await.unwrap();
+        assert!(store.get(&blob.key, None).await.is_err());
+        let _ = std::fs::remove_dir_all(&store.dir);
+    }
+
+    #[tokio::test]
+    async fn test_put_refuses_oversized_content_and_keeps_nothing() {
+        let store = store();
+        let result = store.put(content(&[b"0123456789", b"0123456789"]), 15).await;
+
+        assert!(matches!(result, Err(FhirError::PayloadTooLarge(_))));
+        assert_eq!(std::fs::read_dir(&store.dir).unwrap().count(), 0);
+        let _ = std::fs::remove_dir_all(&store.dir);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_keys_it_did_not_issue() {
+        let store = store();
+        assert!(matches!(store.get("../secrets", None).await, Err(FhirError::Storage(_))));
+    }
+}
@@ -1,11 +1,14 @@
 // src/repository/condition_repository.rs
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
 use chrono::Utc;
 
-use crate::domain::{Condition, Id, Meta, Instant, FhirError, FhirResult};
-use super::{Repository, SearchParams};
+use crate::domain::{Condition, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, insert_history, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
 use crate::domain::resources::condition::ConditionOnset;
 use crate::domain::resources::Resource;
 
@@ -57,7 +60,144 @@ impl ConditionRepository {
             recorded_date: condition.recorded_date.as_ref().map(|d| d.0),
         }
     }
+
+    /// Insert imported conditions as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        conditions: &[Condition],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(conditions.len());
+        for condition in conditions {
+            rows.push((stored_id(condition)?, serde_json::to_value(condition)?, self.extract_search_fields(condition)));
+        }
+
+        // subject_id references patients, so rows for unknown patients are set aside
+        let subjects: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.subject_id).collect();
+        let existing = existing_patients(tx, &subjects).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.subject_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 10).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO conditions (id, resource, subject_id, clinical_status, verification_status, category_code, code_code, code_system, onset_datetime, recorded_date) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.subject_id)
+                    .push_bind(fields.clinical_status)
+                    .push_bind(fields.verification_status)
+                    .push_bind(fields.category_code)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.onset_datetime)
+                    .push_bind(fields.recorded_date);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "conditions", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected conditions from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Condition>(&self.pool, "conditions", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // subject_id references patients; rows pointing at an unknown patient keep their columns
+        let subjects: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, condition)| self.extract_search_fields(condition).subject_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &subjects).await?;
+
+        let mut updated = 0;
+        for (id, condition) in &rows {
+            let fields = self.extract_search_fields(condition);
+            if fields.subject_id.is_some_and(|subject| !existing.contains(&subject)) {
+                tracing::warn!("Not reindexing Condition/{}: subject patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE conditions
+                SET subject_id = $2,
+                    clinical_status = $3,
+                    verification_status = $4,
+                    category_code = $5,
+                    code_code = $6,
+                    code_system = $7,
+                    onset_datetime = $8,
+                    recorded_date = $9
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.subject_id)
+            .bind(fields.clinical_status)
+            .bind(fields.verification_status)
+            .bind(fields.category_code)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.onset_datetime)
+            .bind(fields.recorded_date)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
     
+    /// Conditions recorded during an encounter (`encounter` reference)
+    pub async fn search_by_encounter(&self, encounter_id: &str) -> FhirResult<Vec<Condition>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM conditions
+            WHERE resource @> jsonb_build_object(
+                'encounter', jsonb_build_object('reference', 'Encounter/' || $1)
+            )
+            AND deleted_at IS NULL
+            ORDER BY onset_datetime DESC
+            "#
+        )
+        .bind(encounter_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut conditions = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let condition: Condition = serde_json::from_value(resource_json)?;
+            conditions.push(condition);
+        }
+
+        Ok(conditions)
+    }
+
     pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<Condition>> {
         let uuid = Uuid::parse_str(patient_id)
             .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
@@ -122,14 +262,7 @@ impl Repository<Condition> for ConditionRepository {
         let id = Uuid::new_v4().to_string();
         cond.set_id(Id(id.clone()));
         
-        let meta = Meta {
-            version_id: Some(Id("1".to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(cond.meta.as_ref(), 1);
         cond.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&cond);
@@ -213,8 +346,8 @@ impl Repository<Condition> for ConditionRepository {
                 id: id.to_string(),
             })?;
         
-        let current_version = current.meta
-            .and_then(|m| m.version_id)
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
             .and_then(|v| v.0.parse::<i32>().ok())
             .unwrap_or(1);
         
@@ -223,14 +356,7 @@ impl Repository<Condition> for ConditionRepository {
         let mut updated_cond = condition.clone();
         updated_cond.set_id(Id(id.to_string()));
         
-        let meta = Meta {
-            version_id: Some(Id(new_version.to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(updated_cond.meta.as_ref().or(current.meta.as_ref()), new_version);
         updated_cond.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&updated_cond);
@@ -315,20 +441,18 @@ impl Repository<Condition> for ConditionRepository {
         let limit = params.limit.unwrap_or(100);
         let offset = params.offset.unwrap_or(0);
         
-        let rows = sqlx::query(
-            r#"
-            SELECT resource
-            FROM conditions
-            WHERE deleted_at IS NULL
-            ORDER BY last_updated DESC
-            LIMIT $1 OFFSET $2
-            "#
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| FhirError::Database(e.to_string()))?;
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM conditions WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
         
         let mut conditions = Vec::new();
         for row in rows {
@@ -0,0 +1,454 @@
+// src/repository/document_reference_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::{DocumentReference, Id, Meta, FhirError, FhirResult};
+use super::{
+    codeable_concept_filter, existing_patients, identifier_filter, insert_history, push_any_of,
+    push_date_filter, push_token_filter, reference_search_id, reference_uuid, push_meta_filter, stored_id,
+    stored_rows, BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct DocumentReferenceRepository {
+    pool: PgPool,
+}
+
+impl DocumentReferenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, document: &DocumentReference) -> DocumentReferenceSearchFields {
+        let coding = document.type_.as_ref()
+            .and_then(|type_| type_.coding.as_ref())
+            .and_then(|codings| codings.first());
+        let subject_reference = document.subject.as_ref()
+            .and_then(|subject| subject.reference.as_ref())
+            .map(|reference| reference.0.clone());
+        // Only Patient subjects go in the patient compartment
+        let patient_id = match &subject_reference {
+            Some(reference) if reference.starts_with("Patient/") => reference_uuid(document.subject.as_ref()),
+            _ => None,
+        };
+        DocumentReferenceSearchFields {
+            patient_id,
+            subject_reference,
+            status: document.status.0.clone(),
+            type_code: coding.and_then(|c| c.code.as_ref()).map(|c| c.0.clone()),
+            type_system: coding.and_then(|c| c.system.as_ref()).map(|s| s.0.clone()),
+            date: document.date.as_ref().map(|date| date.0),
+        }
+    }
+
+    /// Insert imported document references as version 1 in multi-row
+    /// statements, with the same search columns as `create`. Ids that already
+    /// exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        documents: &[DocumentReference],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(documents.len());
+        for document in documents {
+            rows.push((stored_id(document)?, serde_json::to_value(document)?, self.extract_search_fields(document)));
+        }
+
+        // patient_id references patients, so rows for unknown patients are set aside
+        let patients: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.patient_id).collect();
+        let existing = existing_patients(tx, &patients).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.patient_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 8).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO document_references (id, resource, patient_id, subject_reference, status, type_code, type_system, date) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.patient_id)
+                    .push_bind(fields.subject_reference)
+                    .push_bind(fields.status)
+                    .push_bind(fields.type_code)
+                    .push_bind(fields.type_system)
+                    .push_bind(fields.date);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "document_references", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected document references from
+    /// their stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<DocumentReference>(&self.pool, "document_references", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // patient_id references patients; rows pointing at an unknown patient keep their columns
+        let patients: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, document)| self.extract_search_fields(document).patient_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &patients).await?;
+
+        let mut updated = 0;
+        for (id, document) in &rows {
+            let fields = self.extract_search_fields(document);
+            if fields.patient_id.is_some_and(|patient| !existing.contains(&patient)) {
+                tracing::warn!("Not reindexing DocumentReference/{}: patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE document_references
+                SET patient_id = $2,
+                    subject_reference = $3,
+                    status = $4,
+                    type_code = $5,
+                    type_system = $6,
+                    date = $7
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.patient_id)
+            .bind(fields.subject_reference)
+            .bind(fields.status)
+            .bind(fields.type_code)
+            .bind(fields.type_system)
+            .bind(fields.date)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<DocumentReference>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM document_references
+            WHERE patient_id = $1 AND deleted_at IS NULL
+            ORDER BY date DESC
+            LIMIT 100
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut documents = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let document: DocumentReference = serde_json::from_value(resource_json)?;
+            documents.push(document);
+        }
+
+        Ok(documents)
+    }
+
+    /// Get document reference history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<DocumentReference>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM document_references_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut documents = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let document: DocumentReference = serde_json::from_value(resource_json)?;
+            documents.push(document);
+        }
+
+        Ok(documents)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<DocumentReference> for DocumentReferenceRepository {
+    async fn create(&self, document: &DocumentReference) -> FhirResult<DocumentReference> {
+        let mut document = document.clone();
+
+        let id = Uuid::new_v4().to_string();
+        document.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(document.meta.as_ref(), 1);
+        document.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&document);
+        let resource_json = serde_json::to_value(&document)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO document_references (
+                id, resource, patient_id, subject_reference, status,
+                type_code, type_system, date
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.patient_id)
+        .bind(search_fields.subject_reference)
+        .bind(search_fields.status)
+        .bind(search_fields.type_code)
+        .bind(search_fields.type_system)
+        .bind(search_fields.date)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO document_references_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(document)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<DocumentReference>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM document_references
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let document: DocumentReference = serde_json::from_value(resource_json)?;
+            Ok(Some(document))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, document: &DocumentReference) -> FhirResult<DocumentReference> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "DocumentReference".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_document = document.clone();
+        updated_document.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_document.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_document.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_document);
+        let resource_json = serde_json::to_value(&updated_document)?;
+
+        sqlx::query(
+            r#"
+            UPDATE document_references
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                patient_id = $4,
+                subject_reference = $5,
+                status = $6,
+                type_code = $7,
+                type_system = $8,
+                date = $9
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.patient_id)
+        .bind(search_fields.subject_reference)
+        .bind(search_fields.status)
+        .bind(search_fields.type_code)
+        .bind(search_fields.type_system)
+        .bind(search_fields.date)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO document_references_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_document)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE document_references
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "DocumentReference".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `patient`, `subject`, `status` (comma-separated values match
+    /// any), `type`, `category`, `date` (with prefixes), `identifier` (also
+    /// matching `masterIdentifier`) and `contenttype`, plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<DocumentReference>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM document_references WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "patient" => {
+                    query.push(" AND patient_id = ").push_bind(reference_search_id(value)?);
+                }
+                "subject" => {
+                    query.push(" AND subject_reference = ").push_bind(value.to_string());
+                }
+                "status" => push_any_of(&mut query, "status", value),
+                "type" => push_token_filter(&mut query, "type_code", "type_system", value),
+                "category" => {
+                    query.push(" AND resource @> ").push_bind(codeable_concept_filter("category", value));
+                }
+                "date" => push_date_filter(&mut query, "date", value)?,
+                "identifier" => {
+                    let identifier = identifier_filter(value);
+                    let master = serde_json::json!({ "masterIdentifier": identifier["identifier"][0] });
+                    query.push(" AND (resource @> ").push_bind(identifier)
+                        .push(" OR resource @> ").push_bind(master)
+                        .push(")");
+                }
+                "contenttype" => {
+                    query.push(" AND resource @> ")
+                        .push_bind(serde_json::json!({ "content": [{ "attachment": { "contentType": value } }] }));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut documents = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let document: DocumentReference = serde_json::from_value(resource_json)?;
+            documents.push(document);
+        }
+
+        Ok(documents)
+    }
+}
+
+struct DocumentReferenceSearchFields {
+    patient_id: Option<Uuid>,
+    subject_reference: Option<String>,
+    status: String,
+    type_code: Option<String>,
+    type_system: Option<String>,
+    date: Option<chrono::DateTime<Utc>>,
+}
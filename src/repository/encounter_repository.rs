@@ -1,11 +1,14 @@
 // src/repository/encounter_repository.rs
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
 use chrono::Utc;
 
-use crate::domain::{Encounter, Id, Meta, Instant, FhirError, FhirResult};
-use super::{Repository, SearchParams};
+use crate::domain::{Encounter, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, insert_history, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
 use crate::domain::resources::Resource;
 
 pub struct EncounterRepository {
@@ -34,6 +37,105 @@ impl EncounterRepository {
                 .map(|e| e.0),
         }
     }
+
+    /// Insert imported encounters as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        encounters: &[Encounter],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(encounters.len());
+        for encounter in encounters {
+            rows.push((stored_id(encounter)?, serde_json::to_value(encounter)?, self.extract_search_fields(encounter)));
+        }
+
+        // subject_id references patients, so rows for unknown patients are set aside
+        let subjects: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.subject_id).collect();
+        let existing = existing_patients(tx, &subjects).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.subject_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 7).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO encounters (id, resource, status, class_code, subject_id, period_start, period_end) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.status)
+                    .push_bind(fields.class_code)
+                    .push_bind(fields.subject_id)
+                    .push_bind(fields.period_start)
+                    .push_bind(fields.period_end);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "encounters", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected encounters from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Encounter>(&self.pool, "encounters", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // subject_id references patients; rows pointing at an unknown patient keep their columns
+        let subjects: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, encounter)| self.extract_search_fields(encounter).subject_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &subjects).await?;
+
+        let mut updated = 0;
+        for (id, encounter) in &rows {
+            let fields = self.extract_search_fields(encounter);
+            if fields.subject_id.is_some_and(|subject| !existing.contains(&subject)) {
+                tracing::warn!("Not reindexing Encounter/{}: subject patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE encounters
+                SET status = $2,
+                    class_code = $3,
+                    subject_id = $4,
+                    period_start = $5,
+                    period_end = $6
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.status)
+            .bind(fields.class_code)
+            .bind(fields.subject_id)
+            .bind(fields.period_start)
+            .bind(fields.period_end)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
     
     pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<Encounter>> {
         let uuid = Uuid::parse_str(patient_id)
@@ -99,14 +201,7 @@ impl Repository<Encounter> for EncounterRepository {
         let id = Uuid::new_v4().to_string();
         enc.set_id(Id(id.clone()));
         
-        let meta = Meta {
-            version_id: Some(Id("1".to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(enc.meta.as_ref(), 1);
         enc.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&enc);
@@ -187,8 +282,8 @@ impl Repository<Encounter> for EncounterRepository {
                 id: id.to_string(),
             })?;
         
-        let current_version = current.meta
-            .and_then(|m| m.version_id)
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
             .and_then(|v| v.0.parse::<i32>().ok())
             .unwrap_or(1);
         
@@ -197,14 +292,7 @@ impl Repository<Encounter> for EncounterRepository {
         let mut updated_enc = encounter.clone();
         updated_enc.set_id(Id(id.to_string()));
         
-        let meta = Meta {
-            version_id: Some(Id(new_version.to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(updated_enc.meta.as_ref().or(current.meta.as_ref()), new_version);
         updated_enc.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&updated_enc);
@@ -283,20 +371,18 @@ impl Repository<Encounter> for EncounterRepository {
         let limit = params.limit.unwrap_or(100);
         let offset = params.offset.unwrap_or(0);
         
-        let rows = sqlx::query(
-            r#"
-            SELECT resource
-            FROM encounters
-            WHERE deleted_at IS NULL
-            ORDER BY last_updated DESC
-            LIMIT $1 OFFSET $2
-            "#
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| FhirError::Database(e.to_string()))?;
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM encounters WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
         
         let mut encounters = Vec::new();
         for row in rows {
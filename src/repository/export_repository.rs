@@ -0,0 +1,153 @@
+// src/repository/export_repository.rs
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+
+use crate::domain::{FhirError, FhirResult};
+use super::{patient_column, push_meta_filter, resource_table};
+
+/// Search parameters `_typeFilter` may use, per resource type, with the
+/// search column each one is matched against. Every exportable type has at
+/// least one; boolean columns are compared as text
+pub const TYPE_FILTER_COLUMNS: &[(&str, &str, &str)] = &[
+    ("Patient", "gender", "gender"),
+    ("Patient", "active", "active::text"),
+    ("Observation", "status", "status"),
+    ("Observation", "code", "code_code"),
+    ("Observation", "category", "category_code"),
+    ("Condition", "clinical-status", "clinical_status"),
+    ("Condition", "verification-status", "verification_status"),
+    ("Condition", "code", "code_code"),
+    ("Condition", "category", "category_code"),
+    ("Encounter", "status", "status"),
+    ("Encounter", "class", "class_code"),
+    ("Practitioner", "gender", "gender"),
+    ("Practitioner", "active", "active::text"),
+    ("PractitionerRole", "active", "active::text"),
+    ("Organization", "active", "active::text"),
+    ("Medication", "status", "status"),
+    ("Medication", "code", "code_code"),
+    ("MedicationRequest", "status", "status"),
+    ("MedicationRequest", "intent", "intent"),
+    ("MedicationRequest", "code", "code_code"),
+    ("MedicationStatement", "status", "status"),
+    ("MedicationStatement", "code", "code_code"),
+    ("AllergyIntolerance", "clinical-status", "clinical_status"),
+    ("AllergyIntolerance", "verification-status", "verification_status"),
+    ("AllergyIntolerance", "criticality", "criticality"),
+    ("AllergyIntolerance", "code", "code_code"),
+    ("Procedure", "status", "status"),
+    ("Procedure", "code", "code_code"),
+    ("DiagnosticReport", "status", "status"),
+    ("DiagnosticReport", "category", "category_code"),
+    ("DiagnosticReport", "code", "code_code"),
+    ("Immunization", "status", "status"),
+    ("Immunization", "vaccine-code", "vaccine_code_code"),
+    ("Location", "status", "status"),
+    ("Group", "type", "type"),
+    ("Group", "code", "code_code"),
+    ("Group", "actual", "actual::text"),
+    ("Group", "active", "active::text"),
+    ("DocumentReference", "status", "status"),
+    ("DocumentReference", "type", "type_code"),
+];
+
+/// Which patients' records an export reads
+#[derive(Debug, Clone)]
+pub enum ExportCompartment {
+    /// Every resource (system-level export)
+    All,
+    /// Resources in any patient compartment (`Patient/$export`)
+    AllPatients,
+    /// Resources in the compartments of these patients
+    Patients(Vec<Uuid>),
+}
+
+/// One `_typeFilter` query. Every condition must hold: each column must equal
+/// one of its values, and the resource must match the meta filter (see
+/// `SearchParams::meta_filter`)
+#[derive(Debug, Clone)]
+pub struct ExportFilter {
+    pub columns: Vec<(&'static str, Vec<String>)>,
+    pub meta: Vec<Vec<serde_json::Value>>,
+}
+
+/// Reads resources in id order, one page at a time, for Bulk Data `$export`
+#[derive(Clone)]
+pub struct ExportRepository {
+    pool: PgPool,
+}
+
+impl ExportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The next page of resources after `after`, as stored JSON. A resource
+    /// passes when it matches any of `filters` (or there are none)
+    pub async fn fetch_page(
+        &self,
+        resource_type: &str,
+        compartment: &ExportCompartment,
+        since: Option<DateTime<Utc>>,
+        filters: &[ExportFilter],
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> FhirResult<Vec<(Uuid, serde_json::Value)>> {
+        let table = resource_table(resource_type)?;
+        let patient_column = patient_column(table);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT id, resource FROM {table} WHERE deleted_at IS NULL"
+        ));
+        if let Some(since) = since {
+            query.push(" AND last_updated >= ").push_bind(since);
+        }
+        match compartment {
+            ExportCompartment::All => {}
+            ExportCompartment::AllPatients => {
+                query.push(format!(" AND {patient_column} IS NOT NULL"));
+            }
+            ExportCompartment::Patients(ids) => {
+                query.push(format!(" AND {patient_column} = ANY(")).push_bind(ids.clone()).push(")");
+            }
+        }
+        if !filters.is_empty() {
+            query.push(" AND (");
+            for (i, filter) in filters.iter().enumerate() {
+                if i > 0 {
+                    query.push(" OR ");
+                }
+                query.push("(TRUE");
+                push_meta_filter(&mut query, &filter.meta);
+                for (column, values) in &filter.columns {
+                    query.push(format!(" AND {column} = ANY(")).push_bind(values.clone()).push(")");
+                }
+                query.push(")");
+            }
+            query.push(")");
+        }
+        if let Some(after) = after {
+            query.push(" AND id > ").push_bind(after);
+        }
+        query.push(" ORDER BY id LIMIT ").push_bind(limit);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut page = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id: Uuid = row.try_get("id")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let resource: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            page.push((id, resource));
+        }
+
+        Ok(page)
+    }
+}
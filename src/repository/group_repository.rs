@@ -0,0 +1,415 @@
+// src/repository/group_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{Group, Id, Meta, FhirError, FhirResult};
+use super::{
+    identifier_filter, insert_history, push_any_of, push_token_filter, reference_search_id,
+    reference_uuid, push_meta_filter, stored_id, stored_rows, token_coding, BatchInsert, ReindexPage, ReindexSelection,
+    Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct GroupRepository {
+    pool: PgPool,
+}
+
+impl GroupRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, group: &Group) -> GroupSearchFields {
+        let coding = group.code.as_ref()
+            .and_then(|c| c.coding.as_ref())
+            .and_then(|codings| codings.first());
+        GroupSearchFields {
+            name: group.name.as_ref().map(|n| n.0.clone()),
+            type_: group.type_.0.clone(),
+            actual: group.actual.0,
+            active: group.active.as_ref().map(|a| a.0),
+            code_code: coding.and_then(|c| c.code.as_ref()).map(|c| c.0.clone()),
+            code_system: coding.and_then(|c| c.system.as_ref()).map(|s| s.0.clone()),
+            managing_entity_id: reference_uuid(group.managing_entity.as_ref()),
+        }
+    }
+
+    /// Insert imported groups as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        groups: &[Group],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(groups.len());
+        for group in groups {
+            rows.push((stored_id(group)?, serde_json::to_value(group)?, self.extract_search_fields(group)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 9).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO groups (id, resource, name, type, actual, active, code_code, code_system, managing_entity_id) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.name)
+                    .push_bind(fields.type_)
+                    .push_bind(fields.actual)
+                    .push_bind(fields.active)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.managing_entity_id);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "groups", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected groups from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Group>(&self.pool, "groups", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, group) in &rows {
+            let fields = self.extract_search_fields(group);
+            sqlx::query(
+                r#"
+                UPDATE groups
+                SET name = $2,
+                    type = $3,
+                    actual = $4,
+                    active = $5,
+                    code_code = $6,
+                    code_system = $7,
+                    managing_entity_id = $8
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.name)
+            .bind(fields.type_)
+            .bind(fields.actual)
+            .bind(fields.active)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.managing_entity_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    /// Get group history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Group>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM groups_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut groups = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let group: Group = serde_json::from_value(resource_json)?;
+            groups.push(group);
+        }
+
+        Ok(groups)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Group> for GroupRepository {
+    async fn create(&self, group: &Group) -> FhirResult<Group> {
+        let mut group = group.clone();
+
+        let id = Uuid::new_v4().to_string();
+        group.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(group.meta.as_ref(), 1);
+        group.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&group);
+        let resource_json = serde_json::to_value(&group)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO groups (
+                id, resource, name, type, actual, active, code_code, code_system, managing_entity_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.name)
+        .bind(search_fields.type_)
+        .bind(search_fields.actual)
+        .bind(search_fields.active)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.managing_entity_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO groups_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(group)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<Group>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM groups
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let group: Group = serde_json::from_value(resource_json)?;
+            Ok(Some(group))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, group: &Group) -> FhirResult<Group> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Group".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_group = group.clone();
+        updated_group.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_group.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_group.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_group);
+        let resource_json = serde_json::to_value(&updated_group)?;
+
+        sqlx::query(
+            r#"
+            UPDATE groups
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                name = $4,
+                type = $5,
+                actual = $6,
+                active = $7,
+                code_code = $8,
+                code_system = $9,
+                managing_entity_id = $10
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.name)
+        .bind(search_fields.type_)
+        .bind(search_fields.actual)
+        .bind(search_fields.active)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.managing_entity_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO groups_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_group)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE groups
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Group".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `name` (contains, case-insensitive), `type` (comma-separated
+    /// values match any), `actual`, `code`, `characteristic`,
+    /// `managing-entity`, `member` and `identifier`, plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Group>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM groups WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "name" => {
+                    query.push(" AND name ILIKE ").push_bind(format!("%{}%", value));
+                }
+                "type" => push_any_of(&mut query, "type", value),
+                "actual" => {
+                    query.push(" AND actual = ").push_bind(value == "true");
+                }
+                "code" => push_token_filter(&mut query, "code_code", "code_system", value),
+                "characteristic" => {
+                    query.push(" AND resource @> ").push_bind(serde_json::json!({
+                        "characteristic": [{ "code": { "coding": [token_coding(value)] } }]
+                    }));
+                }
+                "managing-entity" => {
+                    query.push(" AND managing_entity_id = ").push_bind(reference_search_id(value)?);
+                }
+                "member" => push_member_filter(&mut query, value),
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut groups = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let group: Group = serde_json::from_value(resource_json)?;
+            groups.push(group);
+        }
+
+        Ok(groups)
+    }
+}
+
+struct GroupSearchFields {
+    name: Option<String>,
+    type_: String,
+    actual: bool,
+    active: Option<bool>,
+    code_code: Option<String>,
+    code_system: Option<String>,
+    managing_entity_id: Option<Uuid>,
+}
+
+/// Restrict to groups listing `value` in `member.entity`. `Type/id` is
+/// matched exactly through the GIN index; a bare id matches any type
+fn push_member_filter(query: &mut QueryBuilder<Postgres>, value: &str) {
+    if value.contains('/') {
+        query.push(" AND resource @> ").push_bind(serde_json::json!({
+            "member": [{ "entity": { "reference": value } }]
+        }));
+    } else {
+        query.push(" AND EXISTS (SELECT 1 FROM jsonb_array_elements(resource->'member') member")
+            .push(" WHERE member#>>'{entity,reference}' LIKE ").push_bind(format!("%/{}", value))
+            .push(")");
+    }
+}
@@ -0,0 +1,427 @@
+// src/repository/immunization_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::{Immunization, Id, Meta, FhirError, FhirResult};
+use crate::domain::resources::immunization::ImmunizationOccurrence;
+use super::{
+    existing_patients, identifier_filter, insert_history, push_any_of, push_date_filter,
+    push_token_filter, reference_search_id, reference_uuid, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct ImmunizationRepository {
+    pool: PgPool,
+}
+
+impl ImmunizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, immunization: &Immunization) -> ImmunizationSearchFields {
+        let coding = immunization.vaccine_code.coding.as_ref()
+            .and_then(|codings| codings.first());
+        let occurrence_datetime = match &immunization.occurrence {
+            ImmunizationOccurrence::DateTime(dt) => Some(dt.0),
+            ImmunizationOccurrence::String(_) => None,
+        };
+        ImmunizationSearchFields {
+            patient_id: reference_uuid(Some(&immunization.patient)),
+            status: immunization.status.0.clone(),
+            vaccine_code_code: coding.and_then(|c| c.code.as_ref()).map(|c| c.0.clone()),
+            vaccine_code_system: coding.and_then(|c| c.system.as_ref()).map(|s| s.0.clone()),
+            occurrence_datetime,
+        }
+    }
+
+    /// Insert imported immunizations as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        immunizations: &[Immunization],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(immunizations.len());
+        for immunization in immunizations {
+            rows.push((stored_id(immunization)?, serde_json::to_value(immunization)?, self.extract_search_fields(immunization)));
+        }
+
+        // patient_id references patients, so rows for unknown patients are set aside
+        let patients: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.patient_id).collect();
+        let existing = existing_patients(tx, &patients).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.patient_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 7).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO immunizations (id, resource, patient_id, status, vaccine_code_code, vaccine_code_system, occurrence_datetime) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.patient_id)
+                    .push_bind(fields.status)
+                    .push_bind(fields.vaccine_code_code)
+                    .push_bind(fields.vaccine_code_system)
+                    .push_bind(fields.occurrence_datetime);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "immunizations", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected immunizations from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Immunization>(&self.pool, "immunizations", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // patient_id references patients; rows pointing at an unknown patient keep their columns
+        let patients: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, immunization)| self.extract_search_fields(immunization).patient_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &patients).await?;
+
+        let mut updated = 0;
+        for (id, immunization) in &rows {
+            let fields = self.extract_search_fields(immunization);
+            if fields.patient_id.is_some_and(|patient| !existing.contains(&patient)) {
+                tracing::warn!("Not reindexing Immunization/{}: patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE immunizations
+                SET patient_id = $2,
+                    status = $3,
+                    vaccine_code_code = $4,
+                    vaccine_code_system = $5,
+                    occurrence_datetime = $6
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.patient_id)
+            .bind(fields.status)
+            .bind(fields.vaccine_code_code)
+            .bind(fields.vaccine_code_system)
+            .bind(fields.occurrence_datetime)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<Immunization>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM immunizations
+            WHERE patient_id = $1 AND deleted_at IS NULL
+            ORDER BY occurrence_datetime DESC
+            LIMIT 100
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut immunizations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let immunization: Immunization = serde_json::from_value(resource_json)?;
+            immunizations.push(immunization);
+        }
+
+        Ok(immunizations)
+    }
+
+    /// Get immunization history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Immunization>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM immunizations_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut immunizations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let immunization: Immunization = serde_json::from_value(resource_json)?;
+            immunizations.push(immunization);
+        }
+
+        Ok(immunizations)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Immunization> for ImmunizationRepository {
+    async fn create(&self, immunization: &Immunization) -> FhirResult<Immunization> {
+        let mut immunization = immunization.clone();
+
+        let id = Uuid::new_v4().to_string();
+        immunization.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(immunization.meta.as_ref(), 1);
+        immunization.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&immunization);
+        let resource_json = serde_json::to_value(&immunization)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO immunizations (
+                id, resource, patient_id, status, vaccine_code_code,
+                vaccine_code_system, occurrence_datetime
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.patient_id)
+        .bind(search_fields.status)
+        .bind(search_fields.vaccine_code_code)
+        .bind(search_fields.vaccine_code_system)
+        .bind(search_fields.occurrence_datetime)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO immunizations_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(immunization)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<Immunization>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM immunizations
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let immunization: Immunization = serde_json::from_value(resource_json)?;
+            Ok(Some(immunization))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, immunization: &Immunization) -> FhirResult<Immunization> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Immunization".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_immunization = immunization.clone();
+        updated_immunization.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_immunization.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_immunization.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_immunization);
+        let resource_json = serde_json::to_value(&updated_immunization)?;
+
+        sqlx::query(
+            r#"
+            UPDATE immunizations
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                patient_id = $4,
+                status = $5,
+                vaccine_code_code = $6,
+                vaccine_code_system = $7,
+                occurrence_datetime = $8
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.patient_id)
+        .bind(search_fields.status)
+        .bind(search_fields.vaccine_code_code)
+        .bind(search_fields.vaccine_code_system)
+        .bind(search_fields.occurrence_datetime)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO immunizations_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_immunization)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE immunizations
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Immunization".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `patient`, `status` (comma-separated values match any),
+    /// `vaccine-code`, `date` (occurrence, with prefixes) and `identifier`,
+    /// plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Immunization>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM immunizations WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "patient" => {
+                    query.push(" AND patient_id = ").push_bind(reference_search_id(value)?);
+                }
+                "status" => push_any_of(&mut query, "status", value),
+                "vaccine-code" => push_token_filter(&mut query, "vaccine_code_code", "vaccine_code_system", value),
+                "date" => push_date_filter(&mut query, "occurrence_datetime", value)?,
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut immunizations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let immunization: Immunization = serde_json::from_value(resource_json)?;
+            immunizations.push(immunization);
+        }
+
+        Ok(immunizations)
+    }
+}
+
+struct ImmunizationSearchFields {
+    patient_id: Option<Uuid>,
+    status: String,
+    vaccine_code_code: Option<String>,
+    vaccine_code_system: Option<String>,
+    occurrence_datetime: Option<chrono::DateTime<Utc>>,
+}
@@ -0,0 +1,292 @@
+// src/repository/import_repository.rs
+
+use sqlx::{PgPool, Row};
+use sqlx::postgres::PgRow;
+use uuid::Uuid;
+
+use crate::domain::{
+    Patient, Observation, Condition, Encounter, Practitioner, PractitionerRole, Organization,
+    Medication, MedicationRequest, MedicationStatement, AllergyIntolerance, Procedure, DiagnosticReport,
+    Immunization, Location, Group, DocumentReference,
+    FhirError, FhirResult,
+};
+use super::{
+    BatchInsert, PatientRepository, ObservationRepository, ConditionRepository, EncounterRepository,
+    PractitionerRepository, PractitionerRoleRepository, OrganizationRepository,
+    MedicationRepository, MedicationRequestRepository, MedicationStatementRepository,
+    AllergyIntoleranceRepository, ProcedureRepository, DiagnosticReportRepository,
+    ImmunizationRepository, LocationRepository, GroupRepository, DocumentReferenceRepository,
+};
+
+/// Progress of a bulk `$import` job
+#[derive(Debug, Clone)]
+pub struct ImportJob {
+    pub id: Uuid,
+    pub source: String,
+    pub resource_type: Option<String>,
+    /// `in-progress`, `completed` or `failed`
+    pub status: String,
+    /// Lines of the source file committed so far; a resumed job skips these
+    pub lines_processed: i64,
+    pub lines_imported: i64,
+    pub lines_failed: i64,
+    pub error: Option<String>,
+    /// Length of the error file once the last committed batch's errors are
+    /// written
+    pub error_bytes: i64,
+    /// Error lines of the last committed batch, the end of the error file
+    pub pending_errors: String,
+}
+
+/// Resources parsed from one batch of NDJSON lines, grouped by type
+#[derive(Debug, Default)]
+pub struct ImportBatch {
+    pub patients: Vec<Patient>,
+    pub observations: Vec<Observation>,
+    pub conditions: Vec<Condition>,
+    pub encounters: Vec<Encounter>,
+    pub practitioners: Vec<Practitioner>,
+    pub practitioner_roles: Vec<PractitionerRole>,
+    pub organizations: Vec<Organization>,
+    pub medications: Vec<Medication>,
+    pub medication_requests: Vec<MedicationRequest>,
+    pub medication_statements: Vec<MedicationStatement>,
+    pub allergy_intolerances: Vec<AllergyIntolerance>,
+    pub procedures: Vec<Procedure>,
+    pub diagnostic_reports: Vec<DiagnosticReport>,
+    pub immunizations: Vec<Immunization>,
+    pub locations: Vec<Location>,
+    pub groups: Vec<Group>,
+    pub document_references: Vec<DocumentReference>,
+}
+
+impl ImportBatch {
+    pub fn len(&self) -> usize {
+        self.patients.len()
+            + self.observations.len()
+            + self.conditions.len()
+            + self.encounters.len()
+            + self.practitioners.len()
+            + self.practitioner_roles.len()
+            + self.organizations.len()
+            + self.medications.len()
+            + self.medication_requests.len()
+            + self.medication_statements.len()
+            + self.allergy_intolerances.len()
+            + self.procedures.len()
+            + self.diagnostic_reports.len()
+            + self.immunizations.len()
+            + self.locations.len()
+            + self.groups.len()
+            + self.document_references.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+const JOB_COLUMNS: &str =
+    "id, source, resource_type, status, lines_processed, lines_imported, lines_failed, error, error_bytes, pending_errors";
+
+/// Stores `$import` jobs and commits their batches, each batch in one
+/// transaction together with the job's checkpoint
+pub struct ImportRepository {
+    pool: PgPool,
+    patients: PatientRepository,
+    observations: ObservationRepository,
+    conditions: ConditionRepository,
+    encounters: EncounterRepository,
+    practitioners: PractitionerRepository,
+    practitioner_roles: PractitionerRoleRepository,
+    organizations: OrganizationRepository,
+    medications: MedicationRepository,
+    medication_requests: MedicationRequestRepository,
+    medication_statements: MedicationStatementRepository,
+    allergy_intolerances: AllergyIntoleranceRepository,
+    procedures: ProcedureRepository,
+    diagnostic_reports: DiagnosticReportRepository,
+    immunizations: ImmunizationRepository,
+    locations: LocationRepository,
+    groups: GroupRepository,
+    document_references: DocumentReferenceRepository,
+}
+
+impl ImportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            patients: PatientRepository::new(pool.clone()),
+            observations: ObservationRepository::new(pool.clone()),
+            conditions: ConditionRepository::new(pool.clone()),
+            encounters: EncounterRepository::new(pool.clone()),
+            practitioners: PractitionerRepository::new(pool.clone()),
+            practitioner_roles: PractitionerRoleRepository::new(pool.clone()),
+            organizations: OrganizationRepository::new(pool.clone()),
+            medications: MedicationRepository::new(pool.clone()),
+            medication_requests: MedicationRequestRepository::new(pool.clone()),
+            medication_statements: MedicationStatementRepository::new(pool.clone()),
+            allergy_intolerances: AllergyIntoleranceRepository::new(pool.clone()),
+            procedures: ProcedureRepository::new(pool.clone()),
+            diagnostic_reports: DiagnosticReportRepository::new(pool.clone()),
+            immunizations: ImmunizationRepository::new(pool.clone()),
+            locations: LocationRepository::new(pool.clone()),
+            groups: GroupRepository::new(pool.clone()),
+            document_references: DocumentReferenceRepository::new(pool.clone()),
+            pool,
+        }
+    }
+
+    fn job_from_row(row: &PgRow) -> FhirResult<ImportJob> {
+        let get_err = |e: sqlx::Error| FhirError::Database(e.to_string());
+        Ok(ImportJob {
+            id: row.try_get("id").map_err(get_err)?,
+            source: row.try_get("source").map_err(get_err)?,
+            resource_type: row.try_get("resource_type").map_err(get_err)?,
+            status: row.try_get("status").map_err(get_err)?,
+            lines_processed: row.try_get("lines_processed").map_err(get_err)?,
+            lines_imported: row.try_get("lines_imported").map_err(get_err)?,
+            lines_failed: row.try_get("lines_failed").map_err(get_err)?,
+            error: row.try_get("error").map_err(get_err)?,
+            error_bytes: row.try_get("error_bytes").map_err(get_err)?,
+            pending_errors: row.try_get("pending_errors").map_err(get_err)?,
+        })
+    }
+
+    /// The unfinished job for this file, if an earlier run was interrupted,
+    /// or a new one. A file whose size changed is treated as a new source
+    pub async fn start(
+        &self,
+        source: &str,
+        source_size: i64,
+        resource_type: Option<&str>,
+    ) -> FhirResult<ImportJob> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO import_jobs (id, source, source_size, resource_type)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (source, source_size) WHERE status <> 'completed'
+            DO UPDATE SET status = 'in-progress', error = NULL, updated_at = NOW()
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(source)
+        .bind(source_size)
+        .bind(resource_type)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Self::job_from_row(&row)
+    }
+
+    pub async fn get(&self, id: Uuid) -> FhirResult<Option<ImportJob>> {
+        let row = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM import_jobs WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        row.as_ref().map(Self::job_from_row).transpose()
+    }
+
+    /// Insert one batch and advance the job past its `lines` source lines.
+    /// `rejected` counts lines that failed before reaching the database, and
+    /// `report` turns the insert outcome into the batch's error lines, which
+    /// are checkpointed with it. Patients go first so resources in the same
+    /// batch can reference them
+    pub async fn commit_batch(
+        &self,
+        job_id: Uuid,
+        batch: &ImportBatch,
+        lines: i64,
+        rejected: i64,
+        report: impl FnOnce(&BatchInsert) -> FhirResult<String>,
+    ) -> FhirResult<ImportJob> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut result = self.patients.insert_batch(&mut tx, &batch.patients).await?;
+        for part in [
+            self.observations.insert_batch(&mut tx, &batch.observations).await?,
+            self.conditions.insert_batch(&mut tx, &batch.conditions).await?,
+            self.encounters.insert_batch(&mut tx, &batch.encounters).await?,
+            self.practitioners.insert_batch(&mut tx, &batch.practitioners).await?,
+            self.practitioner_roles.insert_batch(&mut tx, &batch.practitioner_roles).await?,
+            self.organizations.insert_batch(&mut tx, &batch.organizations).await?,
+            self.medications.insert_batch(&mut tx, &batch.medications).await?,
+            self.medication_requests.insert_batch(&mut tx, &batch.medication_requests).await?,
+            self.medication_statements.insert_batch(&mut tx, &batch.medication_statements).await?,
+            self.allergy_intolerances.insert_batch(&mut tx, &batch.allergy_intolerances).await?,
+            self.procedures.insert_batch(&mut tx, &batch.procedures).await?,
+            self.diagnostic_reports.insert_batch(&mut tx, &batch.diagnostic_reports).await?,
+            self.immunizations.insert_batch(&mut tx, &batch.immunizations).await?,
+            self.locations.insert_batch(&mut tx, &batch.locations).await?,
+            self.groups.insert_batch(&mut tx, &batch.groups).await?,
+            self.document_references.insert_batch(&mut tx, &batch.document_references).await?,
+        ] {
+            result.inserted.extend(part.inserted);
+            result.missing_subject.extend(part.missing_subject);
+        }
+
+        let imported = result.inserted.len() as i64;
+        let failed = rejected + batch.len() as i64 - imported;
+        let errors = report(&result)?;
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE import_jobs
+            SET lines_processed = lines_processed + $2,
+                lines_imported = lines_imported + $3,
+                lines_failed = lines_failed + $4,
+                error_bytes = error_bytes + $5,
+                pending_errors = $6,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(job_id)
+        .bind(lines)
+        .bind(imported)
+        .bind(failed)
+        .bind(errors.len() as i64)
+        .bind(&errors)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Self::job_from_row(&row)
+    }
+
+    /// Mark the job finished; it can no longer be resumed
+    pub async fn complete(&self, job_id: Uuid) -> FhirResult<ImportJob> {
+        self.set_status(job_id, "completed", None).await
+    }
+
+    /// Record why the job stopped. Rerunning the same file resumes it
+    pub async fn fail(&self, job_id: Uuid, error: &str) -> FhirResult<ImportJob> {
+        self.set_status(job_id, "failed", Some(error)).await
+    }
+
+    async fn set_status(&self, job_id: Uuid, status: &str, error: Option<&str>) -> FhirResult<ImportJob> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE import_jobs
+            SET status = $2, error = $3, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(job_id)
+        .bind(status)
+        .bind(error)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Self::job_from_row(&row)
+    }
+}
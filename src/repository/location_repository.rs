@@ -0,0 +1,540 @@
+// src/repository/location_repository.rs
+
+use std::sync::OnceLock;
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{Location, Id, Meta, FhirError, FhirResult};
+use super::{
+    codeable_concept_filter, identifier_filter, insert_history, push_any_of, reference_search_id,
+    reference_uuid, push_meta_filter, stored_id, stored_rows, BatchInsert, NearSearch, ReindexPage, ReindexSelection,
+    Repository, SearchParams, BIND_LIMIT, EARTH_RADIUS_M,
+};
+use crate::domain::resources::Resource;
+
+pub struct LocationRepository {
+    pool: PgPool,
+    /// Whether the earthdistance GiST index exists, looked up on the first
+    /// `near` search
+    earth_index: OnceLock<bool>,
+}
+
+impl LocationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, earth_index: OnceLock::new() }
+    }
+
+    fn extract_search_fields(&self, location: &Location) -> LocationSearchFields {
+        let address = location.address.as_ref();
+        LocationSearchFields {
+            name: location.name.as_ref().map(|n| n.0.clone()),
+            status: location.status.as_ref().map(|s| s.0.clone()),
+            address_city: address.and_then(|a| a.city.as_ref()).map(|c| c.0.clone()),
+            address_state: address.and_then(|a| a.state.as_ref()).map(|s| s.0.clone()),
+            address_postal_code: address.and_then(|a| a.postal_code.as_ref()).map(|p| p.0.clone()),
+            address_country: address.and_then(|a| a.country.as_ref()).map(|c| c.0.clone()),
+            latitude: location.position.as_ref().map(|p| p.latitude.0),
+            longitude: location.position.as_ref().map(|p| p.longitude.0),
+            part_of_id: reference_uuid(location.part_of.as_ref()),
+            managing_organization_id: reference_uuid(location.managing_organization.as_ref()),
+        }
+    }
+
+    /// Insert imported locations as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        locations: &[Location],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(locations.len());
+        for location in locations {
+            rows.push((stored_id(location)?, serde_json::to_value(location)?, self.extract_search_fields(location)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 12).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO locations (id, resource, name, status, address_city, address_state, address_postal_code, address_country, latitude, longitude, part_of_id, managing_organization_id) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.name)
+                    .push_bind(fields.status)
+                    .push_bind(fields.address_city)
+                    .push_bind(fields.address_state)
+                    .push_bind(fields.address_postal_code)
+                    .push_bind(fields.address_country)
+                    .push_bind(fields.latitude)
+                    .push_bind(fields.longitude)
+                    .push_bind(fields.part_of_id)
+                    .push_bind(fields.managing_organization_id);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "locations", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected locations from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Location>(&self.pool, "locations", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, location) in &rows {
+            let fields = self.extract_search_fields(location);
+            sqlx::query(
+                r#"
+                UPDATE locations
+                SET name = $2,
+                    status = $3,
+                    address_city = $4,
+                    address_state = $5,
+                    address_postal_code = $6,
+                    address_country = $7,
+                    latitude = $8,
+                    longitude = $9,
+                    part_of_id = $10,
+                    managing_organization_id = $11
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.name)
+            .bind(fields.status)
+            .bind(fields.address_city)
+            .bind(fields.address_state)
+            .bind(fields.address_postal_code)
+            .bind(fields.address_country)
+            .bind(fields.latitude)
+            .bind(fields.longitude)
+            .bind(fields.part_of_id)
+            .bind(fields.managing_organization_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    /// Ids of every location below `id` in the `partOf` hierarchy
+    pub async fn descendant_ids(&self, id: Uuid) -> FhirResult<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE below(id) AS (
+                SELECT id FROM locations WHERE deleted_at IS NULL AND part_of_id = $1
+                UNION
+                SELECT l.id FROM locations l JOIN below ON l.part_of_id = below.id WHERE l.deleted_at IS NULL
+            )
+            SELECT id FROM below
+            "#
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))
+    }
+
+    /// Get location history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Location>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM locations_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut locations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let location: Location = serde_json::from_value(resource_json)?;
+            locations.push(location);
+        }
+
+        Ok(locations)
+    }
+
+    /// Whether the migration could create the earthdistance index
+    async fn has_earth_index(&self) -> FhirResult<bool> {
+        if let Some(available) = self.earth_index.get() {
+            return Ok(*available);
+        }
+        let available: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM pg_indexes WHERE indexname = 'idx_locations_position_earth')"
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        Ok(*self.earth_index.get_or_init(|| available))
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Location> for LocationRepository {
+    async fn create(&self, location: &Location) -> FhirResult<Location> {
+        let mut location = location.clone();
+
+        let id = Uuid::new_v4().to_string();
+        location.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(location.meta.as_ref(), 1);
+        location.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&location);
+        let resource_json = serde_json::to_value(&location)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO locations (
+                id, resource, name, status, address_city, address_state, address_postal_code,
+                address_country, latitude, longitude, part_of_id, managing_organization_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.name)
+        .bind(search_fields.status)
+        .bind(search_fields.address_city)
+        .bind(search_fields.address_state)
+        .bind(search_fields.address_postal_code)
+        .bind(search_fields.address_country)
+        .bind(search_fields.latitude)
+        .bind(search_fields.longitude)
+        .bind(search_fields.part_of_id)
+        .bind(search_fields.managing_organization_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO locations_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(location)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<Location>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM locations
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let location: Location = serde_json::from_value(resource_json)?;
+            Ok(Some(location))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, location: &Location) -> FhirResult<Location> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Location".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_location = location.clone();
+        updated_location.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_location.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_location.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_location);
+        let resource_json = serde_json::to_value(&updated_location)?;
+
+        sqlx::query(
+            r#"
+            UPDATE locations
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                name = $4,
+                status = $5,
+                address_city = $6,
+                address_state = $7,
+                address_postal_code = $8,
+                address_country = $9,
+                latitude = $10,
+                longitude = $11,
+                part_of_id = $12,
+                managing_organization_id = $13
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.name)
+        .bind(search_fields.status)
+        .bind(search_fields.address_city)
+        .bind(search_fields.address_state)
+        .bind(search_fields.address_postal_code)
+        .bind(search_fields.address_country)
+        .bind(search_fields.latitude)
+        .bind(search_fields.longitude)
+        .bind(search_fields.part_of_id)
+        .bind(search_fields.managing_organization_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO locations_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_location)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE locations
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Location".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `name` (contains, case-insensitive, on the name or any alias),
+    /// `address` (contains, on any part), `address-city`, `address-state`,
+    /// `address-postalcode` and `address-country` (starts with), `status`
+    /// (comma-separated values match any), `type`, `partof`, `organization`,
+    /// `identifier` and `near`, plus the meta filters. With `near`, results
+    /// come nearest first
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Location>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let near = params.filters.iter()
+            .find(|filter| filter.field == "near")
+            .map(|filter| NearSearch::parse(&filter.value))
+            .transpose()?;
+        let earth_index = match near {
+            Some(_) => self.has_earth_index().await?,
+            None => false,
+        };
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM locations WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "name" => {
+                    let pattern = format!("%{}%", value);
+                    query.push(" AND (name ILIKE ").push_bind(pattern.clone())
+                        .push(" OR EXISTS (SELECT 1 FROM jsonb_array_elements_text(resource->'alias') alias WHERE alias ILIKE ")
+                        .push_bind(pattern).push("))");
+                }
+                "address" => {
+                    query.push(" AND concat_ws(' ', resource#>>'{address,text}', resource#>>'{address,line}', address_city, address_state, address_postal_code, address_country) ILIKE ")
+                        .push_bind(format!("%{}%", value));
+                }
+                "address-city" => {
+                    query.push(" AND address_city ILIKE ").push_bind(format!("{}%", value));
+                }
+                "address-state" => {
+                    query.push(" AND address_state ILIKE ").push_bind(format!("{}%", value));
+                }
+                "address-postalcode" => {
+                    query.push(" AND address_postal_code ILIKE ").push_bind(format!("{}%", value));
+                }
+                "address-country" => {
+                    query.push(" AND address_country ILIKE ").push_bind(format!("{}%", value));
+                }
+                "status" => push_any_of(&mut query, "status", value),
+                "type" => {
+                    query.push(" AND resource @> ").push_bind(codeable_concept_filter("type", value));
+                }
+                "partof" => {
+                    query.push(" AND part_of_id = ").push_bind(reference_search_id(value)?);
+                }
+                "organization" => {
+                    query.push(" AND managing_organization_id = ").push_bind(reference_search_id(value)?);
+                }
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(near) = &near {
+            push_near_filter(&mut query, near, earth_index);
+            query.push(" ORDER BY ");
+            push_distance(&mut query, near, earth_index);
+        } else {
+            query.push(" ORDER BY last_updated DESC");
+        }
+        query.push(" LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut locations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let location: Location = serde_json::from_value(resource_json)?;
+            locations.push(location);
+        }
+
+        Ok(locations)
+    }
+}
+
+struct LocationSearchFields {
+    name: Option<String>,
+    status: Option<String>,
+    address_city: Option<String>,
+    address_state: Option<String>,
+    address_postal_code: Option<String>,
+    address_country: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    part_of_id: Option<Uuid>,
+    managing_organization_id: Option<Uuid>,
+}
+
+/// Restrict to locations within the `near` circle. With the earthdistance
+/// index, `earth_box` narrows the candidates; otherwise a latitude/longitude
+/// box does. Either way the exact distance is checked after
+fn push_near_filter(query: &mut QueryBuilder<Postgres>, near: &NearSearch, earth_index: bool) {
+    if earth_index {
+        // Restates the partial index predicate so the planner can use it
+        query.push(" AND latitude IS NOT NULL AND longitude IS NOT NULL");
+        query.push(" AND earth_box(ll_to_earth(").push_bind(near.latitude)
+            .push(", ").push_bind(near.longitude)
+            .push("), ").push_bind(near.distance)
+            .push(") @> ll_to_earth(latitude, longitude)");
+    } else {
+        let ((min_lat, max_lat), longitudes) = near.bounding_box();
+        query.push(" AND latitude BETWEEN ").push_bind(min_lat).push(" AND ").push_bind(max_lat);
+        match longitudes {
+            Some((min_lon, max_lon)) => {
+                query.push(" AND longitude BETWEEN ").push_bind(min_lon).push(" AND ").push_bind(max_lon);
+            }
+            None => {
+                query.push(" AND longitude IS NOT NULL");
+            }
+        }
+    }
+    query.push(" AND ");
+    push_distance(query, near, earth_index);
+    query.push(" <= ").push_bind(near.distance);
+}
+
+/// Distance in meters from the `near` point to a location's position
+fn push_distance(query: &mut QueryBuilder<Postgres>, near: &NearSearch, earth_index: bool) {
+    if earth_index {
+        query.push("earth_distance(ll_to_earth(").push_bind(near.latitude)
+            .push(", ").push_bind(near.longitude)
+            .push("), ll_to_earth(latitude, longitude))");
+    } else {
+        // Haversine formula; least() guards asin against rounding above 1
+        query.push(format!("(2 * {} * asin(least(1, sqrt(", EARTH_RADIUS_M))
+            .push("power(sin(radians(latitude - ").push_bind(near.latitude).push(") / 2), 2)")
+            .push(" + cos(radians(").push_bind(near.latitude).push(")) * cos(radians(latitude))")
+            .push(" * power(sin(radians(longitude - ").push_bind(near.longitude).push(") / 2), 2)")
+            .push("))))");
+    }
+}
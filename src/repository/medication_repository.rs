@@ -0,0 +1,359 @@
+// src/repository/medication_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{Medication, Id, Meta, FhirError, FhirResult};
+use super::{
+    identifier_filter, insert_history, push_any_of, push_token_filter, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct MedicationRepository {
+    pool: PgPool,
+}
+
+impl MedicationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+    
+    /// Extract searchable fields from Medication resource
+    fn extract_search_fields(&self, medication: &Medication) -> MedicationSearchFields {
+        MedicationSearchFields {
+            status: medication.status.as_ref().map(|s| s.0.clone()),
+            code_code: medication.code.as_ref()
+                .and_then(|c| c.coding.as_ref())
+                .and_then(|codings| codings.first())
+                .and_then(|coding| coding.code.as_ref())
+                .map(|code| code.0.clone()),
+            code_system: medication.code.as_ref()
+                .and_then(|c| c.coding.as_ref())
+                .and_then(|codings| codings.first())
+                .and_then(|coding| coding.system.as_ref())
+                .map(|sys| sys.0.clone()),
+        }
+    }
+
+    /// Insert imported medications as version 1 in multi-row statements,
+    /// with the same search columns as `create`. Ids that already exist are
+    /// skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        medications: &[Medication],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(medications.len());
+        for medication in medications {
+            rows.push((stored_id(medication)?, serde_json::to_value(medication)?, self.extract_search_fields(medication)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 5).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO medications (id, resource, status, code_code, code_system) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.status)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "medications", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected medications from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Medication>(&self.pool, "medications", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, medication) in &rows {
+            let fields = self.extract_search_fields(medication);
+            sqlx::query(
+                r#"
+                UPDATE medications
+                SET status = $2,
+                    code_code = $3,
+                    code_system = $4
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.status)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+    
+    /// Get medication history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Medication>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medications_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut medications = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let medication: Medication = serde_json::from_value(resource_json)?;
+            medications.push(medication);
+        }
+        
+        Ok(medications)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Medication> for MedicationRepository {
+    async fn create(&self, medication: &Medication) -> FhirResult<Medication> {
+        let mut medication = medication.clone();
+        
+        let id = Uuid::new_v4().to_string();
+        medication.set_id(Id(id.clone()));
+        
+        let meta = Meta::versioned(medication.meta.as_ref(), 1);
+        medication.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&medication);
+        let resource_json = serde_json::to_value(&medication)?;
+        
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO medications (
+                id, resource, status, code_code, code_system
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.status)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO medications_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(medication)
+    }
+    
+    async fn read(&self, id: &str) -> FhirResult<Option<Medication>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medications
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let medication: Medication = serde_json::from_value(resource_json)?;
+            Ok(Some(medication))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    async fn update(&self, id: &str, medication: &Medication) -> FhirResult<Medication> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Medication".to_string(),
+                id: id.to_string(),
+            })?;
+        
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+        
+        let new_version = current_version + 1;
+        
+        let mut updated_medication = medication.clone();
+        updated_medication.set_id(Id(id.to_string()));
+        
+        let meta = Meta::versioned(updated_medication.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_medication.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&updated_medication);
+        let resource_json = serde_json::to_value(&updated_medication)?;
+        
+        sqlx::query(
+            r#"
+            UPDATE medications
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                status = $4,
+                code_code = $5,
+                code_system = $6
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.status)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO medications_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(updated_medication)
+    }
+    
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE medications
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Medication".to_string(),
+                id: id.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Honors `code`, `status` (comma-separated values match any) and
+    /// `identifier`, plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Medication>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+        
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM medications WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "code" => push_token_filter(&mut query, "code_code", "code_system", value),
+                "status" => push_any_of(&mut query, "status", value),
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+        
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut medications = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let medication: Medication = serde_json::from_value(resource_json)?;
+            medications.push(medication);
+        }
+        
+        Ok(medications)
+    }
+}
+
+struct MedicationSearchFields {
+    status: Option<String>,
+    code_code: Option<String>,
+    code_system: Option<String>,
+}
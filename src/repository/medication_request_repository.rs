@@ -0,0 +1,435 @@
+// src/repository/medication_request_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::{MedicationRequest, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, identifier_filter, insert_history, medication_coding, push_any_of,
+    push_date_filter, push_token_filter, reference_search_id, reference_uuid, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct MedicationRequestRepository {
+    pool: PgPool,
+}
+
+impl MedicationRequestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, request: &MedicationRequest) -> MedicationRequestSearchFields {
+        let (code_code, code_system) = medication_coding(&request.medication);
+        MedicationRequestSearchFields {
+            subject_id: reference_uuid(Some(&request.subject)),
+            status: request.status.0.clone(),
+            intent: request.intent.0.clone(),
+            code_code,
+            code_system,
+            authored_on: request.authored_on.as_ref().map(|d| d.0),
+        }
+    }
+
+    /// Insert imported medication requests as version 1 in multi-row
+    /// statements, with the same search columns as `create`. Ids that
+    /// already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        requests: &[MedicationRequest],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(requests.len());
+        for request in requests {
+            rows.push((stored_id(request)?, serde_json::to_value(request)?, self.extract_search_fields(request)));
+        }
+
+        // subject_id references patients, so rows for unknown patients are set aside
+        let subjects: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.subject_id).collect();
+        let existing = existing_patients(tx, &subjects).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.subject_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 8).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO medication_requests (id, resource, subject_id, status, intent, code_code, code_system, authored_on) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.subject_id)
+                    .push_bind(fields.status)
+                    .push_bind(fields.intent)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.authored_on);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "medication_requests", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected medication requests from
+    /// their stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<MedicationRequest>(&self.pool, "medication_requests", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // subject_id references patients; rows pointing at an unknown patient keep their columns
+        let subjects: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, request)| self.extract_search_fields(request).subject_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &subjects).await?;
+
+        let mut updated = 0;
+        for (id, request) in &rows {
+            let fields = self.extract_search_fields(request);
+            if fields.subject_id.is_some_and(|subject| !existing.contains(&subject)) {
+                tracing::warn!("Not reindexing MedicationRequest/{}: subject patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE medication_requests
+                SET subject_id = $2,
+                    status = $3,
+                    intent = $4,
+                    code_code = $5,
+                    code_system = $6,
+                    authored_on = $7
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.subject_id)
+            .bind(fields.status)
+            .bind(fields.intent)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.authored_on)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<MedicationRequest>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medication_requests
+            WHERE subject_id = $1 AND deleted_at IS NULL
+            ORDER BY authored_on DESC
+            LIMIT 100
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut requests = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let request: MedicationRequest = serde_json::from_value(resource_json)?;
+            requests.push(request);
+        }
+
+        Ok(requests)
+    }
+
+    /// Get medication request history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<MedicationRequest>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medication_requests_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut requests = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let request: MedicationRequest = serde_json::from_value(resource_json)?;
+            requests.push(request);
+        }
+
+        Ok(requests)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<MedicationRequest> for MedicationRequestRepository {
+    async fn create(&self, request: &MedicationRequest) -> FhirResult<MedicationRequest> {
+        let mut request = request.clone();
+
+        let id = Uuid::new_v4().to_string();
+        request.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(request.meta.as_ref(), 1);
+        request.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&request);
+        let resource_json = serde_json::to_value(&request)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO medication_requests (
+                id, resource, subject_id, status, intent, code_code, code_system, authored_on
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.subject_id)
+        .bind(search_fields.status)
+        .bind(search_fields.intent)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.authored_on)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO medication_requests_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(request)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<MedicationRequest>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medication_requests
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let request: MedicationRequest = serde_json::from_value(resource_json)?;
+            Ok(Some(request))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, request: &MedicationRequest) -> FhirResult<MedicationRequest> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "MedicationRequest".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_request = request.clone();
+        updated_request.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_request.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_request.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_request);
+        let resource_json = serde_json::to_value(&updated_request)?;
+
+        sqlx::query(
+            r#"
+            UPDATE medication_requests
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                subject_id = $4,
+                status = $5,
+                intent = $6,
+                code_code = $7,
+                code_system = $8,
+                authored_on = $9
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.subject_id)
+        .bind(search_fields.status)
+        .bind(search_fields.intent)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.authored_on)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO medication_requests_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_request)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE medication_requests
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "MedicationRequest".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `patient`/`subject`, `status`, `intent` (comma-separated
+    /// values match any), `code`, `medication`, `authoredon` with date
+    /// prefixes and `identifier`, plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<MedicationRequest>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM medication_requests WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "patient" | "subject" => {
+                    query.push(" AND subject_id = ").push_bind(reference_search_id(value)?);
+                }
+                "status" => push_any_of(&mut query, "status", value),
+                "intent" => push_any_of(&mut query, "intent", value),
+                "code" => push_token_filter(&mut query, "code_code", "code_system", value),
+                "medication" => {
+                    let reference = format!("Medication/{}", reference_search_id(value)?);
+                    query.push(" AND resource @> ")
+                        .push_bind(serde_json::json!({ "medication": { "reference": reference } }));
+                }
+                "authoredon" => push_date_filter(&mut query, "authored_on", value)?,
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut requests = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let request: MedicationRequest = serde_json::from_value(resource_json)?;
+            requests.push(request);
+        }
+
+        Ok(requests)
+    }
+}
+
+struct MedicationRequestSearchFields {
+    subject_id: Option<Uuid>,
+    status: String,
+    intent: String,
+    code_code: Option<String>,
+    code_system: Option<String>,
+    authored_on: Option<chrono::DateTime<Utc>>,
+}
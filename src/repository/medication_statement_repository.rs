@@ -0,0 +1,431 @@
+// src/repository/medication_statement_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::{MedicationStatement, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, identifier_filter, insert_history, medication_coding, push_any_of,
+    push_date_filter, push_token_filter, reference_search_id, reference_uuid, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::medication_statement::MedicationStatementEffective;
+use crate::domain::resources::Resource;
+
+pub struct MedicationStatementRepository {
+    pool: PgPool,
+}
+
+impl MedicationStatementRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, statement: &MedicationStatement) -> MedicationStatementSearchFields {
+        let (code_code, code_system) = medication_coding(&statement.medication);
+        MedicationStatementSearchFields {
+            subject_id: reference_uuid(Some(&statement.subject)),
+            status: statement.status.0.clone(),
+            code_code,
+            code_system,
+            effective_datetime: match &statement.effective {
+                Some(MedicationStatementEffective::DateTime(dt)) => Some(dt.0),
+                Some(MedicationStatementEffective::Period(period)) => period.start.as_ref().map(|d| d.0),
+                None => None,
+            },
+        }
+    }
+
+    /// Insert imported medication statements as version 1 in multi-row
+    /// statements, with the same search columns as `create`. Ids that
+    /// already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        statements: &[MedicationStatement],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(statements.len());
+        for statement in statements {
+            rows.push((stored_id(statement)?, serde_json::to_value(statement)?, self.extract_search_fields(statement)));
+        }
+
+        // subject_id references patients, so rows for unknown patients are set aside
+        let subjects: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.subject_id).collect();
+        let existing = existing_patients(tx, &subjects).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.subject_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 7).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO medication_statements (id, resource, subject_id, status, code_code, code_system, effective_datetime) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.subject_id)
+                    .push_bind(fields.status)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.effective_datetime);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "medication_statements", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected medication statements from
+    /// their stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<MedicationStatement>(&self.pool, "medication_statements", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // subject_id references patients; rows pointing at an unknown patient keep their columns
+        let subjects: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, statement)| self.extract_search_fields(statement).subject_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &subjects).await?;
+
+        let mut updated = 0;
+        for (id, statement) in &rows {
+            let fields = self.extract_search_fields(statement);
+            if fields.subject_id.is_some_and(|subject| !existing.contains(&subject)) {
+                tracing::warn!("Not reindexing MedicationStatement/{}: subject patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE medication_statements
+                SET subject_id = $2,
+                    status = $3,
+                    code_code = $4,
+                    code_system = $5,
+                    effective_datetime = $6
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.subject_id)
+            .bind(fields.status)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.effective_datetime)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<MedicationStatement>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medication_statements
+            WHERE subject_id = $1 AND deleted_at IS NULL
+            ORDER BY effective_datetime DESC
+            LIMIT 100
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut statements = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let statement: MedicationStatement = serde_json::from_value(resource_json)?;
+            statements.push(statement);
+        }
+
+        Ok(statements)
+    }
+
+    /// Get medication statement history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<MedicationStatement>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medication_statements_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut statements = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let statement: MedicationStatement = serde_json::from_value(resource_json)?;
+            statements.push(statement);
+        }
+
+        Ok(statements)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<MedicationStatement> for MedicationStatementRepository {
+    async fn create(&self, statement: &MedicationStatement) -> FhirResult<MedicationStatement> {
+        let mut statement = statement.clone();
+
+        let id = Uuid::new_v4().to_string();
+        statement.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(statement.meta.as_ref(), 1);
+        statement.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&statement);
+        let resource_json = serde_json::to_value(&statement)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO medication_statements (
+                id, resource, subject_id, status, code_code, code_system, effective_datetime
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.subject_id)
+        .bind(search_fields.status)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.effective_datetime)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO medication_statements_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(statement)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<MedicationStatement>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM medication_statements
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let statement: MedicationStatement = serde_json::from_value(resource_json)?;
+            Ok(Some(statement))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, statement: &MedicationStatement) -> FhirResult<MedicationStatement> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "MedicationStatement".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_statement = statement.clone();
+        updated_statement.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_statement.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_statement.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_statement);
+        let resource_json = serde_json::to_value(&updated_statement)?;
+
+        sqlx::query(
+            r#"
+            UPDATE medication_statements
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                subject_id = $4,
+                status = $5,
+                code_code = $6,
+                code_system = $7,
+                effective_datetime = $8
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.subject_id)
+        .bind(search_fields.status)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.effective_datetime)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO medication_statements_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_statement)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE medication_statements
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "MedicationStatement".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `patient`/`subject`, `status` (comma-separated values match
+    /// any), `code`, `medication`, `effective` with date prefixes and
+    /// `identifier`, plus the meta filters. A Period matches on its start
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<MedicationStatement>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM medication_statements WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "patient" | "subject" => {
+                    query.push(" AND subject_id = ").push_bind(reference_search_id(value)?);
+                }
+                "status" => push_any_of(&mut query, "status", value),
+                "code" => push_token_filter(&mut query, "code_code", "code_system", value),
+                "medication" => {
+                    let reference = format!("Medication/{}", reference_search_id(value)?);
+                    query.push(" AND resource @> ")
+                        .push_bind(serde_json::json!({ "medication": { "reference": reference } }));
+                }
+                "effective" => push_date_filter(&mut query, "effective_datetime", value)?,
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut statements = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let statement: MedicationStatement = serde_json::from_value(resource_json)?;
+            statements.push(statement);
+        }
+
+        Ok(statements)
+    }
+}
+
+struct MedicationStatementSearchFields {
+    subject_id: Option<Uuid>,
+    status: String,
+    code_code: Option<String>,
+    code_system: Option<String>,
+    effective_datetime: Option<chrono::DateTime<Utc>>,
+}
@@ -0,0 +1,163 @@
+// src/repository/meta_repository.rs
+
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::{Canonical, Coding, Meta, FhirError, FhirResult};
+use super::{patient_column, resource_table, RESOURCE_TABLES};
+
+/// Meta of a stored resource, with the patient whose compartment it is in
+#[derive(Debug, Clone)]
+pub struct StoredMeta {
+    pub meta: Meta,
+    pub patient_id: Option<String>,
+}
+
+/// Reads and edits `Resource.meta` across resource tables, for the
+/// `$meta`, `$meta-add` and `$meta-delete` operations
+#[derive(Clone)]
+pub struct MetaRepository {
+    pool: PgPool,
+}
+
+impl MetaRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_id(id: &str) -> FhirResult<Uuid> {
+        Uuid::parse_str(id).map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))
+    }
+
+    /// Meta of the current version of a resource
+    pub async fn read(&self, resource_type: &str, id: &str) -> FhirResult<Option<StoredMeta>> {
+        let table = resource_table(resource_type)?;
+        let uuid = Self::parse_id(id)?;
+        let patient_column = patient_column(table);
+
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT COALESCE(resource->'meta', '{{}}'::jsonb) AS meta, {patient_column} AS patient_id
+            FROM {table}
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        ))
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let meta_json: serde_json::Value = row.try_get("meta")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        let patient_id: Option<Uuid> = row.try_get("patient_id")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(Some(StoredMeta {
+            meta: serde_json::from_value(meta_json)?,
+            patient_id: patient_id.map(|id| id.to_string()),
+        }))
+    }
+
+    /// Replace the meta of the current version in place. Meta changes do not
+    /// create a new version, so the matching history row is updated as well
+    pub async fn write(&self, resource_type: &str, id: &str, meta: &Meta) -> FhirResult<()> {
+        let table = resource_table(resource_type)?;
+        let uuid = Self::parse_id(id)?;
+        let meta_json = serde_json::to_value(meta)?;
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE {table}
+            SET resource = jsonb_set(resource, '{{meta}}', $2)
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING version_id
+            "#
+        ))
+        .bind(uuid)
+        .bind(&meta_json)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?
+        .ok_or_else(|| FhirError::NotFound {
+            resource_type: resource_type.to_string(),
+            id: id.to_string(),
+        })?;
+        let version_id: i32 = row.try_get("version_id")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        sqlx::query(&format!(
+            r#"
+            UPDATE {table}_history
+            SET resource = jsonb_set(resource, '{{meta}}', $3)
+            WHERE id = $1 AND version_id = $2
+            "#
+        ))
+        .bind(uuid)
+        .bind(version_id)
+        .bind(&meta_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Every distinct profile, security label and tag in use, across all
+    /// resource types or within one
+    pub async fn in_use(&self, resource_type: Option<&str>) -> FhirResult<Meta> {
+        let tables: Vec<&str> = match resource_type {
+            Some(resource_type) => vec![resource_table(resource_type)?],
+            None => RESOURCE_TABLES.iter().map(|(_, table)| *table).collect(),
+        };
+
+        let mut summary = Meta::default();
+        for table in tables {
+            let rows = sqlx::query(&format!(
+                r#"
+                SELECT DISTINCT 'profile' AS element, value
+                FROM {table}, jsonb_path_query(resource, '$.meta.profile[*]') AS value
+                WHERE deleted_at IS NULL
+                UNION
+                SELECT DISTINCT 'security' AS element, value
+                FROM {table}, jsonb_path_query(resource, '$.meta.security[*]') AS value
+                WHERE deleted_at IS NULL
+                UNION
+                SELECT DISTINCT 'tag' AS element, value
+                FROM {table}, jsonb_path_query(resource, '$.meta.tag[*]') AS value
+                WHERE deleted_at IS NULL
+                "#
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+            let mut found = Meta::default();
+            for row in rows {
+                let element: String = row.try_get("element")
+                    .map_err(|e| FhirError::Database(e.to_string()))?;
+                let value: serde_json::Value = row.try_get("value")
+                    .map_err(|e| FhirError::Database(e.to_string()))?;
+                match element.as_str() {
+                    "profile" => found.profile.get_or_insert_with(Vec::new)
+                        .push(serde_json::from_value::<Canonical>(value)?),
+                    "security" => found.security.get_or_insert_with(Vec::new)
+                        .push(serde_json::from_value::<Coding>(value)?),
+                    _ => found.tag.get_or_insert_with(Vec::new)
+                        .push(serde_json::from_value::<Coding>(value)?),
+                }
+            }
+            summary.add(&found);
+        }
+
+        Ok(summary)
+    }
+}
@@ -4,13 +4,369 @@ pub mod patient_repository;
 pub mod observation_repository;
 pub mod condition_repository;
 pub mod encounter_repository;
+pub mod practitioner_repository;
+pub mod practitioner_role_repository;
+pub mod organization_repository;
+pub mod medication_repository;
+pub mod medication_request_repository;
+pub mod medication_statement_repository;
+pub mod allergy_intolerance_repository;
+pub mod procedure_repository;
+pub mod diagnostic_report_repository;
+pub mod immunization_repository;
+pub mod location_repository;
+pub mod group_repository;
+pub mod document_reference_repository;
+pub mod binary_repository;
+pub mod blob_store;
+pub mod meta_repository;
+pub mod export_repository;
+pub mod import_repository;
+pub mod reindex_repository;
 
 pub use patient_repository::PatientRepository;
 pub use observation_repository::ObservationRepository;
 pub use condition_repository::ConditionRepository;
 pub use encounter_repository::EncounterRepository;
+pub use practitioner_repository::PractitionerRepository;
+pub use practitioner_role_repository::PractitionerRoleRepository;
+pub use organization_repository::OrganizationRepository;
+pub use medication_repository::MedicationRepository;
+pub use medication_request_repository::MedicationRequestRepository;
+pub use medication_statement_repository::MedicationStatementRepository;
+pub use allergy_intolerance_repository::AllergyIntoleranceRepository;
+pub use procedure_repository::ProcedureRepository;
+pub use diagnostic_report_repository::DiagnosticReportRepository;
+pub use immunization_repository::ImmunizationRepository;
+pub use location_repository::LocationRepository;
+pub use group_repository::GroupRepository;
+pub use document_reference_repository::DocumentReferenceRepository;
+pub use binary_repository::{BinaryRepository, StoredBinary};
+pub use blob_store::{BlobStore, BlobStream, LocalBlobStore};
+pub use meta_repository::MetaRepository;
+pub use export_repository::ExportRepository;
+pub use import_repository::ImportRepository;
+pub use reindex_repository::ReindexRepository;
 
-use crate::domain::errors::FhirResult;
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::errors::{FhirError, FhirResult};
+use crate::domain::resources::Resource;
+use crate::domain::{CodeableConcept, CodeableConceptOrReference, Reference};
+
+/// Resource types stored by the server, with their tables
+pub const RESOURCE_TABLES: &[(&str, &str)] = &[
+    ("Patient", "patients"),
+    ("Observation", "observations"),
+    ("Condition", "conditions"),
+    ("Encounter", "encounters"),
+    ("Practitioner", "practitioners"),
+    ("PractitionerRole", "practitioner_roles"),
+    ("Organization", "organizations"),
+    ("Medication", "medications"),
+    ("MedicationRequest", "medication_requests"),
+    ("MedicationStatement", "medication_statements"),
+    ("AllergyIntolerance", "allergy_intolerances"),
+    ("Procedure", "procedures"),
+    ("DiagnosticReport", "diagnostic_reports"),
+    ("Immunization", "immunizations"),
+    ("Location", "locations"),
+    ("Group", "groups"),
+    ("DocumentReference", "document_references"),
+];
+
+/// Table of a stored resource type
+pub fn resource_table(resource_type: &str) -> FhirResult<&'static str> {
+    RESOURCE_TABLES
+        .iter()
+        .find(|(name, _)| *name == resource_type)
+        .map(|(_, table)| *table)
+        .ok_or_else(|| FhirError::InvalidResourceType(resource_type.to_string()))
+}
+
+/// Column holding the patient whose compartment a row of `table` belongs
+/// to, or a NULL expression for types outside any patient compartment
+pub fn patient_column(table: &str) -> &'static str {
+    match table {
+        "patients" => "id",
+        "observations" | "conditions" | "encounters" | "medication_requests"
+        | "medication_statements" | "procedures" | "diagnostic_reports" => "subject_id",
+        "allergy_intolerances" | "immunizations" | "document_references" => "patient_id",
+        _ => "NULL::uuid",
+    }
+}
+
+/// Id of the resource a reference such as `Practitioner/123` points to, when
+/// it is a UUID
+fn reference_uuid(reference: Option<&Reference>) -> Option<Uuid> {
+    reference
+        .and_then(|r| r.reference.as_ref())
+        .and_then(|r| r.0.rsplit('/').next())
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// Id in a reference search value, `Type/id` or a bare id
+fn reference_search_id(value: &str) -> FhirResult<Uuid> {
+    let id = value.rsplit('/').next().unwrap_or(value);
+    Uuid::parse_str(id)
+        .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", value)))
+}
+
+/// First coding of `medication[x]` as (code, system), when it is a code
+fn medication_coding(medication: &CodeableConceptOrReference) -> (Option<String>, Option<String>) {
+    let coding = match medication {
+        CodeableConceptOrReference::CodeableConcept(CodeableConcept { coding, .. }) => {
+            coding.as_ref().and_then(|codings| codings.first())
+        }
+        CodeableConceptOrReference::Reference(_) => None,
+    };
+    (
+        coding.and_then(|c| c.code.as_ref()).map(|c| c.0.clone()),
+        coding.and_then(|c| c.system.as_ref()).map(|s| s.0.clone()),
+    )
+}
+
+/// Add a token search on a code/system column pair, `system|code` or `code`
+fn push_token_filter(query: &mut QueryBuilder<Postgres>, code_column: &str, system_column: &str, token: &str) {
+    let (system, code) = match token.split_once('|') {
+        Some((system, code)) => (Some(system), code),
+        None => (None, token),
+    };
+    if let Some(system) = system.filter(|s| !s.is_empty()) {
+        query.push(format!(" AND {} = ", system_column)).push_bind(system.to_string());
+    }
+    if !code.is_empty() {
+        query.push(format!(" AND {} = ", code_column)).push_bind(code.to_string());
+    }
+}
+
+/// Add a search on a code column matching any of the comma-separated values
+fn push_any_of(query: &mut QueryBuilder<Postgres>, column: &str, values: &str) {
+    let values: Vec<String> = values.split(',').map(|v| v.trim().to_string()).collect();
+    query.push(format!(" AND {} = ANY(", column)).push_bind(values).push(")");
+}
+
+/// Add a `date` search on a timestamp column. The value may carry an `eq`,
+/// `ne`, `gt`, `ge`, `lt` or `le` prefix
+fn push_date_filter(query: &mut QueryBuilder<Postgres>, column: &str, value: &str) -> FhirResult<()> {
+    let (prefix, start, end) = date_search_range(value)?;
+    match prefix {
+        "ne" => query.push(format!(" AND ({0} < ", column)).push_bind(start)
+            .push(format!(" OR {} >= ", column)).push_bind(end).push(")"),
+        "gt" => query.push(format!(" AND {} >= ", column)).push_bind(end),
+        "ge" => query.push(format!(" AND {} >= ", column)).push_bind(start),
+        "lt" => query.push(format!(" AND {} < ", column)).push_bind(start),
+        "le" => query.push(format!(" AND {} < ", column)).push_bind(end),
+        _ => query.push(format!(" AND {} >= ", column)).push_bind(start)
+            .push(format!(" AND {} < ", column)).push_bind(end),
+    };
+    Ok(())
+}
+
+/// Prefix of a date search value and the half-open range its date covers:
+/// a whole day for a date, one second for a dateTime
+fn date_search_range(value: &str) -> FhirResult<(&str, DateTime<Utc>, DateTime<Utc>)> {
+    let (prefix, date) = match value.get(..2) {
+        Some(p @ ("eq" | "ne" | "gt" | "ge" | "lt" | "le")) => (p, &value[2..]),
+        _ => ("eq", value),
+    };
+    // An unencoded '+' in a timezone offset decodes to a space
+    let date = date.trim().replace(' ', "+");
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(&date) {
+        let start = date_time.with_timezone(&Utc);
+        return Ok((prefix, start, start + Duration::seconds(1)));
+    }
+    let start = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+        .ok_or_else(|| FhirError::Validation(format!("Invalid date search value: {}", value)))?;
+    Ok((prefix, start, start + Duration::days(1)))
+}
+
+/// Mean Earth radius in meters, used for `near` distances
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Distance `near` covers when the search value leaves it out
+const DEFAULT_NEAR_DISTANCE_M: f64 = 10_000.0;
+
+/// A `near=latitude|longitude|distance|unit` search. The distance defaults
+/// to 10 km and the unit to `km`; `m` and `[mi_i]` are also understood
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearSearch {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Search radius in meters
+    pub distance: f64,
+}
+
+impl NearSearch {
+    pub fn parse(value: &str) -> FhirResult<Self> {
+        let invalid = || FhirError::Validation(format!("Invalid near search value: {}", value));
+        let mut parts = value.split('|').map(str::trim);
+        let latitude: f64 = parts.next().and_then(|p| p.parse().ok()).ok_or_else(invalid)?;
+        let longitude: f64 = parts.next().and_then(|p| p.parse().ok()).ok_or_else(invalid)?;
+        let distance = match parts.next().filter(|p| !p.is_empty()) {
+            Some(distance) => Some(distance.parse::<f64>().map_err(|_| invalid())?),
+            None => None,
+        };
+        let meters_per_unit = match parts.next().unwrap_or("") {
+            "" | "km" => 1000.0,
+            "m" => 1.0,
+            "[mi_i]" | "mi" => 1609.344,
+            unit => {
+                return Err(FhirError::Validation(format!("Unsupported near distance unit: {}", unit)));
+            }
+        };
+
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(invalid());
+        }
+        let distance = match distance {
+            Some(d) if d.is_finite() && d >= 0.0 => d * meters_per_unit,
+            Some(_) => return Err(invalid()),
+            None => DEFAULT_NEAR_DISTANCE_M,
+        };
+        Ok(Self { latitude, longitude, distance })
+    }
+
+    /// Latitude range and, unless the circle reaches a pole or the
+    /// antimeridian, longitude range of a box around the search circle
+    pub fn bounding_box(&self) -> ((f64, f64), Option<(f64, f64)>) {
+        let angle = self.distance / EARTH_RADIUS_M;
+        let delta_lat = angle.to_degrees();
+        let (min_lat, max_lat) = (self.latitude - delta_lat, self.latitude + delta_lat);
+        if min_lat <= -90.0 || max_lat >= 90.0 {
+            return ((min_lat.max(-90.0), max_lat.min(90.0)), None);
+        }
+
+        let delta_lon = (angle.sin() / self.latitude.to_radians().cos()).asin().to_degrees();
+        let (min_lon, max_lon) = (self.longitude - delta_lon, self.longitude + delta_lon);
+        let longitudes = (min_lon >= -180.0 && max_lon <= 180.0).then_some((min_lon, max_lon));
+        ((min_lat, max_lat), longitudes)
+    }
+}
+
+/// Bind parameters Postgres accepts in one statement; multi-row inserts are
+/// split to stay under it
+pub const BIND_LIMIT: usize = 65535;
+
+/// Outcome of a multi-row insert of imported resources. Resources in neither
+/// list were skipped because their id already exists
+#[derive(Debug, Clone, Default)]
+pub struct BatchInsert {
+    pub inserted: Vec<Uuid>,
+    /// Resources whose subject Patient is not stored
+    pub missing_subject: Vec<Uuid>,
+}
+
+/// Id of a resource about to be stored, which must already be a UUID
+fn stored_id<T: Resource>(resource: &T) -> FhirResult<Uuid> {
+    resource
+        .id()
+        .and_then(|id| Uuid::parse_str(&id.0).ok())
+        .ok_or_else(|| FhirError::Database("Failed to parse UUID".to_string()))
+}
+
+/// The patients among `ids` that are stored, for the `subject_id` foreign keys
+async fn existing_patients(tx: &mut Transaction<'_, Postgres>, ids: &[Uuid]) -> FhirResult<HashSet<Uuid>> {
+    if ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let existing: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM patients WHERE id = ANY($1)")
+        .bind(ids)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+    Ok(existing.into_iter().collect())
+}
+
+/// Record version 1 of newly inserted resources in `{table}_history`
+async fn insert_history(tx: &mut Transaction<'_, Postgres>, table: &str, ids: &[Uuid]) -> FhirResult<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO {table}_history (id, version_id, resource, last_updated, operation)
+        SELECT id, 1, resource, last_updated, 'CREATE'
+        FROM {table}
+        WHERE id = ANY($1)
+        "#
+    ))
+    .bind(ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| FhirError::Database(e.to_string()))?;
+    Ok(())
+}
+
+/// Rows `$reindex` reads from a resource table
+#[derive(Debug, Clone, Copy)]
+pub enum ReindexSelection {
+    /// One resource
+    One(Uuid),
+    /// Up to `limit` resources after the given id, in id order
+    After { after: Option<Uuid>, limit: i64 },
+}
+
+/// Outcome of reindexing one selection
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReindexPage {
+    /// Rows whose search columns were rewritten
+    pub updated: u64,
+    /// Last id read, to continue paging from; `None` when nothing was read
+    pub last_id: Option<Uuid>,
+}
+
+/// Live rows of `table`, parsed from their stored JSON, with the last id
+/// read. Rows that no longer parse are logged and skipped
+async fn stored_rows<T: DeserializeOwned>(
+    pool: &PgPool,
+    table: &str,
+    selection: ReindexSelection,
+) -> FhirResult<(Vec<(Uuid, T)>, Option<Uuid>)> {
+    let (one, after, limit) = match selection {
+        ReindexSelection::One(id) => (Some(id), None, 1),
+        ReindexSelection::After { after, limit } => (None, after, limit),
+    };
+    let records = sqlx::query(&format!(
+        r#"
+        SELECT id, resource FROM {table}
+        WHERE deleted_at IS NULL
+          AND ($1::uuid IS NULL OR id = $1)
+          AND ($2::uuid IS NULL OR id > $2)
+        ORDER BY id
+        LIMIT $3
+        "#
+    ))
+    .bind(one)
+    .bind(after)
+    .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+    let mut rows = Vec::with_capacity(records.len());
+    let mut last_id = None;
+    for record in records {
+        let id: Uuid = record.try_get("id")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        let resource: serde_json::Value = record.try_get("resource")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        last_id = Some(id);
+        match serde_json::from_value(resource) {
+            Ok(resource) => rows.push((id, resource)),
+            Err(e) => tracing::warn!("Skipping {} row {}: {}", table, id, e),
+        }
+    }
+    Ok((rows, last_id))
+}
 
 /// Base trait for all resource repositories
 #[async_trait::async_trait]
@@ -74,10 +430,184 @@ impl SearchParams {
         self.offset = Some(offset);
         self
     }
+
+    /// Add `name=value` query filters as equality filters
+    pub fn with_filters(self, filters: &[(String, String)]) -> Self {
+        filters.iter().fold(self, |params, (field, value)| {
+            params.add_filter(field.clone(), SearchOperator::Equals, value.clone())
+        })
+    }
+}
+
+impl SearchParams {
+    /// JSONB containment documents for the `_tag`, `_profile` and `_security`
+    /// filters, one list per parameter holding a document for each of its
+    /// comma-separated values. A resource matches when, for every
+    /// parameter, it contains one of its documents (see `push_meta_filter`).
+    /// Tokens are `system|code`, `system|` or `code`
+    pub fn meta_filter(&self) -> Vec<Vec<serde_json::Value>> {
+        self.filters
+            .iter()
+            .filter_map(|filter| {
+                let (key, token): (&str, fn(&str) -> serde_json::Value) = match filter.field.as_str() {
+                    "_tag" => ("tag", token_coding),
+                    "_security" => ("security", token_coding),
+                    "_profile" => ("profile", |url| serde_json::Value::String(url.to_string())),
+                    _ => return None,
+                };
+                let documents = filter.value
+                    .split(',')
+                    .filter(|value| !value.is_empty())
+                    .map(|value| serde_json::json!({ "meta": { key: [token(value)] } }))
+                    .collect();
+                Some(documents)
+            })
+            .collect()
+    }
+}
+
+/// Add a `meta_filter` to a query on a resource table: `AND (resource @> $a
+/// OR resource @> $b)` for each parameter, so the GIN index on `resource`
+/// serves every alternative
+pub fn push_meta_filter(query: &mut QueryBuilder<'_, Postgres>, meta: &[Vec<serde_json::Value>]) {
+    for documents in meta.iter().filter(|documents| !documents.is_empty()) {
+        query.push(" AND (");
+        for (i, document) in documents.iter().enumerate() {
+            if i > 0 {
+                query.push(" OR ");
+            }
+            query.push("resource @> ").push_bind(document.clone());
+        }
+        query.push(")");
+    }
+}
+
+/// JSONB containment document for an `identifier` search token,
+/// `system|value` or `value`
+pub fn identifier_filter(token: &str) -> serde_json::Value {
+    let mut identifier = serde_json::Map::new();
+    let (system, value) = match token.split_once('|') {
+        Some((system, value)) => (system, value),
+        None => ("", token),
+    };
+    if !system.is_empty() {
+        identifier.insert("system".to_string(), system.into());
+    }
+    if !value.is_empty() {
+        identifier.insert("value".to_string(), value.into());
+    }
+    serde_json::json!({ "identifier": [identifier] })
+}
+
+/// JSONB containment document for a token search on a repeating
+/// CodeableConcept element, such as `specialty`
+pub fn codeable_concept_filter(element: &str, token: &str) -> serde_json::Value {
+    serde_json::json!({ element: [{ "coding": [token_coding(token)] }] })
+}
+
+fn token_coding(token: &str) -> serde_json::Value {
+    let mut coding = serde_json::Map::new();
+    let (system, code) = match token.split_once('|') {
+        Some((system, code)) => (system, code),
+        None => ("", token),
+    };
+    if !system.is_empty() {
+        coding.insert("system".to_string(), system.into());
+    }
+    if !code.is_empty() {
+        coding.insert("code".to_string(), code.into());
+    }
+    serde_json::Value::Object(coding)
 }
 
 impl Default for SearchParams {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_filter() {
+        assert!(SearchParams::new().meta_filter().is_empty());
+
+        let params = SearchParams::new()
+            .add_filter("_tag".to_string(), SearchOperator::Equals, "http://example.org/tags|needs-review".to_string())
+            .add_filter("_security".to_string(), SearchOperator::Equals, "R".to_string())
+            .add_filter("_profile".to_string(), SearchOperator::Equals, "http://example.org/StructureDefinition/vitals".to_string())
+            .add_filter("family".to_string(), SearchOperator::Equals, "Smith".to_string());
+        assert_eq!(
+            params.meta_filter(),
+            vec![
+                vec![serde_json::json!({ "meta": { "tag": [{ "system": "http://example.org/tags", "code": "needs-review" }] } })],
+                vec![serde_json::json!({ "meta": { "security": [{ "code": "R" }] } })],
+                vec![serde_json::json!({ "meta": { "profile": ["http://example.org/StructureDefinition/vitals"] } })],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_meta_filter_values_are_alternatives() {
+        let params = SearchParams::new()
+            .add_filter("_tag".to_string(), SearchOperator::Equals, "a,urn:x|b".to_string())
+            .add_filter("_security".to_string(), SearchOperator::Equals, "R".to_string());
+        let meta = params.meta_filter();
+        assert_eq!(
+            meta[0],
+            vec![
+                serde_json::json!({ "meta": { "tag": [{ "code": "a" }] } }),
+                serde_json::json!({ "meta": { "tag": [{ "system": "urn:x", "code": "b" }] } }),
+            ]
+        );
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new("SELECT resource FROM patients WHERE deleted_at IS NULL");
+        push_meta_filter(&mut query, &meta);
+        assert_eq!(
+            query.sql(),
+            "SELECT resource FROM patients WHERE deleted_at IS NULL \
+             AND (resource @> $1 OR resource @> $2) AND (resource @> $3)"
+        );
+    }
+
+    #[test]
+    fn test_date_search_range() {
+        let (prefix, start, end) = date_search_range("ge2024-03-01").unwrap();
+        assert_eq!(prefix, "ge");
+        assert_eq!(start.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2024-03-02T00:00:00+00:00");
+
+        let (prefix, start, end) = date_search_range("2024-03-01T10:00:00 02:00").unwrap();
+        assert_eq!(prefix, "eq");
+        assert_eq!(start.to_rfc3339(), "2024-03-01T08:00:00+00:00");
+        assert_eq!((end - start).num_seconds(), 1);
+
+        assert!(date_search_range("lt-March").is_err());
+    }
+
+    #[test]
+    fn test_near_search() {
+        let near = NearSearch::parse("42.256500|-83.694710|11.20|km").unwrap();
+        assert_eq!(near.latitude, 42.2565);
+        assert_eq!(near.longitude, -83.69471);
+        assert!((near.distance - 11_200.0).abs() < 1e-6);
+
+        assert_eq!(NearSearch::parse("42|-83|500|m").unwrap().distance, 500.0);
+        assert_eq!(NearSearch::parse("42|-83").unwrap().distance, 10_000.0);
+        assert!((NearSearch::parse("42|-83|1|[mi_i]").unwrap().distance - 1609.344).abs() < 1e-9);
+        assert!(NearSearch::parse("42|-83|1|furlong").is_err());
+        assert!(NearSearch::parse("95|-83|1|km").is_err());
+        assert!(NearSearch::parse("42").is_err());
+
+        // About 0.09 degrees of latitude per 10 km, and more longitude away from the equator
+        let ((min_lat, max_lat), longitudes) = NearSearch::parse("60|10|10|km").unwrap().bounding_box();
+        assert!((max_lat - 60.0 - 0.0899).abs() < 1e-3 && (60.0 - min_lat - 0.0899).abs() < 1e-3);
+        let (min_lon, max_lon) = longitudes.unwrap();
+        assert!((max_lon - 10.0 - 0.1799).abs() < 1e-3 && (10.0 - min_lon - 0.1799).abs() < 1e-3);
+
+        // Circles over a pole or the antimeridian are not bounded in longitude
+        assert!(NearSearch::parse("89.95|0|10|km").unwrap().bounding_box().1.is_none());
+        assert!(NearSearch::parse("0|179.95|10|km").unwrap().bounding_box().1.is_none());
+    }
+}
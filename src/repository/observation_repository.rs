@@ -1,11 +1,14 @@
 // src/repository/observation_repository.rs
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-use crate::domain::{Observation, Id, Meta, Instant, FhirError, FhirResult};
-use super::{Repository, SearchParams};
+use crate::domain::{Observation, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, insert_history, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
 use crate::domain::resources::observation::ObservationEffective;
 use crate::domain::resources::Resource;
 
@@ -47,7 +50,141 @@ impl ObservationRepository {
             issued: obs.issued.as_ref().map(|i| i.0),
         }
     }
+
+    /// Insert imported observations as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        observations: &[Observation],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(observations.len());
+        for observation in observations {
+            rows.push((stored_id(observation)?, serde_json::to_value(observation)?, self.extract_search_fields(observation)));
+        }
+
+        // subject_id references patients, so rows for unknown patients are set aside
+        let subjects: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.subject_id).collect();
+        let existing = existing_patients(tx, &subjects).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.subject_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 9).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO observations (id, resource, status, subject_id, category_code, code_code, code_system, effective_datetime, issued) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.status)
+                    .push_bind(fields.subject_id)
+                    .push_bind(fields.category_code)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.effective_datetime)
+                    .push_bind(fields.issued);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "observations", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected observations from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Observation>(&self.pool, "observations", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // subject_id references patients; rows pointing at an unknown patient keep their columns
+        let subjects: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, observation)| self.extract_search_fields(observation).subject_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &subjects).await?;
+
+        let mut updated = 0;
+        for (id, observation) in &rows {
+            let fields = self.extract_search_fields(observation);
+            if fields.subject_id.is_some_and(|subject| !existing.contains(&subject)) {
+                tracing::warn!("Not reindexing Observation/{}: subject patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE observations
+                SET status = $2,
+                    subject_id = $3,
+                    category_code = $4,
+                    code_code = $5,
+                    code_system = $6,
+                    effective_datetime = $7,
+                    issued = $8
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.status)
+            .bind(fields.subject_id)
+            .bind(fields.category_code)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.effective_datetime)
+            .bind(fields.issued)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
     
+    /// Observations recorded during an encounter (`encounter` reference)
+    pub async fn search_by_encounter(&self, encounter_id: &str) -> FhirResult<Vec<Observation>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM observations
+            WHERE resource @> jsonb_build_object(
+                'encounter', jsonb_build_object('reference', 'Encounter/' || $1)
+            )
+            AND deleted_at IS NULL
+            ORDER BY effective_datetime DESC
+            "#
+        )
+        .bind(encounter_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut observations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let obs: Observation = serde_json::from_value(resource_json)?;
+            observations.push(obs);
+        }
+
+        Ok(observations)
+    }
+
     pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<Observation>> {
         let uuid = Uuid::parse_str(patient_id)
             .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
@@ -77,6 +214,133 @@ impl ObservationRepository {
         Ok(observations)
     }
     
+    /// The most recent `max` observations of each code for a patient
+    /// ($lastn), ranked in SQL with a window over `effective_datetime`
+    pub async fn last_n(
+        &self,
+        patient_id: &str,
+        category: Option<&str>,
+        codes: Option<&[String]>,
+        max: i64,
+    ) -> FhirResult<Vec<Observation>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM (
+                SELECT resource, code_code, effective_datetime,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY code_code
+                           ORDER BY effective_datetime DESC NULLS LAST, issued DESC NULLS LAST
+                       ) AS recency
+                FROM observations
+                WHERE subject_id = $1
+                  AND deleted_at IS NULL
+                  AND code_code IS NOT NULL
+                  AND ($2::text IS NULL OR category_code = $2)
+                  AND ($3::text[] IS NULL OR code_code = ANY($3))
+            ) ranked
+            WHERE recency <= $4
+            ORDER BY code_code, recency
+            "#
+        )
+        .bind(uuid)
+        .bind(category)
+        .bind(codes)
+        .bind(max)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut observations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let obs: Observation = serde_json::from_value(resource_json)?;
+            observations.push(obs);
+        }
+
+        Ok(observations)
+    }
+
+    /// A patient's observations whose code, or one of whose component codes,
+    /// is in `codes`, effective within the optional window ($stats)
+    pub async fn search_by_patient_and_codes(
+        &self,
+        patient_id: &str,
+        codes: &[String],
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> FhirResult<Vec<Observation>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM observations
+            WHERE subject_id = $1
+              AND deleted_at IS NULL
+              AND (
+                  code_code = ANY($2)
+                  OR EXISTS (
+                      SELECT 1
+                      FROM jsonb_array_elements(COALESCE(resource->'component', '[]'::jsonb)) AS component,
+                           jsonb_array_elements(COALESCE(component->'code'->'coding', '[]'::jsonb)) AS coding
+                      WHERE coding->>'code' = ANY($2)
+                  )
+              )
+              AND ($3::timestamptz IS NULL OR effective_datetime >= $3)
+              AND ($4::timestamptz IS NULL OR effective_datetime <= $4)
+            ORDER BY effective_datetime
+            "#
+        )
+        .bind(uuid)
+        .bind(codes)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut observations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let obs: Observation = serde_json::from_value(resource_json)?;
+            observations.push(obs);
+        }
+
+        Ok(observations)
+    }
+
+    /// The stored observations among `ids`, in no particular order
+    pub async fn read_many(&self, ids: &[Uuid]) -> FhirResult<Vec<Observation>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM observations
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            "#
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut observations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let obs: Observation = serde_json::from_value(resource_json)?;
+            observations.push(obs);
+        }
+
+        Ok(observations)
+    }
+
     pub async fn search_by_code(&self, code: &str) -> FhirResult<Vec<Observation>> {
         let rows = sqlx::query(
             r#"
@@ -112,14 +376,7 @@ impl Repository<Observation> for ObservationRepository {
         let id = Uuid::new_v4().to_string();
         obs.set_id(Id(id.clone()));
         
-        let meta = Meta {
-            version_id: Some(Id("1".to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(obs.meta.as_ref(), 1);
         obs.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&obs);
@@ -202,8 +459,8 @@ impl Repository<Observation> for ObservationRepository {
                 id: id.to_string(),
             })?;
         
-        let current_version = current.meta
-            .and_then(|m| m.version_id)
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
             .and_then(|v| v.0.parse::<i32>().ok())
             .unwrap_or(1);
         
@@ -212,14 +469,7 @@ impl Repository<Observation> for ObservationRepository {
         let mut updated_obs = observation.clone();
         updated_obs.set_id(Id(id.to_string()));
         
-        let meta = Meta {
-            version_id: Some(Id(new_version.to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(updated_obs.meta.as_ref().or(current.meta.as_ref()), new_version);
         updated_obs.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&updated_obs);
@@ -302,20 +552,18 @@ impl Repository<Observation> for ObservationRepository {
         let limit = params.limit.unwrap_or(100);
         let offset = params.offset.unwrap_or(0);
         
-        let rows = sqlx::query(
-            r#"
-            SELECT resource
-            FROM observations
-            WHERE deleted_at IS NULL
-            ORDER BY last_updated DESC
-            LIMIT $1 OFFSET $2
-            "#
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| FhirError::Database(e.to_string()))?;
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM observations WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
         
         let mut observations = Vec::new();
         for row in rows {
@@ -0,0 +1,403 @@
+// src/repository/organization_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{Organization, Id, Meta, FhirError, FhirResult};
+use super::{
+    identifier_filter, insert_history, reference_search_id, reference_uuid, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct OrganizationRepository {
+    pool: PgPool,
+}
+
+impl OrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+    
+    /// Extract searchable fields from Organization resource
+    fn extract_search_fields(&self, organization: &Organization) -> OrganizationSearchFields {
+        OrganizationSearchFields {
+            active: organization.active.as_ref().map(|b| b.0),
+            name: organization.name.as_ref().map(|n| n.0.clone()),
+            part_of_id: reference_uuid(organization.part_of.as_ref()),
+        }
+    }
+
+    /// Insert imported organizations as version 1 in multi-row statements,
+    /// with the same search columns as `create`. Ids that already exist are
+    /// skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        organizations: &[Organization],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(organizations.len());
+        for organization in organizations {
+            rows.push((stored_id(organization)?, serde_json::to_value(organization)?, self.extract_search_fields(organization)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 5).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO organizations (id, resource, active, name, part_of_id) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.active)
+                    .push_bind(fields.name)
+                    .push_bind(fields.part_of_id);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "organizations", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected organizations from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Organization>(&self.pool, "organizations", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, organization) in &rows {
+            let fields = self.extract_search_fields(organization);
+            sqlx::query(
+                r#"
+                UPDATE organizations
+                SET active = $2,
+                    name = $3,
+                    part_of_id = $4
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.active)
+            .bind(fields.name)
+            .bind(fields.part_of_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+    
+    /// Ids of every organization below `id` in the `partOf` hierarchy
+    pub async fn descendant_ids(&self, id: Uuid) -> FhirResult<Vec<Uuid>> {
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new("");
+        push_descendants(&mut query, id);
+        query
+            .build_query_scalar()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))
+    }
+    
+    /// Get organization history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Organization>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM organizations_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut organizations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let organization: Organization = serde_json::from_value(resource_json)?;
+            organizations.push(organization);
+        }
+        
+        Ok(organizations)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Organization> for OrganizationRepository {
+    async fn create(&self, organization: &Organization) -> FhirResult<Organization> {
+        let mut organization = organization.clone();
+        
+        let id = Uuid::new_v4().to_string();
+        organization.set_id(Id(id.clone()));
+        
+        let meta = Meta::versioned(organization.meta.as_ref(), 1);
+        organization.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&organization);
+        let resource_json = serde_json::to_value(&organization)?;
+        
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO organizations (
+                id, resource, active, name, part_of_id
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.active)
+        .bind(search_fields.name)
+        .bind(search_fields.part_of_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO organizations_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(organization)
+    }
+    
+    async fn read(&self, id: &str) -> FhirResult<Option<Organization>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM organizations
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let organization: Organization = serde_json::from_value(resource_json)?;
+            Ok(Some(organization))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    async fn update(&self, id: &str, organization: &Organization) -> FhirResult<Organization> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Organization".to_string(),
+                id: id.to_string(),
+            })?;
+        
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+        
+        let new_version = current_version + 1;
+        
+        let mut updated_organization = organization.clone();
+        updated_organization.set_id(Id(id.to_string()));
+        
+        let meta = Meta::versioned(updated_organization.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_organization.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&updated_organization);
+        let resource_json = serde_json::to_value(&updated_organization)?;
+        
+        sqlx::query(
+            r#"
+            UPDATE organizations
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                active = $4,
+                name = $5,
+                part_of_id = $6
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.active)
+        .bind(search_fields.name)
+        .bind(search_fields.part_of_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO organizations_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(updated_organization)
+    }
+    
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Organization".to_string(),
+                id: id.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Honors `name` (contains, case-insensitive, on the name or any
+    /// alias), `identifier`, `active` and `partof` with its `:above` and
+    /// `:below` modifiers, plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Organization>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+        
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM organizations WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "name" => {
+                    let pattern = format!("%{}%", value);
+                    query.push(" AND (name ILIKE ").push_bind(pattern.clone())
+                        .push(" OR EXISTS (SELECT 1 FROM jsonb_array_elements_text(resource->'alias') alias WHERE alias ILIKE ")
+                        .push_bind(pattern).push("))");
+                }
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                "active" => {
+                    query.push(" AND active = ").push_bind(value == "true");
+                }
+                "partof" => {
+                    query.push(" AND part_of_id = ").push_bind(reference_search_id(value)?);
+                }
+                "partof:below" => {
+                    query.push(" AND id IN (");
+                    push_descendants(&mut query, reference_search_id(value)?);
+                    query.push(")");
+                }
+                "partof:above" => {
+                    query.push(" AND id IN (");
+                    push_ancestors(&mut query, reference_search_id(value)?);
+                    query.push(")");
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+        
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut organizations = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let organization: Organization = serde_json::from_value(resource_json)?;
+            organizations.push(organization);
+        }
+        
+        Ok(organizations)
+    }
+}
+
+struct OrganizationSearchFields {
+    active: Option<bool>,
+    name: Option<String>,
+    part_of_id: Option<Uuid>,
+}
+
+/// Query for the ids of the organizations below `id`, following `partOf`
+/// down through any number of levels. UNION stops at cycles
+fn push_descendants(query: &mut QueryBuilder<Postgres>, id: Uuid) {
+    query
+        .push("WITH RECURSIVE below(id) AS (SELECT id FROM organizations WHERE deleted_at IS NULL AND part_of_id = ")
+        .push_bind(id)
+        .push(" UNION SELECT o.id FROM organizations o JOIN below ON o.part_of_id = below.id WHERE o.deleted_at IS NULL)")
+        .push(" SELECT id FROM below");
+}
+
+/// Query for the ids of the organizations `id` is part of, directly or
+/// through its parents
+fn push_ancestors(query: &mut QueryBuilder<Postgres>, id: Uuid) {
+    query
+        .push("WITH RECURSIVE above(id) AS (SELECT part_of_id FROM organizations WHERE deleted_at IS NULL AND id = ")
+        .push_bind(id)
+        .push(" UNION SELECT o.part_of_id FROM organizations o JOIN above ON o.id = above.id WHERE o.deleted_at IS NULL)")
+        .push(" SELECT id FROM above");
+}
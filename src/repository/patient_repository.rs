@@ -1,13 +1,33 @@
 // src/repository/patient_repository.rs
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::NaiveDate;
 
-use crate::domain::{Patient, Id, Meta, Instant, FhirError, FhirResult};
-use super::{Repository, SearchParams};
+use crate::domain::{Patient, Id, Meta, FhirError, FhirResult};
+use super::{
+    patient_column, resource_table, insert_history, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
 use crate::domain::resources::patient::PatientDeceased;
 use crate::domain::resources::Resource;
+
+/// Resource types in a patient's compartment, with the element referencing
+/// the patient. Their tables and compartment columns come from
+/// `resource_table` and `patient_column`
+const PATIENT_REFERENCES: &[(&str, &str)] = &[
+    ("Observation", "subject"),
+    ("Condition", "subject"),
+    ("Encounter", "subject"),
+    ("MedicationRequest", "subject"),
+    ("MedicationStatement", "subject"),
+    ("AllergyIntolerance", "patient"),
+    ("Procedure", "subject"),
+    ("DiagnosticReport", "subject"),
+    ("Immunization", "patient"),
+    ("DocumentReference", "subject"),
+];
+
 pub struct PatientRepository {
     pool: PgPool,
 }
@@ -39,6 +59,90 @@ impl PatientRepository {
             },
         }
     }
+
+    /// Insert imported patients as version 1 in multi-row statements, with
+    /// the same search columns as `create`. Ids that already exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        patients: &[Patient],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(patients.len());
+        for patient in patients {
+            rows.push((stored_id(patient)?, serde_json::to_value(patient)?, self.extract_search_fields(patient)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 8).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO patients (id, resource, active, family_name, given_name, gender, birth_date, deceased) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.active)
+                    .push_bind(fields.family_name)
+                    .push_bind(fields.given_name)
+                    .push_bind(fields.gender)
+                    .push_bind(fields.birth_date)
+                    .push_bind(fields.deceased);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "patients", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected patients from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Patient>(&self.pool, "patients", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, patient) in &rows {
+            let fields = self.extract_search_fields(patient);
+            sqlx::query(
+                r#"
+                UPDATE patients
+                SET active = $2,
+                    family_name = $3,
+                    given_name = $4,
+                    gender = $5,
+                    birth_date = $6,
+                    deceased = $7
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.active)
+            .bind(fields.family_name)
+            .bind(fields.given_name)
+            .bind(fields.gender)
+            .bind(fields.birth_date)
+            .bind(fields.deceased)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
     
     /// Get patient history (all versions)
     pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Patient>> {
@@ -97,6 +201,205 @@ impl PatientRepository {
         Ok(patients)
     }
     
+    /// Candidate records for Patient/$match: patients sharing an identifier
+    /// value, the birth date, or the first letters of the family name
+    pub async fn find_match_candidates(
+        &self,
+        family_prefixes: &[String],
+        birth_date: Option<NaiveDate>,
+        identifier_values: &[String],
+    ) -> FhirResult<Vec<Patient>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM patients
+            WHERE deleted_at IS NULL
+              AND (
+                  LEFT(LOWER(family_name), 2) = ANY($1)
+                  OR birth_date = $2
+                  OR EXISTS (
+                      SELECT 1
+                      FROM jsonb_array_elements(COALESCE(resource->'identifier', '[]'::jsonb)) AS identifier
+                      WHERE identifier->>'value' = ANY($3)
+                  )
+              )
+            LIMIT 1000
+            "#
+        )
+        .bind(family_prefixes)
+        .bind(birth_date)
+        .bind(identifier_values)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut patients = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let patient: Patient = serde_json::from_value(resource_json)?;
+            patients.push(patient);
+        }
+
+        Ok(patients)
+    }
+
+    /// References ("Observation/123") to every resource referencing the
+    /// patient, i.e. what `merge` would re-point
+    pub async fn subject_references(&self, patient_id: &str) -> FhirResult<Vec<String>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let mut references = Vec::new();
+        for (resource_type, _) in PATIENT_REFERENCES {
+            let table = resource_table(resource_type)?;
+            let rows = sqlx::query(&format!(
+                "SELECT id FROM {} WHERE {} = $1 AND deleted_at IS NULL ORDER BY id",
+                table, patient_column(table)
+            ))
+            .bind(uuid)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+            for row in rows {
+                let id: Uuid = row.try_get("id")
+                    .map_err(|e| FhirError::Database(e.to_string()))?;
+                references.push(format!("{}/{}", resource_type, id));
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// The stored patients among `ids`, in no particular order
+    pub async fn existing_ids(&self, ids: &[Uuid]) -> FhirResult<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        sqlx::query_scalar("SELECT id FROM patients WHERE id = ANY($1) AND deleted_at IS NULL")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))
+    }
+
+    /// Merge `source` into `target` in one transaction: store the given
+    /// versions of both patients (carrying their new links) and re-point every
+    /// resource referencing the source, writing a history row for each
+    /// changed resource. Returns the stored target and the moved references
+    pub async fn merge(&self, source: &Patient, target: &Patient) -> FhirResult<(Patient, Vec<String>)> {
+        let source_id = source.id.as_ref().map(|id| id.0.clone()).unwrap_or_default();
+        let target_id = target.id.as_ref().map(|id| id.0.clone()).unwrap_or_default();
+        let source_uuid = Uuid::parse_str(&source_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", source_id)))?;
+        let target_uuid = Uuid::parse_str(&target_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", target_id)))?;
+
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        self.write_version(&mut tx, source_uuid, source).await?;
+        let target = self.write_version(&mut tx, target_uuid, target).await?;
+
+        let mut moved = Vec::new();
+        for (resource_type, element) in PATIENT_REFERENCES {
+            let rows = sqlx::query(&repoint_statement(resource_table(resource_type)?, element))
+                .bind(source_uuid)
+                .bind(target_uuid)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+
+            for row in rows {
+                let id: Uuid = row.try_get("id")
+                    .map_err(|e| FhirError::Database(e.to_string()))?;
+                moved.push(format!("{}/{}", resource_type, id));
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok((target, moved))
+    }
+
+    /// Store a new version of a patient inside a transaction, with history
+    async fn write_version(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        uuid: Uuid,
+        patient: &Patient,
+    ) -> FhirResult<Patient> {
+        let row = sqlx::query(
+            "SELECT version_id FROM patients WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"
+        )
+        .bind(uuid)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?
+        .ok_or_else(|| FhirError::NotFound {
+            resource_type: "Patient".to_string(),
+            id: uuid.to_string(),
+        })?;
+        let current_version: i32 = row.try_get("version_id")
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        let new_version = current_version + 1;
+
+        let mut updated_patient = patient.clone();
+        updated_patient.set_id(Id(uuid.to_string()));
+        let meta = Meta::versioned(updated_patient.meta.as_ref(), new_version);
+        updated_patient.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_patient);
+        let resource_json = serde_json::to_value(&updated_patient)?;
+
+        sqlx::query(
+            r#"
+            UPDATE patients
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                active = $4,
+                family_name = $5,
+                given_name = $6,
+                gender = $7,
+                birth_date = $8,
+                deceased = $9
+            WHERE id = $1
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.active)
+        .bind(search_fields.family_name)
+        .bind(search_fields.given_name)
+        .bind(search_fields.gender)
+        .bind(search_fields.birth_date)
+        .bind(search_fields.deceased)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO patients_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_patient)
+    }
+
     /// Search by identifier
     pub async fn search_by_identifier(&self, system: &str, value: &str) -> FhirResult<Option<Patient>> {
         let row = sqlx::query(
@@ -139,14 +442,7 @@ impl Repository<Patient> for PatientRepository {
         patient.set_id(Id(id.clone()));
         
         // Set meta
-        let meta = Meta {
-            version_id: Some(Id("1".to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(patient.meta.as_ref(), 1);
         patient.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&patient);
@@ -229,8 +525,8 @@ impl Repository<Patient> for PatientRepository {
                 id: id.to_string(),
             })?;
         
-        let current_version = current.meta
-            .and_then(|m| m.version_id)
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
             .and_then(|v| v.0.parse::<i32>().ok())
             .unwrap_or(1);
         
@@ -239,14 +535,7 @@ impl Repository<Patient> for PatientRepository {
         let mut updated_patient = patient.clone();
         updated_patient.set_id(Id(id.to_string()));
         
-        let meta = Meta {
-            version_id: Some(Id(new_version.to_string())),
-            last_updated: Some(Instant(Utc::now())),
-            source: None,
-            profile: None,
-            security: None,
-            tag: None,
-        };
+        let meta = Meta::versioned(updated_patient.meta.as_ref().or(current.meta.as_ref()), new_version);
         updated_patient.set_meta(meta);
         
         let search_fields = self.extract_search_fields(&updated_patient);
@@ -324,25 +613,27 @@ impl Repository<Patient> for PatientRepository {
         Ok(())
     }
     
+    /// Honors `family` (case-insensitive substring of the family name) and
+    /// the meta filters
     async fn search(&self, params: SearchParams) -> FhirResult<Vec<Patient>> {
-        // Basic search implementation - can be extended
         let limit = params.limit.unwrap_or(100);
         let offset = params.offset.unwrap_or(0);
         
-        let rows = sqlx::query(
-            r#"
-            SELECT resource
-            FROM patients
-            WHERE deleted_at IS NULL
-            ORDER BY last_updated DESC
-            LIMIT $1 OFFSET $2
-            "#
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| FhirError::Database(e.to_string()))?;
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM patients WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in params.filters.iter().filter(|filter| filter.field == "family") {
+            query.push(" AND family_name ILIKE ").push_bind(format!("%{}%", filter.value));
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
         
         let mut patients = Vec::new();
         for row in rows {
@@ -356,6 +647,36 @@ impl Repository<Patient> for PatientRepository {
     }
 }
 
+/// Move the rows of `table` in the compartment of patient `$1` to patient
+/// `$2`, rewriting the reference in `element` and recording new versions
+fn repoint_statement(table: &str, element: &str) -> String {
+    format!(
+        r#"
+        WITH moved AS (
+            UPDATE {table}
+            SET {column} = $2,
+                version_id = version_id + 1,
+                last_updated = NOW(),
+                resource = jsonb_set(
+                    jsonb_set(
+                        jsonb_set(resource, '{{{element},reference}}', to_jsonb('Patient/' || $2::text)),
+                        '{{meta,versionId}}', to_jsonb((version_id + 1)::text)
+                    ),
+                    '{{meta,lastUpdated}}', to_jsonb(NOW())
+                )
+            WHERE {column} = $1 AND deleted_at IS NULL
+            RETURNING id, version_id, resource, last_updated
+        )
+        INSERT INTO {table}_history (id, version_id, resource, last_updated, operation)
+        SELECT id, version_id, resource, last_updated, 'UPDATE' FROM moved
+        RETURNING id
+        "#,
+        table = table,
+        column = patient_column(table),
+        element = element,
+    )
+}
+
 struct PatientSearchFields {
     active: Option<bool>,
     family_name: Option<String>,
@@ -363,4 +684,30 @@ struct PatientSearchFields {
     gender: Option<String>,
     birth_date: Option<chrono::NaiveDate>,
     deceased: Option<bool>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::RESOURCE_TABLES;
+
+    #[test]
+    fn test_merge_repoints_patient_references() {
+        for (resource_type, _) in PATIENT_REFERENCES {
+            let table = resource_table(resource_type).unwrap();
+            assert_ne!(patient_column(table), "NULL::uuid", "{} is outside the patient compartment", table);
+        }
+        // Every type in a patient compartment is re-pointed
+        for (resource_type, table) in RESOURCE_TABLES.iter().filter(|(t, _)| *t != "Patient") {
+            if patient_column(table) != "NULL::uuid" {
+                assert!(PATIENT_REFERENCES.iter().any(|(t, _)| t == resource_type), "{} is not re-pointed", resource_type);
+            }
+        }
+
+        let statement = repoint_statement("allergy_intolerances", "patient");
+        assert!(statement.contains("SET patient_id = $2"));
+        assert!(statement.contains("WHERE patient_id = $1"));
+        assert!(statement.contains("'{patient,reference}'"));
+        assert!(statement.contains("INSERT INTO allergy_intolerances_history"));
+    }
+}
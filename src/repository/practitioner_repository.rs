@@ -0,0 +1,380 @@
+// src/repository/practitioner_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{Practitioner, Id, Meta, FhirError, FhirResult};
+use super::{
+    identifier_filter, insert_history, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct PractitionerRepository {
+    pool: PgPool,
+}
+
+impl PractitionerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+    
+    /// Extract searchable fields from Practitioner resource
+    fn extract_search_fields(&self, practitioner: &Practitioner) -> PractitionerSearchFields {
+        let name = practitioner.name.as_ref().and_then(|names| names.first());
+        PractitionerSearchFields {
+            active: practitioner.active.as_ref().map(|b| b.0),
+            family_name: name
+                .and_then(|name| name.family.as_ref())
+                .map(|f| f.0.clone()),
+            given_name: name
+                .and_then(|name| name.given.as_ref())
+                .and_then(|given| given.first())
+                .map(|g| g.0.clone()),
+            gender: practitioner.gender.as_ref().map(|g| g.0.clone()),
+        }
+    }
+
+    /// Insert imported practitioners as version 1 in multi-row statements,
+    /// with the same search columns as `create`. Ids that already exist are
+    /// skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        practitioners: &[Practitioner],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(practitioners.len());
+        for practitioner in practitioners {
+            rows.push((stored_id(practitioner)?, serde_json::to_value(practitioner)?, self.extract_search_fields(practitioner)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 6).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO practitioners (id, resource, active, family_name, given_name, gender) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.active)
+                    .push_bind(fields.family_name)
+                    .push_bind(fields.given_name)
+                    .push_bind(fields.gender);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "practitioners", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected practitioners from their
+    /// stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Practitioner>(&self.pool, "practitioners", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, practitioner) in &rows {
+            let fields = self.extract_search_fields(practitioner);
+            sqlx::query(
+                r#"
+                UPDATE practitioners
+                SET active = $2,
+                    family_name = $3,
+                    given_name = $4,
+                    gender = $5
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.active)
+            .bind(fields.family_name)
+            .bind(fields.given_name)
+            .bind(fields.gender)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+    
+    /// Get practitioner history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Practitioner>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM practitioners_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut practitioners = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let practitioner: Practitioner = serde_json::from_value(resource_json)?;
+            practitioners.push(practitioner);
+        }
+        
+        Ok(practitioners)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Practitioner> for PractitionerRepository {
+    async fn create(&self, practitioner: &Practitioner) -> FhirResult<Practitioner> {
+        let mut practitioner = practitioner.clone();
+        
+        let id = Uuid::new_v4().to_string();
+        practitioner.set_id(Id(id.clone()));
+        
+        let meta = Meta::versioned(practitioner.meta.as_ref(), 1);
+        practitioner.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&practitioner);
+        let resource_json = serde_json::to_value(&practitioner)?;
+        
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO practitioners (
+                id, resource, active, family_name, given_name, gender
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.active)
+        .bind(search_fields.family_name)
+        .bind(search_fields.given_name)
+        .bind(search_fields.gender)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO practitioners_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(practitioner)
+    }
+    
+    async fn read(&self, id: &str) -> FhirResult<Option<Practitioner>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM practitioners
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let practitioner: Practitioner = serde_json::from_value(resource_json)?;
+            Ok(Some(practitioner))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    async fn update(&self, id: &str, practitioner: &Practitioner) -> FhirResult<Practitioner> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Practitioner".to_string(),
+                id: id.to_string(),
+            })?;
+        
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+        
+        let new_version = current_version + 1;
+        
+        let mut updated_practitioner = practitioner.clone();
+        updated_practitioner.set_id(Id(id.to_string()));
+        
+        let meta = Meta::versioned(updated_practitioner.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_practitioner.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&updated_practitioner);
+        let resource_json = serde_json::to_value(&updated_practitioner)?;
+        
+        sqlx::query(
+            r#"
+            UPDATE practitioners
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                active = $4,
+                family_name = $5,
+                given_name = $6,
+                gender = $7
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.active)
+        .bind(search_fields.family_name)
+        .bind(search_fields.given_name)
+        .bind(search_fields.gender)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO practitioners_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(updated_practitioner)
+    }
+    
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE practitioners
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Practitioner".to_string(),
+                id: id.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Honors `name`, `family` and `given` (contains, case-insensitive),
+    /// `identifier`, `gender` and `active`, plus the meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Practitioner>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+        
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM practitioners WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.clone();
+            match filter.field.as_str() {
+                "name" => {
+                    let pattern = format!("%{}%", value);
+                    query.push(" AND (family_name ILIKE ").push_bind(pattern.clone())
+                        .push(" OR given_name ILIKE ").push_bind(pattern).push(")");
+                }
+                "family" => {
+                    query.push(" AND family_name ILIKE ").push_bind(format!("%{}%", value));
+                }
+                "given" => {
+                    query.push(" AND given_name ILIKE ").push_bind(format!("%{}%", value));
+                }
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(&value));
+                }
+                "gender" => {
+                    query.push(" AND gender = ").push_bind(value);
+                }
+                "active" => {
+                    query.push(" AND active = ").push_bind(value == "true");
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+        
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut practitioners = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let practitioner: Practitioner = serde_json::from_value(resource_json)?;
+            practitioners.push(practitioner);
+        }
+        
+        Ok(practitioners)
+    }
+}
+
+struct PractitionerSearchFields {
+    active: Option<bool>,
+    family_name: Option<String>,
+    given_name: Option<String>,
+    gender: Option<String>,
+}
@@ -0,0 +1,366 @@
+// src/repository/practitioner_role_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{PractitionerRole, Id, Meta, FhirError, FhirResult};
+use super::{
+    codeable_concept_filter, identifier_filter, insert_history, reference_search_id, reference_uuid,
+    push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::Resource;
+
+pub struct PractitionerRoleRepository {
+    pool: PgPool,
+}
+
+impl PractitionerRoleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+    
+    /// Extract searchable fields from PractitionerRole resource
+    fn extract_search_fields(&self, role: &PractitionerRole) -> PractitionerRoleSearchFields {
+        PractitionerRoleSearchFields {
+            active: role.active.as_ref().map(|b| b.0),
+            practitioner_id: reference_uuid(role.practitioner.as_ref()),
+            organization_id: reference_uuid(role.organization.as_ref()),
+        }
+    }
+
+    /// Insert imported practitioner roles as version 1 in multi-row
+    /// statements, with the same search columns as `create`. Ids that already
+    /// exist are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        roles: &[PractitionerRole],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(roles.len());
+        for role in roles {
+            rows.push((stored_id(role)?, serde_json::to_value(role)?, self.extract_search_fields(role)));
+        }
+
+        let mut result = BatchInsert::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 5).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO practitioner_roles (id, resource, active, practitioner_id, organization_id) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.active)
+                    .push_bind(fields.practitioner_id)
+                    .push_bind(fields.organization_id);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "practitioner_roles", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected practitioner roles from
+    /// their stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<PractitionerRole>(&self.pool, "practitioner_roles", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut updated = 0;
+        for (id, role) in &rows {
+            let fields = self.extract_search_fields(role);
+            sqlx::query(
+                r#"
+                UPDATE practitioner_roles
+                SET active = $2,
+                    practitioner_id = $3,
+                    organization_id = $4
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.active)
+            .bind(fields.practitioner_id)
+            .bind(fields.organization_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+    
+    /// Get practitioner role history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<PractitionerRole>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM practitioner_roles_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut roles = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let role: PractitionerRole = serde_json::from_value(resource_json)?;
+            roles.push(role);
+        }
+        
+        Ok(roles)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<PractitionerRole> for PractitionerRoleRepository {
+    async fn create(&self, role: &PractitionerRole) -> FhirResult<PractitionerRole> {
+        let mut role = role.clone();
+        
+        let id = Uuid::new_v4().to_string();
+        role.set_id(Id(id.clone()));
+        
+        let meta = Meta::versioned(role.meta.as_ref(), 1);
+        role.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&role);
+        let resource_json = serde_json::to_value(&role)?;
+        
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO practitioner_roles (
+                id, resource, active, practitioner_id, organization_id
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.active)
+        .bind(search_fields.practitioner_id)
+        .bind(search_fields.organization_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO practitioner_roles_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(role)
+    }
+    
+    async fn read(&self, id: &str) -> FhirResult<Option<PractitionerRole>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM practitioner_roles
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let role: PractitionerRole = serde_json::from_value(resource_json)?;
+            Ok(Some(role))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    async fn update(&self, id: &str, role: &PractitionerRole) -> FhirResult<PractitionerRole> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "PractitionerRole".to_string(),
+                id: id.to_string(),
+            })?;
+        
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+        
+        let new_version = current_version + 1;
+        
+        let mut updated_role = role.clone();
+        updated_role.set_id(Id(id.to_string()));
+        
+        let meta = Meta::versioned(updated_role.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_role.set_meta(meta);
+        
+        let search_fields = self.extract_search_fields(&updated_role);
+        let resource_json = serde_json::to_value(&updated_role)?;
+        
+        sqlx::query(
+            r#"
+            UPDATE practitioner_roles
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                active = $4,
+                practitioner_id = $5,
+                organization_id = $6
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.active)
+        .bind(search_fields.practitioner_id)
+        .bind(search_fields.organization_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO practitioner_roles_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        Ok(updated_role)
+    }
+    
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+        
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE practitioner_roles
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "PractitionerRole".to_string(),
+                id: id.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Honors `practitioner` and `organization` (`Type/id` or id),
+    /// `specialty` and `role` tokens, `identifier` and `active`, plus the
+    /// meta filters
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<PractitionerRole>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+        
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM practitioner_roles WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "practitioner" => {
+                    query.push(" AND practitioner_id = ").push_bind(reference_search_id(value)?);
+                }
+                "organization" => {
+                    query.push(" AND organization_id = ").push_bind(reference_search_id(value)?);
+                }
+                "specialty" => {
+                    query.push(" AND resource @> ").push_bind(codeable_concept_filter("specialty", value));
+                }
+                "role" => {
+                    query.push(" AND resource @> ").push_bind(codeable_concept_filter("code", value));
+                }
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                "active" => {
+                    query.push(" AND active = ").push_bind(value == "true");
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+        
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+        
+        let mut roles = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let role: PractitionerRole = serde_json::from_value(resource_json)?;
+            roles.push(role);
+        }
+        
+        Ok(roles)
+    }
+}
+
+struct PractitionerRoleSearchFields {
+    active: Option<bool>,
+    practitioner_id: Option<Uuid>,
+    organization_id: Option<Uuid>,
+}
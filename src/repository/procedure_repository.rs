@@ -0,0 +1,462 @@
+// src/repository/procedure_repository.rs
+
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::{Procedure, Id, Meta, FhirError, FhirResult};
+use super::{
+    existing_patients, identifier_filter, insert_history, push_any_of,
+    push_date_filter, push_token_filter, reference_search_id, reference_uuid, push_meta_filter, stored_id, stored_rows,
+    BatchInsert, ReindexPage, ReindexSelection, Repository, SearchParams, BIND_LIMIT,
+};
+use crate::domain::resources::procedure::ProcedurePerformed;
+use crate::domain::resources::Resource;
+
+pub struct ProcedureRepository {
+    pool: PgPool,
+}
+
+impl ProcedureRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn extract_search_fields(&self, procedure: &Procedure) -> ProcedureSearchFields {
+        let coding = procedure.code.as_ref()
+            .and_then(|c| c.coding.as_ref())
+            .and_then(|codings| codings.first());
+        ProcedureSearchFields {
+            subject_id: reference_uuid(Some(&procedure.subject)),
+            status: procedure.status.0.clone(),
+            code_code: coding.and_then(|c| c.code.as_ref()).map(|c| c.0.clone()),
+            code_system: coding.and_then(|c| c.system.as_ref()).map(|s| s.0.clone()),
+            performed_datetime: match &procedure.performed {
+                Some(ProcedurePerformed::DateTime(dt)) => Some(dt.0),
+                Some(ProcedurePerformed::Period(period)) => period.start.as_ref().map(|d| d.0),
+                _ => None,
+            },
+        }
+    }
+
+    /// Insert imported procedures as version 1 in multi-row statements,
+    /// with the same search columns as `create`. Ids that already exist
+    /// are skipped
+    pub async fn insert_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        procedures: &[Procedure],
+    ) -> FhirResult<BatchInsert> {
+        let mut rows = Vec::with_capacity(procedures.len());
+        for procedure in procedures {
+            rows.push((stored_id(procedure)?, serde_json::to_value(procedure)?, self.extract_search_fields(procedure)));
+        }
+
+        // subject_id references patients, so rows for unknown patients are set aside
+        let subjects: Vec<Uuid> = rows.iter().filter_map(|(_, _, fields)| fields.subject_id).collect();
+        let existing = existing_patients(tx, &subjects).await?;
+        let mut result = BatchInsert::default();
+        let (rows, missing): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .partition(|(_, _, fields)| fields.subject_id.is_none_or(|id| existing.contains(&id)));
+        result.missing_subject = missing.into_iter().map(|(id, _, _)| id).collect();
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(BIND_LIMIT / 7).collect();
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO procedures (id, resource, subject_id, status, code_code, code_system, performed_datetime) "
+            );
+            query.push_values(chunk, |mut row, (id, resource, fields)| {
+                row.push_bind(id)
+                    .push_bind(resource)
+                    .push_bind(fields.subject_id)
+                    .push_bind(fields.status)
+                    .push_bind(fields.code_code)
+                    .push_bind(fields.code_system)
+                    .push_bind(fields.performed_datetime);
+            });
+            query.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let inserted: Vec<Uuid> = query
+                .build_query_scalar()
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            result.inserted.extend(inserted);
+        }
+
+        insert_history(tx, "procedures", &result.inserted).await?;
+        Ok(result)
+    }
+
+    /// Re-derive the search columns of the selected procedures from
+    /// their stored JSON, as `update` would set them, without a new version
+    pub async fn reindex(&self, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        let (rows, last_id) = stored_rows::<Procedure>(&self.pool, "procedures", selection).await?;
+        let mut tx = self.pool.begin().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // subject_id references patients; rows pointing at an unknown patient keep their columns
+        let subjects: Vec<Uuid> = rows.iter()
+            .filter_map(|(_, procedure)| self.extract_search_fields(procedure).subject_id)
+            .collect();
+        let existing = existing_patients(&mut tx, &subjects).await?;
+
+        let mut updated = 0;
+        for (id, procedure) in &rows {
+            let fields = self.extract_search_fields(procedure);
+            if fields.subject_id.is_some_and(|subject| !existing.contains(&subject)) {
+                tracing::warn!("Not reindexing Procedure/{}: subject patient does not exist", id);
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE procedures
+                SET subject_id = $2,
+                    status = $3,
+                    code_code = $4,
+                    code_system = $5,
+                    performed_datetime = $6
+                WHERE id = $1
+                "#
+            )
+            .bind(id)
+            .bind(fields.subject_id)
+            .bind(fields.status)
+            .bind(fields.code_code)
+            .bind(fields.code_system)
+            .bind(fields.performed_datetime)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+            updated += 1;
+        }
+
+        tx.commit().await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(ReindexPage { updated, last_id })
+    }
+
+    /// Procedures performed during an encounter (`encounter` reference)
+    pub async fn search_by_encounter(&self, encounter_id: &str) -> FhirResult<Vec<Procedure>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM procedures
+            WHERE resource @> jsonb_build_object(
+                'encounter', jsonb_build_object('reference', 'Encounter/' || $1)
+            )
+            AND deleted_at IS NULL
+            ORDER BY performed_datetime DESC
+            "#
+        )
+        .bind(encounter_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut procedures = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let procedure: Procedure = serde_json::from_value(resource_json)?;
+            procedures.push(procedure);
+        }
+
+        Ok(procedures)
+    }
+
+    pub async fn search_by_patient(&self, patient_id: &str) -> FhirResult<Vec<Procedure>> {
+        let uuid = Uuid::parse_str(patient_id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", patient_id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM procedures
+            WHERE subject_id = $1 AND deleted_at IS NULL
+            ORDER BY performed_datetime DESC
+            LIMIT 100
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut procedures = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let procedure: Procedure = serde_json::from_value(resource_json)?;
+            procedures.push(procedure);
+        }
+
+        Ok(procedures)
+    }
+
+    /// Get procedure history (all versions)
+    pub async fn get_history(&self, id: &str) -> FhirResult<Vec<Procedure>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT resource
+            FROM procedures_history
+            WHERE id = $1
+            ORDER BY version_id DESC
+            "#
+        )
+        .bind(uuid)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut procedures = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let procedure: Procedure = serde_json::from_value(resource_json)?;
+            procedures.push(procedure);
+        }
+
+        Ok(procedures)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<Procedure> for ProcedureRepository {
+    async fn create(&self, procedure: &Procedure) -> FhirResult<Procedure> {
+        let mut procedure = procedure.clone();
+
+        let id = Uuid::new_v4().to_string();
+        procedure.set_id(Id(id.clone()));
+
+        let meta = Meta::versioned(procedure.meta.as_ref(), 1);
+        procedure.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&procedure);
+        let resource_json = serde_json::to_value(&procedure)?;
+
+        let uuid = Uuid::parse_str(&id)
+            .map_err(|_| FhirError::Database("Failed to parse UUID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO procedures (
+                id, resource, subject_id, status, code_code, code_system, performed_datetime
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(search_fields.subject_id)
+        .bind(search_fields.status)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.performed_datetime)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO procedures_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, 1, $2, NOW(), 'CREATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(procedure)
+    }
+
+    async fn read(&self, id: &str) -> FhirResult<Option<Procedure>> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT resource
+            FROM procedures
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if let Some(row) = row {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let procedure: Procedure = serde_json::from_value(resource_json)?;
+            Ok(Some(procedure))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, id: &str, procedure: &Procedure) -> FhirResult<Procedure> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let current = self.read(id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Procedure".to_string(),
+                id: id.to_string(),
+            })?;
+
+        let current_version = current.meta.as_ref()
+            .and_then(|m| m.version_id.as_ref())
+            .and_then(|v| v.0.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let new_version = current_version + 1;
+
+        let mut updated_procedure = procedure.clone();
+        updated_procedure.set_id(Id(id.to_string()));
+
+        let meta = Meta::versioned(updated_procedure.meta.as_ref().or(current.meta.as_ref()), new_version);
+        updated_procedure.set_meta(meta);
+
+        let search_fields = self.extract_search_fields(&updated_procedure);
+        let resource_json = serde_json::to_value(&updated_procedure)?;
+
+        sqlx::query(
+            r#"
+            UPDATE procedures
+            SET resource = $2,
+                version_id = $3,
+                last_updated = NOW(),
+                subject_id = $4,
+                status = $5,
+                code_code = $6,
+                code_system = $7,
+                performed_datetime = $8
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .bind(&resource_json)
+        .bind(new_version)
+        .bind(search_fields.subject_id)
+        .bind(search_fields.status)
+        .bind(search_fields.code_code)
+        .bind(search_fields.code_system)
+        .bind(search_fields.performed_datetime)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        // Insert into history
+        sqlx::query(
+            r#"
+            INSERT INTO procedures_history (id, version_id, resource, last_updated, operation)
+            VALUES ($1, $2, $3, NOW(), 'UPDATE')
+            "#
+        )
+        .bind(uuid)
+        .bind(new_version)
+        .bind(&resource_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(updated_procedure)
+    }
+
+    async fn delete(&self, id: &str) -> FhirResult<()> {
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        // Soft delete
+        let result = sqlx::query(
+            r#"
+            UPDATE procedures
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(uuid)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(FhirError::NotFound {
+                resource_type: "Procedure".to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Honors `patient`/`subject`, `status` (comma-separated values match
+    /// any), `code`, `date` (when performed) with prefixes, `encounter` and
+    /// `identifier`, plus the meta filters. A Period matches on its start
+    async fn search(&self, params: SearchParams) -> FhirResult<Vec<Procedure>> {
+        let limit = params.limit.unwrap_or(100);
+        let offset = params.offset.unwrap_or(0);
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT resource FROM procedures WHERE deleted_at IS NULL"
+        );
+        push_meta_filter(&mut query, &params.meta_filter());
+        for filter in &params.filters {
+            let value = filter.value.as_str();
+            match filter.field.as_str() {
+                "patient" | "subject" => {
+                    query.push(" AND subject_id = ").push_bind(reference_search_id(value)?);
+                }
+                "status" => push_any_of(&mut query, "status", value),
+                "code" => push_token_filter(&mut query, "code_code", "code_system", value),
+                "date" => push_date_filter(&mut query, "performed_datetime", value)?,
+                "encounter" => {
+                    let reference = format!("Encounter/{}", reference_search_id(value)?);
+                    query.push(" AND resource @> ")
+                        .push_bind(serde_json::json!({ "encounter": { "reference": reference } }));
+                }
+                "identifier" => {
+                    query.push(" AND resource @> ").push_bind(identifier_filter(value));
+                }
+                _ => {}
+            }
+        }
+        query.push(" ORDER BY last_updated DESC LIMIT ").push_bind(limit)
+            .push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        let mut procedures = Vec::new();
+        for row in rows {
+            let resource_json: serde_json::Value = row.try_get("resource")
+                .map_err(|e| FhirError::Database(e.to_string()))?;
+            let procedure: Procedure = serde_json::from_value(resource_json)?;
+            procedures.push(procedure);
+        }
+
+        Ok(procedures)
+    }
+}
+
+struct ProcedureSearchFields {
+    subject_id: Option<Uuid>,
+    status: String,
+    code_code: Option<String>,
+    code_system: Option<String>,
+    performed_datetime: Option<chrono::DateTime<Utc>>,
+}
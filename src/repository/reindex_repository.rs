@@ -0,0 +1,208 @@
+// src/repository/reindex_repository.rs
+
+use sqlx::{PgPool, Row};
+use sqlx::postgres::PgRow;
+use uuid::Uuid;
+
+use crate::domain::{FhirError, FhirResult};
+use super::{
+    resource_table, ReindexPage, ReindexSelection,
+    PatientRepository, ObservationRepository, ConditionRepository, EncounterRepository,
+    PractitionerRepository, PractitionerRoleRepository, OrganizationRepository,
+    MedicationRepository, MedicationRequestRepository, MedicationStatementRepository,
+    AllergyIntoleranceRepository, ProcedureRepository, DiagnosticReportRepository,
+    ImmunizationRepository, LocationRepository, GroupRepository, DocumentReferenceRepository,
+};
+
+/// Progress of a `$reindex` job over one resource type or all of them
+#[derive(Debug, Clone)]
+pub struct ReindexJob {
+    pub id: Uuid,
+    /// Type being reindexed; `None` for every type
+    pub resource_type: Option<String>,
+    /// `in-progress`, `completed` or `failed`
+    pub status: String,
+    /// Type and id the last processed page ended at
+    pub current_type: Option<String>,
+    pub last_id: Option<Uuid>,
+    pub resources_reindexed: i64,
+    pub error: Option<String>,
+}
+
+const JOB_COLUMNS: &str =
+    "id, resource_type, status, current_type, last_id, resources_reindexed, error";
+
+/// Rewrites search columns across resource tables and stores `$reindex`
+/// job checkpoints
+pub struct ReindexRepository {
+    pool: PgPool,
+    patients: PatientRepository,
+    observations: ObservationRepository,
+    conditions: ConditionRepository,
+    encounters: EncounterRepository,
+    practitioners: PractitionerRepository,
+    practitioner_roles: PractitionerRoleRepository,
+    organizations: OrganizationRepository,
+    medications: MedicationRepository,
+    medication_requests: MedicationRequestRepository,
+    medication_statements: MedicationStatementRepository,
+    allergy_intolerances: AllergyIntoleranceRepository,
+    procedures: ProcedureRepository,
+    diagnostic_reports: DiagnosticReportRepository,
+    immunizations: ImmunizationRepository,
+    locations: LocationRepository,
+    groups: GroupRepository,
+    document_references: DocumentReferenceRepository,
+}
+
+impl ReindexRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            patients: PatientRepository::new(pool.clone()),
+            observations: ObservationRepository::new(pool.clone()),
+            conditions: ConditionRepository::new(pool.clone()),
+            encounters: EncounterRepository::new(pool.clone()),
+            practitioners: PractitionerRepository::new(pool.clone()),
+            practitioner_roles: PractitionerRoleRepository::new(pool.clone()),
+            organizations: OrganizationRepository::new(pool.clone()),
+            medications: MedicationRepository::new(pool.clone()),
+            medication_requests: MedicationRequestRepository::new(pool.clone()),
+            medication_statements: MedicationStatementRepository::new(pool.clone()),
+            allergy_intolerances: AllergyIntoleranceRepository::new(pool.clone()),
+            procedures: ProcedureRepository::new(pool.clone()),
+            diagnostic_reports: DiagnosticReportRepository::new(pool.clone()),
+            immunizations: ImmunizationRepository::new(pool.clone()),
+            locations: LocationRepository::new(pool.clone()),
+            groups: GroupRepository::new(pool.clone()),
+            document_references: DocumentReferenceRepository::new(pool.clone()),
+            pool,
+        }
+    }
+
+    fn job_from_row(row: &PgRow) -> FhirResult<ReindexJob> {
+        let get_err = |e: sqlx::Error| FhirError::Database(e.to_string());
+        Ok(ReindexJob {
+            id: row.try_get("id").map_err(get_err)?,
+            resource_type: row.try_get("resource_type").map_err(get_err)?,
+            status: row.try_get("status").map_err(get_err)?,
+            current_type: row.try_get("current_type").map_err(get_err)?,
+            last_id: row.try_get("last_id").map_err(get_err)?,
+            resources_reindexed: row.try_get("resources_reindexed").map_err(get_err)?,
+            error: row.try_get("error").map_err(get_err)?,
+        })
+    }
+
+    /// Re-derive the search columns of the selected rows of one type
+    pub async fn reindex(&self, resource_type: &str, selection: ReindexSelection) -> FhirResult<ReindexPage> {
+        match resource_table(resource_type)? {
+            "patients" => self.patients.reindex(selection).await,
+            "observations" => self.observations.reindex(selection).await,
+            "conditions" => self.conditions.reindex(selection).await,
+            "encounters" => self.encounters.reindex(selection).await,
+            "practitioners" => self.practitioners.reindex(selection).await,
+            "practitioner_roles" => self.practitioner_roles.reindex(selection).await,
+            "organizations" => self.organizations.reindex(selection).await,
+            "medications" => self.medications.reindex(selection).await,
+            "medication_requests" => self.medication_requests.reindex(selection).await,
+            "medication_statements" => self.medication_statements.reindex(selection).await,
+            "allergy_intolerances" => self.allergy_intolerances.reindex(selection).await,
+            "procedures" => self.procedures.reindex(selection).await,
+            "diagnostic_reports" => self.diagnostic_reports.reindex(selection).await,
+            "immunizations" => self.immunizations.reindex(selection).await,
+            "locations" => self.locations.reindex(selection).await,
+            "groups" => self.groups.reindex(selection).await,
+            "document_references" => self.document_references.reindex(selection).await,
+            _ => Err(FhirError::InvalidResourceType(resource_type.to_string())),
+        }
+    }
+
+    /// The unfinished job for this scope, if an earlier run was interrupted,
+    /// or a new one
+    pub async fn start(&self, resource_type: Option<&str>) -> FhirResult<ReindexJob> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO reindex_jobs (id, resource_type)
+            VALUES ($1, $2)
+            ON CONFLICT ((COALESCE(resource_type, '*'))) WHERE status <> 'completed'
+            DO UPDATE SET status = 'in-progress', error = NULL, updated_at = NOW()
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(resource_type)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Self::job_from_row(&row)
+    }
+
+    pub async fn get(&self, id: Uuid) -> FhirResult<Option<ReindexJob>> {
+        let row = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM reindex_jobs WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        row.as_ref().map(Self::job_from_row).transpose()
+    }
+
+    /// Record that the job has reached `last_id` of `current_type`.
+    /// Reindexing is idempotent, so a page redone after a crash is harmless
+    pub async fn checkpoint(
+        &self,
+        job_id: Uuid,
+        current_type: &str,
+        last_id: Uuid,
+        reindexed: u64,
+    ) -> FhirResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE reindex_jobs
+            SET current_type = $2,
+                last_id = $3,
+                resources_reindexed = resources_reindexed + $4,
+                updated_at = NOW()
+            WHERE id = $1
+            "#
+        )
+        .bind(job_id)
+        .bind(current_type)
+        .bind(last_id)
+        .bind(reindexed as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Mark the job finished; it can no longer be resumed
+    pub async fn complete(&self, job_id: Uuid) -> FhirResult<ReindexJob> {
+        self.set_status(job_id, "completed", None).await
+    }
+
+    /// Record why the job stopped. Starting the same scope again resumes it
+    pub async fn fail(&self, job_id: Uuid, error: &str) -> FhirResult<ReindexJob> {
+        self.set_status(job_id, "failed", Some(error)).await
+    }
+
+    async fn set_status(&self, job_id: Uuid, status: &str, error: Option<&str>) -> FhirResult<ReindexJob> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE reindex_jobs
+            SET status = $2, error = $3, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(job_id)
+        .bind(status)
+        .bind(error)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| FhirError::Database(e.to_string()))?;
+
+        Self::job_from_row(&row)
+    }
+}
@@ -0,0 +1,206 @@
+// src/service/allergy_intolerance_service.rs
+
+use crate::domain::{AllergyIntolerance, FhirError, FhirResult};
+use crate::repository::{AllergyIntoleranceRepository, PatientRepository, Repository, SearchParams};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, AllergyIntoleranceValidator,
+    SecurityContext, CompartmentAuthorizationRules, ValidationMode,
+};
+
+pub struct AllergyIntoleranceService {
+    repository: AllergyIntoleranceRepository,
+    patients: PatientRepository,
+    validator: AllergyIntoleranceValidator,
+    auth_rules: CompartmentAuthorizationRules,
+}
+
+impl AllergyIntoleranceService {
+    pub fn new(repository: AllergyIntoleranceRepository, patients: PatientRepository) -> Self {
+        Self {
+            repository,
+            patients,
+            validator: AllergyIntoleranceValidator,
+            auth_rules: CompartmentAuthorizationRules::new("AllergyIntolerance"),
+        }
+    }
+
+    /// The patient must be stored; references to other types are reported
+    /// by the validator
+    async fn validate_reference(&self, allergy: &AllergyIntolerance) -> FhirResult<()> {
+        let reference = allergy.patient.reference.as_ref().map(|r| r.0.as_str());
+        if let Some(id) = reference.and_then(|r| r.strip_prefix("Patient/")) {
+            if self.patients.read(id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: Patient/{}", id)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Search allergies by patient
+    pub async fn search_by_patient(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<Vec<AllergyIntolerance>> {
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        // Check authorization
+        self.auth_rules.search_patient(context, Some(patient_id))?;
+
+        self.repository.search_by_patient(patient_id).await
+    }
+
+    /// Get active allergies for a patient
+    pub async fn get_active_allergies(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<Vec<AllergyIntolerance>> {
+        let all_allergies = self.search_by_patient(context, patient_id).await?;
+
+        // Filter for active allergies
+        let active = all_allergies.into_iter()
+            .filter(|a| {
+                a.clinical_status.as_ref()
+                    .and_then(|cs| cs.coding.as_ref())
+                    .and_then(|codings| codings.first())
+                    .and_then(|coding| coding.code.as_ref())
+                    .map(|code| code.0 == "active")
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        Ok(active)
+    }
+
+    /// Get allergy history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<AllergyIntolerance>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current patient
+        self.auth_rules.can_read_history(context, id, history.first().map(|a| &a.patient))?;
+
+        Ok(history)
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        allergy: Option<&AllergyIntolerance>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, allergy)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing allergy
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "AllergyIntolerance".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, allergy) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, &resource.patient),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, &resource.patient),
+            (ValidationMode::Delete, Some(id), _) => {
+                self.auth_rules.can_delete(context, id, existing.as_ref().map(|a| &a.patient))
+            }
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the allergy
+        if let Some(resource) = allergy.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_reference(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<AllergyIntolerance> for AllergyIntoleranceService {
+    async fn create(&self, context: &SecurityContext, allergy: AllergyIntolerance) -> FhirResult<AllergyIntolerance> {
+        // Check authorization
+        self.auth_rules.can_create(context, &allergy.patient)?;
+
+        // Validate the allergy
+        self.validator.validate(&allergy)?;
+        self.validate_reference(&allergy).await?;
+
+        self.repository.create(&allergy).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<AllergyIntolerance> {
+        let allergy = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "AllergyIntolerance".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, Some(&allergy.patient))?;
+
+        Ok(allergy)
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, allergy: AllergyIntolerance) -> FhirResult<AllergyIntolerance> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, &current.patient)?;
+        self.auth_rules.can_update(context, id, &allergy.patient)?;
+
+        // Validate the allergy
+        self.validator.validate(&allergy)?;
+        self.validate_reference(&allergy).await?;
+
+        self.repository.update(id, &allergy).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().map(|a| &a.patient))?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<AllergyIntolerance>> {
+        let requested = params.filters.iter()
+            .find(|(name, _)| name == "patient")
+            .map(|(_, value)| value.strip_prefix("Patient/").unwrap_or(value));
+
+        // Check authorization; patients only search their own compartment
+        let patient = self.auth_rules.search_patient(context, requested)?;
+        let mut filters = params.filters.clone();
+        if let (None, Some(patient)) = (requested, patient) {
+            filters.push(("patient".to_string(), patient));
+        }
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
@@ -1,6 +1,6 @@
 // src/service/authorization_rules.rs
 
-use crate::domain::errors::FhirResult;
+use crate::domain::errors::{FhirError, FhirResult};
 use crate::domain::{Observation, Patient, Condition, Encounter, Reference};
 use super::authorization::{SecurityContext, Permission, Authorizer, DefaultAuthorizer};
 
@@ -60,6 +60,11 @@ impl PatientAuthorizationRules {
     pub fn can_read_history(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<()> {
         self.authorizer.check_resource_access(context, "Patient", patient_id, Permission::ReadHistory)
     }
+
+    /// Check if the user can read a patient's whole compartment ($everything)
+    pub fn can_read_compartment(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<()> {
+        self.authorizer.check_patient_compartment_access(context, patient_id, Permission::Read)
+    }
 }
 
 impl Default for PatientAuthorizationRules {
@@ -332,12 +337,343 @@ impl Default for EncounterAuthorizationRules {
     }
 }
 
+/// Authorization rules for directory resources such as Practitioner, which
+/// sit outside any patient compartment. Access depends on the role alone
+pub struct DirectoryAuthorizationRules {
+    authorizer: DefaultAuthorizer,
+    resource_type: &'static str,
+}
+
+impl DirectoryAuthorizationRules {
+    pub fn new(resource_type: &'static str) -> Self {
+        Self {
+            authorizer: DefaultAuthorizer::new(),
+            resource_type,
+        }
+    }
+
+    /// Check if the user can create a resource of this type
+    pub fn can_create(&self, context: &SecurityContext) -> FhirResult<()> {
+        self.authorizer.check_permission(context, self.resource_type, Permission::Create)
+    }
+
+    /// Check if the user can read a resource
+    pub fn can_read(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Read)
+    }
+
+    /// Check if the user can update a resource
+    pub fn can_update(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Update)
+    }
+
+    /// Check if the user can delete a resource
+    pub fn can_delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Delete)
+    }
+
+    /// Check if the user can search resources of this type
+    pub fn can_search(&self, context: &SecurityContext) -> FhirResult<()> {
+        self.authorizer.check_permission(context, self.resource_type, Permission::Search)
+    }
+
+    /// Check if the user can read a resource's history
+    pub fn can_read_history(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::ReadHistory)
+    }
+}
+
+/// Authorization rules for Group. A group's members name other patients, so
+/// patient users are refused; everyone else follows the directory rules
+pub struct GroupAuthorizationRules {
+    directory: DirectoryAuthorizationRules,
+}
+
+impl GroupAuthorizationRules {
+    pub fn new() -> Self {
+        Self {
+            directory: DirectoryAuthorizationRules::new("Group"),
+        }
+    }
+
+    fn deny_patients(&self, context: &SecurityContext) -> FhirResult<()> {
+        if context.is_patient() {
+            return Err(FhirError::Forbidden {
+                message: format!("Patient {} cannot access Group resources", context.user_id),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check if the user can create a group
+    pub fn can_create(&self, context: &SecurityContext) -> FhirResult<()> {
+        self.deny_patients(context)?;
+        self.directory.can_create(context)
+    }
+
+    /// Check if the user can read a group, or expand it to its members
+    pub fn can_read(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.deny_patients(context)?;
+        self.directory.can_read(context, id)
+    }
+
+    /// Check if the user can update a group
+    pub fn can_update(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.deny_patients(context)?;
+        self.directory.can_update(context, id)
+    }
+
+    /// Check if the user can delete a group
+    pub fn can_delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.deny_patients(context)?;
+        self.directory.can_delete(context, id)
+    }
+
+    /// Check if the user can search groups
+    pub fn can_search(&self, context: &SecurityContext) -> FhirResult<()> {
+        self.deny_patients(context)?;
+        self.directory.can_search(context)
+    }
+
+    /// Check if the user can read a group's history
+    pub fn can_read_history(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        self.deny_patients(context)?;
+        self.directory.can_read_history(context, id)
+    }
+}
+
+impl Default for GroupAuthorizationRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Authorization rules for patient-compartment resources such as
+/// MedicationRequest, whose compartment is the Patient in `subject`
+pub struct CompartmentAuthorizationRules {
+    authorizer: DefaultAuthorizer,
+    resource_type: &'static str,
+}
+
+impl CompartmentAuthorizationRules {
+    pub fn new(resource_type: &'static str) -> Self {
+        Self {
+            authorizer: DefaultAuthorizer::new(),
+            resource_type,
+        }
+    }
+
+    /// Check the compartment of `subject`, when it names a patient. Patient
+    /// users are refused resources about anything else, such as a Group
+    fn check_subject(&self, context: &SecurityContext, subject: Option<&Reference>, permission: Permission) -> FhirResult<()> {
+        match extract_patient_id_from_reference(&subject.cloned()) {
+            Some(patient_id) => self.authorizer.check_patient_compartment_access(context, &patient_id, permission),
+            None if context.is_patient() => Err(FhirError::Forbidden {
+                message: format!(
+                    "Patient {} cannot access {} resources outside their compartment",
+                    context.user_id, self.resource_type
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check if the user can create a resource for `subject`
+    pub fn can_create(&self, context: &SecurityContext, subject: &Reference) -> FhirResult<()> {
+        self.authorizer.check_permission(context, self.resource_type, Permission::Create)?;
+        self.check_subject(context, Some(subject), Permission::Create)
+    }
+
+    /// Check if the user can read a resource, and its compartment when known
+    pub fn can_read(&self, context: &SecurityContext, id: &str, subject: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Read)?;
+        self.check_subject(context, subject, Permission::Read)
+    }
+
+    /// Check if the user can update a resource to belong to `subject`
+    pub fn can_update(&self, context: &SecurityContext, id: &str, subject: &Reference) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Update)?;
+        self.check_subject(context, Some(subject), Permission::Update)
+    }
+
+    /// Check if the user can delete a resource, and its compartment when known
+    pub fn can_delete(&self, context: &SecurityContext, id: &str, subject: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Delete)?;
+        self.check_subject(context, subject, Permission::Delete)
+    }
+
+    /// Check if the user can read a resource's history
+    pub fn can_read_history(&self, context: &SecurityContext, id: &str, subject: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::ReadHistory)?;
+        self.check_subject(context, subject, Permission::ReadHistory)
+    }
+
+    /// Check a search and return the patient it is limited to: the one
+    /// requested, or for a patient user without one their own compartment
+    pub fn search_patient(&self, context: &SecurityContext, patient_id: Option<&str>) -> FhirResult<Option<String>> {
+        self.authorizer.check_permission(context, self.resource_type, Permission::Search)?;
+
+        if let Some(pid) = patient_id {
+            self.authorizer.check_patient_compartment_access(context, pid, Permission::Search)?;
+            return Ok(Some(pid.to_string()));
+        }
+        Ok(context.get_patient_id().filter(|_| context.is_patient()).map(str::to_string))
+    }
+}
+
+/// Authorization rules for documents and their content: DocumentReference,
+/// whose compartment is its `subject`, and Binary, whose compartment is its
+/// `securityContext`. Only a Patient reference places a document in a
+/// compartment, and patient users are refused documents outside theirs
+pub struct DocumentAuthorizationRules {
+    authorizer: DefaultAuthorizer,
+    resource_type: &'static str,
+}
+
+impl DocumentAuthorizationRules {
+    pub fn new(resource_type: &'static str) -> Self {
+        Self {
+            authorizer: DefaultAuthorizer::new(),
+            resource_type,
+        }
+    }
+
+    /// Check the compartment of the patient the document is about
+    fn check_patient(&self, context: &SecurityContext, patient: Option<&Reference>, permission: Permission) -> FhirResult<()> {
+        let patient_id = patient
+            .and_then(|r| r.reference.as_ref())
+            .and_then(|r| r.0.strip_prefix("Patient/"));
+        match patient_id {
+            Some(patient_id) => self.authorizer.check_patient_compartment_access(context, patient_id, permission),
+            None if context.is_patient() => Err(FhirError::Forbidden {
+                message: format!(
+                    "Patient {} cannot access {} resources outside their compartment",
+                    context.user_id, self.resource_type
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check if the user can create a document about `patient`
+    pub fn can_create(&self, context: &SecurityContext, patient: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_permission(context, self.resource_type, Permission::Create)?;
+        self.check_patient(context, patient, Permission::Create)
+    }
+
+    /// Check if the user can read a document about `patient`
+    pub fn can_read(&self, context: &SecurityContext, id: &str, patient: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Read)?;
+        self.check_patient(context, patient, Permission::Read)
+    }
+
+    /// Check if the user can update a document to be about `patient`
+    pub fn can_update(&self, context: &SecurityContext, id: &str, patient: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Update)?;
+        self.check_patient(context, patient, Permission::Update)
+    }
+
+    /// Check if the user can delete a document about `patient`
+    pub fn can_delete(&self, context: &SecurityContext, id: &str, patient: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::Delete)?;
+        self.check_patient(context, patient, Permission::Delete)
+    }
+
+    /// Check if the user can read a document's history
+    pub fn can_read_history(&self, context: &SecurityContext, id: &str, patient: Option<&Reference>) -> FhirResult<()> {
+        self.authorizer.check_resource_access(context, self.resource_type, id, Permission::ReadHistory)?;
+        self.check_patient(context, patient, Permission::ReadHistory)
+    }
+
+    /// Check a search and return the patient it is limited to: the one
+    /// requested, or for a patient user without one their own compartment
+    pub fn search_patient(&self, context: &SecurityContext, patient_id: Option<&str>) -> FhirResult<Option<String>> {
+        self.authorizer.check_permission(context, self.resource_type, Permission::Search)?;
+
+        if let Some(pid) = patient_id {
+            self.authorizer.check_patient_compartment_access(context, pid, Permission::Search)?;
+            return Ok(Some(pid.to_string()));
+        }
+        Ok(context.get_patient_id().filter(|_| context.is_patient()).map(str::to_string))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::{Code, CodeableConcept, FhirString, Uri};
     use crate::service::authorization::{SecurityContext, Role};
 
+    #[test]
+    fn test_compartment_search_is_limited_to_own_patient() {
+        let rules = CompartmentAuthorizationRules::new("MedicationRequest");
+        let patient_ctx = SecurityContext::patient("user1".to_string(), "patient1".to_string());
+
+        assert_eq!(rules.search_patient(&patient_ctx, None).unwrap().as_deref(), Some("patient1"));
+        assert!(rules.search_patient(&patient_ctx, Some("patient2")).is_err());
+
+        let clinician_ctx = SecurityContext::clinician("doc1".to_string(), None);
+        assert_eq!(rules.search_patient(&clinician_ctx, None).unwrap(), None);
+        assert!(rules.can_delete(&clinician_ctx, "1", None).is_err());
+    }
+
+    #[test]
+    fn test_compartment_access_outside_a_patient_is_denied_to_patients() {
+        let rules = CompartmentAuthorizationRules::new("Procedure");
+        let reference = |value: &str| Reference {
+            reference: Some(FhirString(value.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let patient_ctx = SecurityContext::patient("user1".to_string(), "patient1".to_string());
+
+        assert!(rules.can_read(&patient_ctx, "proc1", Some(&reference("Patient/patient1"))).is_ok());
+        assert!(rules.can_read(&patient_ctx, "proc1", Some(&reference("Patient/patient2"))).is_err());
+        // Resources about a Group, or about no one, are not the patient's
+        assert!(rules.can_read(&patient_ctx, "proc1", Some(&reference("Group/grp1"))).is_err());
+        assert!(rules.can_read(&patient_ctx, "proc1", None).is_err());
+
+        let clinician_ctx = SecurityContext::clinician("doc1".to_string(), None);
+        assert!(rules.can_read(&clinician_ctx, "proc1", Some(&reference("Group/grp1"))).is_ok());
+    }
+
+    #[test]
+    fn test_group_access_is_denied_to_patients() {
+        let rules = GroupAuthorizationRules::new();
+        let patient_ctx = SecurityContext::patient("user1".to_string(), "patient1".to_string());
+        assert!(rules.can_search(&patient_ctx).is_err());
+        assert!(rules.can_read(&patient_ctx, "grp1").is_err());
+
+        let clinician_ctx = SecurityContext::clinician("doc1".to_string(), None);
+        assert!(rules.can_read(&clinician_ctx, "grp1").is_ok());
+        assert!(rules.can_create(&clinician_ctx).is_ok());
+        assert!(rules.can_delete(&clinician_ctx, "grp1").is_err());
+    }
+
+    #[test]
+    fn test_document_access_follows_patient_reference() {
+        let rules = DocumentAuthorizationRules::new("DocumentReference");
+        let reference = |value: &str| Reference {
+            reference: Some(FhirString(value.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let patient_ctx = SecurityContext::patient("user1".to_string(), "patient1".to_string());
+
+        assert!(rules.can_read(&patient_ctx, "doc1", Some(&reference("Patient/patient1"))).is_ok());
+        assert!(rules.can_read(&patient_ctx, "doc1", Some(&reference("Patient/patient2"))).is_err());
+        // Documents outside any patient compartment are not the patient's
+        assert!(rules.can_read(&patient_ctx, "doc1", Some(&reference("Practitioner/prac1"))).is_err());
+        assert!(rules.can_read(&patient_ctx, "doc1", None).is_err());
+
+        let clinician_ctx = SecurityContext::clinician("doc1".to_string(), None);
+        assert!(rules.can_read(&clinician_ctx, "doc1", None).is_ok());
+        assert!(rules.can_create(&clinician_ctx, Some(&reference("Patient/patient2"))).is_ok());
+        assert!(rules.can_delete(&clinician_ctx, "doc1", None).is_err());
+    }
+
     #[test]
     fn test_observation_authorization_with_patient_context() {
         let rules = ObservationAuthorizationRules::new();
@@ -0,0 +1,178 @@
+// src/service/binary_service.rs
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::StreamExt;
+
+use crate::config::BinaryConfig;
+use crate::domain::{Binary, FhirError, FhirResult, FhirString};
+use crate::repository::{BinaryRepository, BlobStore, BlobStream, StoredBinary};
+use crate::service::{Validator, BinaryValidator, SecurityContext, DocumentAuthorizationRules};
+
+/// Binary resources, with their content on the blob store. The content is
+/// streamed in and out in its native format; `data` is only decoded or
+/// encoded when a Binary is exchanged as a FHIR resource
+pub struct BinaryService {
+    repository: BinaryRepository,
+    blobs: Arc<dyn BlobStore>,
+    config: BinaryConfig,
+    validator: BinaryValidator,
+    auth_rules: DocumentAuthorizationRules,
+}
+
+impl BinaryService {
+    pub fn new(repository: BinaryRepository, blobs: Arc<dyn BlobStore>, config: BinaryConfig) -> Self {
+        Self {
+            repository,
+            blobs,
+            config,
+            validator: BinaryValidator,
+            auth_rules: DocumentAuthorizationRules::new("Binary"),
+        }
+    }
+
+    /// Largest content accepted, in bytes
+    pub fn max_size(&self) -> u64 {
+        self.config.max_size
+    }
+
+    /// Store a Binary whose content is streamed separately. Any `data` on
+    /// the resource is ignored
+    pub async fn create(&self, context: &SecurityContext, binary: Binary, content: BlobStream) -> FhirResult<StoredBinary> {
+        // Check authorization
+        self.auth_rules.can_create(context, binary.security_context.as_ref())?;
+
+        // Validate the binary
+        self.validator.validate(&binary)?;
+
+        let blob = self.blobs.put(content, self.config.max_size).await?;
+        match self.repository.create(&binary, &blob).await {
+            Ok(binary) => Ok(StoredBinary { binary, blob }),
+            Err(e) => {
+                self.discard(&blob.key).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Store a Binary resource carrying its content base64-encoded in `data`
+    pub async fn create_resource(&self, context: &SecurityContext, binary: Binary) -> FhirResult<StoredBinary> {
+        let content = decode_data(&binary)?;
+        self.create(context, binary, content).await
+    }
+
+    /// Replace a Binary and its content. The previous content is removed
+    pub async fn update(&self, context: &SecurityContext, id: &str, binary: Binary, content: BlobStream) -> FhirResult<StoredBinary> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, current.binary.security_context.as_ref())?;
+        self.auth_rules.can_update(context, id, binary.security_context.as_ref())?;
+
+        // Validate the binary
+        self.validator.validate(&binary)?;
+
+        let blob = self.blobs.put(content, self.config.max_size).await?;
+        match self.repository.update(id, &binary, &blob).await {
+            Ok((binary, previous)) => {
+                self.discard(&previous).await;
+                Ok(StoredBinary { binary, blob })
+            }
+            Err(e) => {
+                self.discard(&blob.key).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Replace a Binary with a resource carrying its content in `data`
+    pub async fn update_resource(&self, context: &SecurityContext, id: &str, binary: Binary) -> FhirResult<StoredBinary> {
+        let content = decode_data(&binary)?;
+        self.update(context, id, binary, content).await
+    }
+
+    /// Binary metadata and where its content is stored
+    pub async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<StoredBinary> {
+        let stored = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Binary".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, stored.binary.security_context.as_ref())?;
+
+        Ok(stored)
+    }
+
+    /// A Binary resource with its content base64-encoded in `data`. Content
+    /// above the inline size limit can only be read in its native format
+    pub async fn get_with_data(&self, context: &SecurityContext, id: &str) -> FhirResult<Binary> {
+        let stored = self.get(context, id).await?;
+        if stored.blob.size > self.config.max_inline_size {
+            return Err(FhirError::NotAcceptable(format!(
+                "Binary/{} is {} bytes, over the {} byte limit for inline data; request it in its native format",
+                id, stored.blob.size, self.config.max_inline_size
+            )));
+        }
+
+        let mut content = self.blobs.get(&stored.blob.key, None).await?;
+        let mut bytes = Vec::with_capacity(stored.blob.size as usize);
+        while let Some(chunk) = content.next().await {
+            bytes.extend_from_slice(&chunk.map_err(|e| FhirError::Storage(e.to_string()))?);
+        }
+
+        let mut binary = stored.binary;
+        binary.data = Some(FhirString(base64::engine::general_purpose::STANDARD.encode(bytes)));
+        Ok(binary)
+    }
+
+    /// Stream the content of a Binary read with `get`, or the bytes of it
+    /// within `range`
+    pub async fn content(&self, stored: &StoredBinary, range: Option<Range<u64>>) -> FhirResult<BlobStream> {
+        self.blobs.get(&stored.blob.key, range).await
+    }
+
+    /// Delete a Binary and its content
+    pub async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().and_then(|c| c.binary.security_context.as_ref()))?;
+
+        let key = self.repository.delete(id).await?;
+        self.discard(&key).await;
+        Ok(())
+    }
+
+    /// Get binary history (all versions, without content)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Binary>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current security context
+        self.auth_rules.can_read_history(context, id, history.first().and_then(|b| b.security_context.as_ref()))?;
+
+        Ok(history)
+    }
+
+    /// Remove content no longer referenced by any Binary. A failure only
+    /// leaves an orphaned blob behind, so it is logged rather than returned
+    async fn discard(&self, key: &str) {
+        if let Err(e) = self.blobs.delete(key).await {
+            tracing::warn!("Failed to remove binary content {}: {}", key, e);
+        }
+    }
+}
+
+/// The base64 `data` of a Binary resource as a content stream
+fn decode_data(binary: &Binary) -> FhirResult<BlobStream> {
+    let data = binary.data.as_ref()
+        .ok_or_else(|| FhirError::MissingRequiredField("data".to_string()))?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.0.as_bytes())
+        .map_err(|e| FhirError::Validation(format!("Binary.data is not valid base64: {}", e)))?;
+    Ok(Box::pin(futures_util::stream::once(async move { Ok(Bytes::from(bytes)) })))
+}
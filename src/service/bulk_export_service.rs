@@ -0,0 +1,478 @@
+// src/service/bulk_export_service.rs
+// Bulk Data Access $export (https://hl7.org/fhir/uv/bulkdata/export.html)
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::config::ExportConfig;
+use crate::domain::{Instant, FhirError, FhirResult};
+use crate::repository::export_repository::{ExportCompartment, ExportFilter, TYPE_FILTER_COLUMNS};
+use crate::repository::{resource_table, ExportRepository, SearchOperator, SearchParams, RESOURCE_TABLES};
+use crate::service::{Authorizer, DefaultAuthorizer, Permission, SecurityContext};
+
+/// `_outputFormat` values accepted for NDJSON
+const OUTPUT_FORMATS: &[&str] = &["application/fhir+ndjson", "application/ndjson", "ndjson"];
+
+/// Level an export was requested at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportLevel {
+    /// `/$export`: every resource on the server
+    System,
+    /// `Patient/$export`: every resource in a patient compartment
+    Patient,
+    /// `Group/[id]/$export`: the compartments of the group's patients, as
+    /// expanded by `GroupService::patient_ids`
+    Group(Vec<Uuid>),
+}
+
+/// Kick-off parameters of `$export`
+#[derive(Debug, Clone, Default)]
+pub struct ExportParameters {
+    pub types: Option<Vec<String>>,     // _type: only these resource types
+    pub since: Option<DateTime<Utc>>,   // _since: only resources updated at or after this instant
+    pub type_filters: Vec<String>,      // _typeFilter: `Type?search` queries
+    pub output_format: Option<String>,  // _outputFormat
+}
+
+/// Completion manifest returned by the status endpoint
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportManifest {
+    pub transaction_time: Instant,
+    pub request: String,
+    pub requires_access_token: bool,
+    pub output: Vec<ExportOutput>,
+    pub error: Vec<ExportOutput>,
+}
+
+/// One NDJSON file of the manifest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportOutput {
+    pub type_: String,
+    pub url: String,
+    pub count: u64,
+}
+
+/// State of an export job, as reported by the status endpoint
+#[derive(Debug, Clone)]
+pub enum ExportStatus {
+    /// Still running, with a progress message for `X-Progress`
+    InProgress(String),
+    Completed(ExportManifest),
+    Failed(String),
+}
+
+struct ExportJob {
+    owner: String,
+    status: ExportStatus,
+    task: Option<JoinHandle<()>>,
+}
+
+type JobStore = Arc<Mutex<HashMap<String, ExportJob>>>;
+
+/// What a job reads, resolved and authorized at kick-off
+struct ExportPlan {
+    types: Vec<&'static str>,
+    compartment: ExportCompartment,
+    since: Option<DateTime<Utc>>,
+    filters: HashMap<String, Vec<ExportFilter>>,
+    manifest: ExportManifest,
+    file_url: String,
+}
+
+/// Runs `$export` jobs in the background, writing one NDJSON file per
+/// resource type under the configured output directory
+pub struct BulkExportService {
+    repository: ExportRepository,
+    config: ExportConfig,
+    authorizer: DefaultAuthorizer,
+    jobs: JobStore,
+}
+
+impl BulkExportService {
+    pub fn new(repository: ExportRepository, config: ExportConfig) -> Self {
+        Self {
+            repository,
+            config,
+            authorizer: DefaultAuthorizer::new(),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start an export job and return its id. `request` is the kick-off URL
+    /// and `base_url` the server base used for the file URLs
+    pub fn kick_off(
+        &self,
+        context: &SecurityContext,
+        level: ExportLevel,
+        params: ExportParameters,
+        request: &str,
+        base_url: &str,
+    ) -> FhirResult<String> {
+        if let Some(format) = &params.output_format {
+            if !OUTPUT_FORMATS.contains(&format.as_str()) {
+                return Err(FhirError::Validation(format!("Unsupported _outputFormat: {}", format)));
+            }
+        }
+
+        let types: Vec<&'static str> = match &params.types {
+            Some(types) => types
+                .iter()
+                .map(|t| {
+                    RESOURCE_TABLES
+                        .iter()
+                        .find(|(name, _)| name == t)
+                        .map(|(name, _)| *name)
+                        .ok_or_else(|| FhirError::InvalidResourceType(t.clone()))
+                })
+                .collect::<FhirResult<_>>()?,
+            None => RESOURCE_TABLES.iter().map(|(name, _)| *name).collect(),
+        };
+        for resource_type in &types {
+            self.authorizer.check_permission(context, resource_type, Permission::Search)?;
+        }
+
+        // Patient users may only export their own compartment
+        let compartment = match (level, context.is_patient()) {
+            (ExportLevel::System, false) => ExportCompartment::All,
+            (ExportLevel::Patient, false) => ExportCompartment::AllPatients,
+            (ExportLevel::Group(patients), false) => ExportCompartment::Patients(patients),
+            (ExportLevel::Patient, true) => {
+                let patient_id = context.get_patient_id()
+                    .and_then(|id| Uuid::parse_str(id).ok())
+                    .ok_or_else(|| FhirError::Forbidden {
+                        message: "Patient user has no patient record".to_string(),
+                    })?;
+                ExportCompartment::Patients(vec![patient_id])
+            }
+            (ExportLevel::System | ExportLevel::Group(_), true) => {
+                return Err(FhirError::Forbidden {
+                    message: "Patients may only export their own compartment with Patient/$export".to_string(),
+                });
+            }
+        };
+
+        let mut filters: HashMap<String, Vec<ExportFilter>> = HashMap::new();
+        for value in &params.type_filters {
+            let (resource_type, filter) = parse_type_filter(value)?;
+            filters.entry(resource_type).or_default().push(filter);
+        }
+
+        let job_id = Uuid::new_v4().to_string();
+        let plan = ExportPlan {
+            types,
+            compartment,
+            since: params.since,
+            filters,
+            manifest: ExportManifest {
+                transaction_time: Instant(Utc::now()),
+                request: request.to_string(),
+                requires_access_token: true,
+                output: Vec::new(),
+                error: Vec::new(),
+            },
+            file_url: format!("{}/fhir/bulk-files/{}", base_url, job_id),
+        };
+
+        self.jobs.lock().unwrap().insert(job_id.clone(), ExportJob {
+            owner: context.user_id.clone(),
+            status: ExportStatus::InProgress("Queued".to_string()),
+            task: None,
+        });
+        let task = tokio::spawn(run_job(
+            self.repository.clone(),
+            self.jobs.clone(),
+            job_id.clone(),
+            plan,
+            self.job_dir(&job_id),
+            self.config.page_size,
+        ));
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(&job_id) {
+            job.task = Some(task);
+        }
+
+        Ok(job_id)
+    }
+
+    /// Current state of a job
+    pub fn status(&self, context: &SecurityContext, job_id: &str) -> FhirResult<ExportStatus> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = Self::owned_job(&jobs, context, job_id)?;
+        Ok(job.status.clone())
+    }
+
+    /// Cancel a running job, or release a finished one, deleting its files
+    pub async fn cancel(&self, context: &SecurityContext, job_id: &str) -> FhirResult<()> {
+        let job = {
+            let mut jobs = self.jobs.lock().unwrap();
+            Self::owned_job(&jobs, context, job_id)?;
+            jobs.remove(job_id)
+        };
+        if let Some(task) = job.and_then(|job| job.task) {
+            task.abort();
+        }
+
+        match tokio::fs::remove_dir_all(self.job_dir(job_id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(FhirError::Storage(e.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Path of an output file of a completed job
+    pub fn file_path(&self, context: &SecurityContext, job_id: &str, file_name: &str) -> FhirResult<PathBuf> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = Self::owned_job(&jobs, context, job_id)?;
+
+        let ExportStatus::Completed(manifest) = &job.status else {
+            return Err(FhirError::Conflict(format!("Export {} has not completed", job_id)));
+        };
+        // Only names listed in the manifest are served, never arbitrary paths
+        manifest
+            .output
+            .iter()
+            .find(|output| file_name == ndjson_file_name(&output.type_))
+            .map(|output| self.job_dir(job_id).join(ndjson_file_name(&output.type_)))
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "$export file".to_string(),
+                id: file_name.to_string(),
+            })
+    }
+
+    fn job_dir(&self, job_id: &str) -> PathBuf {
+        self.config.output_dir.join(job_id)
+    }
+
+    /// Jobs are only visible to the user who started them, and to admins
+    fn owned_job<'a>(
+        jobs: &'a HashMap<String, ExportJob>,
+        context: &SecurityContext,
+        job_id: &str,
+    ) -> FhirResult<&'a ExportJob> {
+        let job = jobs.get(job_id).ok_or_else(|| FhirError::NotFound {
+            resource_type: "$export".to_string(),
+            id: job_id.to_string(),
+        })?;
+        if job.owner != context.user_id && !context.is_admin() && !context.is_system() {
+            return Err(FhirError::Forbidden {
+                message: format!("Export {} belongs to another user", job_id),
+            });
+        }
+        Ok(job)
+    }
+}
+
+fn ndjson_file_name(resource_type: &str) -> String {
+    format!("{}.ndjson", resource_type)
+}
+
+async fn run_job(
+    repository: ExportRepository,
+    jobs: JobStore,
+    job_id: String,
+    plan: ExportPlan,
+    dir: PathBuf,
+    page_size: i64,
+) {
+    let result = write_files(&repository, &jobs, &job_id, &plan, &dir, page_size).await;
+
+    let status = match result {
+        Ok(output) => {
+            let mut manifest = plan.manifest;
+            manifest.output = output;
+            ExportStatus::Completed(manifest)
+        }
+        Err(e) => {
+            tracing::error!("Export {} failed: {}", job_id, e);
+            ExportStatus::Failed(e.to_string())
+        }
+    };
+    // A cancelled job is no longer in the store
+    if let Some(job) = jobs.lock().unwrap().get_mut(&job_id) {
+        job.status = status;
+        job.task = None;
+    }
+}
+
+/// Write one NDJSON file per resource type that has matching resources
+async fn write_files(
+    repository: &ExportRepository,
+    jobs: &JobStore,
+    job_id: &str,
+    plan: &ExportPlan,
+    dir: &std::path::Path,
+    page_size: i64,
+) -> FhirResult<Vec<ExportOutput>> {
+    let storage_error = |e: std::io::Error| FhirError::Storage(e.to_string());
+    tokio::fs::create_dir_all(dir).await.map_err(storage_error)?;
+
+    let mut output = Vec::new();
+    for (i, resource_type) in plan.types.iter().enumerate() {
+        if let Some(job) = jobs.lock().unwrap().get_mut(job_id) {
+            job.status = ExportStatus::InProgress(format!(
+                "Exporting {} ({} of {} types)",
+                resource_type,
+                i + 1,
+                plan.types.len()
+            ));
+        }
+
+        let filters = plan.filters.get(*resource_type).map(Vec::as_slice).unwrap_or(&[]);
+        let file_name = ndjson_file_name(resource_type);
+        let mut file = None;
+        let mut after = None;
+        let mut count = 0;
+        loop {
+            let page = repository
+                .fetch_page(resource_type, &plan.compartment, plan.since, filters, after, page_size)
+                .await?;
+            let Some((last_id, _)) = page.last() else {
+                break;
+            };
+            after = Some(*last_id);
+
+            let writer = match &mut file {
+                Some(writer) => writer,
+                None => {
+                    let created = tokio::fs::File::create(dir.join(&file_name)).await.map_err(storage_error)?;
+                    file.insert(BufWriter::new(created))
+                }
+            };
+            for (_, resource) in &page {
+                let mut line = serde_json::to_vec(resource)?;
+                line.push(b'\n');
+                writer.write_all(&line).await.map_err(storage_error)?;
+                count += 1;
+            }
+
+            if (page.len() as i64) < page_size {
+                break;
+            }
+        }
+
+        if let Some(mut writer) = file {
+            writer.flush().await.map_err(storage_error)?;
+            output.push(ExportOutput {
+                type_: resource_type.to_string(),
+                url: format!("{}/{}", plan.file_url, file_name),
+                count,
+            });
+        }
+    }
+
+    Ok(output)
+}
+
+/// Parse a `_typeFilter` value such as `Observation?code=http://loinc.org|8867-4&status=final`.
+/// Token values match on code, comma-separated values on any of them
+fn parse_type_filter(value: &str) -> FhirResult<(String, ExportFilter)> {
+    let (resource_type, query) = value.split_once('?').ok_or_else(|| {
+        FhirError::Validation(format!("_typeFilter must be a [type]?[query] search: {}", value))
+    })?;
+    resource_table(resource_type)?;
+
+    let mut columns = Vec::new();
+    let mut meta_params = SearchParams::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (name, value) = (percent_decode(name), percent_decode(value));
+        match name.as_str() {
+            "_tag" | "_profile" | "_security" => {
+                meta_params = meta_params.add_filter(name, SearchOperator::Equals, value);
+            }
+            _ => {
+                let column = TYPE_FILTER_COLUMNS
+                    .iter()
+                    .find(|(t, param, _)| *t == resource_type && *param == name)
+                    .map(|(_, _, column)| *column)
+                    .ok_or_else(|| FhirError::Validation(format!(
+                        "_typeFilter parameter {} is not supported for {}",
+                        name, resource_type
+                    )))?;
+                let codes = value
+                    .split(',')
+                    .map(|token| token.rsplit_once('|').map_or(token, |(_, code)| code).to_string())
+                    .collect();
+                columns.push((column, codes));
+            }
+        }
+    }
+
+    Ok((resource_type.to_string(), ExportFilter { columns, meta: meta_params.meta_filter() }))
+}
+
+/// Decode `%XX` escapes and `+`; the query inside `_typeFilter` is itself
+/// URL-encoded
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_type_filter() {
+        assert_eq!(percent_decode("http%3A%2F%2Floinc.org%7C8867-4+x%zz"), "http://loinc.org|8867-4 x%zz");
+
+        let (resource_type, filter) =
+            parse_type_filter("Observation?code=http%3A%2F%2Floinc.org%7C8867-4,29463-7&status=final&_tag=urn:x|nightly").unwrap();
+        assert_eq!(resource_type, "Observation");
+        assert_eq!(
+            filter.columns,
+            vec![
+                ("code_code", vec!["8867-4".to_string(), "29463-7".to_string()]),
+                ("status", vec!["final".to_string()]),
+            ]
+        );
+        assert_eq!(filter.meta, vec![vec![serde_json::json!({ "meta": { "tag": [{ "system": "urn:x", "code": "nightly" }] } })]]);
+
+        assert!(parse_type_filter("Observation").is_err());
+        assert!(parse_type_filter("Medication?identifier=123").is_err());
+        assert!(parse_type_filter("Patient?family=Smith").is_err());
+
+        let (_, filter) = parse_type_filter("Immunization?vaccine-code=http://hl7.org/fhir/sid/cvx|207").unwrap();
+        assert_eq!(filter.columns, vec![("vaccine_code_code", vec!["207".to_string()])]);
+        let (_, filter) = parse_type_filter("Practitioner?active=true").unwrap();
+        assert_eq!(filter.columns, vec![("active::text", vec!["true".to_string()])]);
+    }
+
+    #[test]
+    fn test_every_exported_type_has_type_filter_parameters() {
+        for (resource_type, _) in RESOURCE_TABLES {
+            assert!(
+                TYPE_FILTER_COLUMNS.iter().any(|(t, _, _)| t == resource_type),
+                "no _typeFilter parameters for {}", resource_type
+            );
+        }
+    }
+}
@@ -0,0 +1,393 @@
+// src/service/bulk_import_service.rs
+// Bulk $import of NDJSON files into the live and history tables
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use uuid::Uuid;
+
+use crate::config::ImportConfig;
+use crate::domain::{
+    DocumentReference, OperationOutcome, Parameters,
+    Code, FhirInteger, FhirString, Id, Meta, Uri, FhirError, FhirResult,
+};
+use crate::domain::resources::Resource;
+use crate::domain::resources::parameters::ParametersParameter;
+use crate::repository::import_repository::{ImportBatch, ImportJob};
+use crate::repository::{BatchInsert, ImportRepository, RESOURCE_TABLES};
+use crate::service::{
+    Authorizer, DefaultAuthorizer, Permission, SecurityContext,
+    Validator, PatientValidator, ObservationValidator, ConditionValidator, EncounterValidator,
+    PractitionerValidator, PractitionerRoleValidator, OrganizationValidator,
+    MedicationValidator, MedicationRequestValidator, MedicationStatementValidator,
+    AllergyIntoleranceValidator, ProcedureValidator, DiagnosticReportValidator,
+    ImmunizationValidator, LocationValidator, GroupValidator, DocumentReferenceValidator,
+};
+
+/// Imports NDJSON files in batches. Each batch commits together with the
+/// job's line checkpoint, so rerunning an interrupted file resumes it
+pub struct BulkImportService {
+    repository: ImportRepository,
+    config: ImportConfig,
+    authorizer: DefaultAuthorizer,
+}
+
+impl BulkImportService {
+    pub fn new(repository: ImportRepository, config: ImportConfig) -> Self {
+        Self {
+            repository,
+            config,
+            authorizer: DefaultAuthorizer::new(),
+        }
+    }
+
+    /// Resolve a `$import` input path. Requests may only read files inside
+    /// the configured input directory
+    pub async fn resolve_input(&self, path: &str) -> FhirResult<PathBuf> {
+        let base = tokio::fs::canonicalize(&self.config.input_dir)
+            .await
+            .map_err(|e| FhirError::Storage(format!("Import directory unavailable: {}", e)))?;
+        let source = tokio::fs::canonicalize(base.join(path))
+            .await
+            .map_err(|_| FhirError::NotFound {
+                resource_type: "$import input".to_string(),
+                id: path.to_string(),
+            })?;
+        if !source.starts_with(&base) {
+            return Err(FhirError::Forbidden {
+                message: format!("Import input {} is outside the import directory", path),
+            });
+        }
+        Ok(source)
+    }
+
+    /// Register the job for a file, picking up an unfinished job for the same
+    /// file if there is one. Only administrators and the system may import
+    pub async fn start(
+        &self,
+        context: &SecurityContext,
+        source: &Path,
+        resource_type: Option<&str>,
+    ) -> FhirResult<ImportJob> {
+        if !context.is_admin() && !context.is_system() {
+            return Err(FhirError::Forbidden {
+                message: "Bulk import requires administrator access".to_string(),
+            });
+        }
+        let types: Vec<&str> = match resource_type {
+            Some(t) => vec![RESOURCE_TABLES
+                .iter()
+                .find(|(name, _)| *name == t)
+                .map(|(name, _)| *name)
+                .ok_or_else(|| FhirError::InvalidResourceType(t.to_string()))?],
+            None => RESOURCE_TABLES.iter().map(|(name, _)| *name).collect(),
+        };
+        for t in types {
+            self.authorizer.check_permission(context, t, Permission::Create)?;
+        }
+
+        let metadata = tokio::fs::metadata(source)
+            .await
+            .map_err(|e| FhirError::Storage(e.to_string()))?;
+        self.repository
+            .start(&source.to_string_lossy(), metadata.len() as i64, resource_type)
+            .await
+    }
+
+    /// Import the job's file to the end and mark the job completed. Failures
+    /// other than bad lines mark it failed, to be resumed by a rerun
+    pub async fn run(&self, job: ImportJob) -> FhirResult<ImportJob> {
+        match self.import_lines(&job).await {
+            Ok(()) => self.repository.complete(job.id).await,
+            Err(e) => {
+                tracing::error!("Import {} failed: {}", job.id, e);
+                self.repository.fail(job.id, &e.to_string()).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Current state of a job
+    pub async fn status(&self, context: &SecurityContext, job_id: &str) -> FhirResult<ImportJob> {
+        if !context.is_admin() && !context.is_system() {
+            return Err(FhirError::Forbidden {
+                message: "Bulk import requires administrator access".to_string(),
+            });
+        }
+        let not_found = || FhirError::NotFound {
+            resource_type: "$import".to_string(),
+            id: job_id.to_string(),
+        };
+        let id = Uuid::parse_str(job_id).map_err(|_| not_found())?;
+        self.repository.get(id).await?.ok_or_else(not_found)
+    }
+
+    /// NDJSON file of OperationOutcomes, one per rejected line
+    pub fn error_file(&self, job_id: Uuid) -> PathBuf {
+        self.config.error_dir.join(format!("{}.errors.ndjson", job_id))
+    }
+
+    async fn import_lines(&self, job: &ImportJob) -> FhirResult<()> {
+        let storage = |e: std::io::Error| FhirError::Storage(e.to_string());
+
+        // An earlier run may have stopped before writing the errors of its
+        // last committed batch, or after writing some of an uncommitted one
+        tokio::fs::create_dir_all(&self.config.error_dir).await.map_err(storage)?;
+        let mut errors = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.error_file(job.id))
+            .await
+            .map_err(storage)?;
+        write_errors(&mut errors, job).await.map_err(storage)?;
+
+        let file = tokio::fs::File::open(&job.source).await.map_err(storage)?;
+        let mut lines = BufReader::new(file).lines();
+
+        // Skip what earlier runs of this job already committed
+        let mut line_number: i64 = 0;
+        while line_number < job.lines_processed {
+            if lines.next_line().await.map_err(storage)?.is_none() {
+                return Ok(());
+            }
+            line_number += 1;
+        }
+
+        loop {
+            let mut batch = ImportBatch::default();
+            let mut parsed: Vec<(i64, Uuid)> = Vec::new();
+            let mut rejected: Vec<(i64, Vec<FhirError>)> = Vec::new();
+            let mut batch_lines: i64 = 0;
+
+            while (batch_lines as usize) < self.config.batch_size {
+                let Some(line) = lines.next_line().await.map_err(storage)? else {
+                    break;
+                };
+                line_number += 1;
+                batch_lines += 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match parse_line(&line, job.resource_type.as_deref(), &mut batch) {
+                    Ok(id) => parsed.push((line_number, id)),
+                    Err(issues) => rejected.push((line_number, issues)),
+                }
+            }
+            if batch_lines == 0 {
+                return Ok(());
+            }
+
+            let checkpoint = self.repository
+                .commit_batch(job.id, &batch, batch_lines, rejected.len() as i64, |result| {
+                    error_report(rejected, &parsed, result)
+                })
+                .await?;
+            write_errors(&mut errors, &checkpoint).await.map_err(storage)?;
+        }
+    }
+}
+
+/// Error lines of a batch: the lines rejected before reaching the database,
+/// then parsed ones the insert refused, as OperationOutcomes in line order
+fn error_report(
+    mut rejected: Vec<(i64, Vec<FhirError>)>,
+    parsed: &[(i64, Uuid)],
+    result: &BatchInsert,
+) -> FhirResult<String> {
+    let inserted: HashSet<&Uuid> = result.inserted.iter().collect();
+    let missing: HashSet<&Uuid> = result.missing_subject.iter().collect();
+    for (line, id) in parsed {
+        if missing.contains(id) {
+            rejected.push((*line, vec![FhirError::InvalidReference(
+                "Subject patient does not exist".to_string(),
+            )]));
+        } else if !inserted.contains(id) {
+            rejected.push((*line, vec![FhirError::Conflict(format!(
+                "Resource {} already exists",
+                id
+            ))]));
+        }
+    }
+    rejected.sort_by_key(|(line, _)| *line);
+
+    let mut report = String::new();
+    for (line, issues) in &rejected {
+        report.push_str(&serde_json::to_string(&line_outcome(*line, issues))?);
+        report.push('\n');
+    }
+    Ok(report)
+}
+
+/// Bring the error file in line with the job's checkpoint: cut whatever
+/// follows the errors of earlier batches, then append the last committed
+/// batch's errors. Doing so again changes nothing
+async fn write_errors(errors: &mut tokio::fs::File, job: &ImportJob) -> std::io::Result<()> {
+    let written = job.error_bytes - job.pending_errors.len() as i64;
+    errors.set_len(u64::try_from(written).unwrap_or(0)).await?;
+    errors.write_all(job.pending_errors.as_bytes()).await?;
+    errors.flush().await?;
+    errors.sync_data().await
+}
+
+/// Parse, validate and assign the stored id of one NDJSON line, adding it to
+/// the batch. UUID ids are kept so references between imported files
+/// resolve; others are replaced
+fn parse_line(line: &str, expected_type: Option<&str>, batch: &mut ImportBatch) -> Result<Uuid, Vec<FhirError>> {
+    let value: serde_json::Value = serde_json::from_str(line).map_err(|e| vec![FhirError::from(e)])?;
+    let resource_type = value
+        .get("resourceType")
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| vec![FhirError::MissingRequiredField("resourceType".to_string())])?
+        .to_string();
+    if expected_type.is_some_and(|expected| expected != resource_type) {
+        return Err(vec![FhirError::InvalidResourceType(format!(
+            "{} (this import only accepts {})",
+            resource_type,
+            expected_type.unwrap_or_default()
+        ))]);
+    }
+
+    match resource_type.as_str() {
+        "Patient" => prepare(value, &PatientValidator, &mut batch.patients),
+        "Observation" => prepare(value, &ObservationValidator, &mut batch.observations),
+        "Condition" => prepare(value, &ConditionValidator, &mut batch.conditions),
+        "Encounter" => prepare(value, &EncounterValidator, &mut batch.encounters),
+        "Practitioner" => prepare(value, &PractitionerValidator, &mut batch.practitioners),
+        "PractitionerRole" => prepare(value, &PractitionerRoleValidator, &mut batch.practitioner_roles),
+        "Organization" => prepare(value, &OrganizationValidator, &mut batch.organizations),
+        "Medication" => prepare(value, &MedicationValidator, &mut batch.medications),
+        "MedicationRequest" => prepare(value, &MedicationRequestValidator, &mut batch.medication_requests),
+        "MedicationStatement" => prepare(value, &MedicationStatementValidator, &mut batch.medication_statements),
+        "AllergyIntolerance" => prepare(value, &AllergyIntoleranceValidator, &mut batch.allergy_intolerances),
+        "Procedure" => prepare(value, &ProcedureValidator, &mut batch.procedures),
+        "DiagnosticReport" => prepare(value, &DiagnosticReportValidator, &mut batch.diagnostic_reports),
+        "Immunization" => prepare(value, &ImmunizationValidator, &mut batch.immunizations),
+        "Location" => prepare(value, &LocationValidator, &mut batch.locations),
+        "Group" => prepare(value, &GroupValidator, &mut batch.groups),
+        "DocumentReference" => prepare(value, &ImportedDocumentValidator, &mut batch.document_references),
+        other => Err(vec![FhirError::InvalidResourceType(other.to_string())]),
+    }
+}
+
+/// Imported documents must point at their content by url: inline data
+/// belongs on the blob store, which `$import` does not write to
+struct ImportedDocumentValidator;
+
+impl Validator<DocumentReference> for ImportedDocumentValidator {
+    fn issues(&self, document: &DocumentReference) -> Vec<FhirError> {
+        let mut issues = DocumentReferenceValidator.issues(document);
+        if document.content.iter().any(|content| content.attachment.data.is_some()) {
+            issues.push(FhirError::Validation(
+                "Imported DocumentReference attachments must reference their content by url, not inline data".to_string()
+            ));
+        }
+        issues
+    }
+}
+
+fn prepare<T: Resource + DeserializeOwned>(
+    value: serde_json::Value,
+    validator: &impl Validator<T>,
+    resources: &mut Vec<T>,
+) -> Result<Uuid, Vec<FhirError>> {
+    let mut resource: T = serde_json::from_value(value).map_err(|e| vec![FhirError::from(e)])?;
+    let issues = validator.issues(&resource);
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+
+    let id = resource
+        .id()
+        .and_then(|id| Uuid::parse_str(&id.0).ok())
+        .unwrap_or_else(Uuid::new_v4);
+    resource.set_id(Id(id.to_string()));
+    let meta = Meta::versioned(resource.meta(), 1);
+    resource.set_meta(meta);
+    resources.push(resource);
+    Ok(id)
+}
+
+/// Parameters summarizing a job: its source, line counts and, when lines
+/// were rejected, where to fetch their OperationOutcomes
+pub fn import_summary(job: &ImportJob, error_url: &str) -> Parameters {
+    let count = |name: &str, value: i64| {
+        let mut p = ParametersParameter::new(name);
+        p.value_integer = Some(FhirInteger(i32::try_from(value).unwrap_or(i32::MAX)));
+        p
+    };
+
+    let mut parameters = Parameters::new();
+    let mut source = ParametersParameter::new("source");
+    source.value_string = Some(FhirString(job.source.clone()));
+    parameters.add(source);
+    let mut status = ParametersParameter::new("status");
+    status.value_code = Some(Code(job.status.clone()));
+    parameters.add(status);
+    parameters.add(count("processed", job.lines_processed));
+    parameters.add(count("imported", job.lines_imported));
+    parameters.add(count("failed", job.lines_failed));
+    if job.lines_failed > 0 {
+        let mut errors = ParametersParameter::new("errors");
+        errors.value_uri = Some(Uri(error_url.to_string()));
+        parameters.add(errors);
+    }
+    parameters
+}
+
+/// OperationOutcome reporting why a line was rejected
+fn line_outcome(line: i64, issues: &[FhirError]) -> OperationOutcome {
+    let mut outcome = OperationOutcome::from_issues(issues);
+    for issue in &mut outcome.issue {
+        let diagnostics = issue.diagnostics.take().map(|d| d.0).unwrap_or_default();
+        issue.diagnostics = Some(FhirString(format!("Line {}: {}", line, diagnostics)));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        let mut batch = ImportBatch::default();
+        let id = Uuid::new_v4();
+        let line = format!(r#"{{"resourceType":"Patient","id":"{}","gender":"female"}}"#, id);
+        assert_eq!(parse_line(&line, None, &mut batch).unwrap(), id);
+        let patient = &batch.patients[0];
+        assert_eq!(patient.meta.as_ref().unwrap().version_id.as_ref().unwrap().0, "1");
+
+        // Non-UUID ids are replaced
+        let parsed_id = parse_line(r#"{"resourceType":"Patient","id":"abc"}"#, None, &mut batch).unwrap();
+        assert_eq!(batch.patients[1].id.as_ref().unwrap().0, parsed_id.to_string());
+
+        assert!(parse_line("not json", None, &mut batch).is_err());
+        assert!(parse_line(r#"{"resourceType":"Basic"}"#, None, &mut batch).is_err());
+        assert!(parse_line(r#"{"resourceType":"Patient"}"#, Some("Observation"), &mut batch).is_err());
+        assert_eq!(batch.len(), 2);
+
+        let issues = parse_line(r#"{"id":"x"}"#, None, &mut batch).unwrap_err();
+        let outcome = line_outcome(7, &issues);
+        let diagnostics = &outcome.issue[0].diagnostics.as_ref().unwrap().0;
+        assert!(diagnostics.starts_with("Line 7: "));
+    }
+
+    #[test]
+    fn test_error_report_lists_refused_lines_in_order() {
+        let (kept, missing, existing) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let parsed = vec![(1, kept), (2, missing), (4, existing)];
+        let rejected = vec![(3, vec![FhirError::MissingRequiredField("resourceType".to_string())])];
+        let result = BatchInsert { inserted: vec![kept], missing_subject: vec![missing] };
+
+        let report = error_report(rejected, &parsed, &result).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("Line 2: ") && lines[0].contains("does not exist"));
+        assert!(lines[1].contains("Line 3: "));
+        assert!(lines[2].contains("Line 4: ") && lines[2].contains("already exists"));
+
+        assert!(error_report(Vec::new(), &[(1, kept)], &result).unwrap().is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use crate::domain::{Condition, FhirError, FhirResult};
 use crate::repository::{ConditionRepository, Repository, SearchParams};
 use crate::service::{
     ResourceService, SearchParameters, SearchResult, Validator, ConditionValidator,
-    SecurityContext, ConditionAuthorizationRules,
+    SecurityContext, ConditionAuthorizationRules, ValidationMode,
 };
 
 pub struct ConditionService {
@@ -98,6 +98,50 @@ impl ConditionService {
         
         Ok(active)
     }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        condition: Option<&Condition>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, condition)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing condition
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Condition".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, condition) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, resource),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, resource),
+            (ValidationMode::Delete, Some(id), _) => self.auth_rules.can_delete(context, id, existing.as_ref()),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the condition
+        if let Some(resource) = condition.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            if let Some(reference) = &resource.subject.reference {
+                issues.extend(self.validate_reference(&reference.0).await.err());
+            }
+        }
+
+        Ok(issues)
+    }
 }
 
 #[async_trait::async_trait]
@@ -165,7 +209,8 @@ impl ResourceService<Condition> for ConditionService {
 
         let search_params = SearchParams::new()
             .with_limit(limit)
-            .with_offset(offset);
+            .with_offset(offset)
+            .with_filters(&params.filters);
 
         let resources = self.repository.search(search_params).await?;
         let count = resources.len() as u32;
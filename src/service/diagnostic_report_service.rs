@@ -0,0 +1,262 @@
+// src/service/diagnostic_report_service.rs
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::domain::{DiagnosticReport, Observation, FhirError, FhirResult};
+use crate::repository::{
+    DiagnosticReportRepository, ObservationRepository, PatientRepository, Repository, SearchParams,
+};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, DiagnosticReportValidator,
+    SecurityContext, CompartmentAuthorizationRules, ObservationAuthorizationRules, ValidationMode,
+};
+
+pub struct DiagnosticReportService {
+    repository: DiagnosticReportRepository,
+    patients: PatientRepository,
+    observations: ObservationRepository,
+    validator: DiagnosticReportValidator,
+    auth_rules: CompartmentAuthorizationRules,
+    observation_auth_rules: ObservationAuthorizationRules,
+}
+
+impl DiagnosticReportService {
+    pub fn new(
+        repository: DiagnosticReportRepository,
+        patients: PatientRepository,
+        observations: ObservationRepository,
+    ) -> Self {
+        Self {
+            repository,
+            patients,
+            observations,
+            validator: DiagnosticReportValidator,
+            auth_rules: CompartmentAuthorizationRules::new("DiagnosticReport"),
+            observation_auth_rules: ObservationAuthorizationRules::new(),
+        }
+    }
+
+    /// The subject patient must be stored, and every `result` must be a
+    /// stored Observation about the same subject. References to other types
+    /// are reported by the validator
+    async fn validate_references(&self, report: &DiagnosticReport) -> FhirResult<()> {
+        let subject = report.subject.reference.as_ref().map(|r| r.0.as_str());
+        if let Some(id) = subject.and_then(|r| r.strip_prefix("Patient/")) {
+            if self.patients.read(id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: Patient/{}", id)
+                ));
+            }
+        }
+
+        let mut results = Vec::new();
+        for reference in report.result.iter().flatten().filter_map(|r| r.reference.as_ref()) {
+            if let Some(id) = reference.0.strip_prefix("Observation/") {
+                let uuid = Uuid::parse_str(id).map_err(|_| FhirError::InvalidReference(
+                    format!("Referenced observation does not exist: {}", reference.0)
+                ))?;
+                results.push(uuid);
+            }
+        }
+        if results.is_empty() {
+            return Ok(());
+        }
+
+        let observations = self.observations.read_many(&results).await?;
+        for uuid in &results {
+            let id = uuid.to_string();
+            let observation = observations.iter()
+                .find(|o| o.id.as_ref().is_some_and(|i| i.0 == id))
+                .ok_or_else(|| FhirError::InvalidReference(
+                    format!("Referenced observation does not exist: Observation/{}", id)
+                ))?;
+            let observation_subject = observation.subject.as_ref()
+                .and_then(|s| s.reference.as_ref())
+                .map(|r| r.0.as_str());
+            if observation_subject != subject {
+                return Err(FhirError::InvalidReference(
+                    format!("Observation/{} does not belong to the report's subject", id)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Observations referenced by the reports' `result`, for
+    /// `_include=DiagnosticReport:result`. Each is returned once, and only
+    /// when the user may read it
+    pub async fn include_results(
+        &self,
+        context: &SecurityContext,
+        reports: &[DiagnosticReport],
+    ) -> FhirResult<Vec<Observation>> {
+        let mut seen = HashSet::new();
+        let ids: Vec<Uuid> = reports.iter()
+            .flat_map(|report| report.result.iter().flatten())
+            .filter_map(|r| r.reference.as_ref())
+            .filter_map(|r| r.0.strip_prefix("Observation/"))
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .filter(|id| seen.insert(*id))
+            .collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let observations = self.observations.read_many(&ids).await?;
+        Ok(observations.into_iter()
+            .filter(|o| {
+                let id = o.id.as_ref().map(|i| i.0.as_str()).unwrap_or_default();
+                self.observation_auth_rules.can_read(context, id, Some(o)).is_ok()
+            })
+            .collect())
+    }
+
+    /// Search diagnostic reports by patient
+    pub async fn search_by_patient(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<Vec<DiagnosticReport>> {
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        // Check authorization
+        self.auth_rules.search_patient(context, Some(patient_id))?;
+
+        self.repository.search_by_patient(patient_id).await
+    }
+
+    /// Get diagnostic report history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<DiagnosticReport>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current subject
+        self.auth_rules.can_read_history(context, id, history.first().map(|r| &r.subject))?;
+
+        Ok(history)
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        report: Option<&DiagnosticReport>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, report)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing report
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "DiagnosticReport".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, report) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, &resource.subject),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, &resource.subject),
+            (ValidationMode::Delete, Some(id), _) => {
+                self.auth_rules.can_delete(context, id, existing.as_ref().map(|r| &r.subject))
+            }
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the report
+        if let Some(resource) = report.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_references(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<DiagnosticReport> for DiagnosticReportService {
+    async fn create(&self, context: &SecurityContext, report: DiagnosticReport) -> FhirResult<DiagnosticReport> {
+        // Check authorization
+        self.auth_rules.can_create(context, &report.subject)?;
+
+        // Validate the report
+        self.validator.validate(&report)?;
+        self.validate_references(&report).await?;
+
+        self.repository.create(&report).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<DiagnosticReport> {
+        let report = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "DiagnosticReport".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, Some(&report.subject))?;
+
+        Ok(report)
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, report: DiagnosticReport) -> FhirResult<DiagnosticReport> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, &current.subject)?;
+        self.auth_rules.can_update(context, id, &report.subject)?;
+
+        // Validate the report
+        self.validator.validate(&report)?;
+        self.validate_references(&report).await?;
+
+        self.repository.update(id, &report).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().map(|r| &r.subject))?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<DiagnosticReport>> {
+        let requested = params.filters.iter()
+            .find(|(name, _)| name == "patient" || name == "subject")
+            .map(|(_, value)| value.strip_prefix("Patient/").unwrap_or(value));
+
+        // Check authorization; patients only search their own compartment
+        let patient = self.auth_rules.search_patient(context, requested)?;
+        let mut filters = params.filters.clone();
+        if let (None, Some(patient)) = (requested, patient) {
+            filters.push(("patient".to_string(), patient));
+        }
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
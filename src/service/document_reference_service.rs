@@ -0,0 +1,299 @@
+// src/service/document_reference_service.rs
+
+use crate::domain::{Attachment, Binary, Code, DocumentReference, FhirError, FhirResult, FhirString, Reference, UnsignedInt, Uri};
+use crate::repository::{DocumentReferenceRepository, PatientRepository, Repository, SearchParams, StoredBinary};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, DocumentReferenceValidator,
+    SecurityContext, DocumentAuthorizationRules, ValidationMode, BinaryService,
+};
+
+/// DocumentReference resources. Attachment content is never kept in the
+/// resource: inline `data` is moved to a new Binary and the attachment
+/// points at it instead, with its size and hash recorded
+pub struct DocumentReferenceService {
+    repository: DocumentReferenceRepository,
+    patients: PatientRepository,
+    binaries: BinaryService,
+    validator: DocumentReferenceValidator,
+    auth_rules: DocumentAuthorizationRules,
+}
+
+impl DocumentReferenceService {
+    pub fn new(repository: DocumentReferenceRepository, patients: PatientRepository, binaries: BinaryService) -> Self {
+        Self {
+            repository,
+            patients,
+            binaries,
+            validator: DocumentReferenceValidator,
+            auth_rules: DocumentAuthorizationRules::new("DocumentReference"),
+        }
+    }
+
+    /// A Patient subject must be stored; references to other types are
+    /// reported by the validator
+    async fn validate_reference(&self, document: &DocumentReference) -> FhirResult<()> {
+        let reference = document.subject.as_ref()
+            .and_then(|s| s.reference.as_ref())
+            .map(|r| r.0.as_str());
+        if let Some(id) = reference.and_then(|r| r.strip_prefix("Patient/")) {
+            if self.patients.read(id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: Patient/{}", id)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Attachments pointing at a stored Binary must agree with its content
+    /// type, size and hash, and fill in whichever they leave out
+    async fn link_content(&self, context: &SecurityContext, document: &mut DocumentReference) -> FhirResult<()> {
+        for content in &mut document.content {
+            let attachment = &mut content.attachment;
+            if attachment.data.is_some() {
+                continue;
+            }
+            let Some(id) = attachment.url.as_ref().and_then(|url| url.0.strip_prefix("Binary/")) else {
+                continue;
+            };
+            let stored = self.binaries.get(context, id).await.map_err(|e| match e {
+                FhirError::NotFound { .. } => FhirError::InvalidReference(
+                    format!("Referenced binary does not exist: Binary/{}", id)
+                ),
+                other => other,
+            })?;
+            describe_content(attachment, &stored)?;
+        }
+        Ok(())
+    }
+
+    /// Move inline attachment data to new Binary resources, in the
+    /// compartment of the document's patient, and point the attachments at them
+    async fn store_content(&self, context: &SecurityContext, document: &mut DocumentReference) -> FhirResult<()> {
+        let security_context = document_patient(document).cloned();
+        for content in &mut document.content {
+            let attachment = &mut content.attachment;
+            let Some(data) = attachment.data.take() else {
+                continue;
+            };
+            let content_type = attachment.content_type.clone().unwrap_or_else(|| Code(String::new()));
+            let mut binary = Binary::new(content_type);
+            binary.security_context = security_context.clone();
+            binary.data = Some(data);
+
+            // The stored content is authoritative for size and hash
+            let stored = self.binaries.create_resource(context, binary).await?;
+            attachment.size = None;
+            attachment.hash = None;
+            describe_content(attachment, &stored)?;
+        }
+        Ok(())
+    }
+
+    /// Validate, authorize and store content for a create or update
+    async fn prepare(&self, context: &SecurityContext, document: &mut DocumentReference) -> FhirResult<()> {
+        self.validator.validate(document)?;
+        self.validate_reference(document).await?;
+        self.link_content(context, document).await?;
+        self.store_content(context, document).await
+    }
+
+    /// Search document references by patient
+    pub async fn search_by_patient(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<Vec<DocumentReference>> {
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        // Check authorization
+        self.auth_rules.search_patient(context, Some(patient_id))?;
+
+        self.repository.search_by_patient(patient_id).await
+    }
+
+    /// Get document reference history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<DocumentReference>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current subject
+        self.auth_rules.can_read_history(context, id, history.first().and_then(|d| d.subject.as_ref()))?;
+
+        Ok(history)
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first.
+    /// Inline data is checked but not stored
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        document: Option<&DocumentReference>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, document)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing document reference
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "DocumentReference".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, document) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, resource.subject.as_ref()),
+            (ValidationMode::Update, Some(id), Some(resource)) => {
+                self.auth_rules.can_update(context, id, resource.subject.as_ref())
+            }
+            (ValidationMode::Delete, Some(id), _) => {
+                self.auth_rules.can_delete(context, id, existing.as_ref().and_then(|d| d.subject.as_ref()))
+            }
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the document reference and its content
+        if let Some(resource) = document.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_reference(resource).await.err());
+            issues.extend(self.link_content(context, &mut resource.clone()).await.err());
+            for content in &resource.content {
+                if let Some(data) = &content.attachment.data {
+                    issues.extend(check_base64(data).err());
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<DocumentReference> for DocumentReferenceService {
+    async fn create(&self, context: &SecurityContext, mut document: DocumentReference) -> FhirResult<DocumentReference> {
+        // Check authorization
+        self.auth_rules.can_create(context, document.subject.as_ref())?;
+
+        self.prepare(context, &mut document).await?;
+
+        self.repository.create(&document).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<DocumentReference> {
+        let document = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "DocumentReference".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, document.subject.as_ref())?;
+
+        Ok(document)
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, mut document: DocumentReference) -> FhirResult<DocumentReference> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, current.subject.as_ref())?;
+        self.auth_rules.can_update(context, id, document.subject.as_ref())?;
+
+        self.prepare(context, &mut document).await?;
+
+        self.repository.update(id, &document).await
+    }
+
+    /// Deleting a document reference leaves the Binary resources holding its
+    /// content, which other documents may also point at
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().and_then(|d| d.subject.as_ref()))?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<DocumentReference>> {
+        let requested = params.filters.iter()
+            .find(|(name, _)| name == "patient")
+            .map(|(_, value)| value.strip_prefix("Patient/").unwrap_or(value));
+
+        // Check authorization; patients only search their own compartment
+        let patient = self.auth_rules.search_patient(context, requested)?;
+        let mut filters = params.filters.clone();
+        if let (None, Some(patient)) = (requested, patient) {
+            filters.push(("patient".to_string(), patient));
+        }
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
+
+/// Fill in the content type, size and hash of an attachment from the Binary
+/// holding its content, refusing values that disagree with it
+fn describe_content(attachment: &mut Attachment, stored: &StoredBinary) -> FhirResult<()> {
+    let id = stored.binary.id.as_ref().map(|id| id.0.as_str()).unwrap_or_default();
+    let content_type = &stored.binary.content_type;
+    if attachment.content_type.as_ref().is_some_and(|given| given != content_type) {
+        return Err(FhirError::Validation(format!(
+            "Attachment contentType does not match Binary/{} ({})", id, content_type.0
+        )));
+    }
+    let size = u32::try_from(stored.blob.size).ok().map(UnsignedInt);
+    if attachment.size.is_some() && attachment.size != size {
+        return Err(FhirError::Validation(format!(
+            "Attachment size does not match Binary/{} ({} bytes)", id, stored.blob.size
+        )));
+    }
+    if attachment.hash.as_ref().is_some_and(|hash| hash.0 != stored.blob.hash) {
+        return Err(FhirError::Validation(format!(
+            "Attachment hash does not match the SHA-1 of Binary/{}", id
+        )));
+    }
+
+    attachment.content_type = Some(content_type.clone());
+    attachment.url = Some(Uri(format!("Binary/{}", id)));
+    attachment.size = size;
+    attachment.hash = Some(FhirString(stored.blob.hash.clone()));
+    Ok(())
+}
+
+/// Inline attachment data must be base64
+fn check_base64(data: &FhirString) -> FhirResult<()> {
+    use base64::Engine;
+
+    base64::engine::general_purpose::STANDARD
+        .decode(data.0.as_bytes())
+        .map(|_| ())
+        .map_err(|e| FhirError::Validation(format!("Attachment data is not valid base64: {}", e)))
+}
+
+/// The subject of a document when it is a Patient
+fn document_patient(document: &DocumentReference) -> Option<&Reference> {
+    document.subject.as_ref()
+        .filter(|subject| subject.reference.as_ref().is_some_and(|r| r.0.starts_with("Patient/")))
+}
@@ -4,7 +4,7 @@ use crate::domain::{Encounter, FhirError, FhirResult};
 use crate::repository::{EncounterRepository, Repository, SearchParams};
 use crate::service::{
     ResourceService, SearchParameters, SearchResult, Validator, EncounterValidator,
-    SecurityContext, EncounterAuthorizationRules,
+    SecurityContext, EncounterAuthorizationRules, ValidationMode,
 };
 
 pub struct EncounterService {
@@ -121,6 +121,50 @@ impl EncounterService {
         // Update the encounter
         self.repository.update(id, &encounter).await
     }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        encounter: Option<&Encounter>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, encounter)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing encounter
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Encounter".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, encounter) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, resource),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, resource),
+            (ValidationMode::Delete, Some(id), _) => self.auth_rules.can_delete(context, id, existing.as_ref()),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the encounter
+        if let Some(resource) = encounter.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            if let Some(reference) = resource.subject.as_ref().and_then(|s| s.reference.as_ref()) {
+                issues.extend(self.validate_reference(&reference.0).await.err());
+            }
+        }
+
+        Ok(issues)
+    }
 }
 
 #[async_trait::async_trait]
@@ -188,7 +232,8 @@ impl ResourceService<Encounter> for EncounterService {
 
         let search_params = SearchParams::new()
             .with_limit(limit)
-            .with_offset(offset);
+            .with_offset(offset)
+            .with_filters(&params.filters);
 
         let resources = self.repository.search(search_params).await?;
         let count = resources.len() as u32;
@@ -0,0 +1,585 @@
+// src/service/everything_service.rs
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::domain::{
+    AllergyIntolerance, Bundle, Condition, DiagnosticReport, DocumentReference, Encounter, Immunization, MedicationRequest, MedicationStatement, Meta, Observation, Period, Procedure,
+    Reference, UnsignedInt,
+    FhirError, FhirResult,
+};
+use crate::domain::resources::{
+    allergy_intolerance::AllergyIntoleranceOnset, condition::ConditionOnset, diagnostic_report::DiagnosticReportEffective, medication_statement::MedicationStatementEffective, observation::ObservationEffective,
+    immunization::ImmunizationOccurrence, procedure::ProcedurePerformed,
+};
+use crate::domain::resources::Resource;
+use crate::repository::{
+    AllergyIntoleranceRepository, ConditionRepository, DiagnosticReportRepository, DocumentReferenceRepository, EncounterRepository, ImmunizationRepository, MedicationRequestRepository, MedicationStatementRepository,
+    ObservationRepository, PatientRepository, ProcedureRepository, Repository,
+};
+use crate::service::{EncounterAuthorizationRules, PatientAuthorizationRules, SecurityContext};
+
+/// Resource types in the patient compartment that `$everything` returns
+pub const PATIENT_COMPARTMENT_TYPES: &[&str] = &[
+    "Patient", "Observation", "Condition", "Encounter", "MedicationRequest", "MedicationStatement",
+    "AllergyIntolerance", "Procedure", "DiagnosticReport", "Immunization", "DocumentReference",
+];
+
+/// Parameters of the `$everything` operation
+#[derive(Debug, Clone, Default)]
+pub struct EverythingParameters {
+    pub since: Option<DateTime<Utc>>,  // _since: only resources updated at or after this instant
+    pub types: Option<Vec<String>>,    // _type: only these resource types
+    pub start: Option<DateTime<Utc>>,  // start of the care date range
+    pub end: Option<DateTime<Utc>>,    // end of the care date range
+    pub count: Option<u32>,            // _count: page size (all when absent)
+    pub offset: Option<u32>,           // Pagination offset
+}
+
+impl EverythingParameters {
+    fn includes(&self, resource_type: &str) -> bool {
+        self.types
+            .as_ref()
+            .is_none_or(|types| types.iter().any(|t| t == resource_type))
+    }
+
+    /// Whether a compartment resource passes the `_since` and `start`/`end`
+    /// filters. An open-ended care period is ongoing, and resources without a
+    /// clinical date are always in the range
+    fn in_scope(&self, meta: Option<&Meta>, care_period: (Option<DateTime<Utc>>, Option<DateTime<Utc>>)) -> bool {
+        if let Some(since) = self.since {
+            let last_updated = meta.and_then(|m| m.last_updated.as_ref()).map(|i| i.0);
+            if last_updated.is_some_and(|updated| updated < since) {
+                return false;
+            }
+        }
+
+        let (from, to) = care_period;
+        let ends_before_start = matches!((self.start, to), (Some(start), Some(to)) if to < start);
+        let starts_after_end = matches!((self.end, from), (Some(end), Some(from)) if from > end);
+        !ends_before_start && !starts_after_end
+    }
+}
+
+/// Compartment operations that gather resources across resource types
+pub struct EverythingService {
+    patient_repository: PatientRepository,
+    observation_repository: ObservationRepository,
+    condition_repository: ConditionRepository,
+    encounter_repository: EncounterRepository,
+    medication_request_repository: MedicationRequestRepository,
+    medication_statement_repository: MedicationStatementRepository,
+    allergy_intolerance_repository: AllergyIntoleranceRepository,
+    procedure_repository: ProcedureRepository,
+    diagnostic_report_repository: DiagnosticReportRepository,
+    immunization_repository: ImmunizationRepository,
+    document_reference_repository: DocumentReferenceRepository,
+    auth_rules: PatientAuthorizationRules,
+    encounter_auth_rules: EncounterAuthorizationRules,
+}
+
+impl EverythingService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        patient_repository: PatientRepository,
+        observation_repository: ObservationRepository,
+        condition_repository: ConditionRepository,
+        encounter_repository: EncounterRepository,
+        medication_request_repository: MedicationRequestRepository,
+        medication_statement_repository: MedicationStatementRepository,
+        allergy_intolerance_repository: AllergyIntoleranceRepository,
+        procedure_repository: ProcedureRepository,
+        diagnostic_report_repository: DiagnosticReportRepository,
+        immunization_repository: ImmunizationRepository,
+        document_reference_repository: DocumentReferenceRepository,
+    ) -> Self {
+        Self {
+            patient_repository,
+            observation_repository,
+            condition_repository,
+            encounter_repository,
+            medication_request_repository,
+            medication_statement_repository,
+            allergy_intolerance_repository,
+            procedure_repository,
+            diagnostic_report_repository,
+            immunization_repository,
+            document_reference_repository,
+            auth_rules: PatientAuthorizationRules::new(),
+            encounter_auth_rules: EncounterAuthorizationRules::new(),
+        }
+    }
+
+    /// Patient/$everything: the patient and every resource in their
+    /// compartment, as a searchset Bundle page
+    pub async fn patient_everything(
+        &self,
+        context: &SecurityContext,
+        patient_id: &str,
+        params: EverythingParameters,
+    ) -> FhirResult<Bundle> {
+        // Check authorization
+        self.auth_rules.can_read_compartment(context, patient_id)?;
+
+        if let Some(unsupported) = params
+            .types
+            .iter()
+            .flatten()
+            .find(|t| !PATIENT_COMPARTMENT_TYPES.contains(&t.as_str()))
+        {
+            return Err(FhirError::Validation(format!(
+                "Unsupported _type for Patient/$everything: {}",
+                unsupported
+            )));
+        }
+
+        let patient = self.patient_repository.read(patient_id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Patient".to_string(),
+                id: patient_id.to_string(),
+            })?;
+
+        let mut entries = Vec::new();
+        if params.includes("Patient") {
+            entries.push((to_json(&patient)?, "match"));
+        }
+
+        if params.includes("Observation") {
+            for observation in self.observation_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(observation.meta.as_ref(), observation_period(&observation)) {
+                    entries.push((to_json(&observation)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("Condition") {
+            for condition in self.condition_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(condition.meta.as_ref(), condition_period(&condition)) {
+                    entries.push((to_json(&condition)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("Encounter") {
+            for encounter in self.encounter_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(encounter.meta.as_ref(), period_bounds(encounter.period.as_ref())) {
+                    entries.push((to_json(&encounter)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("MedicationRequest") {
+            for request in self.medication_request_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(request.meta.as_ref(), medication_request_period(&request)) {
+                    entries.push((to_json(&request)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("MedicationStatement") {
+            for statement in self.medication_statement_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(statement.meta.as_ref(), medication_statement_period(&statement)) {
+                    entries.push((to_json(&statement)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("AllergyIntolerance") {
+            for allergy in self.allergy_intolerance_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(allergy.meta.as_ref(), allergy_period(&allergy)) {
+                    entries.push((to_json(&allergy)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("Procedure") {
+            for procedure in self.procedure_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(procedure.meta.as_ref(), procedure_period(&procedure)) {
+                    entries.push((to_json(&procedure)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("DiagnosticReport") {
+            for report in self.diagnostic_report_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(report.meta.as_ref(), diagnostic_report_period(&report)) {
+                    entries.push((to_json(&report)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("Immunization") {
+            for immunization in self.immunization_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(immunization.meta.as_ref(), immunization_period(&immunization)) {
+                    entries.push((to_json(&immunization)?, "include"));
+                }
+            }
+        }
+
+        if params.includes("DocumentReference") {
+            for document in self.document_reference_repository.search_by_patient(patient_id).await? {
+                if params.in_scope(document.meta.as_ref(), document_reference_period(&document)) {
+                    entries.push((to_json(&document)?, "include"));
+                }
+            }
+        }
+
+        Ok(into_page(entries, &params))
+    }
+
+    /// Encounter/$everything: the encounter, its subject, the conditions it
+    /// diagnosed, and the observations, conditions and procedures recorded
+    /// during it. Conditions, observations and procedures about anyone but
+    /// the encounter's subject are left out, however they are referenced
+    pub async fn encounter_everything(&self, context: &SecurityContext, encounter_id: &str) -> FhirResult<Bundle> {
+        let encounter = self.encounter_repository.read(encounter_id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Encounter".to_string(),
+                id: encounter_id.to_string(),
+            })?;
+
+        // Check authorization
+        self.encounter_auth_rules.can_read(context, encounter_id, Some(&encounter))?;
+        let patient_id = encounter.subject.as_ref()
+            .and_then(|s| s.reference.as_ref())
+            .and_then(|r| r.0.strip_prefix("Patient/"))
+            .map(str::to_string);
+        if let Some(patient_id) = &patient_id {
+            self.auth_rules.can_read_compartment(context, patient_id)?;
+        }
+
+        let mut entries = vec![(to_json(&encounter)?, "match")];
+        if let Some(patient) = match &patient_id {
+            Some(patient_id) => self.patient_repository.read(patient_id).await?,
+            None => None,
+        } {
+            entries.push((to_json(&patient)?, "include"));
+        }
+
+        let mut condition_ids = Vec::new();
+        let diagnosed = encounter.diagnosis.iter().flatten()
+            .filter_map(|d| d.condition.reference.as_ref())
+            .filter_map(|r| r.0.strip_prefix("Condition/"));
+        for condition_id in diagnosed {
+            if condition_ids.iter().any(|id| id == condition_id) {
+                continue;
+            }
+            let condition = self.condition_repository.read(condition_id).await?
+                .filter(|condition| shares_subject(Some(&condition.subject), &encounter));
+            if let Some(condition) = condition {
+                condition_ids.push(condition_id.to_string());
+                entries.push((to_json(&condition)?, "include"));
+            }
+        }
+
+        for condition in self.condition_repository.search_by_encounter(encounter_id).await? {
+            if !shares_subject(Some(&condition.subject), &encounter) {
+                continue;
+            }
+            let id = condition.id().map(|id| id.0.clone()).unwrap_or_default();
+            if !condition_ids.contains(&id) {
+                condition_ids.push(id);
+                entries.push((to_json(&condition)?, "include"));
+            }
+        }
+
+        for observation in self.observation_repository.search_by_encounter(encounter_id).await? {
+            if !shares_subject(observation.subject.as_ref(), &encounter) {
+                continue;
+            }
+            entries.push((to_json(&observation)?, "include"));
+        }
+
+        for procedure in self.procedure_repository.search_by_encounter(encounter_id).await? {
+            if !shares_subject(Some(&procedure.subject), &encounter) {
+                continue;
+            }
+            entries.push((to_json(&procedure)?, "include"));
+        }
+
+        Ok(into_page(entries, &EverythingParameters::default()))
+    }
+}
+
+
+fn to_json<T: Serialize>(resource: &T) -> FhirResult<serde_json::Value> {
+    Ok(serde_json::to_value(resource)?)
+}
+
+/// Slice the matching entries into the requested page
+fn into_page(entries: Vec<(serde_json::Value, &str)>, params: &EverythingParameters) -> Bundle {
+    let mut bundle = Bundle::new("searchset");
+    bundle.total = Some(UnsignedInt(entries.len() as u32));
+
+    let offset = params.offset.unwrap_or(0) as usize;
+    let count = params.count.map_or(usize::MAX, |count| count as usize);
+    for (resource, mode) in entries.into_iter().skip(offset).take(count) {
+        bundle.add_entry(resource, Some(mode));
+    }
+    bundle
+}
+
+/// Whether a resource with this subject is about the encounter's subject.
+/// Encounters without a subject cannot vouch for any resource
+fn shares_subject(subject: Option<&Reference>, encounter: &Encounter) -> bool {
+    let encounter_subject = encounter.subject.as_ref().and_then(|s| s.reference.as_ref());
+    encounter_subject.is_some() && subject.and_then(|s| s.reference.as_ref()) == encounter_subject
+}
+
+fn period_bounds(period: Option<&Period>) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match period {
+        Some(period) => (
+            period.start.as_ref().map(|s| s.0),
+            period.end.as_ref().map(|e| e.0),
+        ),
+        None => (None, None),
+    }
+}
+
+/// Clinically relevant time of an observation
+fn observation_period(observation: &Observation) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &observation.effective {
+        Some(ObservationEffective::DateTime(dt)) => (Some(dt.0), Some(dt.0)),
+        Some(ObservationEffective::Instant(instant)) => (Some(instant.0), Some(instant.0)),
+        Some(ObservationEffective::Period(period)) => period_bounds(Some(period)),
+        None => {
+            let issued = observation.issued.as_ref().map(|i| i.0);
+            (issued, issued)
+        }
+    }
+}
+
+/// Onset of a condition, falling back to when it was recorded
+fn condition_period(condition: &Condition) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &condition.onset {
+        Some(ConditionOnset::DateTime(dt)) => (Some(dt.0), None),
+        Some(ConditionOnset::Period(period)) => period_bounds(Some(period)),
+        _ => {
+            let recorded = condition.recorded_date.as_ref().map(|d| d.0);
+            (recorded, recorded)
+        }
+    }
+}
+
+/// When a medication request was written
+fn medication_request_period(request: &MedicationRequest) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let authored = request.authored_on.as_ref().map(|d| d.0);
+    (authored, authored)
+}
+
+/// Onset of an allergy, falling back to when it was recorded
+fn allergy_period(allergy: &AllergyIntolerance) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &allergy.onset {
+        Some(AllergyIntoleranceOnset::DateTime(dt)) => (Some(dt.0), None),
+        Some(AllergyIntoleranceOnset::Period(period)) => period_bounds(Some(period)),
+        _ => {
+            let recorded = allergy.recorded_date.as_ref().map(|d| d.0);
+            (recorded, recorded)
+        }
+    }
+}
+
+/// When a procedure was performed
+fn procedure_period(procedure: &Procedure) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &procedure.performed {
+        Some(ProcedurePerformed::DateTime(dt)) => (Some(dt.0), Some(dt.0)),
+        Some(ProcedurePerformed::Period(period)) => period_bounds(Some(period)),
+        _ => (None, None),
+    }
+}
+
+/// Clinically relevant time of a report, falling back to when it was issued
+fn diagnostic_report_period(report: &DiagnosticReport) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &report.effective {
+        Some(DiagnosticReportEffective::DateTime(dt)) => (Some(dt.0), Some(dt.0)),
+        Some(DiagnosticReportEffective::Period(period)) => period_bounds(Some(period)),
+        None => {
+            let issued = report.issued.as_ref().map(|i| i.0);
+            (issued, issued)
+        }
+    }
+}
+
+/// When a vaccine was given, falling back to when that was recorded if the
+/// occurrence is only known as free text
+fn immunization_period(immunization: &Immunization) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &immunization.occurrence {
+        ImmunizationOccurrence::DateTime(dt) => (Some(dt.0), Some(dt.0)),
+        ImmunizationOccurrence::String(_) => {
+            let recorded = immunization.recorded.as_ref().map(|d| d.0);
+            (recorded, recorded)
+        }
+    }
+}
+
+/// Service period the document covers, falling back to when it was created
+fn document_reference_period(document: &DocumentReference) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match document.context.as_ref().and_then(|context| context.period.as_ref()) {
+        Some(period) => period_bounds(Some(period)),
+        None => {
+            let date = document.date.as_ref().map(|d| d.0);
+            (date, date)
+        }
+    }
+}
+
+/// When a medication was taken, falling back to when that was asserted
+fn medication_statement_period(statement: &MedicationStatement) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match &statement.effective {
+        Some(MedicationStatementEffective::DateTime(dt)) => (Some(dt.0), Some(dt.0)),
+        Some(MedicationStatementEffective::Period(period)) => period_bounds(Some(period)),
+        None => {
+            let asserted = statement.date_asserted.as_ref().map(|d| d.0);
+            (asserted, asserted)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::domain::{Code, CodeableConcept, Coding, FhirDateTime, FhirString, Instant};
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn observation_on(date: DateTime<Utc>) -> Observation {
+        let mut observation = Observation::new(
+            Code("final".to_string()),
+            CodeableConcept { coding: None, text: None },
+        );
+        observation.effective = Some(ObservationEffective::DateTime(FhirDateTime(date)));
+        observation
+    }
+
+    #[test]
+    fn test_care_date_range() {
+        let params = EverythingParameters {
+            start: Some(at(2024, 1, 1)),
+            end: Some(at(2024, 12, 31)),
+            ..Default::default()
+        };
+
+        let inside = observation_on(at(2024, 6, 1));
+        let before = observation_on(at(2023, 6, 1));
+        let after = observation_on(at(2025, 6, 1));
+        assert!(params.in_scope(None, observation_period(&inside)));
+        assert!(!params.in_scope(None, observation_period(&before)));
+        assert!(!params.in_scope(None, observation_period(&after)));
+
+        // Ongoing encounters overlapping the range are included
+        let ongoing = Period { start: Some(FhirDateTime(at(2023, 6, 1))), end: None };
+        assert!(params.in_scope(None, period_bounds(Some(&ongoing))));
+        // Undated resources are always included
+        assert!(params.in_scope(None, (None, None)));
+    }
+
+    #[test]
+    fn test_since_and_type_filters() {
+        let params = EverythingParameters {
+            since: Some(at(2024, 1, 1)),
+            types: Some(vec!["Observation".to_string()]),
+            ..Default::default()
+        };
+
+        let mut meta = Meta {
+            version_id: None,
+            last_updated: Some(Instant(at(2023, 1, 1))),
+            source: None,
+            profile: None,
+            security: None,
+            tag: None,
+        };
+        assert!(!params.in_scope(Some(&meta), (None, None)));
+        meta.last_updated = Some(Instant(at(2024, 2, 1)));
+        assert!(params.in_scope(Some(&meta), (None, None)));
+
+        assert!(params.includes("Observation"));
+        assert!(!params.includes("Encounter"));
+    }
+
+    fn reference(reference: &str) -> Reference {
+        Reference {
+            reference: Some(FhirString(reference.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        }
+    }
+
+    fn encounter_of(subject: Option<&str>) -> Encounter {
+        let class = Coding {
+            system: None,
+            version: None,
+            code: Some(Code("AMB".to_string())),
+            display: None,
+            user_selected: None,
+        };
+        let mut encounter = Encounter::new(Code("finished".to_string()), class);
+        encounter.subject = subject.map(reference);
+        encounter
+    }
+
+    #[test]
+    fn test_encounter_conditions_must_share_its_subject() {
+        let mut encounter = encounter_of(None);
+        let own = Condition::new(reference("Patient/a"));
+        let other = Condition::new(reference("Patient/b"));
+
+        // Without a subject the encounter cannot vouch for any condition
+        assert!(!shares_subject(Some(&own.subject), &encounter));
+
+        encounter.subject = Some(reference("Patient/a"));
+        assert!(shares_subject(Some(&own.subject), &encounter));
+        // A diagnosis pointing at another patient's condition is not included
+        assert!(!shares_subject(Some(&other.subject), &encounter));
+    }
+
+    #[test]
+    fn test_encounter_observations_must_share_its_subject() {
+        let encounter = encounter_of(Some("Patient/a"));
+        let mut own = observation_on(at(2024, 6, 1));
+        own.subject = Some(reference("Patient/a"));
+        let mut other = observation_on(at(2024, 6, 1));
+        other.subject = Some(reference("Patient/b"));
+
+        assert!(shares_subject(own.subject.as_ref(), &encounter));
+        // Another patient's observation naming this encounter is not included
+        assert!(!shares_subject(other.subject.as_ref(), &encounter));
+        // Nor is one without a subject
+        assert!(!shares_subject(None, &encounter));
+    }
+
+    #[test]
+    fn test_encounter_procedures_must_share_its_subject() {
+        let encounter = encounter_of(Some("Patient/a"));
+        let own = Procedure::new(Code("completed".to_string()), reference("Patient/a"));
+        let other = Procedure::new(Code("completed".to_string()), reference("Patient/b"));
+
+        assert!(shares_subject(Some(&own.subject), &encounter));
+        // Another patient's procedure naming this encounter is not included
+        assert!(!shares_subject(Some(&other.subject), &encounter));
+    }
+
+    #[test]
+    fn test_paging() {
+        let entries = (0..5)
+            .map(|i| (serde_json::json!({"resourceType": "Observation", "id": i.to_string()}), "include"))
+            .collect();
+        let params = EverythingParameters {
+            count: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        };
+
+        let bundle = into_page(entries, &params);
+        let entry = bundle.entry.unwrap();
+        assert_eq!(bundle.total, Some(UnsignedInt(5)));
+        assert_eq!(entry.len(), 2);
+        assert_eq!(entry[0].resource.as_ref().unwrap()["id"], "2");
+    }
+}
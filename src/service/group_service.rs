@@ -0,0 +1,289 @@
+// src/service/group_service.rs
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{Group, FhirError, FhirResult};
+use crate::repository::{GroupRepository, PatientRepository, Repository, SearchParams};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, GroupValidator,
+    SecurityContext, GroupAuthorizationRules, ValidationMode,
+};
+
+pub struct GroupService {
+    repository: GroupRepository,
+    patients: PatientRepository,
+    validator: GroupValidator,
+    auth_rules: GroupAuthorizationRules,
+}
+
+impl GroupService {
+    pub fn new(repository: GroupRepository, patients: PatientRepository) -> Self {
+        Self {
+            repository,
+            patients,
+            validator: GroupValidator,
+            auth_rules: GroupAuthorizationRules::new(),
+        }
+    }
+
+    /// Get group history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Group>> {
+        // Check authorization
+        self.auth_rules.can_read_history(context, id)?;
+
+        self.repository.get_history(id).await
+    }
+
+    /// Expand an actual group to the ids of its current Patient members,
+    /// following nested groups. Inactive members, memberships whose period
+    /// has ended and patients no longer stored are left out. Definitional
+    /// groups have no member list and cannot be expanded
+    pub async fn patient_ids(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Uuid>> {
+        // Check authorization
+        self.auth_rules.can_read(context, id)?;
+
+        let now = Utc::now();
+        let mut patients = HashSet::new();
+        let mut visited = HashSet::from([id.to_string()]);
+        let mut pending = vec![id.to_string()];
+        while let Some(group_id) = pending.pop() {
+            let Some(group) = self.repository.read(&group_id).await? else {
+                if group_id == id {
+                    return Err(FhirError::NotFound {
+                        resource_type: "Group".to_string(),
+                        id: group_id,
+                    });
+                }
+                // A nested group deleted since it was added has no members
+                continue;
+            };
+            if !group.actual.0 {
+                return Err(FhirError::Validation(format!(
+                    "Group/{} is definitional and cannot be expanded to its members", group_id
+                )));
+            }
+
+            let (members, nested) = current_members(&group, now);
+            patients.extend(members);
+            pending.extend(nested.into_iter().filter(|nested| visited.insert(nested.clone())));
+        }
+
+        let patients: Vec<Uuid> = patients.into_iter().collect();
+        let mut stored = self.patients.existing_ids(&patients).await?;
+        stored.sort();
+        Ok(stored)
+    }
+
+    /// Patient and Group members must be stored
+    async fn validate_members(&self, group: &Group) -> FhirResult<()> {
+        let mut patients = Vec::new();
+        for reference in group.member.iter().flatten().filter_map(|m| m.entity.reference.as_ref()) {
+            if let Some(id) = reference.0.strip_prefix("Patient/") {
+                let uuid = Uuid::parse_str(id).map_err(|_| FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: {}", reference.0)
+                ))?;
+                patients.push(uuid);
+            } else if let Some(id) = reference.0.strip_prefix("Group/") {
+                if self.repository.read(id).await?.is_none() {
+                    return Err(FhirError::InvalidReference(
+                        format!("Referenced group does not exist: {}", reference.0)
+                    ));
+                }
+            }
+        }
+
+        let stored: HashSet<Uuid> = self.patients.existing_ids(&patients).await?.into_iter().collect();
+        if let Some(missing) = patients.iter().find(|id| !stored.contains(id)) {
+            return Err(FhirError::InvalidReference(
+                format!("Referenced patient does not exist: Patient/{}", missing)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        group: Option<&Group>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, group)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing group
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            if self.repository.read(id).await?.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Group".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id) {
+            (ValidationMode::Create, _) => self.auth_rules.can_create(context),
+            (ValidationMode::Update, Some(id)) => self.auth_rules.can_update(context, id),
+            (ValidationMode::Delete, Some(id)) => self.auth_rules.can_delete(context, id),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the group
+        if let Some(resource) = group.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_members(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Patient ids and nested group ids among the members of `group` that are
+/// active at `now`
+fn current_members(group: &Group, now: DateTime<Utc>) -> (Vec<Uuid>, Vec<String>) {
+    let mut patients = Vec::new();
+    let mut groups = Vec::new();
+    for member in group.member.iter().flatten() {
+        let inactive = member.inactive.as_ref().is_some_and(|i| i.0);
+        let period = member.period.as_ref();
+        let started = period.and_then(|p| p.start.as_ref()).is_none_or(|start| start.0 <= now);
+        let ended = period.and_then(|p| p.end.as_ref()).is_some_and(|end| end.0 < now);
+        if inactive || !started || ended {
+            continue;
+        }
+
+        let Some(reference) = member.entity.reference.as_ref() else {
+            continue;
+        };
+        if let Some(id) = reference.0.strip_prefix("Patient/") {
+            patients.extend(Uuid::parse_str(id).ok());
+        } else if let Some(id) = reference.0.strip_prefix("Group/") {
+            groups.push(id.to_string());
+        }
+    }
+    (patients, groups)
+}
+
+#[async_trait::async_trait]
+impl ResourceService<Group> for GroupService {
+    async fn create(&self, context: &SecurityContext, group: Group) -> FhirResult<Group> {
+        // Check authorization
+        self.auth_rules.can_create(context)?;
+
+        // Validate the group
+        self.validator.validate(&group)?;
+        self.validate_members(&group).await?;
+
+        self.repository.create(&group).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<Group> {
+        // Check authorization
+        self.auth_rules.can_read(context, id)?;
+
+        self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Group".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, group: Group) -> FhirResult<Group> {
+        // Check authorization
+        self.auth_rules.can_update(context, id)?;
+
+        // Validate the group
+        self.validator.validate(&group)?;
+        self.validate_members(&group).await?;
+
+        self.repository.update(id, &group).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        // Check authorization
+        self.auth_rules.can_delete(context, id)?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<Group>> {
+        // Check authorization
+        self.auth_rules.can_search(context)?;
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&params.filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::domain::{Code, FhirBoolean, FhirDateTime, FhirString, Period, Reference};
+    use crate::domain::resources::group::GroupMember;
+
+    fn member(reference: &str) -> GroupMember {
+        GroupMember {
+            entity: Reference {
+                reference: Some(FhirString(reference.to_string())),
+                type_: None,
+                identifier: None,
+                display: None,
+            },
+            period: None,
+            inactive: None,
+        }
+    }
+
+    #[test]
+    fn test_current_members() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let current = Uuid::new_v4();
+        let left = Uuid::new_v4();
+        let inactive = Uuid::new_v4();
+
+        let mut ended = member(&format!("Patient/{}", left));
+        ended.period = Some(Period {
+            start: None,
+            end: Some(FhirDateTime(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())),
+        });
+        let mut paused = member(&format!("Patient/{}", inactive));
+        paused.inactive = Some(FhirBoolean(true));
+
+        let mut group = Group::new(Code("person".to_string()), FhirBoolean(true));
+        group.member = Some(vec![
+            member(&format!("Patient/{}", current)),
+            ended,
+            paused,
+            member("Group/nested"),
+            member("Practitioner/prac-1"),
+        ]);
+
+        let (patients, groups) = current_members(&group, now);
+        assert_eq!(patients, vec![current]);
+        assert_eq!(groups, vec!["nested".to_string()]);
+    }
+}
@@ -0,0 +1,187 @@
+// src/service/immunization_service.rs
+
+use crate::domain::{Immunization, FhirError, FhirResult};
+use crate::repository::{ImmunizationRepository, PatientRepository, Repository, SearchParams};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, ImmunizationValidator,
+    SecurityContext, CompartmentAuthorizationRules, ValidationMode,
+};
+
+pub struct ImmunizationService {
+    repository: ImmunizationRepository,
+    patients: PatientRepository,
+    validator: ImmunizationValidator,
+    auth_rules: CompartmentAuthorizationRules,
+}
+
+impl ImmunizationService {
+    pub fn new(repository: ImmunizationRepository, patients: PatientRepository) -> Self {
+        Self {
+            repository,
+            patients,
+            validator: ImmunizationValidator,
+            auth_rules: CompartmentAuthorizationRules::new("Immunization"),
+        }
+    }
+
+    /// The patient must be stored; references to other types are reported
+    /// by the validator
+    async fn validate_reference(&self, immunization: &Immunization) -> FhirResult<()> {
+        let reference = immunization.patient.reference.as_ref().map(|r| r.0.as_str());
+        if let Some(id) = reference.and_then(|r| r.strip_prefix("Patient/")) {
+            if self.patients.read(id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: Patient/{}", id)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Search immunizations by patient
+    pub async fn search_by_patient(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<Vec<Immunization>> {
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        // Check authorization
+        self.auth_rules.search_patient(context, Some(patient_id))?;
+
+        self.repository.search_by_patient(patient_id).await
+    }
+
+    /// Get immunization history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Immunization>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current patient
+        self.auth_rules.can_read_history(context, id, history.first().map(|i| &i.patient))?;
+
+        Ok(history)
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        immunization: Option<&Immunization>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, immunization)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing immunization
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Immunization".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, immunization) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, &resource.patient),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, &resource.patient),
+            (ValidationMode::Delete, Some(id), _) => {
+                self.auth_rules.can_delete(context, id, existing.as_ref().map(|i| &i.patient))
+            }
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the immunization
+        if let Some(resource) = immunization.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_reference(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<Immunization> for ImmunizationService {
+    async fn create(&self, context: &SecurityContext, immunization: Immunization) -> FhirResult<Immunization> {
+        // Check authorization
+        self.auth_rules.can_create(context, &immunization.patient)?;
+
+        // Validate the immunization
+        self.validator.validate(&immunization)?;
+        self.validate_reference(&immunization).await?;
+
+        self.repository.create(&immunization).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<Immunization> {
+        let immunization = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Immunization".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, Some(&immunization.patient))?;
+
+        Ok(immunization)
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, immunization: Immunization) -> FhirResult<Immunization> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, &current.patient)?;
+        self.auth_rules.can_update(context, id, &immunization.patient)?;
+
+        // Validate the immunization
+        self.validator.validate(&immunization)?;
+        self.validate_reference(&immunization).await?;
+
+        self.repository.update(id, &immunization).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().map(|i| &i.patient))?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<Immunization>> {
+        let requested = params.filters.iter()
+            .find(|(name, _)| name == "patient")
+            .map(|(_, value)| value.strip_prefix("Patient/").unwrap_or(value));
+
+        // Check authorization; patients only search their own compartment
+        let patient = self.auth_rules.search_patient(context, requested)?;
+        let mut filters = params.filters.clone();
+        if let (None, Some(patient)) = (requested, patient) {
+            filters.push(("patient".to_string(), patient));
+        }
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
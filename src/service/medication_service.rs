@@ -0,0 +1,136 @@
+// src/service/medication_service.rs
+
+use crate::domain::{Medication, FhirError, FhirResult};
+use crate::repository::{MedicationRepository, Repository, SearchParams};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, MedicationValidator,
+    SecurityContext, DirectoryAuthorizationRules, ValidationMode,
+};
+
+pub struct MedicationService {
+    repository: MedicationRepository,
+    validator: MedicationValidator,
+    auth_rules: DirectoryAuthorizationRules,
+}
+
+impl MedicationService {
+    pub fn new(repository: MedicationRepository) -> Self {
+        Self {
+            repository,
+            validator: MedicationValidator,
+            auth_rules: DirectoryAuthorizationRules::new("Medication"),
+        }
+    }
+
+    /// Get medication history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Medication>> {
+        // Check authorization
+        self.auth_rules.can_read_history(context, id)?;
+
+        self.repository.get_history(id).await
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        medication: Option<&Medication>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, medication)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing medication
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            if self.repository.read(id).await?.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Medication".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id) {
+            (ValidationMode::Create, _) => self.auth_rules.can_create(context),
+            (ValidationMode::Update, Some(id)) => self.auth_rules.can_update(context, id),
+            (ValidationMode::Delete, Some(id)) => self.auth_rules.can_delete(context, id),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the medication
+        if let Some(resource) = medication.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<Medication> for MedicationService {
+    async fn create(&self, context: &SecurityContext, medication: Medication) -> FhirResult<Medication> {
+        // Check authorization
+        self.auth_rules.can_create(context)?;
+
+        // Validate the medication
+        self.validator.validate(&medication)?;
+
+        self.repository.create(&medication).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<Medication> {
+        // Check authorization
+        self.auth_rules.can_read(context, id)?;
+
+        self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Medication".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, medication: Medication) -> FhirResult<Medication> {
+        // Check authorization
+        self.auth_rules.can_update(context, id)?;
+
+        // Validate the medication
+        self.validator.validate(&medication)?;
+
+        self.repository.update(id, &medication).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        // Check authorization
+        self.auth_rules.can_delete(context, id)?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<Medication>> {
+        // Check authorization
+        self.auth_rules.can_search(context)?;
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&params.filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
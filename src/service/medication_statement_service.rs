@@ -0,0 +1,196 @@
+// src/service/medication_statement_service.rs
+
+use crate::domain::{MedicationStatement, CodeableConceptOrReference, Reference, FhirError, FhirResult};
+use crate::repository::{
+    MedicationRepository, MedicationStatementRepository, PatientRepository, Repository, SearchParams,
+};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, MedicationStatementValidator,
+    SecurityContext, CompartmentAuthorizationRules, ValidationMode,
+};
+
+pub struct MedicationStatementService {
+    repository: MedicationStatementRepository,
+    patients: PatientRepository,
+    medications: MedicationRepository,
+    validator: MedicationStatementValidator,
+    auth_rules: CompartmentAuthorizationRules,
+}
+
+impl MedicationStatementService {
+    pub fn new(
+        repository: MedicationStatementRepository,
+        patients: PatientRepository,
+        medications: MedicationRepository,
+    ) -> Self {
+        Self {
+            repository,
+            patients,
+            medications,
+            validator: MedicationStatementValidator,
+            auth_rules: CompartmentAuthorizationRules::new("MedicationStatement"),
+        }
+    }
+
+    /// Get medication statement history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<MedicationStatement>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current subject
+        self.auth_rules.can_read_history(context, id, history.first().map(|r| &r.subject))?;
+
+        Ok(history)
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        statement: Option<&MedicationStatement>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, statement)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing medication statement
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "MedicationStatement".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, statement) {
+            (ValidationMode::Create, _, Some(statement)) => self.auth_rules.can_create(context, &statement.subject),
+            (ValidationMode::Update, Some(id), Some(statement)) => self.auth_rules.can_update(context, id, &statement.subject),
+            (ValidationMode::Delete, Some(id), _) => {
+                self.auth_rules.can_delete(context, id, existing.as_ref().map(|r| &r.subject))
+            }
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the medication statement
+        if let Some(resource) = statement.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_references(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+
+    /// The subject patient and any referenced Medication must be stored
+    async fn validate_references(&self, statement: &MedicationStatement) -> FhirResult<()> {
+        // References to other types are reported by the validator
+        let target = |reference: &Reference, prefix: &str| {
+            reference.reference.as_ref()
+                .and_then(|r| r.0.strip_prefix(prefix).map(|id| (r.0.clone(), id.to_string())))
+        };
+
+        if let Some((reference, id)) = target(&statement.subject, "Patient/") {
+            if self.patients.read(&id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: {}", reference)
+                ));
+            }
+        }
+        if let CodeableConceptOrReference::Reference(medication) = &statement.medication {
+            if let Some((reference, id)) = target(medication, "Medication/") {
+                if self.medications.read(&id).await?.is_none() {
+                    return Err(FhirError::InvalidReference(
+                        format!("Referenced medication does not exist: {}", reference)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<MedicationStatement> for MedicationStatementService {
+    async fn create(&self, context: &SecurityContext, statement: MedicationStatement) -> FhirResult<MedicationStatement> {
+        // Check authorization
+        self.auth_rules.can_create(context, &statement.subject)?;
+
+        // Validate the medication statement
+        self.validator.validate(&statement)?;
+        self.validate_references(&statement).await?;
+
+        self.repository.create(&statement).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<MedicationStatement> {
+        let statement = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "MedicationStatement".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, Some(&statement.subject))?;
+
+        Ok(statement)
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, statement: MedicationStatement) -> FhirResult<MedicationStatement> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, &current.subject)?;
+        self.auth_rules.can_update(context, id, &statement.subject)?;
+
+        // Validate the medication statement
+        self.validator.validate(&statement)?;
+        self.validate_references(&statement).await?;
+
+        self.repository.update(id, &statement).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().map(|r| &r.subject))?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<MedicationStatement>> {
+        let requested = params.filters.iter()
+            .find(|(name, _)| name == "patient" || name == "subject")
+            .map(|(_, value)| value.strip_prefix("Patient/").unwrap_or(value));
+
+        // Check authorization; patients only search their own compartment
+        let patient = self.auth_rules.search_patient(context, requested)?;
+        let mut filters = params.filters.clone();
+        if let (None, Some(patient)) = (requested, patient) {
+            filters.push(("patient".to_string(), patient));
+        }
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
@@ -0,0 +1,163 @@
+// src/service/meta_service.rs
+// Resource.meta operations: $meta, $meta-add and $meta-delete
+
+use crate::domain::{Coding, Meta, Parameters, FhirError, FhirResult};
+use crate::domain::resources::parameters::ParametersParameter;
+use crate::repository::MetaRepository;
+use crate::service::{Authorizer, DefaultAuthorizer, Permission, SecurityContext};
+
+/// Reads and edits the profiles, security labels and tags of stored resources
+pub struct MetaService {
+    repository: MetaRepository,
+    authorizer: DefaultAuthorizer,
+}
+
+impl MetaService {
+    pub fn new(repository: MetaRepository) -> Self {
+        Self {
+            repository,
+            authorizer: DefaultAuthorizer::new(),
+        }
+    }
+
+    /// Meta of one resource (`Type/:id/$meta`)
+    pub async fn read(&self, context: &SecurityContext, resource_type: &str, id: &str) -> FhirResult<Meta> {
+        self.authorized_meta(context, resource_type, id, Permission::Read).await
+    }
+
+    /// Profiles, security labels and tags in use, for one resource type
+    /// (`Type/$meta`) or the whole server (`$meta`)
+    pub async fn in_use(&self, context: &SecurityContext, resource_type: Option<&str>) -> FhirResult<Meta> {
+        // Summaries span every patient's records
+        if context.is_patient() {
+            return Err(FhirError::Forbidden {
+                message: "Patients may only use $meta on their own resources".to_string(),
+            });
+        }
+        self.authorizer.check_permission(context, resource_type.unwrap_or("Resource"), Permission::Search)?;
+
+        self.repository.in_use(resource_type).await
+    }
+
+    /// Add profiles, security labels and tags to a resource (`$meta-add`).
+    /// The resource keeps its version
+    pub async fn add(&self, context: &SecurityContext, resource_type: &str, id: &str, meta: &Meta) -> FhirResult<Meta> {
+        validate_codings(meta)?;
+        let mut stored = self.authorized_meta(context, resource_type, id, Permission::Update).await?;
+        stored.add(meta);
+        self.repository.write(resource_type, id, &stored).await?;
+        Ok(stored)
+    }
+
+    /// Remove profiles, security labels and tags from a resource
+    /// (`$meta-delete`). The resource keeps its version
+    pub async fn delete(&self, context: &SecurityContext, resource_type: &str, id: &str, meta: &Meta) -> FhirResult<Meta> {
+        let mut stored = self.authorized_meta(context, resource_type, id, Permission::Update).await?;
+        stored.remove(meta);
+        self.repository.write(resource_type, id, &stored).await?;
+        Ok(stored)
+    }
+
+    async fn authorized_meta(
+        &self,
+        context: &SecurityContext,
+        resource_type: &str,
+        id: &str,
+        permission: Permission,
+    ) -> FhirResult<Meta> {
+        self.authorizer.check_resource_access(context, resource_type, id, permission.clone())?;
+
+        let stored = self.repository.read(resource_type, id).await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+            })?;
+        if let Some(patient_id) = &stored.patient_id {
+            self.authorizer.check_patient_compartment_access(context, patient_id, permission)?;
+        }
+
+        Ok(stored.meta)
+    }
+}
+
+/// Tags and security labels are matched on system and code, so each needs one
+fn validate_codings(meta: &Meta) -> FhirResult<()> {
+    let mut codings = meta.tag.iter().chain(&meta.security).flatten();
+    if codings.any(|c: &Coding| c.system.is_none() && c.code.is_none()) {
+        return Err(FhirError::Validation(
+            "Tags and security labels need a system or a code".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Output of the meta operations: a `return` parameter holding the meta
+pub fn meta_parameters(meta: Meta) -> Parameters {
+    let mut parameters = Parameters::new();
+    let mut result = ParametersParameter::new("return");
+    result.value_meta = Some(meta);
+    parameters.add(result);
+    parameters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Canonical, Code, FhirString, Id, Uri};
+
+    fn coding(system: &str, code: &str) -> Coding {
+        Coding {
+            system: Some(Uri(system.to_string())),
+            version: None,
+            code: Some(Code(code.to_string())),
+            display: None,
+            user_selected: None,
+        }
+    }
+
+    #[test]
+    fn test_meta_add_and_remove() {
+        let client = Meta {
+            version_id: Some(Id("7".to_string())),
+            tag: Some(vec![coding("http://example.org/tags", "needs-review")]),
+            ..Default::default()
+        };
+
+        let mut meta = Meta::versioned(Some(&client), 1);
+        assert_eq!(meta.version_id, Some(Id("1".to_string())));
+        assert_eq!(meta.tag, client.tag);
+
+        let added = Meta {
+            profile: Some(vec![Canonical("http://example.org/StructureDefinition/vitals".to_string())]),
+            tag: Some(vec![coding("http://example.org/tags", "needs-review"), coding("http://example.org/tags", "imported")]),
+            ..Default::default()
+        };
+        meta.add(&added);
+        meta.add(&added);
+        assert_eq!(meta.tag.as_ref().map(Vec::len), Some(2));
+        assert_eq!(meta.profile.as_ref().map(Vec::len), Some(1));
+
+        let removed = Meta {
+            profile: added.profile.clone(),
+            tag: Some(vec![coding("http://example.org/tags", "needs-review")]),
+            ..Default::default()
+        };
+        meta.remove(&removed);
+        assert_eq!(meta.tag, Some(vec![coding("http://example.org/tags", "imported")]));
+        assert_eq!(meta.profile, None);
+        assert_eq!(meta.version_id, Some(Id("1".to_string())));
+
+        let invalid = Meta {
+            security: Some(vec![Coding {
+                system: None,
+                version: None,
+                code: None,
+                display: Some(FhirString("Restricted".to_string())),
+                user_selected: None,
+            }]),
+            ..Default::default()
+        };
+        assert!(validate_codings(&invalid).is_err());
+        assert!(validate_codings(&added).is_ok());
+    }
+}
@@ -4,6 +4,27 @@ pub mod patient_service;
 pub mod observation_service;
 pub mod condition_service;
 pub mod encounter_service;
+pub mod practitioner_service;
+pub mod practitioner_role_service;
+pub mod organization_service;
+pub mod medication_service;
+pub mod medication_request_service;
+pub mod medication_statement_service;
+pub mod allergy_intolerance_service;
+pub mod procedure_service;
+pub mod diagnostic_report_service;
+pub mod immunization_service;
+pub mod location_service;
+pub mod group_service;
+pub mod document_reference_service;
+pub mod binary_service;
+pub mod everything_service;
+pub mod meta_service;
+pub mod bulk_export_service;
+pub mod bulk_import_service;
+pub mod reindex_service;
+pub mod observation_stats;
+pub mod patient_matching;
 pub mod validation;
 pub mod authorization;
 pub mod authorization_rules;
@@ -12,11 +33,30 @@ pub use patient_service::PatientService;
 pub use observation_service::ObservationService;
 pub use condition_service::ConditionService;
 pub use encounter_service::EncounterService;
+pub use practitioner_service::PractitionerService;
+pub use practitioner_role_service::PractitionerRoleService;
+pub use organization_service::OrganizationService;
+pub use medication_service::MedicationService;
+pub use medication_request_service::MedicationRequestService;
+pub use medication_statement_service::MedicationStatementService;
+pub use allergy_intolerance_service::AllergyIntoleranceService;
+pub use procedure_service::ProcedureService;
+pub use diagnostic_report_service::DiagnosticReportService;
+pub use immunization_service::ImmunizationService;
+pub use location_service::LocationService;
+pub use group_service::GroupService;
+pub use document_reference_service::DocumentReferenceService;
+pub use binary_service::BinaryService;
+pub use everything_service::{EverythingService, EverythingParameters};
+pub use meta_service::MetaService;
+pub use bulk_export_service::{BulkExportService, ExportLevel, ExportParameters, ExportStatus};
+pub use bulk_import_service::BulkImportService;
+pub use reindex_service::ReindexService;
 pub use validation::*;
 pub use authorization::*;
 pub use authorization_rules::*;
 
-use crate::domain::errors::FhirResult;
+use crate::domain::errors::{FhirError, FhirResult};
 
 /// Base trait for all resource services
 #[async_trait::async_trait]
@@ -28,6 +68,43 @@ pub trait ResourceService<T> {
     async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<T>>;
 }
 
+/// Mode of the `$validate` operation: which interaction to dry-run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Validate the resource content only
+    General,
+    Create,
+    Update,
+    Delete,
+}
+
+impl ValidationMode {
+    /// Parse the `mode` parameter; absent means general validation
+    pub fn parse(mode: Option<&str>) -> FhirResult<Self> {
+        match mode {
+            None => Ok(Self::General),
+            Some("create") => Ok(Self::Create),
+            Some("update") => Ok(Self::Update),
+            Some("delete") => Ok(Self::Delete),
+            Some(other) => Err(FhirError::Validation(format!("Unsupported $validate mode: {}", other))),
+        }
+    }
+
+    /// Check that the request carries what this mode needs: update and
+    /// delete target an existing instance, and all but delete need a resource
+    pub fn check_request<T>(self, id: Option<&str>, resource: Option<&T>) -> FhirResult<()> {
+        if matches!(self, Self::Update | Self::Delete) && id.is_none() {
+            return Err(FhirError::Validation(
+                "$validate with mode=update or mode=delete must be invoked on a resource instance".to_string(),
+            ));
+        }
+        if self != Self::Delete && resource.is_none() {
+            return Err(FhirError::Validation("$validate requires a resource to validate".to_string()));
+        }
+        Ok(())
+    }
+}
+
 /// FHIR search parameters
 #[derive(Debug, Clone, Default)]
 pub struct SearchParameters {
@@ -1,11 +1,14 @@
 // src/service/observation_service.rs
 
-use crate::domain::{Observation, FhirError, FhirResult};
+use chrono::{DateTime, Utc};
+
+use crate::domain::{Observation, Parameters, FhirError, FhirResult};
 use crate::repository::{ObservationRepository, Repository, SearchParams};
 use crate::service::{
     ResourceService, SearchParameters, SearchResult, Validator, ObservationValidator,
-    SecurityContext, ObservationAuthorizationRules,
+    SecurityContext, ObservationAuthorizationRules, ValidationMode,
 };
+use crate::service::observation_stats::{self, Statistic, StatsParameters};
 
 pub struct ObservationService {
     repository: ObservationRepository,
@@ -92,6 +95,91 @@ impl ObservationService {
         self.repository.search_by_code(code).await
     }
 
+    /// The most recent `max` (default 1) observations of each code for a
+    /// patient (Observation/$lastn). `category` and `code` are token
+    /// parameters; `code` may list several codes separated by commas
+    pub async fn last_n(
+        &self,
+        context: &SecurityContext,
+        patient: &str,
+        category: Option<&str>,
+        code: Option<&str>,
+        max: Option<u32>,
+    ) -> FhirResult<Vec<Observation>> {
+        let patient_id = patient.strip_prefix("Patient/").unwrap_or(patient);
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        let max = max.unwrap_or(1);
+        if max == 0 {
+            return Err(FhirError::Validation("max must be at least 1".to_string()));
+        }
+
+        // Check authorization
+        self.auth_rules.can_search(context, Some(patient_id))?;
+
+        let category = category.map(token_code);
+        let codes: Option<Vec<String>> = code.map(|codes| {
+            codes.split(',').map(|c| token_code(c).to_string()).collect()
+        });
+
+        self.repository
+            .last_n(patient_id, category, codes.as_deref(), max as i64)
+            .await
+    }
+
+    /// Numeric statistics over a patient's quantity values for one or more
+    /// codes (Observation/$stats). `code` may list several comma-separated
+    /// tokens; a `system|` prefix restricts matches to that code system
+    pub async fn stats(
+        &self,
+        context: &SecurityContext,
+        patient: &str,
+        code: &str,
+        system: Option<&str>,
+        period: (Option<DateTime<Utc>>, Option<DateTime<Utc>>),
+        statistic: Option<&str>,
+    ) -> FhirResult<Parameters> {
+        let patient_id = patient.strip_prefix("Patient/").unwrap_or(patient);
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        let codes: Vec<String> = code
+            .split(',')
+            .map(|c| token_code(c).to_string())
+            .filter(|c| !c.is_empty())
+            .collect();
+        if codes.is_empty() {
+            return Err(FhirError::Validation("code is required".to_string()));
+        }
+
+        let system = system.map(str::to_string).or_else(|| {
+            code.split_once('|')
+                .map(|(system, _)| system.trim().to_string())
+                .filter(|system| !system.is_empty())
+        });
+
+        let params = StatsParameters {
+            patient_id: patient_id.to_string(),
+            codes,
+            system,
+            start: period.0,
+            end: period.1,
+            statistics: Statistic::parse_list(statistic)?,
+        };
+
+        // Check authorization
+        self.auth_rules.can_search(context, Some(patient_id))?;
+
+        let observations = self.repository
+            .search_by_patient_and_codes(patient_id, &params.codes, params.start, params.end)
+            .await?;
+
+        Ok(observation_stats::summarize(&observations, &params))
+    }
+
     /// Search observations by patient and code
     pub async fn search_by_patient_and_code(
         &self,
@@ -112,6 +200,56 @@ impl ObservationService {
 
         Ok(all_observations)
     }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        observation: Option<&Observation>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, observation)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing observation
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Observation".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, observation) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, resource),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, resource),
+            (ValidationMode::Delete, Some(id), _) => self.auth_rules.can_delete(context, id, existing.as_ref()),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the observation
+        if let Some(resource) = observation.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            if let Some(reference) = resource.subject.as_ref().and_then(|s| s.reference.as_ref()) {
+                issues.extend(self.validate_reference(&reference.0).await.err());
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// The code of a `[system|]code` token; codes are indexed without system
+fn token_code(token: &str) -> &str {
+    let token = token.trim();
+    token.split_once('|').map_or(token, |(_, code)| code)
 }
 
 #[async_trait::async_trait]
@@ -179,7 +317,8 @@ impl ResourceService<Observation> for ObservationService {
 
         let search_params = SearchParams::new()
             .with_limit(limit)
-            .with_offset(offset);
+            .with_offset(offset)
+            .with_filters(&params.filters);
 
         let resources = self.repository.search(search_params).await?;
         let count = resources.len() as u32;
@@ -191,4 +330,15 @@ impl ResourceService<Observation> for ObservationService {
             count,
         ))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_code_drops_system() {
+        assert_eq!(token_code("http://loinc.org|8867-4"), "8867-4");
+        assert_eq!(token_code(" vital-signs "), "vital-signs");
+        assert_eq!(token_code("|85354-9"), "85354-9");
+    }
+}
@@ -0,0 +1,343 @@
+// src/service/observation_stats.rs
+// Numeric aggregates over Observation quantities (Observation/$stats)
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::{
+    Code, CodeableConcept, Coding, FhirDateTime, FhirDecimal, FhirError, FhirResult, FhirString,
+    Observation, Parameters, Period, Quantity, Reference, Uri,
+};
+use crate::domain::resources::observation::{ObservationComponent, ObservationEffective, ObservationValue};
+use crate::domain::resources::parameters::ParametersParameter;
+
+const STATISTICS_SYSTEM: &str = "http://terminology.hl7.org/CodeSystem/observation-statistics";
+
+/// A statistic $stats can compute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statistic {
+    Count,
+    Minimum,
+    Maximum,
+    Average,
+    Median,
+    StdDev,
+}
+
+impl Statistic {
+    pub const ALL: &'static [Statistic] = &[
+        Statistic::Count,
+        Statistic::Minimum,
+        Statistic::Maximum,
+        Statistic::Average,
+        Statistic::Median,
+        Statistic::StdDev,
+    ];
+
+    /// Code in the observation-statistics code system
+    pub fn code(self) -> &'static str {
+        match self {
+            Statistic::Count => "count",
+            Statistic::Minimum => "minimum",
+            Statistic::Maximum => "maximum",
+            Statistic::Average => "average",
+            Statistic::Median => "median",
+            Statistic::StdDev => "std-dev",
+        }
+    }
+
+    /// Parse a comma-separated `statistic` parameter; all statistics when absent
+    pub fn parse_list(value: Option<&str>) -> FhirResult<Vec<Statistic>> {
+        let Some(value) = value else {
+            return Ok(Self::ALL.to_vec());
+        };
+
+        value
+            .split(',')
+            .map(|code| {
+                let code = code.trim();
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|s| s.code() == code)
+                    .ok_or_else(|| FhirError::Validation(format!("Unsupported statistic: {}", code)))
+            })
+            .collect()
+    }
+
+    fn compute(self, values: &[f64]) -> Option<f64> {
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        match self {
+            Statistic::Count => Some(count),
+            Statistic::Minimum => values.iter().copied().reduce(f64::min),
+            Statistic::Maximum => values.iter().copied().reduce(f64::max),
+            Statistic::Average => Some(mean),
+            Statistic::Median => {
+                let mut sorted = values.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let middle = sorted.len() / 2;
+                if sorted.len().is_multiple_of(2) {
+                    Some((sorted[middle - 1] + sorted[middle]) / 2.0)
+                } else {
+                    Some(sorted[middle])
+                }
+            }
+            // Sample standard deviation; undefined for a single value
+            Statistic::StdDev if values.len() > 1 => {
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1.0);
+                Some(variance.sqrt())
+            }
+            Statistic::StdDev => None,
+        }
+    }
+}
+
+/// Parameters of the $stats operation
+#[derive(Debug, Clone)]
+pub struct StatsParameters {
+    pub patient_id: String,
+    pub codes: Vec<String>,             // Codes to summarize, from the observation or a component
+    pub system: Option<String>,         // Code system the codes must belong to
+    pub start: Option<DateTime<Utc>>,   // Start of the effective period
+    pub end: Option<DateTime<Utc>>,     // End of the effective period
+    pub statistics: Vec<Statistic>,
+}
+
+/// Quantities recorded for one code in one unit
+struct Series {
+    coding: Coding,
+    unit: Quantity,
+    values: Vec<f64>,
+    first: Option<DateTime<Utc>>,
+    last: Option<DateTime<Utc>>,
+}
+
+/// Summarize the quantities recorded for each requested code, as one
+/// `statistics` Observation per code and unit
+pub fn summarize(observations: &[Observation], params: &StatsParameters) -> Parameters {
+    let mut series: Vec<Series> = Vec::new();
+
+    for observation in observations {
+        let effective = match &observation.effective {
+            Some(ObservationEffective::DateTime(dt)) => Some(dt.0),
+            Some(ObservationEffective::Instant(instant)) => Some(instant.0),
+            Some(ObservationEffective::Period(period)) => period.start.as_ref().map(|s| s.0),
+            None => None,
+        };
+
+        // Values may sit on the observation itself or on its components
+        // (e.g. systolic/diastolic in a blood pressure panel)
+        let components = observation.component.iter().flatten().map(|c: &ObservationComponent| (&c.code, &c.value));
+        for (code, value) in std::iter::once((&observation.code, &observation.value)).chain(components) {
+            let (Some(coding), Some(ObservationValue::Quantity(quantity))) = (matching_coding(code, params), value) else {
+                continue;
+            };
+            let Some(FhirDecimal(value)) = quantity.value else {
+                continue;
+            };
+
+            let unit = unit_of(quantity);
+            let index = match series.iter().position(|s| s.coding.code == coding.code && s.unit == unit) {
+                Some(index) => index,
+                None => {
+                    series.push(Series { coding: coding.clone(), unit, values: Vec::new(), first: None, last: None });
+                    series.len() - 1
+                }
+            };
+
+            let entry = &mut series[index];
+            entry.values.push(value);
+            if let Some(effective) = effective {
+                entry.first = Some(entry.first.map_or(effective, |first| first.min(effective)));
+                entry.last = Some(entry.last.map_or(effective, |last| last.max(effective)));
+            }
+        }
+    }
+
+    let mut parameters = Parameters::new();
+    for entry in series {
+        let mut parameter = ParametersParameter::new("statistics");
+        parameter.resource = serde_json::to_value(statistics_observation(entry, params)).ok();
+        parameters.add(parameter);
+    }
+    parameters
+}
+
+/// The coding of `code` that matches one of the requested codes, if any
+fn matching_coding<'a>(code: &'a CodeableConcept, params: &StatsParameters) -> Option<&'a Coding> {
+    code.coding.iter().flatten().find(|coding| {
+        let code_matches = coding.code.as_ref().is_some_and(|c| params.codes.contains(&c.0));
+        let system_matches = params.system.as_ref().is_none_or(|system| {
+            coding.system.as_ref().is_some_and(|s| &s.0 == system)
+        });
+        code_matches && system_matches
+    })
+}
+
+/// The unit part of a quantity, used to keep values in different units apart
+fn unit_of(quantity: &Quantity) -> Quantity {
+    Quantity {
+        value: None,
+        comparator: None,
+        unit: quantity.unit.clone(),
+        system: quantity.system.clone(),
+        code: quantity.code.clone(),
+    }
+}
+
+fn statistics_observation(series: Series, params: &StatsParameters) -> Observation {
+    let mut observation = Observation::new(
+        Code("final".to_string()),
+        CodeableConcept {
+            coding: Some(vec![series.coding]),
+            text: None,
+        },
+    );
+    observation.subject = Some(Reference {
+        reference: Some(FhirString(format!("Patient/{}", params.patient_id))),
+        type_: None,
+        identifier: None,
+        display: None,
+    });
+    observation.effective = Some(ObservationEffective::Period(Period {
+        start: series.first.or(params.start).map(FhirDateTime),
+        end: series.last.or(params.end).map(FhirDateTime),
+    }));
+
+    let components = params
+        .statistics
+        .iter()
+        .filter_map(|statistic| {
+            let value = statistic.compute(&series.values)?;
+            let quantity = match statistic {
+                Statistic::Count => Quantity {
+                    value: Some(FhirDecimal(value)),
+                    comparator: None,
+                    unit: None,
+                    system: None,
+                    code: None,
+                },
+                _ => Quantity { value: Some(FhirDecimal(value)), ..series.unit.clone() },
+            };
+
+            Some(ObservationComponent {
+                code: CodeableConcept {
+                    coding: Some(vec![Coding {
+                        system: Some(Uri(STATISTICS_SYSTEM.to_string())),
+                        version: None,
+                        code: Some(Code(statistic.code().to_string())),
+                        display: None,
+                        user_selected: None,
+                    }]),
+                    text: None,
+                },
+                value: Some(ObservationValue::Quantity(quantity)),
+                data_absent_reason: None,
+                interpretation: None,
+                reference_range: None,
+            })
+        })
+        .collect();
+    observation.component = Some(components);
+
+    observation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn coding(code: &str) -> CodeableConcept {
+        CodeableConcept {
+            coding: Some(vec![Coding {
+                system: Some(Uri("http://loinc.org".to_string())),
+                version: None,
+                code: Some(Code(code.to_string())),
+                display: None,
+                user_selected: None,
+            }]),
+            text: None,
+        }
+    }
+
+    fn mm_hg(value: f64) -> Option<ObservationValue> {
+        Some(ObservationValue::Quantity(Quantity {
+            value: Some(FhirDecimal(value)),
+            comparator: None,
+            unit: Some(FhirString("mmHg".to_string())),
+            system: Some(Uri("http://unitsofmeasure.org".to_string())),
+            code: Some(Code("mm[Hg]".to_string())),
+        }))
+    }
+
+    fn blood_pressure(day: u32, systolic: f64, diastolic: f64) -> Observation {
+        let mut observation = Observation::new(Code("final".to_string()), coding("85354-9"));
+        observation.effective = Some(ObservationEffective::DateTime(FhirDateTime(
+            Utc.with_ymd_and_hms(2024, 1, day, 8, 0, 0).unwrap(),
+        )));
+        observation.component = Some(
+            [("8480-6", systolic), ("8462-4", diastolic)]
+                .into_iter()
+                .map(|(code, value)| ObservationComponent {
+                    code: coding(code),
+                    value: mm_hg(value),
+                    data_absent_reason: None,
+                    interpretation: None,
+                    reference_range: None,
+                })
+                .collect(),
+        );
+        observation
+    }
+
+    fn statistic(observation: &serde_json::Value, code: &str) -> Option<f64> {
+        observation["component"]
+            .as_array()?
+            .iter()
+            .find(|c| c["code"]["coding"][0]["code"] == code)
+            .and_then(|c| c["value"]["value"].as_f64())
+    }
+
+    #[test]
+    fn test_component_statistics() {
+        let observations = vec![
+            blood_pressure(1, 120.0, 80.0),
+            blood_pressure(2, 130.0, 90.0),
+            blood_pressure(3, 140.0, 85.0),
+        ];
+        let params = StatsParameters {
+            patient_id: "p1".to_string(),
+            codes: vec!["8480-6".to_string()],
+            system: Some("http://loinc.org".to_string()),
+            start: None,
+            end: None,
+            statistics: Statistic::ALL.to_vec(),
+        };
+
+        let result = summarize(&observations, &params);
+        let parameters = result.parameter.unwrap();
+        assert_eq!(parameters.len(), 1);
+
+        let systolic = parameters[0].resource.as_ref().unwrap();
+        assert_eq!(systolic["code"]["coding"][0]["code"], "8480-6");
+        assert_eq!(statistic(systolic, "count"), Some(3.0));
+        assert_eq!(statistic(systolic, "minimum"), Some(120.0));
+        assert_eq!(statistic(systolic, "maximum"), Some(140.0));
+        assert_eq!(statistic(systolic, "average"), Some(130.0));
+        assert_eq!(statistic(systolic, "median"), Some(130.0));
+        assert_eq!(statistic(systolic, "std-dev"), Some(10.0));
+        assert_eq!(systolic["effective"]["start"], "2024-01-01T08:00:00Z");
+    }
+
+    #[test]
+    fn test_statistic_list() {
+        assert_eq!(
+            Statistic::parse_list(Some("average, median")).unwrap(),
+            vec![Statistic::Average, Statistic::Median]
+        );
+        assert!(Statistic::parse_list(Some("mode")).is_err());
+        assert_eq!(Statistic::Median.compute(&[1.0, 4.0, 2.0, 3.0]), Some(2.5));
+        assert_eq!(Statistic::StdDev.compute(&[5.0]), None);
+    }
+}
@@ -0,0 +1,171 @@
+// src/service/organization_service.rs
+
+use uuid::Uuid;
+
+use crate::domain::{Organization, FhirError, FhirResult};
+use crate::repository::{OrganizationRepository, Repository, SearchParams};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, OrganizationValidator,
+    SecurityContext, DirectoryAuthorizationRules, ValidationMode,
+};
+
+pub struct OrganizationService {
+    repository: OrganizationRepository,
+    validator: OrganizationValidator,
+    auth_rules: DirectoryAuthorizationRules,
+}
+
+impl OrganizationService {
+    pub fn new(repository: OrganizationRepository) -> Self {
+        Self {
+            repository,
+            validator: OrganizationValidator,
+            auth_rules: DirectoryAuthorizationRules::new("Organization"),
+        }
+    }
+
+    /// Get organization history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Organization>> {
+        // Check authorization
+        self.auth_rules.can_read_history(context, id)?;
+
+        self.repository.get_history(id).await
+    }
+
+    /// The parent in `partOf` must be stored, and must not be the
+    /// organization itself or one of its descendants
+    async fn validate_part_of(&self, id: Option<&str>, organization: &Organization) -> FhirResult<()> {
+        // References to other types are reported by the validator
+        let Some(reference) = organization.part_of.as_ref().and_then(|r| r.reference.as_ref()) else {
+            return Ok(());
+        };
+        let Some(parent_id) = reference.0.strip_prefix("Organization/") else {
+            return Ok(());
+        };
+        if self.repository.read(parent_id).await?.is_none() {
+            return Err(FhirError::InvalidReference(
+                format!("Referenced organization does not exist: {}", reference.0)
+            ));
+        }
+
+        if let Some(id) = id {
+            let uuid = Uuid::parse_str(id)
+                .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+            let parent = Uuid::parse_str(parent_id)
+                .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", parent_id)))?;
+            if parent == uuid || self.repository.descendant_ids(uuid).await?.contains(&parent) {
+                return Err(FhirError::Validation(
+                    format!("partOf {} would make the organization part of itself", reference.0)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        organization: Option<&Organization>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, organization)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing organization
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            if self.repository.read(id).await?.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Organization".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id) {
+            (ValidationMode::Create, _) => self.auth_rules.can_create(context),
+            (ValidationMode::Update, Some(id)) => self.auth_rules.can_update(context, id),
+            (ValidationMode::Delete, Some(id)) => self.auth_rules.can_delete(context, id),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the organization
+        if let Some(resource) = organization.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_part_of(id, resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<Organization> for OrganizationService {
+    async fn create(&self, context: &SecurityContext, organization: Organization) -> FhirResult<Organization> {
+        // Check authorization
+        self.auth_rules.can_create(context)?;
+
+        // Validate the organization
+        self.validator.validate(&organization)?;
+        self.validate_part_of(None, &organization).await?;
+
+        self.repository.create(&organization).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<Organization> {
+        // Check authorization
+        self.auth_rules.can_read(context, id)?;
+
+        self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Organization".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, organization: Organization) -> FhirResult<Organization> {
+        // Check authorization
+        self.auth_rules.can_update(context, id)?;
+
+        // Validate the organization
+        self.validator.validate(&organization)?;
+        self.validate_part_of(Some(id), &organization).await?;
+
+        self.repository.update(id, &organization).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        // Check authorization
+        self.auth_rules.can_delete(context, id)?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<Organization>> {
+        // Check authorization
+        self.auth_rules.can_search(context)?;
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&params.filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
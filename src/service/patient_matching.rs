@@ -0,0 +1,322 @@
+// src/service/patient_matching.rs
+// Probabilistic patient record matching (Patient/$match)
+
+use crate::config::MatchConfig;
+use crate::domain::{Address, ContactPoint, HumanName, Identifier, Patient};
+
+/// Confidence of a match, as reported in the `match-grade` extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchGrade {
+    CertainlyNot,
+    Possible,
+    Probable,
+    Certain,
+}
+
+impl MatchGrade {
+    pub fn from_score(score: f64, config: &MatchConfig) -> Self {
+        if score >= config.certain_threshold {
+            MatchGrade::Certain
+        } else if score >= config.probable_threshold {
+            MatchGrade::Probable
+        } else if score >= config.possible_threshold {
+            MatchGrade::Possible
+        } else {
+            MatchGrade::CertainlyNot
+        }
+    }
+
+    /// Code in the match-grade value set
+    pub fn code(self) -> &'static str {
+        match self {
+            MatchGrade::Certain => "certain",
+            MatchGrade::Probable => "probable",
+            MatchGrade::Possible => "possible",
+            MatchGrade::CertainlyNot => "certainly-not",
+        }
+    }
+}
+
+/// Score a candidate against the input patient, between 0 and 1. Fields the
+/// input or the candidate lacks contribute nothing
+pub fn score(input: &Patient, candidate: &Patient, config: &MatchConfig) -> f64 {
+    let total = config.total_weight();
+    if total <= 0.0 {
+        return 0.0;
+    }
+
+    let mut sum = 0.0;
+    sum += config.identifier_weight * best(&input.identifier, &candidate.identifier, identifier_similarity);
+    sum += config.name_weight * best(&input.name, &candidate.name, name_similarity);
+    sum += config.address_weight * best(&input.address, &candidate.address, address_similarity);
+    sum += config.telecom_weight * best(&input.telecom, &candidate.telecom, telecom_similarity);
+
+    if let (Some(a), Some(b)) = (&input.birth_date, &candidate.birth_date) {
+        sum += config.birth_date_weight * birth_date_similarity(&a.0, &b.0);
+    }
+    if let (Some(a), Some(b)) = (&input.gender, &candidate.gender) {
+        if a.0.eq_ignore_ascii_case(&b.0) {
+            sum += config.gender_weight;
+        }
+    }
+
+    (sum / total).clamp(0.0, 1.0)
+}
+
+/// Best similarity between any pair of repeated elements
+fn best<T>(a: &Option<Vec<T>>, b: &Option<Vec<T>>, similarity: fn(&T, &T) -> f64) -> f64 {
+    let (Some(a), Some(b)) = (a, b) else {
+        return 0.0;
+    };
+    a.iter()
+        .flat_map(|x| b.iter().map(move |y| similarity(x, y)))
+        .fold(0.0, f64::max)
+}
+
+fn identifier_similarity(a: &Identifier, b: &Identifier) -> f64 {
+    let same_value = matches!((&a.value, &b.value), (Some(x), Some(y)) if x.0.trim() == y.0.trim());
+    let same_system = match (&a.system, &b.system) {
+        (Some(x), Some(y)) => x.0 == y.0,
+        _ => true,
+    };
+    if same_value && same_system { 1.0 } else { 0.0 }
+}
+
+/// Family name weighs more than the first given name
+fn name_similarity(a: &HumanName, b: &HumanName) -> f64 {
+    let family = match (&a.family, &b.family) {
+        (Some(x), Some(y)) => fuzzy_similarity(&x.0, &y.0),
+        _ => 0.0,
+    };
+    let first_given = |name: &HumanName| name.given.as_ref().and_then(|g| g.first()).map(|g| g.0.clone());
+    match (first_given(a), first_given(b)) {
+        (Some(x), Some(y)) => 0.6 * family + 0.4 * fuzzy_similarity(&x, &y),
+        _ => family,
+    }
+}
+
+/// Exact dates match; a single-digit typo or swapped month and day is a
+/// partial match
+fn birth_date_similarity(a: &chrono::NaiveDate, b: &chrono::NaiveDate) -> f64 {
+    use chrono::Datelike;
+
+    if a == b {
+        return 1.0;
+    }
+    let swapped = a.year() == b.year() && a.month() == b.day() && a.day() == b.month();
+    let typo = levenshtein(&a.format("%Y%m%d").to_string(), &b.format("%Y%m%d").to_string()) == 1;
+    if swapped || typo { 0.5 } else { 0.0 }
+}
+
+fn address_similarity(a: &Address, b: &Address) -> f64 {
+    let postal_code = match (&a.postal_code, &b.postal_code) {
+        (Some(x), Some(y)) if normalize(&x.0) == normalize(&y.0) => 1.0,
+        _ => 0.0,
+    };
+    let city = match (&a.city, &b.city) {
+        (Some(x), Some(y)) => fuzzy_similarity(&x.0, &y.0),
+        _ => 0.0,
+    };
+    let first_line = |address: &Address| address.line.as_ref().and_then(|l| l.first()).map(|l| normalize(&l.0));
+    let line = match (first_line(a), first_line(b)) {
+        (Some(x), Some(y)) => edit_similarity(&x, &y),
+        _ => 0.0,
+    };
+    0.4 * postal_code + 0.3 * city + 0.3 * line
+}
+
+/// Phone numbers compare on digits only, other systems case-insensitively
+fn telecom_similarity(a: &ContactPoint, b: &ContactPoint) -> f64 {
+    let (Some(x), Some(y)) = (&a.value, &b.value) else {
+        return 0.0;
+    };
+    let is_phone = |c: &ContactPoint| c.system.as_ref().is_none_or(|s| s.0 == "phone" || s.0 == "sms");
+    let same = if is_phone(a) && is_phone(b) {
+        let digits = |v: &str| v.chars().filter(char::is_ascii_digit).collect::<String>();
+        let (x, y) = (digits(&x.0), digits(&y.0));
+        !x.is_empty() && x == y
+    } else {
+        x.0.trim().eq_ignore_ascii_case(y.0.trim())
+    };
+    if same { 1.0 } else { 0.0 }
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Similarity of two names: Jaro-Winkler, raised for names that sound alike
+fn fuzzy_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize(a), normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let similarity = jaro_winkler(&a, &b);
+    if soundex(&a) == soundex(&b) {
+        similarity.max(0.85)
+    } else {
+        similarity
+    }
+}
+
+/// Edit distance normalized to 0..1
+fn edit_similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, ca) in a.iter().enumerate() {
+        let from = i.saturating_sub(window);
+        let to = (i + window + 1).min(b.len());
+        for j in from..to {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, m)| **m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, m)| **m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// American Soundex code, e.g. "Robert" and "Rupert" are both R163
+fn soundex(value: &str) -> String {
+    let digit = |c: char| match c {
+        'b' | 'f' | 'p' | 'v' => Some('1'),
+        'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+        'd' | 't' => Some('3'),
+        'l' => Some('4'),
+        'm' | 'n' => Some('5'),
+        'r' => Some('6'),
+        _ => None,
+    };
+
+    let mut letters = value.chars().filter(|c| c.is_ascii_alphabetic()).map(|c| c.to_ascii_lowercase());
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+
+    let mut code = first.to_ascii_uppercase().to_string();
+    let mut last = digit(first);
+    for c in letters {
+        let current = digit(c);
+        if current.is_some() && current != last {
+            code.extend(current);
+        }
+        // 'h' and 'w' do not separate letters with the same code
+        if c != 'h' && c != 'w' {
+            last = current;
+        }
+        if code.len() == 4 {
+            break;
+        }
+    }
+    format!("{:0<4}", code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Code, FhirDate, FhirString, Uri};
+    use chrono::NaiveDate;
+
+    fn patient(family: &str, given: &str, birth_date: (i32, u32, u32), gender: &str) -> Patient {
+        let mut patient = Patient::new();
+        patient.name = Some(vec![HumanName {
+            use_: None,
+            text: None,
+            family: Some(FhirString(family.to_string())),
+            given: Some(vec![FhirString(given.to_string())]),
+            prefix: None,
+            suffix: None,
+            period: None,
+        }]);
+        let (y, m, d) = birth_date;
+        patient.birth_date = Some(FhirDate(NaiveDate::from_ymd_opt(y, m, d).unwrap()));
+        patient.gender = Some(Code(gender.to_string()));
+        patient
+    }
+
+    #[test]
+    fn test_string_comparisons() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert!((jaro_winkler("martha", "marhta") - 0.9611).abs() < 0.001);
+        assert!(fuzzy_similarity("Smith", "Smyth") >= 0.85);
+    }
+
+    #[test]
+    fn test_match_grades() {
+        let config = MatchConfig::default();
+        let mut input = patient("Smith", "John", (1980, 3, 15), "male");
+        input.identifier = Some(vec![Identifier {
+            use_: None,
+            type_: None,
+            system: Some(Uri("urn:oid:1.2.36.146.595.217.0.1".to_string())),
+            value: Some(FhirString("12345".to_string())),
+            period: None,
+            assigner: None,
+        }]);
+
+        let same_person = {
+            let mut candidate = patient("Smyth", "Jon", (1980, 3, 15), "male");
+            candidate.identifier = input.identifier.clone();
+            candidate
+        };
+        let same_demographics = patient("Smith", "John", (1980, 3, 15), "male");
+        let typo = patient("Smith", "John", (1980, 3, 16), "male");
+        let stranger = patient("Garcia", "Maria", (1975, 7, 1), "female");
+
+        let grade = |candidate: &Patient| MatchGrade::from_score(score(&input, candidate, &config), &config);
+        assert_eq!(grade(&same_person), MatchGrade::Certain);
+        assert_eq!(grade(&same_demographics), MatchGrade::Possible);
+        assert!(score(&input, &typo, &config) < score(&input, &same_demographics, &config));
+        assert_eq!(grade(&stranger), MatchGrade::CertainlyNot);
+    }
+}
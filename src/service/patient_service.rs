@@ -1,16 +1,34 @@
 // src/service/patient_service.rs
 
-use crate::domain::{Patient, FhirError, FhirResult};
+use crate::config::MatchConfig;
+use crate::domain::{
+    Bundle, Code, Extension, FhirDecimal, FhirString, OperationOutcome, Parameters, Patient, Reference,
+    UnsignedInt, FhirError, FhirResult,
+};
+use crate::domain::resources::operation_outcome::OperationOutcomeIssue;
+use crate::domain::resources::parameters::ParametersParameter;
+use crate::domain::resources::patient::PatientLink;
 use crate::repository::{PatientRepository, Repository, SearchParams};
 use crate::service::{
     ResourceService, SearchParameters, SearchResult, Validator, PatientValidator,
-    SecurityContext, PatientAuthorizationRules,
+    SecurityContext, PatientAuthorizationRules, ValidationMode,
 };
+use crate::service::patient_matching::{self, MatchGrade};
+
+/// Extension carrying the match grade on Patient/$match entries
+const MATCH_GRADE_URL: &str = "http://hl7.org/fhir/StructureDefinition/match-grade";
+
+/// Default number of Patient/$match results
+const DEFAULT_MATCH_COUNT: usize = 10;
+
+/// Longest `replaced-by` chain followed before giving up
+const MAX_REPLACED_BY_HOPS: usize = 10;
 
 pub struct PatientService {
     repository: PatientRepository,
     validator: PatientValidator,
     auth_rules: PatientAuthorizationRules,
+    match_config: MatchConfig,
 }
 
 impl PatientService {
@@ -19,9 +37,16 @@ impl PatientService {
             repository,
             validator: PatientValidator,
             auth_rules: PatientAuthorizationRules::new(),
+            match_config: MatchConfig::default(),
         }
     }
 
+    /// Use custom Patient/$match weights and thresholds
+    pub fn with_match_config(mut self, match_config: MatchConfig) -> Self {
+        self.match_config = match_config;
+        self
+    }
+
     /// Validate and create a new patient
     async fn validate_and_create(&self, context: &SecurityContext, patient: Patient) -> FhirResult<Patient> {
         // Check authorization
@@ -61,6 +86,179 @@ impl PatientService {
         self.repository.search_by_family(family).await
     }
 
+    /// Patient/$match: score existing records against `patient` and return
+    /// the likely duplicates, best first, as a searchset Bundle
+    pub async fn match_patients(
+        &self,
+        context: &SecurityContext,
+        patient: &Patient,
+        only_certain_matches: bool,
+        count: Option<u32>,
+    ) -> FhirResult<Bundle> {
+        // Check authorization
+        self.auth_rules.can_search(context)?;
+
+        if patient.name.is_none() && patient.identifier.is_none() && patient.birth_date.is_none() {
+            return Err(FhirError::Validation(
+                "$match requires a name, identifier or birthDate to match on".to_string(),
+            ));
+        }
+
+        let family_prefixes: Vec<String> = patient.name.iter().flatten()
+            .filter_map(|name| name.family.as_ref())
+            .map(|family| family.0.to_lowercase().chars().take(2).collect())
+            .collect();
+        let identifier_values: Vec<String> = patient.identifier.iter().flatten()
+            .filter_map(|identifier| identifier.value.as_ref())
+            .map(|value| value.0.clone())
+            .collect();
+
+        let candidates = self.repository
+            .find_match_candidates(&family_prefixes, patient.birth_date.as_ref().map(|d| d.0), &identifier_values)
+            .await?;
+
+        let mut matches: Vec<(Patient, f64, MatchGrade)> = candidates
+            .into_iter()
+            .map(|candidate| {
+                let score = patient_matching::score(patient, &candidate, &self.match_config);
+                let grade = MatchGrade::from_score(score, &self.match_config);
+                (candidate, score, grade)
+            })
+            .filter(|(_, _, grade)| {
+                if only_certain_matches {
+                    *grade == MatchGrade::Certain
+                } else {
+                    *grade >= MatchGrade::Possible
+                }
+            })
+            .collect();
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+        matches.truncate(count.map_or(DEFAULT_MATCH_COUNT, |count| count as usize));
+
+        let mut bundle = Bundle::new("searchset");
+        bundle.total = Some(UnsignedInt(matches.len() as u32));
+        for (candidate, score, grade) in matches {
+            bundle.add_entry(serde_json::to_value(&candidate)?, Some("match"));
+            if let Some(search) = bundle.entry.as_mut()
+                .and_then(|entries| entries.last_mut())
+                .and_then(|entry| entry.search.as_mut())
+            {
+                let mut extension = Extension::new(MATCH_GRADE_URL);
+                extension.value_code = Some(Code(grade.code().to_string()));
+                search.extension = Some(vec![extension]);
+                search.score = Some(FhirDecimal((score * 1000.0).round() / 1000.0));
+            }
+        }
+
+        Ok(bundle)
+    }
+
+    /// Patient/$merge: retire `source_id` in favour of `target_id`. The source
+    /// becomes inactive with a `replaced-by` link, the target gains a
+    /// `replaces` link, and everything referencing the source is re-pointed to
+    /// the target. With `preview`, nothing is stored and the outcome lists
+    /// what would change
+    pub async fn merge(
+        &self,
+        context: &SecurityContext,
+        source_id: &str,
+        target_id: &str,
+        preview: bool,
+    ) -> FhirResult<Parameters> {
+        if source_id == target_id {
+            return Err(FhirError::Validation(
+                "Source and target patient must be different".to_string(),
+            ));
+        }
+
+        let mut source = self.get(context, source_id).await?;
+        let mut target = self.get(context, target_id).await?;
+        for (id, patient) in [(source_id, &source), (target_id, &target)] {
+            if has_link(patient, "replaced-by") {
+                return Err(FhirError::Conflict(format!(
+                    "Patient/{} has already been merged into another record",
+                    id
+                )));
+            }
+        }
+
+        source.active = Some(crate::domain::FhirBoolean(false));
+        add_link(&mut source, "replaced-by", target_id);
+        add_link(&mut target, "replaces", source_id);
+
+        // Check authorization
+        self.auth_rules.can_update(context, source_id, &source)?;
+        self.auth_rules.can_update(context, target_id, &target)?;
+
+        let (target, moved) = if preview {
+            let references = self.repository.subject_references(source_id).await?;
+            (target, references)
+        } else {
+            self.repository.merge(&source, &target).await?
+        };
+
+        let verb = if preview { "would be" } else { "was" };
+        let mut issues = vec![
+            OperationOutcomeIssue::new(
+                "information",
+                "informational",
+                format!("Patient/{} {} marked inactive and replaced by Patient/{}", source_id, verb, target_id),
+            ),
+        ];
+        issues.extend(moved.iter().map(|reference| {
+            OperationOutcomeIssue::new(
+                "information",
+                "informational",
+                format!("{} {} re-pointed to Patient/{}", reference, verb, target_id),
+            )
+        }));
+
+        let mut parameters = Parameters::new();
+        let mut outcome = ParametersParameter::new("outcome");
+        outcome.resource = Some(serde_json::to_value(OperationOutcome::new(issues))?);
+        parameters.add(outcome);
+        let mut result = ParametersParameter::new("result");
+        result.resource = Some(serde_json::to_value(&target)?);
+        parameters.add(result);
+
+        Ok(parameters)
+    }
+
+    /// Follow `replaced-by` links from a merged patient to the surviving
+    /// record. Patients without such a link are returned unchanged
+    pub async fn resolve_replaced_by(&self, context: &SecurityContext, patient: Patient) -> FhirResult<Patient> {
+        let mut current = patient;
+        for _ in 0..MAX_REPLACED_BY_HOPS {
+            let Some(next_id) = replaced_by(&current) else {
+                return Ok(current);
+            };
+            current = self.get(context, &next_id).await?;
+        }
+
+        Err(FhirError::Conflict(format!(
+            "replaced-by links from Patient/{} form a chain longer than {} or a cycle",
+            current.id.as_ref().map(|id| id.0.as_str()).unwrap_or_default(),
+            MAX_REPLACED_BY_HOPS
+        )))
+    }
+
+    /// Replace merged patients in search results with their surviving
+    /// records, dropping duplicates
+    pub async fn resolve_all_replaced_by(
+        &self,
+        context: &SecurityContext,
+        patients: Vec<Patient>,
+    ) -> FhirResult<Vec<Patient>> {
+        let mut resolved: Vec<Patient> = Vec::with_capacity(patients.len());
+        for patient in patients {
+            let patient = self.resolve_replaced_by(context, patient).await?;
+            if !resolved.iter().any(|p| p.id == patient.id) {
+                resolved.push(patient);
+            }
+        }
+        Ok(resolved)
+    }
+
     /// Search patients by identifier
     pub async fn search_by_identifier(&self, context: &SecurityContext, system: &str, value: &str) -> FhirResult<Option<Patient>> {
         // Check authorization
@@ -149,6 +347,77 @@ impl PatientService {
             }
         }
     }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        patient: Option<&Patient>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, patient)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing patient
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            if self.repository.read(id).await?.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Patient".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, patient) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, resource),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, resource),
+            (ValidationMode::Delete, Some(id), _) => self.auth_rules.can_delete(context, id),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the patient
+        if let Some(resource) = patient.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+        }
+
+        Ok(issues)
+    }
+}
+
+/// ID of the patient a merged record was replaced by, if any
+fn replaced_by(patient: &Patient) -> Option<String> {
+    patient.link.iter().flatten()
+        .filter(|link| link.type_.0 == "replaced-by")
+        .filter_map(|link| link.other.reference.as_ref())
+        .find_map(|reference| reference.0.strip_prefix("Patient/").map(str::to_string))
+}
+
+fn has_link(patient: &Patient, type_: &str) -> bool {
+    patient.link.iter().flatten().any(|link| link.type_.0 == type_)
+}
+
+/// Link `patient` to `Patient/{other_id}`, unless that link already exists
+fn add_link(patient: &mut Patient, type_: &str, other_id: &str) {
+    let reference = format!("Patient/{}", other_id);
+    let links = patient.link.get_or_insert_with(Vec::new);
+    let exists = links.iter().any(|link| {
+        link.type_.0 == type_ && link.other.reference.as_ref().is_some_and(|r| r.0 == reference)
+    });
+    if !exists {
+        links.push(PatientLink {
+            other: Reference {
+                reference: Some(FhirString(reference)),
+                type_: None,
+                identifier: None,
+                display: None,
+            },
+            type_: Code(type_.to_string()),
+        });
+    }
 }
 
 #[async_trait::async_trait]
@@ -210,12 +479,17 @@ impl ResourceService<Patient> for PatientService {
         // Check authorization
         self.auth_rules.can_search(context)?;
 
+        if params.filters.iter().any(|(name, value)| name == "family" && value.trim().is_empty()) {
+            return Err(FhirError::Validation("Family name cannot be empty".to_string()));
+        }
+
         let limit = params.count.unwrap_or(100) as i64;
         let offset = params.offset.unwrap_or(0) as i64;
 
         let search_params = SearchParams::new()
             .with_limit(limit)
-            .with_offset(offset);
+            .with_offset(offset)
+            .with_filters(&params.filters);
 
         let resources = self.repository.search(search_params).await?;
         let count = resources.len() as u32;
@@ -249,4 +523,19 @@ mod tests {
         let validator = PatientValidator;
         assert!(validator.validate(&patient).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_merge_links() {
+        let mut patient = Patient::new();
+        assert!(!has_link(&patient, "replaced-by"));
+
+        add_link(&mut patient, "replaced-by", "target");
+        add_link(&mut patient, "replaced-by", "target");
+        let links = patient.link.as_ref().unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].other.reference.as_ref().unwrap().0, "Patient/target");
+        assert!(has_link(&patient, "replaced-by"));
+        assert!(!has_link(&patient, "replaces"));
+        assert_eq!(replaced_by(&patient).as_deref(), Some("target"));
+    }
+}
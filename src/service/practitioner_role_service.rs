@@ -0,0 +1,175 @@
+// src/service/practitioner_role_service.rs
+
+use crate::domain::{PractitionerRole, Reference, FhirError, FhirResult};
+use crate::repository::{
+    OrganizationRepository, PractitionerRepository, PractitionerRoleRepository, Repository, SearchParams,
+};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, PractitionerRoleValidator,
+    SecurityContext, DirectoryAuthorizationRules, ValidationMode,
+};
+
+pub struct PractitionerRoleService {
+    repository: PractitionerRoleRepository,
+    practitioners: PractitionerRepository,
+    organizations: OrganizationRepository,
+    validator: PractitionerRoleValidator,
+    auth_rules: DirectoryAuthorizationRules,
+}
+
+impl PractitionerRoleService {
+    pub fn new(
+        repository: PractitionerRoleRepository,
+        practitioners: PractitionerRepository,
+        organizations: OrganizationRepository,
+    ) -> Self {
+        Self {
+            repository,
+            practitioners,
+            organizations,
+            validator: PractitionerRoleValidator,
+            auth_rules: DirectoryAuthorizationRules::new("PractitionerRole"),
+        }
+    }
+
+    /// Get practitioner role history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<PractitionerRole>> {
+        // Check authorization
+        self.auth_rules.can_read_history(context, id)?;
+
+        self.repository.get_history(id).await
+    }
+
+    /// The practitioner and organization a role refers to must be stored
+    async fn validate_references(&self, role: &PractitionerRole) -> FhirResult<()> {
+        // References to other types are reported by the validator
+        let target = |reference: Option<&Reference>, prefix: &str| {
+            reference
+                .and_then(|r| r.reference.as_ref())
+                .and_then(|r| r.0.strip_prefix(prefix).map(|id| (r.0.clone(), id.to_string())))
+        };
+
+        if let Some((reference, id)) = target(role.practitioner.as_ref(), "Practitioner/") {
+            if self.practitioners.read(&id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced practitioner does not exist: {}", reference)
+                ));
+            }
+        }
+        if let Some((reference, id)) = target(role.organization.as_ref(), "Organization/") {
+            if self.organizations.read(&id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced organization does not exist: {}", reference)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        role: Option<&PractitionerRole>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, role)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing practitioner role
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            if self.repository.read(id).await?.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "PractitionerRole".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id) {
+            (ValidationMode::Create, _) => self.auth_rules.can_create(context),
+            (ValidationMode::Update, Some(id)) => self.auth_rules.can_update(context, id),
+            (ValidationMode::Delete, Some(id)) => self.auth_rules.can_delete(context, id),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the practitioner role
+        if let Some(resource) = role.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_references(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<PractitionerRole> for PractitionerRoleService {
+    async fn create(&self, context: &SecurityContext, role: PractitionerRole) -> FhirResult<PractitionerRole> {
+        // Check authorization
+        self.auth_rules.can_create(context)?;
+
+        // Validate the practitioner role
+        self.validator.validate(&role)?;
+        self.validate_references(&role).await?;
+
+        self.repository.create(&role).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<PractitionerRole> {
+        // Check authorization
+        self.auth_rules.can_read(context, id)?;
+
+        self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "PractitionerRole".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, role: PractitionerRole) -> FhirResult<PractitionerRole> {
+        // Check authorization
+        self.auth_rules.can_update(context, id)?;
+
+        // Validate the practitioner role
+        self.validator.validate(&role)?;
+        self.validate_references(&role).await?;
+
+        self.repository.update(id, &role).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        // Check authorization
+        self.auth_rules.can_delete(context, id)?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<PractitionerRole>> {
+        // Check authorization
+        self.auth_rules.can_search(context)?;
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&params.filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
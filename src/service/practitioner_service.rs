@@ -0,0 +1,136 @@
+// src/service/practitioner_service.rs
+
+use crate::domain::{Practitioner, FhirError, FhirResult};
+use crate::repository::{PractitionerRepository, Repository, SearchParams};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, PractitionerValidator,
+    SecurityContext, DirectoryAuthorizationRules, ValidationMode,
+};
+
+pub struct PractitionerService {
+    repository: PractitionerRepository,
+    validator: PractitionerValidator,
+    auth_rules: DirectoryAuthorizationRules,
+}
+
+impl PractitionerService {
+    pub fn new(repository: PractitionerRepository) -> Self {
+        Self {
+            repository,
+            validator: PractitionerValidator,
+            auth_rules: DirectoryAuthorizationRules::new("Practitioner"),
+        }
+    }
+
+    /// Get practitioner history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Practitioner>> {
+        // Check authorization
+        self.auth_rules.can_read_history(context, id)?;
+
+        self.repository.get_history(id).await
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        practitioner: Option<&Practitioner>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, practitioner)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing practitioner
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            if self.repository.read(id).await?.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Practitioner".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id) {
+            (ValidationMode::Create, _) => self.auth_rules.can_create(context),
+            (ValidationMode::Update, Some(id)) => self.auth_rules.can_update(context, id),
+            (ValidationMode::Delete, Some(id)) => self.auth_rules.can_delete(context, id),
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the practitioner
+        if let Some(resource) = practitioner.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<Practitioner> for PractitionerService {
+    async fn create(&self, context: &SecurityContext, practitioner: Practitioner) -> FhirResult<Practitioner> {
+        // Check authorization
+        self.auth_rules.can_create(context)?;
+
+        // Validate the practitioner
+        self.validator.validate(&practitioner)?;
+
+        self.repository.create(&practitioner).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<Practitioner> {
+        // Check authorization
+        self.auth_rules.can_read(context, id)?;
+
+        self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Practitioner".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, practitioner: Practitioner) -> FhirResult<Practitioner> {
+        // Check authorization
+        self.auth_rules.can_update(context, id)?;
+
+        // Validate the practitioner
+        self.validator.validate(&practitioner)?;
+
+        self.repository.update(id, &practitioner).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        // Check authorization
+        self.auth_rules.can_delete(context, id)?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<Practitioner>> {
+        // Check authorization
+        self.auth_rules.can_search(context)?;
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&params.filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
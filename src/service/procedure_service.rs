@@ -0,0 +1,208 @@
+// src/service/procedure_service.rs
+
+use crate::domain::{Procedure, Reference, FhirError, FhirResult};
+use crate::repository::{
+    ConditionRepository, ProcedureRepository, PatientRepository, Repository, SearchParams,
+};
+use crate::service::{
+    ResourceService, SearchParameters, SearchResult, Validator, ProcedureValidator,
+    SecurityContext, CompartmentAuthorizationRules, ValidationMode,
+};
+
+pub struct ProcedureService {
+    repository: ProcedureRepository,
+    patients: PatientRepository,
+    conditions: ConditionRepository,
+    validator: ProcedureValidator,
+    auth_rules: CompartmentAuthorizationRules,
+}
+
+impl ProcedureService {
+    pub fn new(
+        repository: ProcedureRepository,
+        patients: PatientRepository,
+        conditions: ConditionRepository,
+    ) -> Self {
+        Self {
+            repository,
+            patients,
+            conditions,
+            validator: ProcedureValidator,
+            auth_rules: CompartmentAuthorizationRules::new("Procedure"),
+        }
+    }
+
+    /// The subject patient and every reasonReference Condition must be
+    /// stored; references to other types are reported by the validator
+    async fn validate_references(&self, procedure: &Procedure) -> FhirResult<()> {
+        let target = |reference: &Reference, prefix: &str| {
+            reference.reference.as_ref()
+                .and_then(|r| r.0.strip_prefix(prefix).map(|id| (r.0.clone(), id.to_string())))
+        };
+
+        if let Some((reference, id)) = target(&procedure.subject, "Patient/") {
+            if self.patients.read(&id).await?.is_none() {
+                return Err(FhirError::InvalidReference(
+                    format!("Referenced patient does not exist: {}", reference)
+                ));
+            }
+        }
+        for reason in procedure.reason_reference.iter().flatten() {
+            if let Some((reference, id)) = target(reason, "Condition/") {
+                if self.conditions.read(&id).await?.is_none() {
+                    return Err(FhirError::InvalidReference(
+                        format!("Referenced condition does not exist: {}", reference)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Search procedures by patient
+    pub async fn search_by_patient(&self, context: &SecurityContext, patient_id: &str) -> FhirResult<Vec<Procedure>> {
+        if patient_id.trim().is_empty() {
+            return Err(FhirError::Validation("Patient ID cannot be empty".to_string()));
+        }
+
+        // Check authorization
+        self.auth_rules.search_patient(context, Some(patient_id))?;
+
+        self.repository.search_by_patient(patient_id).await
+    }
+
+    /// Get procedure history (all versions)
+    pub async fn get_history(&self, context: &SecurityContext, id: &str) -> FhirResult<Vec<Procedure>> {
+        let history = self.repository.get_history(id).await?;
+
+        // Check authorization against the current subject
+        self.auth_rules.can_read_history(context, id, history.first().map(|p| &p.subject))?;
+
+        Ok(history)
+    }
+
+    /// Dry-run a create, update or delete (`$validate`), collecting every
+    /// validation and authorization issue instead of stopping at the first
+    pub async fn validate_operation(
+        &self,
+        context: &SecurityContext,
+        mode: ValidationMode,
+        id: Option<&str>,
+        procedure: Option<&Procedure>,
+    ) -> FhirResult<Vec<FhirError>> {
+        mode.check_request(id, procedure)?;
+        let mut issues = Vec::new();
+
+        // Update and delete need an existing procedure
+        let mut existing = None;
+        if let (ValidationMode::Update | ValidationMode::Delete, Some(id)) = (mode, id) {
+            existing = self.repository.read(id).await?;
+            if existing.is_none() {
+                issues.push(FhirError::NotFound {
+                    resource_type: "Procedure".to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Check authorization
+        let authorized = match (mode, id, procedure) {
+            (ValidationMode::Create, _, Some(resource)) => self.auth_rules.can_create(context, &resource.subject),
+            (ValidationMode::Update, Some(id), Some(resource)) => self.auth_rules.can_update(context, id, &resource.subject),
+            (ValidationMode::Delete, Some(id), _) => {
+                self.auth_rules.can_delete(context, id, existing.as_ref().map(|p| &p.subject))
+            }
+            _ => Ok(()),
+        };
+        issues.extend(authorized.err());
+
+        // Validate the procedure
+        if let Some(resource) = procedure.filter(|_| mode != ValidationMode::Delete) {
+            issues.extend(self.validator.issues(resource));
+            issues.extend(self.validate_references(resource).await.err());
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceService<Procedure> for ProcedureService {
+    async fn create(&self, context: &SecurityContext, procedure: Procedure) -> FhirResult<Procedure> {
+        // Check authorization
+        self.auth_rules.can_create(context, &procedure.subject)?;
+
+        // Validate the procedure
+        self.validator.validate(&procedure)?;
+        self.validate_references(&procedure).await?;
+
+        self.repository.create(&procedure).await
+    }
+
+    async fn get(&self, context: &SecurityContext, id: &str) -> FhirResult<Procedure> {
+        let procedure = self.repository.read(id)
+            .await?
+            .ok_or_else(|| FhirError::NotFound {
+                resource_type: "Procedure".to_string(),
+                id: id.to_string(),
+            })?;
+
+        // Check authorization
+        self.auth_rules.can_read(context, id, Some(&procedure.subject))?;
+
+        Ok(procedure)
+    }
+
+    async fn update(&self, context: &SecurityContext, id: &str, procedure: Procedure) -> FhirResult<Procedure> {
+        // The current version must be in the user's compartment too
+        let current = self.get(context, id).await?;
+        self.auth_rules.can_update(context, id, &current.subject)?;
+        self.auth_rules.can_update(context, id, &procedure.subject)?;
+
+        // Validate the procedure
+        self.validator.validate(&procedure)?;
+        self.validate_references(&procedure).await?;
+
+        self.repository.update(id, &procedure).await
+    }
+
+    async fn delete(&self, context: &SecurityContext, id: &str) -> FhirResult<()> {
+        let current = self.repository.read(id).await?;
+
+        // Check authorization
+        self.auth_rules.can_delete(context, id, current.as_ref().map(|p| &p.subject))?;
+
+        self.repository.delete(id).await
+    }
+
+    async fn search(&self, context: &SecurityContext, params: SearchParameters) -> FhirResult<SearchResult<Procedure>> {
+        let requested = params.filters.iter()
+            .find(|(name, _)| name == "patient" || name == "subject")
+            .map(|(_, value)| value.strip_prefix("Patient/").unwrap_or(value));
+
+        // Check authorization; patients only search their own compartment
+        let patient = self.auth_rules.search_patient(context, requested)?;
+        let mut filters = params.filters.clone();
+        if let (None, Some(patient)) = (requested, patient) {
+            filters.push(("patient".to_string(), patient));
+        }
+
+        let limit = params.count.unwrap_or(100) as i64;
+        let offset = params.offset.unwrap_or(0) as i64;
+
+        let search_params = SearchParams::new()
+            .with_limit(limit)
+            .with_offset(offset)
+            .with_filters(&filters);
+
+        let resources = self.repository.search(search_params).await?;
+        let count = resources.len() as u32;
+
+        Ok(SearchResult::new(
+            resources,
+            None,
+            params.offset.unwrap_or(0),
+            count,
+        ))
+    }
+}
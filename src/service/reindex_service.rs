@@ -0,0 +1,210 @@
+// src/service/reindex_service.rs
+// $reindex: rebuild extracted search columns from stored resources
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use crate::config::ReindexConfig;
+use crate::domain::{Code, FhirInteger, FhirString, Parameters, FhirError, FhirResult};
+use crate::domain::resources::parameters::ParametersParameter;
+use crate::repository::reindex_repository::ReindexJob;
+use crate::repository::{resource_table, ReindexRepository, ReindexSelection, RESOURCE_TABLES};
+use crate::service::SecurityContext;
+
+/// Re-derives search columns in throttled batches. Jobs checkpoint after
+/// every batch, so starting the same scope again resumes an unfinished job
+pub struct ReindexService {
+    repository: ReindexRepository,
+    config: ReindexConfig,
+    /// Jobs with a runner in this process, so a repeated request does not
+    /// start a second one
+    running: Mutex<HashSet<Uuid>>,
+}
+
+impl ReindexService {
+    pub fn new(repository: ReindexRepository, config: ReindexConfig) -> Self {
+        Self {
+            repository,
+            config,
+            running: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn require_admin(context: &SecurityContext) -> FhirResult<()> {
+        if !context.is_admin() && !context.is_system() {
+            return Err(FhirError::Forbidden {
+                message: "$reindex requires administrator access".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Reindex one resource immediately, returning whether it was found
+    pub async fn reindex_resource(&self, context: &SecurityContext, resource_type: &str, id: &str) -> FhirResult<bool> {
+        Self::require_admin(context)?;
+        let uuid = Uuid::parse_str(id)
+            .map_err(|_| FhirError::InvalidReference(format!("Invalid UUID: {}", id)))?;
+
+        let page = self.repository.reindex(resource_type, ReindexSelection::One(uuid)).await?;
+        if page.last_id.is_none() {
+            return Err(FhirError::NotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+            });
+        }
+        Ok(page.updated > 0)
+    }
+
+    /// Register the job for one type, or every type, picking up an
+    /// unfinished job for the same scope if there is one
+    pub async fn start(&self, context: &SecurityContext, resource_type: Option<&str>) -> FhirResult<ReindexJob> {
+        Self::require_admin(context)?;
+        if let Some(resource_type) = resource_type {
+            resource_table(resource_type)?;
+        }
+        self.repository.start(resource_type).await
+    }
+
+    /// Reindex the job's types to the end and mark it completed. Returns
+    /// straight away if the job is already running in this process
+    pub async fn run(&self, job: ReindexJob) -> FhirResult<ReindexJob> {
+        if !self.running.lock().unwrap().insert(job.id) {
+            return Ok(job);
+        }
+        let result = self.reindex_types(&job).await;
+        self.running.lock().unwrap().remove(&job.id);
+
+        match result {
+            Ok(()) => self.repository.complete(job.id).await,
+            Err(e) => {
+                tracing::error!("Reindex {} failed: {}", job.id, e);
+                self.repository.fail(job.id, &e.to_string()).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Current state of a job
+    pub async fn status(&self, context: &SecurityContext, job_id: &str) -> FhirResult<ReindexJob> {
+        Self::require_admin(context)?;
+        let not_found = || FhirError::NotFound {
+            resource_type: "$reindex".to_string(),
+            id: job_id.to_string(),
+        };
+        let id = Uuid::parse_str(job_id).map_err(|_| not_found())?;
+        self.repository.get(id).await?.ok_or_else(not_found)
+    }
+
+    async fn reindex_types(&self, job: &ReindexJob) -> FhirResult<()> {
+        let types = job_types(job);
+        for (resource_type, mut after) in types {
+            loop {
+                let page = self.repository
+                    .reindex(resource_type, ReindexSelection::After { after, limit: self.config.batch_size })
+                    .await?;
+                let Some(last_id) = page.last_id else {
+                    break;
+                };
+                self.repository.checkpoint(job.id, resource_type, last_id, page.updated).await?;
+                after = Some(last_id);
+                tokio::time::sleep(self.config.batch_delay).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Types a job still has to reindex, in order, each with the id to continue
+/// after. Types before the checkpointed one are already done
+fn job_types(job: &ReindexJob) -> Vec<(&'static str, Option<Uuid>)> {
+    let scope: Vec<&'static str> = RESOURCE_TABLES
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| job.resource_type.as_deref().is_none_or(|t| t == *name))
+        .collect();
+
+    let resume_at = job.current_type.as_deref()
+        .and_then(|current| scope.iter().position(|name| *name == current));
+    match resume_at {
+        Some(position) => scope[position..]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (*name, if i == 0 { job.last_id } else { None }))
+            .collect(),
+        None => scope.into_iter().map(|name| (name, None)).collect(),
+    }
+}
+
+/// Parameters summarizing a job: its scope, status and progress
+pub fn reindex_summary(job: &ReindexJob) -> Parameters {
+    let mut parameters = Parameters::new();
+    if let Some(resource_type) = &job.resource_type {
+        let mut scope = ParametersParameter::new("type");
+        scope.value_code = Some(Code(resource_type.clone()));
+        parameters.add(scope);
+    }
+    let mut status = ParametersParameter::new("status");
+    status.value_code = Some(Code(job.status.clone()));
+    parameters.add(status);
+    let mut count = ParametersParameter::new("reindexed");
+    count.value_integer = Some(FhirInteger(i32::try_from(job.resources_reindexed).unwrap_or(i32::MAX)));
+    parameters.add(count);
+    if let Some(error) = &job.error {
+        let mut message = ParametersParameter::new("error");
+        message.value_string = Some(FhirString(error.clone()));
+        parameters.add(message);
+    }
+    parameters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(resource_type: Option<&str>, current_type: Option<&str>, last_id: Option<Uuid>) -> ReindexJob {
+        ReindexJob {
+            id: Uuid::new_v4(),
+            resource_type: resource_type.map(str::to_string),
+            status: "in-progress".to_string(),
+            current_type: current_type.map(str::to_string),
+            last_id,
+            resources_reindexed: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_job_types_resume() {
+        let all: Vec<_> = job_types(&job(None, None, None)).into_iter().map(|(t, _)| t).collect();
+        assert_eq!(all, vec![
+            "Patient", "Observation", "Condition", "Encounter", "Practitioner", "PractitionerRole", "Organization",
+            "Medication", "MedicationRequest", "MedicationStatement", "AllergyIntolerance",
+            "Procedure", "DiagnosticReport", "Immunization", "Location", "Group", "DocumentReference",
+        ]);
+
+        assert_eq!(job_types(&job(Some("Condition"), None, None)), vec![("Condition", None)]);
+
+        // A resumed job continues after the checkpoint and skips finished types
+        let last = Uuid::new_v4();
+        let resumed = job_types(&job(None, Some("Condition"), Some(last)));
+        assert_eq!(resumed, vec![
+            ("Condition", Some(last)),
+            ("Encounter", None),
+            ("Practitioner", None),
+            ("PractitionerRole", None),
+            ("Organization", None),
+            ("Medication", None),
+            ("MedicationRequest", None),
+            ("MedicationStatement", None),
+            ("AllergyIntolerance", None),
+            ("Procedure", None),
+            ("DiagnosticReport", None),
+            ("Immunization", None),
+            ("Location", None),
+            ("Group", None),
+            ("DocumentReference", None),
+        ]);
+    }
+}
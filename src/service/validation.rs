@@ -2,23 +2,40 @@
 // FHIR resource validation logic
 
 use crate::domain::{
-    Patient, Observation, Condition, Encounter,
+    Patient, Observation, Condition, Encounter, Practitioner, PractitionerRole, Organization,
+    Medication, MedicationRequest, MedicationStatement, AllergyIntolerance, Procedure, DiagnosticReport,
+    Immunization, Location, Group, DocumentReference, Binary,
+    Attachment, CodeableConcept, CodeableConceptOrReference, Code, ContactPoint, Dosage, HumanName, Identifier, Period, Reference,
     FhirError, FhirResult,
 };
 
+/// Identifier system of US National Provider Identifiers
+pub const NPI_SYSTEM: &str = "http://hl7.org/fhir/sid/us-npi";
+
 /// Validator trait for FHIR resources
 pub trait Validator<T> {
-    fn validate(&self, resource: &T) -> FhirResult<()>;
+    /// Every problem found with the resource
+    fn issues(&self, resource: &T) -> Vec<FhirError>;
+
+    /// Validate the resource, failing with the first problem found
+    fn validate(&self, resource: &T) -> FhirResult<()> {
+        match self.issues(resource).into_iter().next() {
+            Some(issue) => Err(issue),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Patient validator
 pub struct PatientValidator;
 
 impl Validator<Patient> for PatientValidator {
-    fn validate(&self, patient: &Patient) -> FhirResult<()> {
+    fn issues(&self, patient: &Patient) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
         // Validate resource type
         if patient.resource_type != "Patient" {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 format!("Invalid resourceType: expected 'Patient', got '{}'", patient.resource_type)
             ));
         }
@@ -26,14 +43,14 @@ impl Validator<Patient> for PatientValidator {
         // Validate name if present
         if let Some(names) = &patient.name {
             if names.is_empty() {
-                return Err(FhirError::Validation(
+                issues.push(FhirError::Validation(
                     "Name array cannot be empty if present".to_string()
                 ));
             }
             
             for name in names {
                 if name.family.is_none() && name.given.is_none() && name.text.is_none() {
-                    return Err(FhirError::Validation(
+                    issues.push(FhirError::Validation(
                         "HumanName must have at least family, given, or text".to_string()
                     ));
                 }
@@ -44,7 +61,7 @@ impl Validator<Patient> for PatientValidator {
         if let Some(gender) = &patient.gender {
             let valid_genders = ["male", "female", "other", "unknown"];
             if !valid_genders.contains(&gender.0.as_str()) {
-                return Err(FhirError::Validation(
+                issues.push(FhirError::Validation(
                     format!("Invalid gender value: '{}'. Must be one of: male, female, other, unknown", gender.0)
                 ));
             }
@@ -54,14 +71,43 @@ impl Validator<Patient> for PatientValidator {
         if let Some(identifiers) = &patient.identifier {
             for identifier in identifiers {
                 if identifier.value.is_none() && identifier.system.is_none() {
-                    return Err(FhirError::Validation(
+                    issues.push(FhirError::Validation(
                         "Identifier must have at least a value or system".to_string()
                     ));
                 }
             }
         }
+
+        // Validate links if present
+        if let Some(links) = &patient.link {
+            let valid_types = ["replaced-by", "replaces", "refer", "seealso"];
+            for link in links {
+                if !valid_types.contains(&link.type_.0.as_str()) {
+                    issues.push(FhirError::Validation(
+                        format!("Invalid link type: '{}'. Must be one of: replaced-by, replaces, refer, seealso", link.type_.0)
+                    ));
+                }
+
+                match link.other.reference.as_ref().map(|r| r.0.as_str()) {
+                    None => issues.push(FhirError::MissingRequiredField("link.other.reference".to_string())),
+                    Some(reference) if !reference.starts_with("Patient/") && !reference.starts_with("RelatedPerson/") => {
+                        issues.push(FhirError::InvalidReference(
+                            format!("link.other must reference a Patient or RelatedPerson: {}", reference)
+                        ));
+                    }
+                    Some(reference) => {
+                        let own_reference = patient.id.as_ref().map(|id| format!("Patient/{}", id.0));
+                        if own_reference.as_deref() == Some(reference) {
+                            issues.push(FhirError::Validation(
+                                "A patient cannot link to itself".to_string()
+                            ));
+                        }
+                    }
+                }
+            }
+        }
         
-        Ok(())
+        issues
     }
 }
 
@@ -69,17 +115,19 @@ impl Validator<Patient> for PatientValidator {
 pub struct ObservationValidator;
 
 impl Validator<Observation> for ObservationValidator {
-    fn validate(&self, observation: &Observation) -> FhirResult<()> {
+    fn issues(&self, observation: &Observation) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
         // Validate resource type
         if observation.resource_type != "Observation" {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 format!("Invalid resourceType: expected 'Observation', got '{}'", observation.resource_type)
             ));
         }
         
         // Validate required fields
         if observation.status.0.is_empty() {
-            return Err(FhirError::MissingRequiredField("status".to_string()));
+            issues.push(FhirError::MissingRequiredField("status".to_string()));
         }
         
         // Validate status values
@@ -87,15 +135,15 @@ impl Validator<Observation> for ObservationValidator {
             "registered", "preliminary", "final", "amended",
             "corrected", "cancelled", "entered-in-error", "unknown"
         ];
-        if !valid_statuses.contains(&observation.status.0.as_str()) {
-            return Err(FhirError::Validation(
+        if !observation.status.0.is_empty() && !valid_statuses.contains(&observation.status.0.as_str()) {
+            issues.push(FhirError::Validation(
                 format!("Invalid status value: '{}'", observation.status.0)
             ));
         }
         
         // Validate code (required)
         if observation.code.coding.is_none() && observation.code.text.is_none() {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 "Observation.code must have at least coding or text".to_string()
             ));
         }
@@ -105,7 +153,7 @@ impl Validator<Observation> for ObservationValidator {
         let has_absent_reason = observation.data_absent_reason.is_some();
         
         if has_value && has_absent_reason {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 "Cannot have both value and dataAbsentReason".to_string()
             ));
         }
@@ -114,7 +162,7 @@ impl Validator<Observation> for ObservationValidator {
         if let Some(components) = &observation.component {
             for component in components {
                 if component.code.coding.is_none() && component.code.text.is_none() {
-                    return Err(FhirError::Validation(
+                    issues.push(FhirError::Validation(
                         "Component.code must have at least coding or text".to_string()
                     ));
                 }
@@ -124,14 +172,14 @@ impl Validator<Observation> for ObservationValidator {
                 let comp_has_absent = component.data_absent_reason.is_some();
                 
                 if comp_has_value && comp_has_absent {
-                    return Err(FhirError::Validation(
+                    issues.push(FhirError::Validation(
                         "Component cannot have both value and dataAbsentReason".to_string()
                     ));
                 }
             }
         }
         
-        Ok(())
+        issues
     }
 }
 
@@ -139,17 +187,19 @@ impl Validator<Observation> for ObservationValidator {
 pub struct ConditionValidator;
 
 impl Validator<Condition> for ConditionValidator {
-    fn validate(&self, condition: &Condition) -> FhirResult<()> {
+    fn issues(&self, condition: &Condition) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
         // Validate resource type
         if condition.resource_type != "Condition" {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 format!("Invalid resourceType: expected 'Condition', got '{}'", condition.resource_type)
             ));
         }
         
         // Validate subject (required)
         if condition.subject.reference.is_none() && condition.subject.identifier.is_none() {
-            return Err(FhirError::MissingRequiredField(
+            issues.push(FhirError::MissingRequiredField(
                 "subject (must have reference or identifier)".to_string()
             ));
         }
@@ -157,7 +207,7 @@ impl Validator<Condition> for ConditionValidator {
         // Validate clinical status if present
         if let Some(clinical_status) = &condition.clinical_status {
             if clinical_status.coding.is_none() {
-                return Err(FhirError::Validation(
+                issues.push(FhirError::Validation(
                     "clinicalStatus must have coding".to_string()
                 ));
             }
@@ -166,7 +216,7 @@ impl Validator<Condition> for ConditionValidator {
         // Validate verification status if present
         if let Some(verification_status) = &condition.verification_status {
             if verification_status.coding.is_none() {
-                return Err(FhirError::Validation(
+                issues.push(FhirError::Validation(
                     "verificationStatus must have coding".to_string()
                 ));
             }
@@ -181,13 +231,13 @@ impl Validator<Condition> for ConditionValidator {
                 .map(|code| code.0.as_str());
             
             if verification_code != Some("entered-in-error") {
-                return Err(FhirError::Validation(
+                issues.push(FhirError::Validation(
                     "If clinicalStatus is absent, verificationStatus must be 'entered-in-error'".to_string()
                 ));
             }
         }
         
-        Ok(())
+        issues
     }
 }
 
@@ -195,17 +245,19 @@ impl Validator<Condition> for ConditionValidator {
 pub struct EncounterValidator;
 
 impl Validator<Encounter> for EncounterValidator {
-    fn validate(&self, encounter: &Encounter) -> FhirResult<()> {
+    fn issues(&self, encounter: &Encounter) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
         // Validate resource type
         if encounter.resource_type != "Encounter" {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 format!("Invalid resourceType: expected 'Encounter', got '{}'", encounter.resource_type)
             ));
         }
         
         // Validate required fields
         if encounter.status.0.is_empty() {
-            return Err(FhirError::MissingRequiredField("status".to_string()));
+            issues.push(FhirError::MissingRequiredField("status".to_string()));
         }
         
         // Validate status values
@@ -213,15 +265,15 @@ impl Validator<Encounter> for EncounterValidator {
             "planned", "arrived", "triaged", "in-progress",
             "onleave", "finished", "cancelled", "entered-in-error", "unknown"
         ];
-        if !valid_statuses.contains(&encounter.status.0.as_str()) {
-            return Err(FhirError::Validation(
+        if !encounter.status.0.is_empty() && !valid_statuses.contains(&encounter.status.0.as_str()) {
+            issues.push(FhirError::Validation(
                 format!("Invalid status value: '{}'", encounter.status.0)
             ));
         }
         
         // Validate class (required)
         if encounter.class.code.is_none() && encounter.class.display.is_none() {
-            return Err(FhirError::Validation(
+            issues.push(FhirError::Validation(
                 "Encounter.class must have at least code or display".to_string()
             ));
         }
@@ -230,7 +282,7 @@ impl Validator<Encounter> for EncounterValidator {
         if let Some(period) = &encounter.period {
             if let (Some(start), Some(end)) = (&period.start, &period.end) {
                 if end.0 < start.0 {
-                    return Err(FhirError::Validation(
+                    issues.push(FhirError::Validation(
                         "Period.end must be after or equal to period.start".to_string()
                     ));
                 }
@@ -241,72 +293,1444 @@ impl Validator<Encounter> for EncounterValidator {
         if let Some(history) = &encounter.status_history {
             for item in history {
                 if !valid_statuses.contains(&item.status.0.as_str()) {
-                    return Err(FhirError::Validation(
+                    issues.push(FhirError::Validation(
                         format!("Invalid status in history: '{}'", item.status.0)
                     ));
                 }
             }
         }
+
+        for location in encounter.location.iter().flatten() {
+            check_reference_type("location.location", Some(&location.location), "Location", &mut issues);
+        }
         
-        Ok(())
+        issues
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::{Code, CodeableConcept, Coding, FhirString, Uri, Reference};
-    
-    #[test]
-    fn test_patient_validation_success() {
-        let mut patient = Patient::new();
-        patient.resource_type = "Patient".to_string();
-        
-        let validator = PatientValidator;
-        assert!(validator.validate(&patient).is_ok());
+/// Practitioner validator
+pub struct PractitionerValidator;
+
+impl Validator<Practitioner> for PractitionerValidator {
+    fn issues(&self, practitioner: &Practitioner) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if practitioner.resource_type != "Practitioner" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Practitioner', got '{}'", practitioner.resource_type)
+            ));
+        }
+
+        if let Some(names) = &practitioner.name {
+            check_names(names, &mut issues);
+        }
+
+        // Validate gender if present
+        if let Some(gender) = &practitioner.gender {
+            let valid_genders = ["male", "female", "other", "unknown"];
+            if !valid_genders.contains(&gender.0.as_str()) {
+                issues.push(FhirError::Validation(
+                    format!("Invalid gender value: '{}'. Must be one of: male, female, other, unknown", gender.0)
+                ));
+            }
+        }
+
+        if let Some(identifiers) = &practitioner.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+
+        if let Some(telecom) = &practitioner.telecom {
+            check_telecom(telecom, &mut issues);
+        }
+
+        // Validate qualifications if present
+        if let Some(qualifications) = &practitioner.qualification {
+            for qualification in qualifications {
+                if qualification.code.coding.is_none() && qualification.code.text.is_none() {
+                    issues.push(FhirError::Validation(
+                        "Qualification.code must have at least coding or text".to_string()
+                    ));
+                }
+                if let Some(period) = &qualification.period {
+                    check_period(period, &mut issues);
+                }
+            }
+        }
+
+        issues
     }
-    
-    #[test]
-    fn test_patient_validation_invalid_gender() {
-        let mut patient = Patient::new();
-        patient.gender = Some(Code("invalid".to_string()));
-        
-        let validator = PatientValidator;
-        assert!(validator.validate(&patient).is_err());
+}
+
+/// PractitionerRole validator
+pub struct PractitionerRoleValidator;
+
+impl Validator<PractitionerRole> for PractitionerRoleValidator {
+    fn issues(&self, role: &PractitionerRole) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if role.resource_type != "PractitionerRole" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'PractitionerRole', got '{}'", role.resource_type)
+            ));
+        }
+
+        check_reference_type("practitioner", role.practitioner.as_ref(), "Practitioner", &mut issues);
+        check_reference_type("organization", role.organization.as_ref(), "Organization", &mut issues);
+
+        if let Some(period) = &role.period {
+            check_period(period, &mut issues);
+        }
+
+        if let Some(identifiers) = &role.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+
+        if let Some(telecom) = &role.telecom {
+            check_telecom(telecom, &mut issues);
+        }
+
+        // Validate available times if present
+        if let Some(times) = &role.available_time {
+            let valid_days = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+            for time in times {
+                for day in time.days_of_week.iter().flatten() {
+                    if !valid_days.contains(&day.0.as_str()) {
+                        issues.push(FhirError::Validation(
+                            format!("Invalid daysOfWeek value: '{}'", day.0)
+                        ));
+                    }
+                }
+                if let (Some(start), Some(end)) = (&time.available_start_time, &time.available_end_time) {
+                    if end.0 < start.0 {
+                        issues.push(FhirError::Validation(
+                            "availableEndTime must be after or equal to availableStartTime".to_string()
+                        ));
+                    }
+                }
+            }
+        }
+
+        issues
     }
-    
-    #[test]
-    fn test_observation_validation_requires_status() {
-        let observation = Observation::new(
-            Code("final".to_string()),
-            CodeableConcept {
-                coding: Some(vec![Coding {
-                    system: Some(Uri("http://loinc.org".to_string())),
-                    code: Some(Code("1234-5".to_string())),
-                    display: None,
-                    version: None,
-                    user_selected: None,
-                }]),
-                text: None,
+}
+
+/// Organization validator
+pub struct OrganizationValidator;
+
+impl Validator<Organization> for OrganizationValidator {
+    fn issues(&self, organization: &Organization) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if organization.resource_type != "Organization" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Organization', got '{}'", organization.resource_type)
+            ));
+        }
+
+        // org-1: an organization needs a name or an identifier
+        if organization.name.is_none() && organization.identifier.as_ref().is_none_or(|i| i.is_empty()) {
+            issues.push(FhirError::Validation(
+                "Organization must have at least a name or an identifier".to_string()
+            ));
+        }
+
+        if let Some(identifiers) = &organization.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+
+        // org-2, org-3: an organization has no home address or telecom
+        if let Some(telecom) = &organization.telecom {
+            check_telecom(telecom, &mut issues);
+            if telecom.iter().any(|t| t.use_.as_ref().is_some_and(|u| u.0 == "home")) {
+                issues.push(FhirError::Validation(
+                    "Organization telecom cannot use 'home'".to_string()
+                ));
             }
+        }
+        if organization.address.iter().flatten().any(|a| a.use_.as_ref().is_some_and(|u| u.0 == "home")) {
+            issues.push(FhirError::Validation(
+                "Organization address cannot use 'home'".to_string()
+            ));
+        }
+
+        check_reference_type("partOf", organization.part_of.as_ref(), "Organization", &mut issues);
+        let part_of_self = organization.id.as_ref().zip(organization.part_of.as_ref())
+            .and_then(|(id, part_of)| part_of.reference.as_ref().map(|r| r.0 == format!("Organization/{}", id.0)))
+            .unwrap_or(false);
+        if part_of_self {
+            issues.push(FhirError::InvalidReference(
+                "Organization cannot be partOf itself".to_string()
+            ));
+        }
+
+        issues
+    }
+}
+
+/// Medication validator
+pub struct MedicationValidator;
+
+impl Validator<Medication> for MedicationValidator {
+    fn issues(&self, medication: &Medication) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if medication.resource_type != "Medication" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Medication', got '{}'", medication.resource_type)
+            ));
+        }
+
+        if let Some(status) = &medication.status {
+            check_code("status", status, &["active", "inactive", "entered-in-error"], &mut issues);
+        }
+
+        if let Some(identifiers) = &medication.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+
+        check_reference_type("manufacturer", medication.manufacturer.as_ref(), "Organization", &mut issues);
+
+        issues
+    }
+}
+
+/// MedicationRequest validator
+pub struct MedicationRequestValidator;
+
+impl Validator<MedicationRequest> for MedicationRequestValidator {
+    fn issues(&self, request: &MedicationRequest) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if request.resource_type != "MedicationRequest" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'MedicationRequest', got '{}'", request.resource_type)
+            ));
+        }
+
+        check_code("status", &request.status, &[
+            "active", "on-hold", "cancelled", "completed",
+            "entered-in-error", "stopped", "draft", "unknown",
+        ], &mut issues);
+        check_code("intent", &request.intent, &[
+            "proposal", "plan", "order", "original-order",
+            "reflex-order", "filler-order", "instance-order", "option",
+        ], &mut issues);
+        if let Some(priority) = &request.priority {
+            check_code("priority", priority, &["routine", "urgent", "asap", "stat"], &mut issues);
+        }
+
+        check_subject(&request.subject, &mut issues);
+        check_medication(&request.medication, &mut issues);
+        check_reference_type("encounter", request.encounter.as_ref(), "Encounter", &mut issues);
+
+        for dosage in request.dosage_instruction.iter().flatten() {
+            check_dosage(dosage, &mut issues);
+        }
+        if let Some(validity) = request.dispense_request.as_ref().and_then(|d| d.validity_period.as_ref()) {
+            check_period(validity, &mut issues);
+        }
+
+        issues
+    }
+}
+
+/// MedicationStatement validator
+pub struct MedicationStatementValidator;
+
+impl Validator<MedicationStatement> for MedicationStatementValidator {
+    fn issues(&self, statement: &MedicationStatement) -> Vec<FhirError> {
+        use crate::domain::resources::medication_statement::MedicationStatementEffective;
+
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if statement.resource_type != "MedicationStatement" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'MedicationStatement', got '{}'", statement.resource_type)
+            ));
+        }
+
+        check_code("status", &statement.status, &[
+            "active", "completed", "entered-in-error", "intended",
+            "stopped", "on-hold", "unknown", "not-taken",
+        ], &mut issues);
+
+        check_subject(&statement.subject, &mut issues);
+        check_medication(&statement.medication, &mut issues);
+
+        if let Some(MedicationStatementEffective::Period(period)) = &statement.effective {
+            check_period(period, &mut issues);
+        }
+        for dosage in statement.dosage.iter().flatten() {
+            check_dosage(dosage, &mut issues);
+        }
+
+        issues
+    }
+}
+
+/// AllergyIntolerance validator
+pub struct AllergyIntoleranceValidator;
+
+impl Validator<AllergyIntolerance> for AllergyIntoleranceValidator {
+    fn issues(&self, allergy: &AllergyIntolerance) -> Vec<FhirError> {
+        use crate::domain::resources::allergy_intolerance::AllergyIntoleranceOnset;
+
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if allergy.resource_type != "AllergyIntolerance" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'AllergyIntolerance', got '{}'", allergy.resource_type)
+            ));
+        }
+
+        // Validate patient (required)
+        if allergy.patient.reference.is_none() && allergy.patient.identifier.is_none() {
+            issues.push(FhirError::MissingRequiredField(
+                "patient (must have reference or identifier)".to_string()
+            ));
+        }
+        check_reference_type("patient", Some(&allergy.patient), "Patient", &mut issues);
+        check_reference_type("encounter", allergy.encounter.as_ref(), "Encounter", &mut issues);
+
+        check_status_concept(
+            "clinicalStatus", allergy.clinical_status.as_ref(), &["active", "inactive", "resolved"], &mut issues,
         );
-        
-        let validator = ObservationValidator;
-        assert!(validator.validate(&observation).is_ok());
+        let verification_status = check_status_concept(
+            "verificationStatus", allergy.verification_status.as_ref(),
+            &["unconfirmed", "confirmed", "refuted", "entered-in-error"], &mut issues,
+        );
+
+        // clinicalStatus is required unless the record was entered in error,
+        // and must be absent when it was
+        let entered_in_error = verification_status == Some("entered-in-error");
+        if allergy.clinical_status.is_none() && !entered_in_error {
+            issues.push(FhirError::Validation(
+                "clinicalStatus is required unless verificationStatus is 'entered-in-error'".to_string()
+            ));
+        }
+        if allergy.clinical_status.is_some() && entered_in_error {
+            issues.push(FhirError::Validation(
+                "clinicalStatus must be absent when verificationStatus is 'entered-in-error'".to_string()
+            ));
+        }
+
+        if let Some(type_) = &allergy.type_ {
+            check_code("type", type_, &["allergy", "intolerance"], &mut issues);
+        }
+        for category in allergy.category.iter().flatten() {
+            check_code("category", category, &["food", "medication", "environment", "biologic"], &mut issues);
+        }
+        if let Some(criticality) = &allergy.criticality {
+            check_code("criticality", criticality, &["low", "high", "unable-to-assess"], &mut issues);
+        }
+
+        if let Some(AllergyIntoleranceOnset::Period(period)) = &allergy.onset {
+            check_period(period, &mut issues);
+        }
+
+        // Each reaction needs at least one manifestation
+        for reaction in allergy.reaction.iter().flatten() {
+            if reaction.manifestation.is_empty() {
+                issues.push(FhirError::MissingRequiredField("reaction.manifestation".to_string()));
+            }
+            if let Some(severity) = &reaction.severity {
+                check_code("reaction.severity", severity, &["mild", "moderate", "severe"], &mut issues);
+            }
+        }
+
+        issues
     }
-    
-    #[test]
-    fn test_condition_requires_subject() {
-        let subject = Reference {
-            reference: Some(FhirString("Patient/123".to_string())),
-            type_: None,
-            identifier: None,
-            display: None,
-        };
-        
-        let condition = Condition::new(subject);
-        
-        let validator = ConditionValidator;
-        assert!(validator.validate(&condition).is_ok());
+}
+
+/// Procedure validator
+pub struct ProcedureValidator;
+
+impl Validator<Procedure> for ProcedureValidator {
+    fn issues(&self, procedure: &Procedure) -> Vec<FhirError> {
+        use crate::domain::resources::procedure::ProcedurePerformed;
+
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if procedure.resource_type != "Procedure" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Procedure', got '{}'", procedure.resource_type)
+            ));
+        }
+
+        check_code("status", &procedure.status, &[
+            "preparation", "in-progress", "not-done", "on-hold",
+            "stopped", "completed", "entered-in-error", "unknown",
+        ], &mut issues);
+
+        check_subject(&procedure.subject, &mut issues);
+        check_reference_type("encounter", procedure.encounter.as_ref(), "Encounter", &mut issues);
+        for reason in procedure.reason_reference.iter().flatten() {
+            check_reference_type("reasonReference", Some(reason), "Condition", &mut issues);
+        }
+
+        // Each performer needs an actor
+        for performer in procedure.performer.iter().flatten() {
+            if performer.actor.reference.is_none() && performer.actor.identifier.is_none() {
+                issues.push(FhirError::MissingRequiredField(
+                    "performer.actor (must have reference or identifier)".to_string()
+                ));
+            }
+        }
+
+        if let Some(ProcedurePerformed::Period(period)) = &procedure.performed {
+            check_period(period, &mut issues);
+        }
+
+        issues
+    }
+}
+
+/// DiagnosticReport validator
+pub struct DiagnosticReportValidator;
+
+impl Validator<DiagnosticReport> for DiagnosticReportValidator {
+    fn issues(&self, report: &DiagnosticReport) -> Vec<FhirError> {
+        use crate::domain::resources::diagnostic_report::DiagnosticReportEffective;
+
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if report.resource_type != "DiagnosticReport" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'DiagnosticReport', got '{}'", report.resource_type)
+            ));
+        }
+
+        check_code("status", &report.status, &[
+            "registered", "partial", "preliminary", "final", "amended",
+            "corrected", "appended", "cancelled", "entered-in-error", "unknown",
+        ], &mut issues);
+
+        // Validate code (required)
+        if report.code.coding.as_ref().is_none_or(|c| c.is_empty()) && report.code.text.is_none() {
+            issues.push(FhirError::Validation(
+                "DiagnosticReport.code must have coding or text".to_string()
+            ));
+        }
+
+        check_subject(&report.subject, &mut issues);
+        check_reference_type("encounter", report.encounter.as_ref(), "Encounter", &mut issues);
+        for result in report.result.iter().flatten() {
+            check_reference_type("result", Some(result), "Observation", &mut issues);
+        }
+
+        if let Some(DiagnosticReportEffective::Period(period)) = &report.effective {
+            check_period(period, &mut issues);
+        }
+        for form in report.presented_form.iter().flatten() {
+            check_attachment("presentedForm", form, &mut issues);
+        }
+
+        issues
     }
-}
\ No newline at end of file
+}
+
+/// Immunization validator
+pub struct ImmunizationValidator;
+
+impl Validator<Immunization> for ImmunizationValidator {
+    fn issues(&self, immunization: &Immunization) -> Vec<FhirError> {
+        use crate::domain::resources::immunization::ImmunizationDoseNumber;
+
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if immunization.resource_type != "Immunization" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Immunization', got '{}'", immunization.resource_type)
+            ));
+        }
+
+        check_code("status", &immunization.status, &["completed", "entered-in-error", "not-done"], &mut issues);
+
+        // Validate vaccineCode (required)
+        let vaccine_code = &immunization.vaccine_code;
+        if vaccine_code.coding.as_ref().is_none_or(|c| c.is_empty()) && vaccine_code.text.is_none() {
+            issues.push(FhirError::Validation(
+                "Immunization.vaccineCode must have coding or text".to_string()
+            ));
+        }
+
+        // Validate patient (required)
+        if immunization.patient.reference.is_none() && immunization.patient.identifier.is_none() {
+            issues.push(FhirError::MissingRequiredField(
+                "patient (must have reference or identifier)".to_string()
+            ));
+        }
+        check_reference_type("patient", Some(&immunization.patient), "Patient", &mut issues);
+        check_reference_type("encounter", immunization.encounter.as_ref(), "Encounter", &mut issues);
+        check_reference_type("manufacturer", immunization.manufacturer.as_ref(), "Organization", &mut issues);
+
+        // Each performer needs an actor
+        for performer in immunization.performer.iter().flatten() {
+            if performer.actor.reference.is_none() && performer.actor.identifier.is_none() {
+                issues.push(FhirError::MissingRequiredField(
+                    "performer.actor (must have reference or identifier)".to_string()
+                ));
+            }
+        }
+
+        // Dose numbers count from 1, and a dose cannot lie beyond its series
+        for protocol in immunization.protocol_applied.iter().flatten() {
+            let dose_number = match &protocol.dose_number {
+                ImmunizationDoseNumber::PositiveInt(n) => Some(n.0),
+                ImmunizationDoseNumber::String(_) => None,
+            };
+            let series_doses = match &protocol.series_doses {
+                Some(ImmunizationDoseNumber::PositiveInt(n)) => Some(n.0),
+                _ => None,
+            };
+            if dose_number == Some(0) {
+                issues.push(FhirError::Validation(
+                    "protocolApplied.doseNumberPositiveInt must be at least 1".to_string()
+                ));
+            }
+            if series_doses == Some(0) {
+                issues.push(FhirError::Validation(
+                    "protocolApplied.seriesDosesPositiveInt must be at least 1".to_string()
+                ));
+            }
+            if let (Some(dose), Some(series)) = (dose_number, series_doses) {
+                if dose > series {
+                    issues.push(FhirError::Validation(format!(
+                        "protocolApplied.doseNumber {} exceeds seriesDoses {}", dose, series
+                    )));
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+/// Location validator
+pub struct LocationValidator;
+
+impl Validator<Location> for LocationValidator {
+    fn issues(&self, location: &Location) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if location.resource_type != "Location" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Location', got '{}'", location.resource_type)
+            ));
+        }
+
+        if let Some(status) = &location.status {
+            check_code("status", status, &["active", "suspended", "inactive"], &mut issues);
+        }
+        if let Some(mode) = &location.mode {
+            check_code("mode", mode, &["instance", "kind"], &mut issues);
+        }
+        if let Some(identifiers) = &location.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+        if let Some(telecom) = &location.telecom {
+            check_telecom(telecom, &mut issues);
+        }
+
+        // WGS84 coordinates
+        if let Some(position) = &location.position {
+            if !(-90.0..=90.0).contains(&position.latitude.0) {
+                issues.push(FhirError::Validation(
+                    format!("position.latitude must be between -90 and 90, got {}", position.latitude.0)
+                ));
+            }
+            if !(-180.0..=180.0).contains(&position.longitude.0) {
+                issues.push(FhirError::Validation(
+                    format!("position.longitude must be between -180 and 180, got {}", position.longitude.0)
+                ));
+            }
+        }
+
+        check_reference_type("managingOrganization", location.managing_organization.as_ref(), "Organization", &mut issues);
+        check_reference_type("partOf", location.part_of.as_ref(), "Location", &mut issues);
+        let part_of_self = location.id.as_ref().zip(location.part_of.as_ref())
+            .and_then(|(id, part_of)| part_of.reference.as_ref().map(|r| r.0 == format!("Location/{}", id.0)))
+            .unwrap_or(false);
+        if part_of_self {
+            issues.push(FhirError::InvalidReference(
+                "Location cannot be partOf itself".to_string()
+            ));
+        }
+
+        for hours in location.hours_of_operation.iter().flatten() {
+            for day in hours.days_of_week.iter().flatten() {
+                check_code("hoursOfOperation.daysOfWeek", day, &["mon", "tue", "wed", "thu", "fri", "sat", "sun"], &mut issues);
+            }
+        }
+
+        issues
+    }
+}
+
+/// Entity types a member of each `Group.type` may reference. Nested
+/// groups are allowed for every type
+const GROUP_MEMBER_TYPES: &[(&str, &[&str])] = &[
+    ("person", &["Patient", "RelatedPerson", "Practitioner", "PractitionerRole", "Group"]),
+    ("animal", &["Patient", "Group"]),
+    ("practitioner", &["Practitioner", "PractitionerRole", "Group"]),
+    ("device", &["Device", "Group"]),
+    ("medication", &["Medication", "Group"]),
+    ("substance", &["Substance", "Group"]),
+];
+
+/// Group validator
+pub struct GroupValidator;
+
+impl Validator<Group> for GroupValidator {
+    fn issues(&self, group: &Group) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if group.resource_type != "Group" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Group', got '{}'", group.resource_type)
+            ));
+        }
+
+        let types: Vec<&str> = GROUP_MEMBER_TYPES.iter().map(|(t, _)| *t).collect();
+        check_code("type", &group.type_, &types, &mut issues);
+        if let Some(identifiers) = &group.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+        check_reference_types(
+            "managingEntity",
+            group.managing_entity.as_ref(),
+            &["Organization", "RelatedPerson", "Practitioner", "PractitionerRole"],
+            &mut issues,
+        );
+
+        for characteristic in group.characteristic.iter().flatten() {
+            if characteristic.code.coding.as_ref().is_none_or(|c| c.is_empty()) && characteristic.code.text.is_none() {
+                issues.push(FhirError::MissingRequiredField(
+                    "characteristic.code (must have coding or text)".to_string()
+                ));
+            }
+            if let Some(period) = &characteristic.period {
+                check_period(period, &mut issues);
+            }
+        }
+
+        // Only actual groups enumerate their members (grp-1)
+        let members = group.member.as_deref().unwrap_or_default();
+        if !members.is_empty() && !group.actual.0 {
+            issues.push(FhirError::Validation(
+                "Only actual groups can list members; a definitional group is described by its characteristics".to_string()
+            ));
+        }
+        let entity_types = GROUP_MEMBER_TYPES.iter()
+            .find(|(t, _)| *t == group.type_.0)
+            .map(|(_, entity_types)| *entity_types);
+        let own_reference = group.id.as_ref().map(|id| format!("Group/{}", id.0));
+        for member in members {
+            if member.entity.reference.is_none() && member.entity.identifier.is_none() {
+                issues.push(FhirError::MissingRequiredField(
+                    "member.entity (must have reference or identifier)".to_string()
+                ));
+            }
+            if let Some(entity_types) = entity_types {
+                check_reference_types("member.entity", Some(&member.entity), entity_types, &mut issues);
+            }
+            let reference = member.entity.reference.as_ref().map(|r| r.0.as_str());
+            if reference.is_some() && reference == own_reference.as_deref() {
+                issues.push(FhirError::InvalidReference(
+                    "Group cannot be a member of itself".to_string()
+                ));
+            }
+            if let Some(period) = &member.period {
+                check_period(period, &mut issues);
+            }
+        }
+
+        issues
+    }
+}
+
+/// DocumentReference validator
+pub struct DocumentReferenceValidator;
+
+impl Validator<DocumentReference> for DocumentReferenceValidator {
+    fn issues(&self, document: &DocumentReference) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if document.resource_type != "DocumentReference" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'DocumentReference', got '{}'", document.resource_type)
+            ));
+        }
+
+        check_code("status", &document.status, &["current", "superseded", "entered-in-error"], &mut issues);
+        if let Some(doc_status) = &document.doc_status {
+            check_code("docStatus", doc_status, &["preliminary", "final", "amended", "entered-in-error"], &mut issues);
+        }
+        if let Some(identifiers) = &document.identifier {
+            check_identifiers(identifiers, &mut issues);
+        }
+        check_reference_types(
+            "subject",
+            document.subject.as_ref(),
+            &["Patient", "Practitioner", "Group", "Device"],
+            &mut issues,
+        );
+        check_reference_type("custodian", document.custodian.as_ref(), "Organization", &mut issues);
+
+        // The content itself is required; each attachment carries it inline or by URL
+        if document.content.is_empty() {
+            issues.push(FhirError::MissingRequiredField("content".to_string()));
+        }
+        for content in &document.content {
+            check_attachment("content.attachment", &content.attachment, &mut issues);
+        }
+
+        for relates_to in document.relates_to.iter().flatten() {
+            check_code("relatesTo.code", &relates_to.code, &["replaces", "transforms", "signs", "appends"], &mut issues);
+            check_reference_type("relatesTo.target", Some(&relates_to.target), "DocumentReference", &mut issues);
+        }
+
+        if let Some(context) = &document.context {
+            for encounter in context.encounter.iter().flatten() {
+                check_reference_types("context.encounter", Some(encounter), &["Encounter", "EpisodeOfCare"], &mut issues);
+            }
+            check_reference_type("context.sourcePatientInfo", context.source_patient_info.as_ref(), "Patient", &mut issues);
+            if let Some(period) = &context.period {
+                check_period(period, &mut issues);
+            }
+        }
+
+        issues
+    }
+}
+
+/// Binary validator
+pub struct BinaryValidator;
+
+impl Validator<Binary> for BinaryValidator {
+    fn issues(&self, binary: &Binary) -> Vec<FhirError> {
+        let mut issues = Vec::new();
+
+        // Validate resource type
+        if binary.resource_type != "Binary" {
+            issues.push(FhirError::Validation(
+                format!("Invalid resourceType: expected 'Binary', got '{}'", binary.resource_type)
+            ));
+        }
+
+        // contentType is a MIME type such as application/pdf
+        let content_type = binary.content_type.0.as_str();
+        if content_type.is_empty() {
+            issues.push(FhirError::MissingRequiredField("contentType".to_string()));
+        } else if !content_type.split_once('/').is_some_and(|(t, subtype)| !t.is_empty() && !subtype.is_empty()) {
+            issues.push(FhirError::Validation(
+                format!("Invalid contentType value: '{}'", content_type)
+            ));
+        }
+
+        issues
+    }
+}
+
+/// A status CodeableConcept needs a coding whose code is one of `valid`.
+/// Returns the code when it is valid
+fn check_status_concept<'a>(
+    element: &str,
+    status: Option<&'a CodeableConcept>,
+    valid: &[&str],
+    issues: &mut Vec<FhirError>,
+) -> Option<&'a str> {
+    let status = status?;
+    let code = status.coding.as_ref()
+        .and_then(|codings| codings.first())
+        .and_then(|coding| coding.code.as_ref())
+        .map(|code| code.0.as_str());
+    match code {
+        Some(code) if valid.contains(&code) => Some(code),
+        Some(code) => {
+            issues.push(FhirError::Validation(format!("Invalid {} value: '{}'", element, code)));
+            None
+        }
+        None => {
+            issues.push(FhirError::Validation(format!("{} must have coding", element)));
+            None
+        }
+    }
+}
+
+/// A required code must be present and one of `valid`
+fn check_code(element: &str, code: &Code, valid: &[&str], issues: &mut Vec<FhirError>) {
+    if code.0.is_empty() {
+        issues.push(FhirError::MissingRequiredField(element.to_string()));
+    } else if !valid.contains(&code.0.as_str()) {
+        issues.push(FhirError::Validation(
+            format!("Invalid {} value: '{}'", element, code.0)
+        ));
+    }
+}
+
+/// Patient-compartment resources need a subject, which must be a Patient
+fn check_subject(subject: &Reference, issues: &mut Vec<FhirError>) {
+    if subject.reference.is_none() && subject.identifier.is_none() {
+        issues.push(FhirError::MissingRequiredField(
+            "subject (must have reference or identifier)".to_string()
+        ));
+    }
+    check_reference_type("subject", Some(subject), "Patient", issues);
+}
+
+/// `medication[x]` is a coded medication or a reference to a Medication
+fn check_medication(medication: &CodeableConceptOrReference, issues: &mut Vec<FhirError>) {
+    match medication {
+        CodeableConceptOrReference::CodeableConcept(code) => {
+            if code.coding.as_ref().is_none_or(|c| c.is_empty()) && code.text.is_none() {
+                issues.push(FhirError::Validation(
+                    "medicationCodeableConcept must have coding or text".to_string()
+                ));
+            }
+        }
+        CodeableConceptOrReference::Reference(reference) => {
+            check_reference_type("medicationReference", Some(reference), "Medication", issues);
+        }
+    }
+}
+
+/// Timing units and days of the week come from fixed value sets
+fn check_dosage(dosage: &Dosage, issues: &mut Vec<FhirError>) {
+    const UNITS: &[&str] = &["s", "min", "h", "d", "wk", "mo", "a"];
+    const DAYS: &[&str] = &["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+    let Some(repeat) = dosage.timing.as_ref().and_then(|t| t.repeat.as_ref()) else {
+        return;
+    };
+    for unit in [&repeat.duration_unit, &repeat.period_unit].into_iter().flatten() {
+        check_code("Timing unit", unit, UNITS, issues);
+    }
+    for day in repeat.day_of_week.iter().flatten() {
+        check_code("Timing dayOfWeek", day, DAYS, issues);
+    }
+    if repeat.period.as_ref().is_some_and(|p| p.0 <= 0.0) {
+        issues.push(FhirError::Validation(
+            "Timing period must be greater than zero".to_string()
+        ));
+    }
+}
+
+/// Every HumanName needs a family name, given name or text
+fn check_names(names: &[HumanName], issues: &mut Vec<FhirError>) {
+    if names.is_empty() {
+        issues.push(FhirError::Validation(
+            "Name array cannot be empty if present".to_string()
+        ));
+    }
+
+    for name in names {
+        if name.family.is_none() && name.given.is_none() && name.text.is_none() {
+            issues.push(FhirError::Validation(
+                "HumanName must have at least family, given, or text".to_string()
+            ));
+        }
+    }
+}
+
+/// Identifiers need a value or system, and NPIs a valid check digit
+fn check_identifiers(identifiers: &[Identifier], issues: &mut Vec<FhirError>) {
+    for identifier in identifiers {
+        if identifier.value.is_none() && identifier.system.is_none() {
+            issues.push(FhirError::Validation(
+                "Identifier must have at least a value or system".to_string()
+            ));
+        }
+
+        if identifier.system.as_ref().is_some_and(|s| s.0 == NPI_SYSTEM) {
+            match &identifier.value {
+                Some(value) if is_valid_npi(&value.0) => {}
+                Some(value) => issues.push(FhirError::Validation(
+                    format!("Invalid NPI: '{}'", value.0)
+                )),
+                None => issues.push(FhirError::MissingRequiredField("NPI identifier value".to_string())),
+            }
+        }
+    }
+}
+
+/// An NPI is 10 digits whose last digit is the Luhn check digit of the
+/// first nine, computed with the `80840` card issuer prefix
+pub fn is_valid_npi(npi: &str) -> bool {
+    if npi.len() != 10 || !npi.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    let sum: u32 = format!("80840{}", npi)
+        .bytes()
+        .rev()
+        .map(|b| u32::from(b - b'0'))
+        .enumerate()
+        .map(|(i, digit)| match (i % 2 == 1, digit * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => digit,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn check_telecom(telecom: &[ContactPoint], issues: &mut Vec<FhirError>) {
+    let valid_systems = ["phone", "fax", "email", "pager", "url", "sms", "other"];
+    for contact in telecom {
+        if let Some(system) = &contact.system {
+            if !valid_systems.contains(&system.0.as_str()) {
+                issues.push(FhirError::Validation(
+                    format!("Invalid telecom system: '{}'", system.0)
+                ));
+            }
+        }
+        if contact.value.is_some() && contact.system.is_none() {
+            issues.push(FhirError::Validation(
+                "ContactPoint with a value must have a system".to_string()
+            ));
+        }
+    }
+}
+
+fn check_period(period: &Period, issues: &mut Vec<FhirError>) {
+    if let (Some(start), Some(end)) = (&period.start, &period.end) {
+        if end.0 < start.0 {
+            issues.push(FhirError::Validation(
+                "Period.end must be after or equal to period.start".to_string()
+            ));
+        }
+    }
+}
+
+/// An attachment carries its content inline or by URL, and inline data
+/// needs a contentType
+fn check_attachment(element: &str, attachment: &Attachment, issues: &mut Vec<FhirError>) {
+    if attachment.data.is_none() && attachment.url.is_none() {
+        issues.push(FhirError::Validation(
+            format!("{} must have data or a url", element)
+        ));
+    }
+    if attachment.data.is_some() && attachment.content_type.is_none() {
+        issues.push(FhirError::Validation(
+            format!("{} with data must have a contentType", element)
+        ));
+    }
+}
+
+/// A literal reference in `element` must point at `resource_type`
+fn check_reference_type(element: &str, reference: Option<&Reference>, resource_type: &str, issues: &mut Vec<FhirError>) {
+    check_reference_types(element, reference, &[resource_type], issues);
+}
+
+/// A literal reference in `element` must point at one of `resource_types`
+fn check_reference_types(element: &str, reference: Option<&Reference>, resource_types: &[&str], issues: &mut Vec<FhirError>) {
+    if let Some(reference) = reference.and_then(|r| r.reference.as_ref()) {
+        let points_at_type = reference.0
+            .split_once('/')
+            .is_some_and(|(t, id)| resource_types.contains(&t) && !id.is_empty());
+        if !points_at_type {
+            issues.push(FhirError::InvalidReference(
+                format!("{} must reference a {}: {}", element, resource_types.join(" or "), reference.0)
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Code, CodeableConcept, Coding, FhirString, Uri, Reference};
+    use crate::service::ValidationMode;
+    
+    #[test]
+    fn test_patient_validation_success() {
+        let mut patient = Patient::new();
+        patient.resource_type = "Patient".to_string();
+        
+        let validator = PatientValidator;
+        assert!(validator.validate(&patient).is_ok());
+    }
+    
+    #[test]
+    fn test_patient_validation_invalid_gender() {
+        let mut patient = Patient::new();
+        patient.gender = Some(Code("invalid".to_string()));
+        
+        let validator = PatientValidator;
+        assert!(validator.validate(&patient).is_err());
+    }
+
+    #[test]
+    fn test_patient_validation_links() {
+        use crate::domain::resources::patient::PatientLink;
+
+        let link = |reference: &str, type_: &str| PatientLink {
+            other: Reference {
+                reference: Some(FhirString(reference.to_string())),
+                type_: None,
+                identifier: None,
+                display: None,
+            },
+            type_: Code(type_.to_string()),
+        };
+        let mut patient = Patient::new();
+        patient.id = Some(crate::domain::Id("p1".to_string()));
+        patient.link = Some(vec![link("Patient/p2", "replaced-by")]);
+
+        let validator = PatientValidator;
+        assert!(validator.validate(&patient).is_ok());
+
+        patient.link = Some(vec![
+            link("Patient/p2", "duplicate"),
+            link("Observation/o1", "seealso"),
+            link("Patient/p1", "refer"),
+        ]);
+        assert_eq!(validator.issues(&patient).len(), 3);
+    }
+    
+    #[test]
+    fn test_observation_validation_requires_status() {
+        let observation = Observation::new(
+            Code("final".to_string()),
+            CodeableConcept {
+                coding: Some(vec![Coding {
+                    system: Some(Uri("http://loinc.org".to_string())),
+                    code: Some(Code("1234-5".to_string())),
+                    display: None,
+                    version: None,
+                    user_selected: None,
+                }]),
+                text: None,
+            }
+        );
+        
+        let validator = ObservationValidator;
+        assert!(validator.validate(&observation).is_ok());
+    }
+    
+    #[test]
+    fn test_condition_requires_subject() {
+        let subject = Reference {
+            reference: Some(FhirString("Patient/123".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        
+        let condition = Condition::new(subject);
+        
+        let validator = ConditionValidator;
+        assert!(validator.validate(&condition).is_ok());
+    }
+
+    #[test]
+    fn test_validator_reports_every_issue() {
+        let mut encounter = Encounter::new(
+            Code("bogus".to_string()),
+            Coding {
+                system: None,
+                code: None,
+                display: None,
+                version: None,
+                user_selected: None,
+            },
+        );
+        encounter.resource_type = "Patient".to_string();
+
+        let validator = EncounterValidator;
+        let issues = validator.issues(&encounter);
+        assert_eq!(issues.len(), 3);
+        assert!(matches!(validator.validate(&encounter), Err(FhirError::Validation(m)) if m.contains("resourceType")));
+    }
+
+    #[test]
+    fn test_practitioner_npi_validation() {
+        use crate::domain::Identifier;
+
+        assert!(is_valid_npi("1234567893"));
+        assert!(!is_valid_npi("1234567890"));
+        assert!(!is_valid_npi("123456789"));
+        assert!(!is_valid_npi("12345678a3"));
+
+        let npi = |value: &str| Identifier {
+            use_: None,
+            type_: None,
+            system: Some(Uri(NPI_SYSTEM.to_string())),
+            value: Some(FhirString(value.to_string())),
+            period: None,
+            assigner: None,
+        };
+        let mut practitioner = Practitioner::new();
+        practitioner.identifier = Some(vec![npi("1234567893")]);
+
+        let validator = PractitionerValidator;
+        assert!(validator.validate(&practitioner).is_ok());
+
+        practitioner.identifier = Some(vec![npi("1234567890")]);
+        practitioner.gender = Some(Code("invalid".to_string()));
+        assert_eq!(validator.issues(&practitioner).len(), 2);
+    }
+
+    #[test]
+    fn test_practitioner_role_reference_types() {
+        let reference = |value: &str| Reference {
+            reference: Some(FhirString(value.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let mut role = PractitionerRole::new();
+        role.practitioner = Some(reference("Practitioner/123"));
+        role.organization = Some(reference("Organization/456"));
+
+        let validator = PractitionerRoleValidator;
+        assert!(validator.validate(&role).is_ok());
+
+        role.practitioner = Some(reference("Patient/123"));
+        role.organization = Some(reference("Organization/"));
+        assert_eq!(validator.issues(&role).len(), 2);
+    }
+
+    #[test]
+    fn test_organization_name_or_identifier() {
+        use crate::domain::{ContactPoint, Id};
+
+        let validator = OrganizationValidator;
+        let mut organization = Organization::new();
+        assert_eq!(validator.issues(&organization).len(), 1);
+
+        organization.name = Some(FhirString("General Hospital".to_string()));
+        assert!(validator.validate(&organization).is_ok());
+
+        organization.id = Some(Id("123".to_string()));
+        organization.part_of = Some(Reference {
+            reference: Some(FhirString("Organization/123".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+        organization.telecom = Some(vec![ContactPoint {
+            system: Some(Code("phone".to_string())),
+            value: Some(FhirString("555-0100".to_string())),
+            use_: Some(Code("home".to_string())),
+            rank: None,
+            period: None,
+        }]);
+        assert_eq!(validator.issues(&organization).len(), 2);
+    }
+
+    #[test]
+    fn test_medication_request_value_sets() {
+        let validator = MedicationRequestValidator;
+        let medication = CodeableConceptOrReference::CodeableConcept(CodeableConcept {
+            coding: Some(vec![Coding {
+                system: Some(Uri("http://www.nlm.nih.gov/research/umls/rxnorm".to_string())),
+                version: None,
+                code: Some(Code("197361".to_string())),
+                display: None,
+                user_selected: None,
+            }]),
+            text: None,
+        });
+        let subject = Reference {
+            reference: Some(FhirString("Patient/123".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let mut request = MedicationRequest::new(
+            Code("active".to_string()),
+            Code("order".to_string()),
+            medication,
+            subject,
+        );
+        assert!(validator.validate(&request).is_ok());
+
+        request.status = Code("pending".to_string());
+        request.intent = Code("".to_string());
+        request.medication = CodeableConceptOrReference::Reference(Reference {
+            reference: Some(FhirString("Substance/1".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+        assert_eq!(validator.issues(&request).len(), 3);
+    }
+
+    #[test]
+    fn test_allergy_status_invariants() {
+        let validator = AllergyIntoleranceValidator;
+        let status = |code: &str| CodeableConcept {
+            coding: Some(vec![Coding {
+                system: None,
+                version: None,
+                code: Some(Code(code.to_string())),
+                display: None,
+                user_selected: None,
+            }]),
+            text: None,
+        };
+        let mut allergy = AllergyIntolerance::new(Reference {
+            reference: Some(FhirString("Patient/123".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+
+        // clinicalStatus is required unless the record was entered in error
+        assert!(validator.validate(&allergy).is_err());
+        allergy.clinical_status = Some(status("active"));
+        allergy.criticality = Some(Code("high".to_string()));
+        assert!(validator.validate(&allergy).is_ok());
+
+        allergy.verification_status = Some(status("entered-in-error"));
+        assert_eq!(validator.issues(&allergy).len(), 1);
+        allergy.clinical_status = None;
+        assert!(validator.validate(&allergy).is_ok());
+
+        allergy.criticality = Some(Code("severe".to_string()));
+        allergy.reaction = Some(vec![crate::domain::resources::allergy_intolerance::AllergyIntoleranceReaction {
+            substance: None,
+            manifestation: vec![],
+            description: None,
+            onset: None,
+            severity: Some(Code("mild".to_string())),
+            exposure_route: None,
+            note: None,
+        }]);
+        assert_eq!(validator.issues(&allergy).len(), 2);
+    }
+
+    #[test]
+    fn test_procedure_references() {
+        let validator = ProcedureValidator;
+        let reference = |reference: &str| Reference {
+            reference: Some(FhirString(reference.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let mut procedure = Procedure::new(Code("completed".to_string()), reference("Patient/123"));
+        procedure.encounter = Some(reference("Encounter/456"));
+        procedure.reason_reference = Some(vec![reference("Condition/789")]);
+        assert!(validator.validate(&procedure).is_ok());
+
+        // Encounter and reason references must point at the right types
+        procedure.encounter = Some(reference("Observation/456"));
+        procedure.reason_reference = Some(vec![reference("Condition/789"), reference("Observation/1")]);
+        assert_eq!(validator.issues(&procedure).len(), 2);
+
+        procedure.status = Code("done".to_string());
+        assert_eq!(validator.issues(&procedure).len(), 3);
+    }
+
+    #[test]
+    fn test_diagnostic_report_results_and_forms() {
+        let validator = DiagnosticReportValidator;
+        let reference = |reference: &str| Reference {
+            reference: Some(FhirString(reference.to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let code = CodeableConcept { coding: None, text: Some(FhirString("Lipid panel".to_string())) };
+        let mut report = DiagnosticReport::new(Code("final".to_string()), code, reference("Patient/123"));
+        report.result = Some(vec![reference("Observation/1"), reference("Observation/2")]);
+        assert!(validator.validate(&report).is_ok());
+
+        // Results must be Observations
+        report.result = Some(vec![reference("Observation/1"), reference("Condition/2")]);
+        assert_eq!(validator.issues(&report).len(), 1);
+
+        // Inline data needs a contentType
+        report.result = None;
+        report.presented_form = Some(vec![Attachment {
+            content_type: None,
+            language: None,
+            data: Some(FhirString("aGVsbG8=".to_string())),
+            url: None,
+            size: None,
+            hash: None,
+            title: None,
+            creation: None,
+        }]);
+        assert_eq!(validator.issues(&report).len(), 1);
+    }
+
+    #[test]
+    fn test_immunization_dose_numbers() {
+        use crate::domain::PositiveInt;
+        use crate::domain::resources::immunization::{
+            ImmunizationDoseNumber, ImmunizationOccurrence, ImmunizationProtocolApplied,
+        };
+
+        let validator = ImmunizationValidator;
+        let vaccine = CodeableConcept { coding: None, text: Some(FhirString("MMR".to_string())) };
+        let patient = Reference {
+            reference: Some(FhirString("Patient/123".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        };
+        let occurrence = ImmunizationOccurrence::String(FhirString("at school entry".to_string()));
+        let mut immunization = Immunization::new(Code("completed".to_string()), vaccine, patient, occurrence);
+        let protocol = |dose: u32, series: u32| ImmunizationProtocolApplied {
+            series: None,
+            authority: None,
+            target_disease: None,
+            dose_number: ImmunizationDoseNumber::PositiveInt(PositiveInt(dose)),
+            series_doses: Some(ImmunizationDoseNumber::PositiveInt(PositiveInt(series))),
+        };
+        immunization.protocol_applied = Some(vec![protocol(1, 2)]);
+        assert!(validator.validate(&immunization).is_ok());
+
+        // Dose numbers start at 1 and stay within the series
+        immunization.protocol_applied = Some(vec![protocol(0, 2), protocol(3, 2)]);
+        assert_eq!(validator.issues(&immunization).len(), 2);
+
+        immunization.protocol_applied = None;
+        immunization.status = Code("given".to_string());
+        assert!(validator.validate(&immunization).is_err());
+    }
+
+    #[test]
+    fn test_location_position_and_part_of() {
+        use crate::domain::{FhirDecimal, Id};
+        use crate::domain::resources::location::LocationPosition;
+
+        let validator = LocationValidator;
+        let mut location = Location::new();
+        location.id = Some(Id("loc-1".to_string()));
+        location.status = Some(Code("active".to_string()));
+        location.position = Some(LocationPosition {
+            longitude: FhirDecimal(-83.69471),
+            latitude: FhirDecimal(42.2565),
+            altitude: None,
+        });
+        assert!(validator.validate(&location).is_ok());
+
+        // Coordinates are checked, and a location cannot contain itself
+        location.position = Some(LocationPosition {
+            longitude: FhirDecimal(200.0),
+            latitude: FhirDecimal(-91.0),
+            altitude: None,
+        });
+        location.part_of = Some(Reference {
+            reference: Some(FhirString("Location/loc-1".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+        assert_eq!(validator.issues(&location).len(), 3);
+    }
+
+    #[test]
+    fn test_group_members() {
+        use crate::domain::{FhirBoolean, Id};
+        use crate::domain::resources::group::GroupMember;
+
+        let member = |reference: &str| GroupMember {
+            entity: Reference {
+                reference: Some(FhirString(reference.to_string())),
+                type_: None,
+                identifier: None,
+                display: None,
+            },
+            period: None,
+            inactive: None,
+        };
+        let validator = GroupValidator;
+        let mut group = Group::new(Code("person".to_string()), FhirBoolean(true));
+        group.id = Some(Id("grp-1".to_string()));
+        group.member = Some(vec![member("Patient/pat-1"), member("Group/grp-2")]);
+        assert!(validator.validate(&group).is_ok());
+
+        // Members must match the group type and cannot include the group
+        group.member = Some(vec![member("Device/dev-1"), member("Group/grp-1")]);
+        assert_eq!(validator.issues(&group).len(), 2);
+
+        // Definitional groups have no members
+        group.member = Some(vec![member("Patient/pat-1")]);
+        group.actual = FhirBoolean(false);
+        assert_eq!(validator.issues(&group).len(), 1);
+    }
+
+    #[test]
+    fn test_document_reference_content_and_subject() {
+        use crate::domain::Attachment;
+        use crate::domain::resources::document_reference::DocumentReferenceContent;
+
+        let validator = DocumentReferenceValidator;
+        let attachment = Attachment {
+            content_type: Some(Code("application/pdf".to_string())),
+            language: None,
+            data: None,
+            url: Some(Uri("Binary/bin-1".to_string())),
+            size: None,
+            hash: None,
+            title: None,
+            creation: None,
+        };
+        let mut document = DocumentReference::new(
+            Code("current".to_string()),
+            vec![DocumentReferenceContent { attachment: attachment.clone(), format: None }],
+        );
+        document.subject = Some(Reference {
+            reference: Some(FhirString("Patient/pat-1".to_string())),
+            type_: None,
+            identifier: None,
+            display: None,
+        });
+        assert!(validator.validate(&document).is_ok());
+
+        // Inline data needs a contentType, and attachments need data or a url
+        let mut inline = attachment.clone();
+        inline.content_type = None;
+        inline.url = None;
+        inline.data = Some(FhirString("JVBERi0=".to_string()));
+        let mut empty = attachment;
+        empty.url = None;
+        document.content = vec![
+            DocumentReferenceContent { attachment: inline, format: None },
+            DocumentReferenceContent { attachment: empty, format: None },
+        ];
+        assert_eq!(validator.issues(&document).len(), 2);
+
+        document.content = Vec::new();
+        document.subject.as_mut().unwrap().reference = Some(FhirString("Organization/org-1".to_string()));
+        assert_eq!(validator.issues(&document).len(), 2);
+    }
+
+    #[test]
+    fn test_binary_content_type() {
+        let validator = BinaryValidator;
+        assert!(validator.validate(&Binary::new(Code("application/pdf".to_string()))).is_ok());
+        assert!(validator.validate(&Binary::new(Code("pdf".to_string()))).is_err());
+        assert!(validator.validate(&Binary::new(Code("".to_string()))).is_err());
+    }
+
+    #[test]
+    fn test_validation_mode_request_requirements() {
+        let patient = Patient::new();
+
+        assert!(ValidationMode::parse(Some("profile")).is_err());
+        assert!(ValidationMode::Create.check_request(None, Some(&patient)).is_ok());
+        assert!(ValidationMode::Create.check_request::<Patient>(None, None).is_err());
+        assert!(ValidationMode::Update.check_request(None, Some(&patient)).is_err());
+        assert!(ValidationMode::Delete.check_request::<Patient>(Some("1"), None).is_ok());
+    }
+}